@@ -0,0 +1,181 @@
+//! 큰 행 이벤트를 읽을 때의 메모리 할당 비교
+//!
+//! `cargo bench --bench packet_alloc`
+//!
+//! - copying: 패킷마다 새 `Vec<u8>` 로 복사한 뒤 파싱 (이전 방식)
+//! - zero-copy: `PacketChannel` 의 읽기 버퍼를 `Bytes` 로 공유하여 파싱
+//!
+//! 두 방식 모두 TABLE_MAP 으로 행을 디코딩하며, zero-copy 에서는 행 이미지 (`rows_data`) 와
+//! BLOB 셀 값이 패킷 버퍼 안을 가리키는지 포인터로 확인합니다.
+
+use bytes::Bytes;
+use rust_mysql::binlog::BinlogParser;
+use rust_mysql::binlog_writer::BinlogEncoder;
+use rust_mysql::events::{
+    BinlogEvent, BinlogEventData, CellValue, EventHeader, EventType, TableMapData, WriteRowsData,
+};
+use rust_mysql::protocol::PacketChannel;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::io::Cursor;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// 할당 횟수와 크기를 세는 allocator
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(new_size, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const EVENT_COUNT: usize = 2_000;
+const ROW_IMAGE_SIZE: usize = 256 * 1024;
+
+/// LONGBLOB 컬럼 하나인 테이블
+fn table_map() -> TableMapData {
+    TableMapData {
+        table_id: 1,
+        database: "bench".to_string(),
+        table: "blobs".to_string(),
+        column_types: vec![251],
+        column_meta: vec![vec![4]],
+        nullable_bitmap: vec![0x01],
+        unsigned_columns: Vec::new(),
+    }
+}
+
+fn event(data: BinlogEventData) -> BinlogEvent {
+    BinlogEvent {
+        header: EventHeader {
+            timestamp: 1_700_000_000,
+            event_type: EventType::Unknown,
+            server_id: 1,
+            event_length: 0,
+            next_pos: 0,
+            flags: 0,
+        },
+        data,
+    }
+}
+
+/// WRITE_ROWS 이벤트 패킷들 (OK 바이트 + 큰 BLOB 한 행의 이벤트)
+fn workload() -> Vec<u8> {
+    let mut encoder = BinlogEncoder::default();
+    encoder
+        .encode(&event(BinlogEventData::TableMap(table_map())), 4)
+        .unwrap();
+    let write_rows = event(BinlogEventData::WriteRows(WriteRowsData {
+        table_id: 1,
+        flags: 0,
+        column_count: 1,
+        columns_present: vec![0x01],
+        rows: vec![vec![CellValue::Bytes(Bytes::from(vec![0xAB; ROW_IMAGE_SIZE]))]],
+        rows_data: Bytes::new(),
+    }));
+
+    let mut stream = Vec::new();
+    let mut position = 4;
+    for i in 0..EVENT_COUNT {
+        let mut payload = vec![0x00];
+        payload.extend_from_slice(&encoder.encode(&write_rows, position).unwrap());
+        position += payload.len() as u64 - 1;
+
+        let mut frame = (payload.len() as u32).to_le_bytes();
+        frame[3] = i as u8;
+        stream.extend_from_slice(&frame);
+        stream.extend_from_slice(&payload);
+    }
+    stream
+}
+
+struct Report {
+    allocations: usize,
+    allocated_bytes: usize,
+    elapsed: Duration,
+}
+
+async fn run(stream: Vec<u8>, copying: bool) -> Report {
+    let mut channel = PacketChannel::from_stream(Cursor::new(stream));
+    let table_map = table_map();
+
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let allocated_bytes = ALLOCATED_BYTES.load(Ordering::Relaxed);
+    let started = Instant::now();
+
+    for _ in 0..EVENT_COUNT {
+        let packet = channel.read_packet().await.unwrap();
+        let packet = if copying {
+            Bytes::from(packet.to_vec())
+        } else {
+            packet
+        };
+        let mut event = BinlogParser::parse_event(packet.slice(1..)).unwrap();
+        BinlogParser::decode_rows(&mut event.data, &table_map).unwrap();
+        if !copying {
+            let BinlogEventData::WriteRows(rows) = &event.data else {
+                panic!("expected write rows");
+            };
+            let [CellValue::Bytes(blob)] = &rows.rows[0][..] else {
+                panic!("expected a blob cell");
+            };
+            assert_eq!(blob.len(), ROW_IMAGE_SIZE);
+            let buffer = packet.as_ptr_range();
+            assert!(buffer.contains(&rows.rows_data.as_ptr()));
+            assert!(buffer.contains(&blob.as_ptr()));
+            assert!(blob.as_ptr_range().end <= buffer.end);
+        }
+        std::hint::black_box(event);
+    }
+
+    Report {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        allocated_bytes: ALLOCATED_BYTES.load(Ordering::Relaxed) - allocated_bytes,
+        elapsed: started.elapsed(),
+    }
+}
+
+fn main() {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+
+    println!(
+        "{} WRITE_ROWS events, {} KB row image each",
+        EVENT_COUNT,
+        ROW_IMAGE_SIZE / 1024
+    );
+    println!(
+        "{:<10} {:>14} {:>18} {:>12}",
+        "mode", "allocs/event", "alloc bytes/event", "elapsed"
+    );
+
+    for (name, copying) in [("copying", true), ("zero-copy", false)] {
+        let report = runtime.block_on(run(workload(), copying));
+        println!(
+            "{:<10} {:>14.1} {:>18} {:>12?}",
+            name,
+            report.allocations as f64 / EVENT_COUNT as f64,
+            report.allocated_bytes / EVENT_COUNT,
+            report.elapsed
+        );
+    }
+}
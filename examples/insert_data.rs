@@ -1,6 +1,6 @@
-/// MySQL에 테스트 데이터 INSERT
-///
-/// 이 프로그램은 MySQL에 데이터를 INSERT합니다.
+//! MySQL에 테스트 데이터 INSERT
+//!
+//! 이 프로그램은 MySQL에 데이터를 INSERT합니다.
 
 use mysql_async::prelude::*;
 use std::env;
@@ -1,17 +1,17 @@
-/// 실시간 CDC 테스트
-///
-/// 이 프로그램을 실행한 후, 다른 터미널에서 MySQL에 데이터를 INSERT/UPDATE/DELETE하면
-/// binlog 이벤트를 실시간으로 캡처합니다.
-///
-/// 사용법:
-/// 1. 이 프로그램 실행: `cargo run --example live_cdc_test`
-/// 2. 다른 터미널에서:
-///    ```sql
-///    mysql -u root -prootpassword testdb
-///    INSERT INTO users (name, email) VALUES ('테스트', 'test@example.com');
-///    ```
+//! 실시간 CDC 테스트
+//!
+//! 이 프로그램을 실행한 후, 다른 터미널에서 MySQL에 데이터를 INSERT/UPDATE/DELETE하면
+//! binlog 이벤트를 실시간으로 캡처합니다.
+//!
+//! 사용법:
+//! 1. 이 프로그램 실행: `cargo run --example live_cdc_test`
+//! 2. 다른 터미널에서:
+//!    ```sql
+//!    mysql -u root -prootpassword testdb
+//!    INSERT INTO users (name, email) VALUES ('테스트', 'test@example.com');
+//!    ```
 
-use rust_mysql::cdc_engine::{CdcConfig, CdcEngine, SnapshotMode};
+use rust_mysql::cdc_engine::{CdcConfig, CdcEngine, SnapshotMode, DEFAULT_SNAPSHOT_CHUNK_SIZE};
 use rust_mysql::connection::ConnectionConfig;
 use std::env;
 use tokio::time::Duration;
@@ -34,12 +34,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             database: Some(env::var("DB_NAME").unwrap_or_else(|_| "testdb".to_string())),
             server_id: 1,
             timeout: Duration::from_secs(30),
+            ..Default::default()
         },
         databases: vec!["testdb".to_string()],
         tables: None,
         snapshot_mode: SnapshotMode::Never,
         include_ddl: true,
         gtid_filter: None,
+        queue: Default::default(),
+        snapshot_locking: Default::default(),
+        snapshot_chunk_size: DEFAULT_SNAPSHOT_CHUNK_SIZE,
     };
 
     info!("=== 실시간 MySQL CDC 모니터링 시작 ===");
@@ -1,6 +1,6 @@
-/// MySQL 변경 감지 - 간단한 폴링 방식
-///
-/// 실제 binlog 프로토콜 대신 주기적으로 테이블을 조회하여 변경사항을 감지하는 예제
+//! MySQL 변경 감지 - 간단한 폴링 방식
+//!
+//! 실제 binlog 프로토콜 대신 주기적으로 테이블을 조회하여 변경사항을 감지하는 예제
 
 use mysql_async::prelude::*;
 use mysql_async::{Opts, Pool};
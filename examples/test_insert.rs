@@ -1,8 +1,8 @@
-/// MySQL CDC 테스트: 데이터 INSERT 후 변경사항 캡처
-///
-/// 이 예제는 MySQL에 데이터를 insert하고 CDC로 변경사항을 실시간으로 캡처합니다.
+//! MySQL CDC 테스트: 데이터 INSERT 후 변경사항 캡처
+//!
+//! 이 예제는 MySQL에 데이터를 insert하고 CDC로 변경사항을 실시간으로 캡처합니다.
 
-use rust_mysql::cdc_engine::{CdcConfig, CdcEngine, SnapshotMode};
+use rust_mysql::cdc_engine::{CdcConfig, CdcEngine, SnapshotMode, DEFAULT_SNAPSHOT_CHUNK_SIZE};
 use rust_mysql::connection::ConnectionConfig;
 use std::env;
 use std::time::Duration;
@@ -27,12 +27,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             database: Some(env::var("DB_NAME").unwrap_or_else(|_| "testdb".to_string())),
             server_id: 1,
             timeout: Duration::from_secs(30),
+            ..Default::default()
         },
         databases: vec!["testdb".to_string()],
         tables: None,
         snapshot_mode: SnapshotMode::Never, // 스냅샷 스킵, 실시간만 모니터링
         include_ddl: true,
         gtid_filter: None,
+        queue: Default::default(),
+        snapshot_locking: Default::default(),
+        snapshot_chunk_size: DEFAULT_SNAPSHOT_CHUNK_SIZE,
     };
 
     info!("=== MySQL CDC 테스트 시작 ===");
@@ -102,7 +106,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 /// 테스트 데이터 INSERT
 async fn insert_test_data(config: ConnectionConfig) -> Result<(), Box<dyn std::error::Error>> {
     use mysql_async::prelude::*;
-    use mysql_async::{Opts, OptsBuilder};
+    use mysql_async::Opts;
 
     // 연결 문자열 생성
     let connection_string = if let Some(ref db) = config.database {
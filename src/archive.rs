@@ -0,0 +1,461 @@
+//! Binlog 원본 보관 (`mysqlbinlog --raw --stop-never` 와 같은 동작)
+//!
+//! 서버에서 받은 이벤트를 체크섬까지 그대로 기록하여 서버의 binlog 와 바이트 단위로 같은 파일을 만듭니다.
+//! 서버가 보내는 가상 ROTATE (next_pos = 0) 로 새 파일을 시작하고, 재시작하면 마지막 로컬 파일의
+//! 크기부터 이어서 받습니다. 기록하는 이벤트는 next_pos 가 파일 위치와 맞는지 확인하며,
+//! 체크섬은 파싱할 때 검증됩니다.
+
+use crate::binlog::BINLOG_MAGIC;
+use crate::binlog_client::ends_transaction;
+use crate::binlog_file::{BinlogFileReader, FIRST_EVENT_POSITION};
+use crate::binlog_index::BinlogIndex;
+use crate::error::{CdcError, Result};
+use crate::events::{BinlogEvent, BinlogEventData, ChecksumAlgorithm, EventType};
+use crate::offset::BinlogPosition;
+use std::path::{Path, PathBuf};
+use tokio::fs::{self, File, OpenOptions};
+use tokio::io::AsyncWriteExt;
+use tracing::{info, warn};
+
+/// 서버가 만든 가상 이벤트 (파일에 기록되지 않음) 플래그
+const LOG_EVENT_ARTIFICIAL_F: u16 = 0x20;
+
+/// fsync 시점
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FsyncPolicy {
+    /// fsync 하지 않음 (OS 에 맡김)
+    Never,
+    /// 파일을 닫을 때 (ROTATE)
+    OnRotate,
+    /// 트랜잭션이 끝나는 이벤트 (XID, COMMIT, DDL) 마다
+    #[default]
+    EveryTransaction,
+    /// 모든 이벤트마다
+    EveryEvent,
+}
+
+/// 보관 설정
+#[derive(Debug, Clone)]
+pub struct ArchiveConfig {
+    /// binlog 파일을 기록할 디렉터리
+    pub dir: PathBuf,
+    pub fsync: FsyncPolicy,
+}
+
+impl ArchiveConfig {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        ArchiveConfig {
+            dir: dir.into(),
+            fsync: FsyncPolicy::default(),
+        }
+    }
+
+    pub fn fsync(mut self, fsync: FsyncPolicy) -> Self {
+        self.fsync = fsync;
+        self
+    }
+}
+
+/// 기록 중인 파일
+struct ArchiveFile {
+    name: String,
+    file: File,
+    size: u64,
+}
+
+/// 받은 이벤트를 서버 파일명 그대로 기록하는 보관기
+pub struct BinlogArchiver {
+    config: ArchiveConfig,
+    current: Option<ArchiveFile>,
+    resume: Option<BinlogPosition>,
+    checksum: ChecksumAlgorithm,
+}
+
+impl BinlogArchiver {
+    /// 보관 디렉터리 열기
+    ///
+    /// 마지막 로컬 파일을 끝까지 읽어 재개 위치를 정하며, 끝에 덜 기록된 이벤트가 있으면 잘라냅니다.
+    pub async fn open(config: ArchiveConfig) -> Result<Self> {
+        fs::create_dir_all(&config.dir).await.map_err(|e| {
+            CdcError::IoError(format!(
+                "Failed to create archive directory {}: {}",
+                config.dir.display(),
+                e
+            ))
+        })?;
+
+        let resume = match last_local_file(&config.dir).await? {
+            Some(path) => Some(recover_file(&path).await?),
+            None => None,
+        };
+        if let Some(resume) = &resume {
+            info!("Resuming binlog archive from {}", resume);
+        }
+
+        Ok(BinlogArchiver {
+            config,
+            current: None,
+            resume,
+            checksum: ChecksumAlgorithm::None,
+        })
+    }
+
+    /// 로컬 파일에서 이어 받을 위치 (보관된 파일이 없으면 None)
+    pub fn resume_position(&self) -> Option<&BinlogPosition> {
+        self.resume.as_ref()
+    }
+
+    /// 받는 이벤트의 체크섬 알고리즘 (FORMAT_DESCRIPTION 을 받으면 갱신)
+    pub fn checksum(&self) -> ChecksumAlgorithm {
+        self.checksum
+    }
+
+    /// FORMAT_DESCRIPTION 전에 오는 가상 ROTATE 를 위한 체크섬 알고리즘 (`@master_binlog_checksum`)
+    pub fn set_checksum(&mut self, checksum: ChecksumAlgorithm) {
+        self.checksum = checksum;
+    }
+
+    /// 기록 중인 파일과 크기
+    pub fn position(&self) -> Option<BinlogPosition> {
+        self.current
+            .as_ref()
+            .map(|current| BinlogPosition::new(current.name.clone(), current.size))
+    }
+
+    /// 받은 이벤트 (`raw`: 헤더 ~ 체크섬) 기록
+    ///
+    /// 가상 ROTATE 는 파일 전환, 그 외 가상 이벤트 (재개 시의 FORMAT_DESCRIPTION, heartbeat) 는 무시합니다.
+    pub async fn write_event(&mut self, raw: &[u8], event: &BinlogEvent) -> Result<()> {
+        let header = &event.header;
+        if let BinlogEventData::FormatDescription(format) = &event.data {
+            self.checksum = format.checksum;
+        }
+        let artificial = header.next_pos == 0
+            || header.flags & LOG_EVENT_ARTIFICIAL_F != 0
+            || matches!(
+                header.event_type,
+                EventType::HeartbeatLogEvent | EventType::HeartbeatLogEventV2
+            );
+
+        if artificial {
+            if let BinlogEventData::Rotate(rotate) = &event.data {
+                self.switch_file(&rotate.next_binlog_name, rotate.position).await?;
+            }
+            return Ok(());
+        }
+
+        let current = self.current.as_mut().ok_or_else(|| {
+            CdcError::InvalidEvent("Binlog event received before the first ROTATE".to_string())
+        })?;
+
+        // 첫 이벤트는 FORMAT_DESCRIPTION (binlog v4) 이어야 함
+        let is_format = matches!(&event.data, BinlogEventData::FormatDescription(_));
+        if is_format != (current.size == FIRST_EVENT_POSITION) {
+            return Err(CdcError::InvalidEvent(format!(
+                "Format description event must be the first event of {} (at {})",
+                current.name, current.size
+            )));
+        }
+        if let BinlogEventData::FormatDescription(format) = &event.data {
+            if format.binlog_version != 4 {
+                return Err(CdcError::InvalidEvent(format!(
+                    "Unsupported binlog version {} in {}",
+                    format.binlog_version, current.name
+                )));
+            }
+        }
+
+        let expected = current.size + raw.len() as u64;
+        if header.event_length as usize != raw.len() || header.next_pos as u64 != expected {
+            return Err(CdcError::InvalidEvent(format!(
+                "Event at {}:{} ends at {} but next_pos is {}",
+                current.name, current.size, expected, header.next_pos
+            )));
+        }
+
+        current.file.write_all(raw).await?;
+        current.file.flush().await?;
+        current.size = expected;
+
+        let sync = match self.config.fsync {
+            FsyncPolicy::Never | FsyncPolicy::OnRotate => false,
+            FsyncPolicy::EveryTransaction => ends_transaction(&event.data),
+            FsyncPolicy::EveryEvent => true,
+        };
+        if sync {
+            self.sync().await?;
+        }
+
+        // 파일의 마지막 이벤트, 다음 가상 ROTATE 에서 새 파일 시작
+        if let BinlogEventData::Rotate(_) = &event.data {
+            self.close().await?;
+        }
+
+        Ok(())
+    }
+
+    /// 기록 중인 파일 fsync
+    pub async fn sync(&mut self) -> Result<()> {
+        if let Some(current) = self.current.as_mut() {
+            current.file.sync_data().await?;
+        }
+        Ok(())
+    }
+
+    /// 기록 중인 파일 닫기 (fsync 정책이 Never 가 아니면 fsync)
+    pub async fn close(&mut self) -> Result<()> {
+        if self.config.fsync != FsyncPolicy::Never {
+            self.sync().await?;
+        }
+        if let Some(current) = self.current.take() {
+            info!("Closed archived binlog {} ({} bytes)", current.name, current.size);
+        }
+        Ok(())
+    }
+
+    /// `name` 파일의 `position` 부터 기록하도록 전환
+    async fn switch_file(&mut self, name: &str, position: u64) -> Result<()> {
+        if self.current.as_ref().is_some_and(|c| c.name == name && c.size == position) {
+            return Ok(());
+        }
+        self.close().await?;
+
+        let path = self.file_path(name)?;
+        let current = if position == FIRST_EVENT_POSITION {
+            let mut file = File::create(&path).await?;
+            file.write_all(BINLOG_MAGIC).await?;
+            file.flush().await?;
+            info!("Archiving binlog {}", path.display());
+            ArchiveFile {
+                name: name.to_string(),
+                file,
+                size: FIRST_EVENT_POSITION,
+            }
+        } else {
+            // 이어 받기는 로컬 파일 크기와 위치가 같아야 바이트 단위로 같은 파일이 됨
+            let size = match fs::metadata(&path).await {
+                Ok(metadata) => metadata.len(),
+                Err(_) => 0,
+            };
+            if size != position {
+                return Err(CdcError::InvalidEvent(format!(
+                    "Cannot archive {} from {}: local file has {} bytes",
+                    name, position, size
+                )));
+            }
+            let file = OpenOptions::new().append(true).open(&path).await?;
+            ArchiveFile {
+                name: name.to_string(),
+                file,
+                size,
+            }
+        };
+
+        self.current = Some(current);
+        Ok(())
+    }
+
+    /// 보관 디렉터리 안의 경로 (서버가 보낸 파일명에 디렉터리가 섞이지 않도록 확인)
+    fn file_path(&self, name: &str) -> Result<PathBuf> {
+        let valid = !name.is_empty() && Path::new(name).file_name() == Some(name.as_ref());
+        if !valid {
+            return Err(CdcError::InvalidEvent(format!("Invalid binlog file name: {}", name)));
+        }
+        Ok(self.config.dir.join(name))
+    }
+}
+
+/// 시퀀스 번호가 가장 큰 로컬 binlog 파일
+async fn last_local_file(dir: &Path) -> Result<Option<PathBuf>> {
+    let mut entries = fs::read_dir(dir).await?;
+    let mut files = Vec::new();
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        if BinlogPosition::new(name, 0).file_sequence().is_some() && path.is_file() {
+            files.push(path);
+        }
+    }
+    Ok(BinlogIndex::new(files).files().last().cloned())
+}
+
+/// 로컬 파일을 끝까지 읽어 마지막 완전한 이벤트 뒤 위치 반환 (덜 기록된 부분은 잘라냄)
+async fn recover_file(path: &Path) -> Result<BinlogPosition> {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let size = fs::metadata(path).await?.len();
+
+    let end = if size < FIRST_EVENT_POSITION {
+        // 매직 넘버도 다 쓰지 못한 파일은 처음부터 다시 받음
+        FIRST_EVENT_POSITION
+    } else {
+        let path = path.to_path_buf();
+        tokio::task::spawn_blocking(move || -> Result<u64> {
+            let mut reader = BinlogFileReader::open(&path)?;
+            for event in reader.by_ref() {
+                event?;
+            }
+            Ok(reader.position())
+        })
+        .await
+        .map_err(|e| CdcError::Other(format!("Archive recovery task failed: {}", e)))??
+    };
+
+    if size < FIRST_EVENT_POSITION || end < size {
+        warn!("Truncating partial event in {} ({} -> {} bytes)", path.display(), size, end);
+        let file = OpenOptions::new().write(true).open(path).await?;
+        if size < FIRST_EVENT_POSITION {
+            file.set_len(0).await?;
+        } else {
+            file.set_len(end).await?;
+        }
+        file.sync_all().await?;
+    }
+
+    Ok(BinlogPosition::new(name, end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binlog::{BinlogParser, EVENT_HEADER_SIZE};
+    use crate::events::ChecksumAlgorithm;
+    use crate::test_fixtures::{format_description_body, rotate_body, RawEvent};
+    use bytes::Bytes;
+
+    /// CRC32 가 붙은 이벤트 (next_pos 는 `offset` 기준)
+    fn raw_event(event_type: u8, offset: Option<u64>, body: &[u8]) -> Vec<u8> {
+        let event = RawEvent::new(event_type, body).crc(true);
+        let next_pos = offset.map_or(0, |offset| offset as u32 + event.build().len() as u32);
+        event.next_pos(next_pos).build()
+    }
+
+    fn format_description(offset: Option<u64>) -> Vec<u8> {
+        raw_event(15, offset, &format_description_body("8.0.35", Some(1)))
+    }
+
+    fn fde_len() -> u64 {
+        format_description(None).len() as u64
+    }
+
+    fn rotate(name: &str, position: u64, offset: Option<u64>) -> Vec<u8> {
+        raw_event(4, offset, &rotate_body(name, position))
+    }
+
+    fn xid(xid: u64, offset: u64) -> Vec<u8> {
+        raw_event(16, Some(offset), &xid.to_le_bytes())
+    }
+
+    /// 스트림처럼 파싱한 뒤 기록
+    async fn write(archiver: &mut BinlogArchiver, raw: Vec<u8>) -> Result<()> {
+        let event = BinlogParser::parse_event_with_checksum(
+            Bytes::from(raw.clone()),
+            ChecksumAlgorithm::Crc32,
+        )?;
+        archiver.write_event(&raw, &event).await
+    }
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("archive_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    /// 가상 ROTATE + FORMAT_DESCRIPTION + XID + ROTATE 로 끝나는 파일 받기
+    async fn archive_file(archiver: &mut BinlogArchiver, name: &str, next: &str) -> Vec<u8> {
+        let mut expected = BINLOG_MAGIC.to_vec();
+        write(archiver, rotate(name, 4, None)).await.unwrap();
+        for event in [
+            |offset| format_description(Some(offset)),
+            |offset| xid(1, offset),
+        ] {
+            let event = event(expected.len() as u64);
+            expected.extend(&event);
+            write(archiver, event).await.unwrap();
+        }
+        let rotate = rotate(next, 4, Some(expected.len() as u64));
+        expected.extend(&rotate);
+        write(archiver, rotate).await.unwrap();
+        expected
+    }
+
+    #[tokio::test]
+    async fn test_archive_byte_identical_files() {
+        let dir = test_dir("rotate");
+        let config = ArchiveConfig::new(&dir).fsync(FsyncPolicy::EveryEvent);
+        let mut archiver = BinlogArchiver::open(config).await.unwrap();
+        assert!(archiver.resume_position().is_none());
+
+        let first = archive_file(&mut archiver, "mysql-bin.000001", "mysql-bin.000002").await;
+        let second = archive_file(&mut archiver, "mysql-bin.000002", "mysql-bin.000003").await;
+        assert_eq!(std::fs::read(dir.join("mysql-bin.000001")).unwrap(), first);
+        assert_eq!(std::fs::read(dir.join("mysql-bin.000002")).unwrap(), second);
+
+        // 받은 파일은 오프라인 리더로 읽을 수 있음
+        let events = BinlogFileReader::open(dir.join("mysql-bin.000002")).unwrap();
+        assert_eq!(events.collect::<Result<Vec<_>>>().unwrap().len(), 3);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_archive_resume_from_last_file() {
+        let dir = test_dir("resume");
+        let mut archiver = BinlogArchiver::open(ArchiveConfig::new(&dir)).await.unwrap();
+        archive_file(&mut archiver, "mysql-bin.000001", "mysql-bin.000002").await;
+
+        // 두 번째 파일의 마지막 이벤트가 덜 기록된 채 종료
+        write(&mut archiver, rotate("mysql-bin.000002", 4, None)).await.unwrap();
+        let mut expected = BINLOG_MAGIC.to_vec();
+        let fde = format_description(Some(4));
+        expected.extend(&fde);
+        write(&mut archiver, fde).await.unwrap();
+        drop(archiver);
+        let path = dir.join("mysql-bin.000002");
+        let mut partial = expected.clone();
+        partial.extend(&xid(1, expected.len() as u64)[..10]);
+        std::fs::write(&path, &partial).unwrap();
+
+        let mut archiver = BinlogArchiver::open(ArchiveConfig::new(&dir)).await.unwrap();
+        let resume = archiver.resume_position().unwrap().clone();
+        assert_eq!(resume, BinlogPosition::new("mysql-bin.000002".to_string(), 4 + fde_len()));
+        assert_eq!(std::fs::read(&path).unwrap(), expected);
+
+        // 재개 시 서버는 가상 ROTATE 와 가상 FORMAT_DESCRIPTION 을 보냄
+        write(&mut archiver, rotate("mysql-bin.000002", resume.position, None)).await.unwrap();
+        write(&mut archiver, format_description(None)).await.unwrap();
+        let xid = xid(2, resume.position);
+        expected.extend(&xid);
+        write(&mut archiver, xid).await.unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), expected);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_archive_rejects_invalid_events() {
+        let dir = test_dir("invalid");
+        let mut archiver = BinlogArchiver::open(ArchiveConfig::new(&dir)).await.unwrap();
+
+        // 첫 ROTATE 전 이벤트, 디렉터리가 섞인 파일명, 로컬 파일이 없는 위치
+        assert!(write(&mut archiver, xid(1, 4)).await.is_err());
+        assert!(write(&mut archiver, rotate("../mysql-bin.000001", 4, None)).await.is_err());
+        assert!(write(&mut archiver, rotate("mysql-bin.000001", 500, None)).await.is_err());
+
+        write(&mut archiver, rotate("mysql-bin.000001", 4, None)).await.unwrap();
+        // FORMAT_DESCRIPTION 이 아닌 첫 이벤트, 위치가 맞지 않는 이벤트
+        assert!(write(&mut archiver, xid(1, 4)).await.is_err());
+        write(&mut archiver, format_description(Some(4))).await.unwrap();
+        assert!(write(&mut archiver, xid(1, 8)).await.is_err());
+
+        // 체크섬 불일치는 파싱에서 거부
+        let mut corrupted = xid(1, 4 + fde_len());
+        corrupted[EVENT_HEADER_SIZE] ^= 0xff;
+        assert!(write(&mut archiver, corrupted).await.is_err());
+        assert_eq!(archiver.position().unwrap().position, 4 + fde_len());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,8 +1,14 @@
 //! MySQL 인증 처리
 //!
-//! Native password authentication 구현
+//! 지원 플러그인:
+//! - mysql_native_password
+//! - caching_sha2_password
+//! - sha256_password (TLS 또는 RSA 공개키 암호화)
+//! - mysql_clear_password (TLS 또는 명시적 허용 시에만)
 
+use crate::error::{CdcError, Result};
 use byteorder::{LittleEndian, WriteBytesExt};
+use std::collections::BTreeMap;
 use std::io::Write;
 
 /// Client capability flags
@@ -27,6 +33,127 @@ pub mod capabilities {
     pub const PS_MULTI_RESULTS: u32 = 1 << 18;
     pub const PLUGIN_AUTH: u32 = 1 << 19;
     pub const CONNECT_ATTRS: u32 = 1 << 20;
+    pub const PLUGIN_AUTH_LENENC_CLIENT_DATA: u32 = 1 << 21;
+    pub const ZSTD_COMPRESSION_ALGORITHM: u32 = 1 << 26;
+}
+
+/// AuthMoreData 패킷 헤더
+pub const AUTH_MORE_DATA: u8 = 0x01;
+
+/// AuthSwitchRequest 패킷 헤더
+pub const AUTH_SWITCH_REQUEST: u8 = 0xFE;
+
+/// caching_sha2_password: 캐시된 해시로 인증 성공 (뒤이어 OK 패킷)
+pub const CACHING_SHA2_FAST_AUTH_SUCCESS: u8 = 0x03;
+
+/// caching_sha2_password: 전체 인증 필요
+pub const CACHING_SHA2_PERFORM_FULL_AUTH: u8 = 0x04;
+
+/// caching_sha2_password: 서버 공개키 요청
+const CACHING_SHA2_REQUEST_PUBLIC_KEY: u8 = 0x02;
+
+/// sha256_password: 서버 공개키 요청
+const SHA256_REQUEST_PUBLIC_KEY: u8 = 0x01;
+
+/// 인증 플러그인
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthPlugin {
+    MysqlNativePassword,
+    CachingSha2Password,
+    Sha256Password,
+    MysqlClearPassword,
+}
+
+impl AuthPlugin {
+    /// 플러그인 이름으로부터 변환 (greeting, AuthSwitchRequest)
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "mysql_native_password" => Some(AuthPlugin::MysqlNativePassword),
+            "caching_sha2_password" => Some(AuthPlugin::CachingSha2Password),
+            "sha256_password" => Some(AuthPlugin::Sha256Password),
+            "mysql_clear_password" => Some(AuthPlugin::MysqlClearPassword),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            AuthPlugin::MysqlNativePassword => "mysql_native_password",
+            AuthPlugin::CachingSha2Password => "caching_sha2_password",
+            AuthPlugin::Sha256Password => "sha256_password",
+            AuthPlugin::MysqlClearPassword => "mysql_clear_password",
+        }
+    }
+}
+
+/// 플러그인별 인증 응답 생성에 필요한 정보
+#[derive(Debug, Clone, Copy)]
+pub struct AuthContext<'a> {
+    pub password: &'a str,
+    /// TLS 또는 Unix socket 처럼 RSA 없이 비밀번호를 보내도 되는 채널 여부 (sha256 계열 전체 인증)
+    pub secure_transport: bool,
+    /// TLS 로 암호화된 채널 여부 (mysql_clear_password 는 Unix socket 이어도 opt-in 필요)
+    pub encrypted_transport: bool,
+    /// TLS 가 아닌 채널에서 mysql_clear_password 허용 여부
+    pub allow_cleartext_password: bool,
+    /// 서버 공개키 자동 요청 허용 여부
+    pub allow_public_key_retrieval: bool,
+    /// 미리 설정된 서버 RSA 공개키 (PEM)
+    pub server_public_key: Option<&'a [u8]>,
+}
+
+impl AuthContext<'_> {
+    /// 핸드셰이크 응답 또는 AuthSwitchRequest에 대한 첫 인증 데이터 생성
+    pub fn initial_response(&self, plugin: AuthPlugin, scramble: &[u8]) -> Result<Vec<u8>> {
+        match plugin {
+            AuthPlugin::MysqlNativePassword => Ok(create_auth_response(self.password, scramble)),
+            AuthPlugin::CachingSha2Password => {
+                Ok(create_caching_sha2_response(self.password, scramble))
+            }
+            AuthPlugin::Sha256Password => {
+                if self.password.is_empty() {
+                    Ok(vec![0])
+                } else if self.secure_transport {
+                    Ok(create_clear_password_response(self.password))
+                } else if let Some(public_key) = self.server_public_key {
+                    encrypt_password_rsa(self.password, scramble, public_key)
+                } else {
+                    self.check_public_key_retrieval()?;
+                    Ok(vec![SHA256_REQUEST_PUBLIC_KEY])
+                }
+            }
+            AuthPlugin::MysqlClearPassword => {
+                if !self.encrypted_transport && !self.allow_cleartext_password {
+                    return Err(CdcError::ConfigError(
+                        "mysql_clear_password requires TLS; set allow_cleartext_password to send it over an unencrypted connection".to_string(),
+                    ));
+                }
+                Ok(create_clear_password_response(self.password))
+            }
+        }
+    }
+
+    /// caching_sha2_password 전체 인증 단계 응답 생성
+    pub fn full_auth_response(&self, scramble: &[u8]) -> Result<Vec<u8>> {
+        if self.secure_transport {
+            Ok(create_clear_password_response(self.password))
+        } else if let Some(public_key) = self.server_public_key {
+            encrypt_password_rsa(self.password, scramble, public_key)
+        } else {
+            self.check_public_key_retrieval()?;
+            Ok(vec![CACHING_SHA2_REQUEST_PUBLIC_KEY])
+        }
+    }
+
+    fn check_public_key_retrieval(&self) -> Result<()> {
+        if self.allow_public_key_retrieval {
+            Ok(())
+        } else {
+            Err(CdcError::ConfigError(
+                "Server public key is required; configure server_public_key_path or enable allow_public_key_retrieval".to_string(),
+            ))
+        }
+    }
 }
 
 /// Native password 인증 응답 생성
@@ -63,60 +190,223 @@ fn sha1(data: &[u8]) -> Vec<u8> {
     hasher.finalize().to_vec()
 }
 
-/// 인증 패킷 생성
+/// caching_sha2_password 인증 응답 생성
+///
+/// XOR(SHA256(password), SHA256(SHA256(SHA256(password)) + scramble))
+pub fn create_caching_sha2_response(password: &str, scramble: &[u8]) -> Vec<u8> {
+    if password.is_empty() {
+        return Vec::new();
+    }
+
+    let stage1 = sha256(password.as_bytes());
+    let stage2 = sha256(&stage1);
+
+    let mut combined = stage2;
+    combined.extend_from_slice(scramble);
+    let stage3 = sha256(&combined);
+
+    stage1.iter().zip(stage3.iter()).map(|(a, b)| a ^ b).collect()
+}
+
+/// 평문 비밀번호 응답 (null-terminated)
+pub fn create_clear_password_response(password: &str) -> Vec<u8> {
+    let mut result = password.as_bytes().to_vec();
+    result.push(0);
+    result
+}
+
+/// RSA 공개키로 비밀번호 암호화 (sha256_password, caching_sha2_password)
+///
+/// 서버는 (password + '\0') XOR scramble 을 RSA-OAEP(SHA1)로 암호화한 값을 기대합니다.
+pub fn encrypt_password_rsa(password: &str, scramble: &[u8], public_key_pem: &[u8]) -> Result<Vec<u8>> {
+    use rsa::pkcs1::DecodeRsaPublicKey;
+    use rsa::pkcs8::DecodePublicKey;
+    use rsa::{Oaep, RsaPublicKey};
+
+    let pem = std::str::from_utf8(public_key_pem)
+        .map_err(|e| CdcError::ConfigError(format!("Invalid public key encoding: {}", e)))?
+        .trim_end_matches('\0');
+
+    let public_key = RsaPublicKey::from_public_key_pem(pem)
+        .or_else(|_| RsaPublicKey::from_pkcs1_pem(pem))
+        .map_err(|e| CdcError::ConfigError(format!("Invalid server public key: {}", e)))?;
+
+    let mut plain = create_clear_password_response(password);
+    if !scramble.is_empty() {
+        for (i, byte) in plain.iter_mut().enumerate() {
+            *byte ^= scramble[i % scramble.len()];
+        }
+    }
+
+    public_key
+        .encrypt(&mut rand::thread_rng(), Oaep::new::<sha1::Sha1>(), &plain)
+        .map_err(|e| CdcError::ConnectionError(format!("Failed to encrypt password: {}", e)))
+}
+
+/// SHA256 해시 계산
+fn sha256(data: &[u8]) -> Vec<u8> {
+    use sha2::{Digest, Sha256};
+    let mut hasher = Sha256::new();
+    hasher.update(data);
+    hasher.finalize().to_vec()
+}
+
+/// 인증 패킷 생성 (mysql_native_password)
 pub fn create_handshake_response(
     username: &str,
     password: &str,
     database: Option<&str>,
     scramble: &[u8],
     collation: u8,
-) -> Result<Vec<u8>, std::io::Error> {
+) -> std::result::Result<Vec<u8>, std::io::Error> {
+    let auth_response = create_auth_response(password, scramble);
+    HandshakeResponse {
+        username,
+        auth_response: &auth_response,
+        database,
+        collation,
+        plugin: AuthPlugin::MysqlNativePassword,
+        extra_capabilities: 0,
+        connect_attrs: None,
+        zstd_level: None,
+    }
+    .encode()
+}
+
+/// SSL request 패킷 생성 (TLS 업그레이드 전 전송)
+///
+/// 핸드셰이크 응답의 앞 32 바이트와 동일하며, 이후 TLS 위에서 전체 응답을 다시 보냅니다.
+pub fn create_ssl_request(
+    database: Option<&str>,
+    collation: u8,
+) -> std::result::Result<Vec<u8>, std::io::Error> {
     let mut buffer = Vec::new();
 
-    // Client capability flags (4 bytes)
+    buffer.write_u32::<LittleEndian>(client_capabilities(database) | capabilities::SSL)?;
+    buffer.write_u32::<LittleEndian>(0)?;
+    buffer.write_u8(collation)?;
+    buffer.write_all(&[0u8; 23])?;
+
+    Ok(buffer)
+}
+
+/// 기본 클라이언트 capability flags
+fn client_capabilities(database: Option<&str>) -> u32 {
     let mut capabilities = capabilities::LONG_PASSWORD
         | capabilities::LONG_FLAG
         | capabilities::PROTOCOL_41
         | capabilities::SECURE_CONNECTION
         | capabilities::MULTI_STATEMENTS
         | capabilities::MULTI_RESULTS
-        | capabilities::PLUGIN_AUTH;
+        | capabilities::PLUGIN_AUTH
+        | capabilities::PLUGIN_AUTH_LENENC_CLIENT_DATA;
 
     if database.is_some() {
         capabilities |= capabilities::CONNECT_WITH_DB;
     }
 
-    buffer.write_u32::<LittleEndian>(capabilities)?;
+    capabilities
+}
 
-    // Max packet size (4 bytes) - 0 means default (16MB)
-    buffer.write_u32::<LittleEndian>(0)?;
+/// 핸드셰이크 응답 패킷 (플러그인 지정, 인증 데이터는 미리 계산된 값 사용)
+#[derive(Debug, Clone)]
+pub struct HandshakeResponse<'a> {
+    pub username: &'a str,
+    pub auth_response: &'a [u8],
+    pub database: Option<&'a str>,
+    pub collation: u8,
+    pub plugin: AuthPlugin,
+    /// 기본 capability flags 에 추가로 설정할 값 (예: SSL, COMPRESS)
+    pub extra_capabilities: u32,
+    /// 연결 속성 (설정 시 CLIENT_CONNECT_ATTRS 와 함께 전송)
+    pub connect_attrs: Option<&'a BTreeMap<String, String>>,
+    /// CLIENT_ZSTD_COMPRESSION_ALGORITHM 사용 시 응답 끝에 붙는 압축 레벨
+    pub zstd_level: Option<u8>,
+}
 
-    // Character set (1 byte)
-    buffer.write_u8(collation)?;
+impl HandshakeResponse<'_> {
+    /// 패킷 payload 생성
+    pub fn encode(&self) -> std::result::Result<Vec<u8>, std::io::Error> {
+        let mut buffer = Vec::new();
 
-    // Reserved (23 bytes of zeros)
-    buffer.write_all(&[0u8; 23])?;
+        let mut capabilities = client_capabilities(self.database) | self.extra_capabilities;
+        if self.connect_attrs.is_some() {
+            capabilities |= capabilities::CONNECT_ATTRS;
+        }
 
-    // Username (null-terminated string)
-    buffer.write_all(username.as_bytes())?;
-    buffer.write_u8(0)?;
+        // Client capability flags (4 bytes)
+        buffer.write_u32::<LittleEndian>(capabilities)?;
 
-    // Authentication response
-    let auth_response = create_auth_response(password, scramble);
-    buffer.write_u8(auth_response.len() as u8)?;
-    buffer.write_all(&auth_response)?;
+        // Max packet size (4 bytes) - 0 means default (16MB)
+        buffer.write_u32::<LittleEndian>(0)?;
+
+        // Character set (1 byte)
+        buffer.write_u8(self.collation)?;
+
+        // Reserved (23 bytes of zeros)
+        buffer.write_all(&[0u8; 23])?;
 
-    // Database name (null-terminated string, if provided)
-    if let Some(db) = database {
-        buffer.write_all(db.as_bytes())?;
+        // Username (null-terminated string)
+        buffer.write_all(self.username.as_bytes())?;
         buffer.write_u8(0)?;
+
+        // Authentication response (length-encoded, RSA 암호문은 255 바이트를 넘을 수 있음)
+        write_lenenc_int(&mut buffer, self.auth_response.len() as u64)?;
+        buffer.write_all(self.auth_response)?;
+
+        // Database name (null-terminated string, if provided)
+        if let Some(db) = self.database {
+            buffer.write_all(db.as_bytes())?;
+            buffer.write_u8(0)?;
+        }
+
+        // Authentication plugin name (null-terminated)
+        buffer.write_all(self.plugin.name().as_bytes())?;
+        buffer.write_u8(0)?;
+
+        // Connection attributes (전체 길이 + length-encoded key/value 목록)
+        if let Some(attrs) = self.connect_attrs {
+            let mut encoded = Vec::new();
+            for (key, value) in attrs {
+                write_lenenc_str(&mut encoded, key)?;
+                write_lenenc_str(&mut encoded, value)?;
+            }
+            write_lenenc_int(&mut buffer, encoded.len() as u64)?;
+            buffer.write_all(&encoded)?;
+        }
+
+        // zstd compression level (1 byte)
+        if let Some(level) = self.zstd_level {
+            buffer.write_u8(level)?;
+        }
+
+        Ok(buffer)
     }
+}
 
-    // Authentication plugin name (null-terminated)
-    buffer.write_all(b"mysql_native_password")?;
-    buffer.write_u8(0)?;
+/// Length-encoded string 쓰기
+pub(crate) fn write_lenenc_str<W: Write>(writer: &mut W, value: &str) -> std::io::Result<()> {
+    write_lenenc_int(writer, value.len() as u64)?;
+    writer.write_all(value.as_bytes())
+}
 
-    Ok(buffer)
+/// Length-encoded integer 쓰기
+pub(crate) fn write_lenenc_int<W: Write>(writer: &mut W, value: u64) -> std::io::Result<()> {
+    match value {
+        0..=0xfa => writer.write_u8(value as u8),
+        0xfb..=0xffff => {
+            writer.write_u8(0xfc)?;
+            writer.write_u16::<LittleEndian>(value as u16)
+        }
+        0x1_0000..=0xff_ffff => {
+            writer.write_u8(0xfd)?;
+            writer.write_u24::<LittleEndian>(value as u32)
+        }
+        _ => {
+            writer.write_u8(0xfe)?;
+            writer.write_u64::<LittleEndian>(value)
+        }
+    }
 }
 
 #[cfg(test)]
@@ -151,4 +441,112 @@ mod tests {
         // 패킷이 합리적인 크기인지 확인
         assert!(packet.len() > 50);
     }
+
+    #[test]
+    fn test_create_ssl_request() {
+        let packet = create_ssl_request(None, 33).unwrap();
+        assert_eq!(packet.len(), 32);
+
+        let flags = u32::from_le_bytes([packet[0], packet[1], packet[2], packet[3]]);
+        assert_ne!(flags & capabilities::SSL, 0);
+        assert_eq!(packet[8], 33);
+    }
+
+    #[test]
+    fn test_handshake_response_attrs_and_zstd_level() {
+        let attrs = BTreeMap::from([("_pid".to_string(), "42".to_string())]);
+        let packet = HandshakeResponse {
+            username: "root",
+            auth_response: &[],
+            database: None,
+            collation: 33,
+            plugin: AuthPlugin::CachingSha2Password,
+            extra_capabilities: capabilities::ZSTD_COMPRESSION_ALGORITHM,
+            connect_attrs: Some(&attrs),
+            zstd_level: Some(7),
+        }
+        .encode()
+        .unwrap();
+
+        let flags = u32::from_le_bytes([packet[0], packet[1], packet[2], packet[3]]);
+        assert_ne!(flags & capabilities::ZSTD_COMPRESSION_ALGORITHM, 0);
+        assert_ne!(flags & capabilities::CONNECT_ATTRS, 0);
+        assert!(packet.ends_with(b"caching_sha2_password\0\x08\x04_pid\x0242\x07"));
+    }
+
+    #[test]
+    fn test_auth_plugin_from_name() {
+        assert_eq!(AuthPlugin::from_name("sha256_password"), Some(AuthPlugin::Sha256Password));
+        assert_eq!(AuthPlugin::from_name("mysql_clear_password"), Some(AuthPlugin::MysqlClearPassword));
+        assert_eq!(AuthPlugin::from_name("auth_gssapi_client"), None);
+        assert_eq!(AuthPlugin::CachingSha2Password.name(), "caching_sha2_password");
+    }
+
+    #[test]
+    fn test_clear_password_requires_tls_or_opt_in() {
+        let mut ctx = AuthContext {
+            password: "secret",
+            secure_transport: false,
+            encrypted_transport: false,
+            allow_cleartext_password: false,
+            allow_public_key_retrieval: false,
+            server_public_key: None,
+        };
+        assert!(ctx.initial_response(AuthPlugin::MysqlClearPassword, &[]).is_err());
+
+        // Unix socket 은 sha256 계열에는 안전한 채널이지만 평문 비밀번호는 opt-in 필요
+        ctx.secure_transport = true;
+        assert!(ctx.initial_response(AuthPlugin::MysqlClearPassword, &[]).is_err());
+        let response = ctx.initial_response(AuthPlugin::Sha256Password, &[]).unwrap();
+        assert_eq!(response, b"secret\0");
+
+        ctx.encrypted_transport = true;
+        assert!(ctx.initial_response(AuthPlugin::MysqlClearPassword, &[]).is_ok());
+        ctx.secure_transport = false;
+        ctx.encrypted_transport = false;
+
+        ctx.allow_cleartext_password = true;
+        let response = ctx.initial_response(AuthPlugin::MysqlClearPassword, &[]).unwrap();
+        assert_eq!(response, b"secret\0");
+    }
+
+    #[test]
+    fn test_sha256_password_public_key() {
+        use rsa::pkcs8::EncodePublicKey;
+        use rsa::{Oaep, RsaPrivateKey, RsaPublicKey};
+
+        let private_key = RsaPrivateKey::new(&mut rand::thread_rng(), 512).unwrap();
+        let pem = RsaPublicKey::from(&private_key)
+            .to_public_key_pem(rsa::pkcs8::LineEnding::LF)
+            .unwrap();
+        let scramble = [0x11u8; 20];
+
+        let mut ctx = AuthContext {
+            password: "secret",
+            secure_transport: false,
+            encrypted_transport: false,
+            allow_cleartext_password: false,
+            allow_public_key_retrieval: false,
+            server_public_key: None,
+        };
+        // 공개키도 없고 요청도 허용되지 않으면 거부
+        assert!(ctx.initial_response(AuthPlugin::Sha256Password, &scramble).is_err());
+
+        ctx.allow_public_key_retrieval = true;
+        let request = ctx.initial_response(AuthPlugin::Sha256Password, &scramble).unwrap();
+        assert_eq!(request, vec![SHA256_REQUEST_PUBLIC_KEY]);
+
+        ctx.server_public_key = Some(pem.as_bytes());
+        let encrypted = ctx.initial_response(AuthPlugin::Sha256Password, &scramble).unwrap();
+        let decrypted = private_key.decrypt(Oaep::new::<sha1::Sha1>(), &encrypted).unwrap();
+        let expected: Vec<u8> = b"secret\0".iter().map(|b| b ^ 0x11).collect();
+        assert_eq!(decrypted, expected);
+    }
+
+    #[test]
+    fn test_create_caching_sha2_response() {
+        let scramble = [0x40u8; 20];
+        assert_eq!(create_caching_sha2_response("password", &scramble).len(), 32);
+        assert!(create_caching_sha2_response("", &scramble).is_empty());
+    }
 }
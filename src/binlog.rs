@@ -11,18 +11,33 @@
 //!   - Flags (2 bytes)
 //!   - Event Data (variable)
 
+use crate::compression::Compression;
 use crate::error::{CdcError, Result};
 use crate::events::*;
+use crate::gtid::MariadbGtid;
 use crate::offset::SourceInfo;
-use bytes::Buf;
+use crate::server::ServerVersion;
 use byteorder::{LittleEndian, ReadBytesExt};
+use bytes::Bytes;
+use chrono::DateTime;
+use mysql_async::consts::ColumnType;
 use std::io::{Cursor, Read};
 use std::sync::Arc;
 use parking_lot::RwLock;
 use tokio::sync::mpsc;
 
-const BINLOG_MAGIC: &[u8] = &[0xfe, 0x62, 0x69, 0x6e]; // ".bin" in ASCII
-const EVENT_HEADER_SIZE: usize = 19;
+/// binlog 파일 매직 넘버
+pub const BINLOG_MAGIC: &[u8] = &[0xfe, 0x62, 0x69, 0x6e]; // ".bin" in ASCII
+/// 이벤트 헤더 크기 (binlog v4)
+pub const EVENT_HEADER_SIZE: usize = 19;
+
+/// FORMAT_DESCRIPTION 이벤트에 체크섬 알고리즘이 기록되기 시작한 버전
+pub(crate) const CHECKSUM_VERSION: ServerVersion = ServerVersion::new(5, 6, 1);
+
+/// MariaDB GTID flag: BEGIN/COMMIT 없는 단일 이벤트 트랜잭션
+const MARIADB_GTID_FL_STANDALONE: u8 = 0x01;
+/// MariaDB GTID flag: commit_id 포함
+const MARIADB_GTID_FL_GROUP_COMMIT_ID: u8 = 0x02;
 
 /// Binlog 파일 파서
 pub struct BinlogParser;
@@ -75,6 +90,165 @@ impl BinlogParser {
         ))
     }
 
+    /// 이벤트 전체 파싱 (헤더 + 타입별 데이터, 체크섬 없음)
+    ///
+    /// 행 이미지 (`rows_data`) 와 알 수 없는 이벤트의 body 는 `data` 를 복사 없이 공유합니다.
+    /// 행 이벤트의 `rows` 는 TABLE_MAP 이 필요하므로 비워 두며 `decode_rows` 로 채웁니다.
+    pub fn parse_event(data: Bytes) -> Result<BinlogEvent> {
+        Self::parse_event_with_checksum(data, ChecksumAlgorithm::None)
+    }
+
+    /// 체크섬이 붙은 이벤트 파싱 (체크섬을 검증한 뒤 제외하고 파싱)
+    ///
+    /// FORMAT_DESCRIPTION 이벤트는 자신에 기록된 알고리즘으로 검증합니다.
+    pub fn parse_event_with_checksum(
+        data: Bytes,
+        checksum: ChecksumAlgorithm,
+    ) -> Result<BinlogEvent> {
+        let (header, offset) = Self::parse_header(&data)?;
+
+        let end = header.event_length as usize;
+        if end < offset || end > data.len() {
+            return Err(CdcError::BinlogParseError(format!(
+                "Invalid event length: {} (available {})",
+                header.event_length,
+                data.len()
+            )));
+        }
+
+        if header.event_type == EventType::FormatDescriptionEvent {
+            let format = Self::parse_format_description_event(&data[offset..end])?;
+            if format.checksum == ChecksumAlgorithm::Crc32 {
+                verify_checksum(&data[..end])?;
+            }
+            let data = BinlogEventData::FormatDescription(format);
+            return Ok(BinlogEvent { header, data });
+        }
+
+        if checksum == ChecksumAlgorithm::Crc32 {
+            verify_checksum(&data[..end])?;
+        }
+        let end = end
+            .checked_sub(checksum.checksum_len())
+            .filter(|&end| end >= offset)
+            .ok_or_else(|| CdcError::BinlogParseError("Event too short for checksum".to_string()))?;
+        let body = data.slice(offset..end);
+
+        // MariaDB 이벤트 타입 (160~) 은 MySQL 과 겹치지 않으므로 같은 파서에서 처리
+        let data = match header.event_type {
+            EventType::TableMapEvent => {
+                BinlogEventData::TableMap(Self::parse_table_map_event(&body)?)
+            }
+            EventType::WriteRowsEvent
+            | EventType::WriteRowsEventV1
+            | EventType::WriteRowsCompressedEventV1
+            | EventType::WriteRowsCompressedEvent => BinlogEventData::WriteRows(
+                Self::write_rows_event(&body, RowsFormat::of(header.event_type))?,
+            ),
+            EventType::UpdateRowsEvent
+            | EventType::UpdateRowsEventV1
+            | EventType::UpdateRowsCompressedEventV1
+            | EventType::UpdateRowsCompressedEvent => BinlogEventData::UpdateRows(
+                Self::update_rows_event(&body, RowsFormat::of(header.event_type))?,
+            ),
+            EventType::DeleteRowsEvent
+            | EventType::DeleteRowsEventV1
+            | EventType::DeleteRowsCompressedEventV1
+            | EventType::DeleteRowsCompressedEvent => BinlogEventData::DeleteRows(
+                Self::delete_rows_event(&body, RowsFormat::of(header.event_type))?,
+            ),
+            EventType::QueryEvent => BinlogEventData::Query(Self::query_event(&body, false)?),
+            EventType::QueryCompressedEvent => {
+                BinlogEventData::Query(Self::query_event(&body, true)?)
+            }
+            EventType::RotateEvent => BinlogEventData::Rotate(Self::parse_rotate_event(&body)?),
+            EventType::GtidEvent => BinlogEventData::Gtid(Self::parse_gtid_event(&body)?),
+            EventType::XidEvent => BinlogEventData::Xid(Self::parse_xid_event(&body)?),
+            EventType::RowsQueryEvent => {
+                // 1 byte length (무시됨) + 원본 쿼리
+                let query = body.get(1..).unwrap_or_default();
+                BinlogEventData::RowsQuery(String::from_utf8_lossy(query).to_string())
+            }
+            EventType::AnnotateRowsEvent => {
+                BinlogEventData::RowsQuery(String::from_utf8_lossy(&body).to_string())
+            }
+            EventType::MariadbGtidEvent => BinlogEventData::MariadbGtid(
+                Self::parse_mariadb_gtid_event(&body, header.server_id)?,
+            ),
+            EventType::MariadbGtidListEvent => {
+                BinlogEventData::MariadbGtidList(Self::parse_mariadb_gtid_list_event(&body)?)
+            }
+            EventType::BinlogCheckpointEvent => {
+                BinlogEventData::BinlogCheckpoint(Self::parse_binlog_checkpoint_event(&body)?)
+            }
+            EventType::StartEncryptionEvent => {
+                BinlogEventData::StartEncryption(Self::parse_start_encryption_event(&body)?)
+            }
+            _ => BinlogEventData::Unknown(body),
+        };
+
+        Ok(BinlogEvent { header, data })
+    }
+
+    /// 포맷 설명 이벤트 파싱 (15)
+    ///
+    /// binlog_version (2) + server_version (50) + create_timestamp (4) + header_length (1)
+    /// + post-header 길이 배열 [+ checksum 알고리즘 (1) + checksum (4), 5.6.1 이상]
+    pub fn parse_format_description_event(data: &[u8]) -> Result<FormatDescriptionData> {
+        if data.len() < 57 {
+            return Err(CdcError::BinlogParseError(
+                "Invalid format description event".to_string(),
+            ));
+        }
+
+        let mut cursor = Cursor::new(data);
+
+        let binlog_version = cursor.read_u16::<LittleEndian>()?;
+        let mut server_version = [0u8; 50];
+        cursor.read_exact(&mut server_version)?;
+        let server_version_len = server_version.iter().position(|&b| b == 0).unwrap_or(50);
+        let server_version =
+            String::from_utf8_lossy(&server_version[..server_version_len]).to_string();
+        let create_timestamp = cursor.read_u32::<LittleEndian>()?;
+        let header_length = cursor.read_u8()?;
+
+        // 체크섬 알고리즘은 끝에서 5 번째 바이트 (MariaDB 는 "5.5.5-" 없이 10.x 로 기록)
+        let has_checksum = ServerVersion::parse(&server_version)
+            .is_some_and(|version| version >= CHECKSUM_VERSION);
+        let checksum = match data.len().checked_sub(5).filter(|_| has_checksum) {
+            Some(index) => match data[index] {
+                0 | 0xff => ChecksumAlgorithm::None,
+                1 => ChecksumAlgorithm::Crc32,
+                other => {
+                    return Err(CdcError::BinlogParseError(format!(
+                        "Unsupported checksum algorithm: {}",
+                        other
+                    )))
+                }
+            },
+            None => ChecksumAlgorithm::None,
+        };
+
+        Ok(FormatDescriptionData {
+            binlog_version,
+            server_version,
+            create_timestamp,
+            header_length,
+            checksum,
+        })
+    }
+
+    /// XID 이벤트 파싱 (16)
+    pub fn parse_xid_event(data: &[u8]) -> Result<u64> {
+        if data.len() < 8 {
+            return Err(CdcError::BinlogParseError(
+                "Invalid xid event".to_string(),
+            ));
+        }
+
+        Ok(Cursor::new(data).read_u64::<LittleEndian>()?)
+    }
+
     /// 테이블 맵 이벤트 파싱 (19)
     pub fn parse_table_map_event(data: &[u8]) -> Result<TableMapData> {
         if data.len() < 8 {
@@ -86,7 +260,7 @@ impl BinlogParser {
         let mut cursor = Cursor::new(data);
 
         let table_id = cursor.read_u48::<LittleEndian>()
-            .unwrap_or(0) as u64;
+            .unwrap_or(0);
         let _flags = cursor.read_u16::<LittleEndian>()?;
 
         // 데이터베이스명 길이
@@ -94,30 +268,51 @@ impl BinlogParser {
         let mut db_bytes = vec![0u8; db_len];
         cursor.read_exact(&mut db_bytes)?;
         let database = String::from_utf8_lossy(&db_bytes).to_string();
+        cursor.read_u8()?; // null terminator
 
         // 테이블명 길이
         let tbl_len = cursor.read_u8()? as usize;
         let mut tbl_bytes = vec![0u8; tbl_len];
         cursor.read_exact(&mut tbl_bytes)?;
         let table = String::from_utf8_lossy(&tbl_bytes).to_string();
+        cursor.read_u8()?; // null terminator
 
         // 컬럼 개수
         let column_count = read_lcb(&mut cursor)? as usize;
         let mut column_types = vec![0u8; column_count];
         cursor.read_exact(&mut column_types)?;
 
-        // Metadata
+        // Metadata (타입별 0~2 바이트)
         let metadata_length = read_lcb(&mut cursor)? as usize;
-        let mut column_meta = vec![Vec::new(); column_count];
-
-        let mut metadata_cursor = Cursor::new(vec![0u8; metadata_length]);
-        cursor.read_exact(metadata_cursor.get_mut())?;
+        let mut metadata = vec![0u8; metadata_length];
+        cursor.read_exact(&mut metadata)?;
+        let mut metadata = &metadata[..];
+        let mut column_meta = Vec::with_capacity(column_count);
+        for &column_type in &column_types {
+            let length = column_meta_length(column_type);
+            let meta = metadata.get(..length).ok_or_else(|| {
+                CdcError::BinlogParseError("Truncated table map metadata".to_string())
+            })?;
+            column_meta.push(meta.to_vec());
+            metadata = &metadata[length..];
+        }
 
         // nullable bitmap
-        let nullable_count = (column_count + 7) / 8;
+        let nullable_count = column_count.div_ceil(8);
         let mut nullable_bitmap = vec![0u8; nullable_count];
         cursor.read_exact(&mut nullable_bitmap)?;
 
+        // optional metadata (8.0.1 이상, type + length + value) 중 SIGNEDNESS 만 사용
+        let mut unsigned_columns = Vec::new();
+        while let Ok(field_type) = cursor.read_u8() {
+            let length = read_lcb(&mut cursor)? as usize;
+            let mut value = vec![0u8; length];
+            cursor.read_exact(&mut value)?;
+            if field_type == TABLE_MAP_SIGNEDNESS {
+                unsigned_columns = unsigned_columns_from(&column_types, &value);
+            }
+        }
+
         Ok(TableMapData {
             table_id,
             database,
@@ -125,62 +320,65 @@ impl BinlogParser {
             column_types,
             column_meta,
             nullable_bitmap,
+            unsigned_columns,
         })
     }
 
     /// WRITE_ROWS 이벤트 파싱 (30)
-    pub fn parse_write_rows_event(data: &[u8]) -> Result<WriteRowsData> {
+    pub fn parse_write_rows_event(data: &Bytes) -> Result<WriteRowsData> {
+        Self::write_rows_event(data, RowsFormat::V2)
+    }
+
+    /// UPDATE_ROWS 이벤트 파싱 (31)
+    pub fn parse_update_rows_event(data: &Bytes) -> Result<UpdateRowsData> {
+        Self::update_rows_event(data, RowsFormat::V2)
+    }
+
+    /// DELETE_ROWS 이벤트 파싱 (32)
+    pub fn parse_delete_rows_event(data: &Bytes) -> Result<DeleteRowsData> {
+        Self::delete_rows_event(data, RowsFormat::V2)
+    }
+
+    fn write_rows_event(data: &Bytes, format: RowsFormat) -> Result<WriteRowsData> {
         if data.len() < 6 {
             return Err(CdcError::BinlogParseError(
                 "Invalid write rows event".to_string(),
             ));
         }
 
-        let mut cursor = Cursor::new(data);
-
-        let table_id = cursor.read_u48::<LittleEndian>()
-            .unwrap_or(0) as u64;
-        let flags = cursor.read_u16::<LittleEndian>()?;
-
-        // 컬럼 개수
-        let column_count = read_lcb(&mut cursor)?;
+        let mut cursor = Cursor::new(&data[..]);
+        let (table_id, flags, column_count) = read_rows_header(&mut cursor, format)?;
 
         // 컬럼 존재 비트맵
-        let bitmap_bytes = (column_count as usize + 7) / 8;
+        let bitmap_bytes = (column_count as usize).div_ceil(8);
         let mut columns_present = vec![0u8; bitmap_bytes];
         cursor.read_exact(&mut columns_present)?;
 
-        // 행 데이터
-        let rows = parse_row_data(&mut cursor, column_count as usize, &columns_present)?;
+        // 행 데이터 (행은 TABLE_MAP 이 있어야 디코딩 가능)
+        let rows_data = read_rows_data(data, cursor.position() as usize, format)?;
 
         Ok(WriteRowsData {
             table_id,
             flags,
             column_count,
             columns_present,
-            rows,
+            rows: Vec::new(),
+            rows_data,
         })
     }
 
-    /// UPDATE_ROWS 이벤트 파싱 (31)
-    pub fn parse_update_rows_event(data: &[u8]) -> Result<UpdateRowsData> {
+    fn update_rows_event(data: &Bytes, format: RowsFormat) -> Result<UpdateRowsData> {
         if data.len() < 6 {
             return Err(CdcError::BinlogParseError(
                 "Invalid update rows event".to_string(),
             ));
         }
 
-        let mut cursor = Cursor::new(data);
-
-        let table_id = cursor.read_u48::<LittleEndian>()
-            .unwrap_or(0) as u64;
-        let flags = cursor.read_u16::<LittleEndian>()?;
-
-        // 컬럼 개수
-        let column_count = read_lcb(&mut cursor)?;
+        let mut cursor = Cursor::new(&data[..]);
+        let (table_id, flags, column_count) = read_rows_header(&mut cursor, format)?;
 
         // 컬럼 존재 비트맵
-        let bitmap_bytes = (column_count as usize + 7) / 8;
+        let bitmap_bytes = (column_count as usize).div_ceil(8);
         let mut columns_present = vec![0u8; bitmap_bytes];
         cursor.read_exact(&mut columns_present)?;
 
@@ -188,18 +386,8 @@ impl BinlogParser {
         let mut columns_changed = vec![0u8; bitmap_bytes];
         cursor.read_exact(&mut columns_changed)?;
 
-        // 변경 전후 데이터
-        let mut rows = Vec::new();
-        while (cursor.position() as usize) < data.len() {
-            let before = parse_row_data(&mut cursor, column_count as usize, &columns_present)?;
-            if before.is_empty() {
-                break;
-            }
-            let after = parse_row_data(&mut cursor, column_count as usize, &columns_changed)?;
-            if !after.is_empty() {
-                rows.push((before[0].clone(), after[0].clone()));
-            }
-        }
+        // 변경 전후 데이터 (행은 TABLE_MAP 이 있어야 디코딩 가능)
+        let rows_data = read_rows_data(data, cursor.position() as usize, format)?;
 
         Ok(UpdateRowsData {
             table_id,
@@ -207,46 +395,69 @@ impl BinlogParser {
             column_count,
             columns_present,
             columns_changed,
-            rows,
+            rows: Vec::new(),
+            rows_data,
         })
     }
 
-    /// DELETE_ROWS 이벤트 파싱 (32)
-    pub fn parse_delete_rows_event(data: &[u8]) -> Result<DeleteRowsData> {
+    fn delete_rows_event(data: &Bytes, format: RowsFormat) -> Result<DeleteRowsData> {
         if data.len() < 6 {
             return Err(CdcError::BinlogParseError(
                 "Invalid delete rows event".to_string(),
             ));
         }
 
-        let mut cursor = Cursor::new(data);
-
-        let table_id = cursor.read_u48::<LittleEndian>()
-            .unwrap_or(0) as u64;
-        let flags = cursor.read_u16::<LittleEndian>()?;
-
-        // 컬럼 개수
-        let column_count = read_lcb(&mut cursor)?;
+        let mut cursor = Cursor::new(&data[..]);
+        let (table_id, flags, column_count) = read_rows_header(&mut cursor, format)?;
 
         // 컬럼 존재 비트맵
-        let bitmap_bytes = (column_count as usize + 7) / 8;
+        let bitmap_bytes = (column_count as usize).div_ceil(8);
         let mut columns_present = vec![0u8; bitmap_bytes];
         cursor.read_exact(&mut columns_present)?;
 
-        // 행 데이터
-        let rows = parse_row_data(&mut cursor, column_count as usize, &columns_present)?;
+        // 행 데이터 (행은 TABLE_MAP 이 있어야 디코딩 가능)
+        let rows_data = read_rows_data(data, cursor.position() as usize, format)?;
 
         Ok(DeleteRowsData {
             table_id,
             flags,
             column_count,
             columns_present,
-            rows,
+            rows: Vec::new(),
+            rows_data,
         })
     }
 
+    /// 행 이벤트의 `rows` 를 같은 table_id 의 TABLE_MAP 으로 디코딩
+    ///
+    /// `rows_data` 의 모든 행을 컬럼 타입과 메타데이터에 따라 읽습니다. 행 이미지에 없는 컬럼은
+    /// `CellValue::Null` 이고, 행 이벤트가 아니면 아무것도 하지 않습니다.
+    pub fn decode_rows(data: &mut BinlogEventData, table_map: &TableMapData) -> Result<()> {
+        match data {
+            BinlogEventData::WriteRows(rows) => {
+                let mut reader = RowImageReader::new(&rows.rows_data, table_map, rows.column_count)?;
+                rows.rows = reader.read_rows(&rows.columns_present)?;
+            }
+            BinlogEventData::UpdateRows(rows) => {
+                let mut reader = RowImageReader::new(&rows.rows_data, table_map, rows.column_count)?;
+                rows.rows = reader.read_row_pairs(&rows.columns_present, &rows.columns_changed)?;
+            }
+            BinlogEventData::DeleteRows(rows) => {
+                let mut reader = RowImageReader::new(&rows.rows_data, table_map, rows.column_count)?;
+                rows.rows = reader.read_rows(&rows.columns_present)?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
     /// QUERY 이벤트 파싱 (2)
     pub fn parse_query_event(data: &[u8]) -> Result<QueryEventData> {
+        Self::query_event(data, false)
+    }
+
+    /// QUERY / MariaDB QUERY_COMPRESSED 이벤트 파싱 (2, 165)
+    fn query_event(data: &[u8], compressed: bool) -> Result<QueryEventData> {
         if data.len() < 13 {
             return Err(CdcError::BinlogParseError(
                 "Invalid query event".to_string(),
@@ -258,7 +469,7 @@ impl BinlogParser {
         let thread_id = cursor.read_u32::<LittleEndian>()?;
         let exec_time = cursor.read_u32::<LittleEndian>()?;
         let db_len = cursor.read_u8()? as usize;
-        let error_code = cursor.read_u16::<LittleEndian>()?;
+        let _error_code = cursor.read_u16::<LittleEndian>()?;
         let status_len = cursor.read_u16::<LittleEndian>()? as usize;
 
         // Status variables skip
@@ -275,8 +486,12 @@ impl BinlogParser {
         cursor.read_u8().ok();
 
         // 쿼리
-        let remaining = &data[cursor.position() as usize..];
-        let query = String::from_utf8_lossy(remaining).to_string();
+        let remaining = data.get(cursor.position() as usize..).unwrap_or_default();
+        let query = if compressed {
+            String::from_utf8_lossy(&mariadb_uncompress(remaining)?).to_string()
+        } else {
+            String::from_utf8_lossy(remaining).to_string()
+        };
 
         Ok(QueryEventData {
             thread_id,
@@ -329,6 +544,187 @@ impl BinlogParser {
 
         Ok(GtidEventData { gtid, committed })
     }
+
+    /// MariaDB GTID 이벤트 파싱 (162)
+    ///
+    /// sequence (8) + domain_id (4) + flags (1) [+ commit_id (8)]
+    pub fn parse_mariadb_gtid_event(data: &[u8], server_id: u32) -> Result<MariadbGtidEventData> {
+        if data.len() < 13 {
+            return Err(CdcError::BinlogParseError(
+                "Invalid MariaDB GTID event".to_string(),
+            ));
+        }
+
+        let mut cursor = Cursor::new(data);
+
+        let sequence = cursor.read_u64::<LittleEndian>()?;
+        let domain_id = cursor.read_u32::<LittleEndian>()?;
+        let flags = cursor.read_u8()?;
+        let commit_id = if flags & MARIADB_GTID_FL_GROUP_COMMIT_ID != 0 {
+            Some(cursor.read_u64::<LittleEndian>()?)
+        } else {
+            None
+        };
+
+        Ok(MariadbGtidEventData {
+            gtid: MariadbGtid::new(domain_id, server_id, sequence),
+            standalone: flags & MARIADB_GTID_FL_STANDALONE != 0,
+            commit_id,
+        })
+    }
+
+    /// MariaDB GTID_LIST 이벤트 파싱 (163)
+    ///
+    /// count (4, 하위 28 bits) + count * (domain_id (4) + server_id (4) + sequence (8))
+    pub fn parse_mariadb_gtid_list_event(data: &[u8]) -> Result<Vec<MariadbGtid>> {
+        let mut cursor = Cursor::new(data);
+
+        let count = cursor.read_u32::<LittleEndian>()? & 0x0FFF_FFFF;
+        let mut gtids = Vec::with_capacity((count as usize).min(data.len() / 16));
+        for _ in 0..count {
+            let domain_id = cursor.read_u32::<LittleEndian>()?;
+            let server_id = cursor.read_u32::<LittleEndian>()?;
+            let sequence = cursor.read_u64::<LittleEndian>()?;
+            gtids.push(MariadbGtid::new(domain_id, server_id, sequence));
+        }
+
+        Ok(gtids)
+    }
+
+    /// MariaDB BINLOG_CHECKPOINT 이벤트 파싱 (161)
+    pub fn parse_binlog_checkpoint_event(data: &[u8]) -> Result<String> {
+        let mut cursor = Cursor::new(data);
+
+        let length = cursor.read_u32::<LittleEndian>()? as usize;
+        let filename = data.get(4..4 + length).ok_or_else(|| {
+            CdcError::BinlogParseError("Invalid binlog checkpoint event".to_string())
+        })?;
+
+        Ok(String::from_utf8_lossy(filename).to_string())
+    }
+
+    /// MariaDB START_ENCRYPTION 이벤트 파싱 (164)
+    pub fn parse_start_encryption_event(data: &[u8]) -> Result<StartEncryptionData> {
+        let mut cursor = Cursor::new(data);
+
+        let scheme = cursor.read_u8()?;
+        let key_version = cursor.read_u32::<LittleEndian>()?;
+        let mut nonce = [0u8; 12];
+        cursor.read_exact(&mut nonce)?;
+
+        Ok(StartEncryptionData {
+            scheme,
+            key_version,
+            nonce,
+        })
+    }
+}
+
+/// 이벤트 끝 4 바이트의 CRC32 검증 (헤더부터 체크섬 앞까지)
+fn verify_checksum(event: &[u8]) -> Result<()> {
+    let split = event
+        .len()
+        .checked_sub(4)
+        .ok_or_else(|| CdcError::BinlogParseError("Event too short for checksum".to_string()))?;
+    let (data, checksum) = event.split_at(split);
+
+    let expected = u32::from_le_bytes([checksum[0], checksum[1], checksum[2], checksum[3]]);
+    let actual = crc32fast::hash(data);
+    if actual != expected {
+        return Err(CdcError::BinlogParseError(format!(
+            "Event checksum mismatch: expected {:08x}, got {:08x}",
+            expected, actual
+        )));
+    }
+
+    Ok(())
+}
+
+/// 행 이벤트 형식 (v1/v2, MariaDB 압축 여부)
+#[derive(Debug, Clone, Copy)]
+struct RowsFormat {
+    /// v2 이벤트는 flags 뒤에 extra data 가 있음
+    v2: bool,
+    /// MariaDB 압축 행 이벤트는 행 이미지가 zlib 으로 압축됨
+    compressed: bool,
+}
+
+impl RowsFormat {
+    const V2: RowsFormat = RowsFormat {
+        v2: true,
+        compressed: false,
+    };
+
+    fn of(event_type: EventType) -> Self {
+        match event_type {
+            EventType::WriteRowsEventV1
+            | EventType::UpdateRowsEventV1
+            | EventType::DeleteRowsEventV1 => RowsFormat {
+                v2: false,
+                compressed: false,
+            },
+            EventType::WriteRowsCompressedEventV1
+            | EventType::UpdateRowsCompressedEventV1
+            | EventType::DeleteRowsCompressedEventV1 => RowsFormat {
+                v2: false,
+                compressed: true,
+            },
+            EventType::WriteRowsCompressedEvent
+            | EventType::UpdateRowsCompressedEvent
+            | EventType::DeleteRowsCompressedEvent => RowsFormat {
+                v2: true,
+                compressed: true,
+            },
+            _ => RowsFormat::V2,
+        }
+    }
+}
+
+/// 행 이벤트 공통 헤더 읽기 (table_id, flags, 컬럼 개수)
+fn read_rows_header(cursor: &mut Cursor<&[u8]>, format: RowsFormat) -> Result<(u64, u16, u64)> {
+    let table_id = cursor.read_u48::<LittleEndian>().unwrap_or(0);
+    let flags = cursor.read_u16::<LittleEndian>()?;
+
+    // v2 extra data (길이 2 바이트 자신을 포함)
+    if format.v2 {
+        let extra_len = cursor.read_u16::<LittleEndian>()? as u64;
+        cursor.set_position(cursor.position() + extra_len.saturating_sub(2));
+    }
+
+    // 컬럼 개수
+    let column_count = read_lcb(cursor)?;
+
+    Ok((table_id, flags, column_count))
+}
+
+/// 행 이미지 (압축 이벤트는 압축 해제, 그 외에는 복사 없이 공유)
+fn read_rows_data(data: &Bytes, offset: usize, format: RowsFormat) -> Result<Bytes> {
+    let rows_data = data.slice(offset.min(data.len())..);
+    if format.compressed {
+        return Ok(Bytes::from(mariadb_uncompress(&rows_data)?));
+    }
+    Ok(rows_data)
+}
+
+/// MariaDB 압축 데이터 해제
+///
+/// 0x80 | 길이 바이트 수 (1 byte) + 원본 길이 (big endian) + zlib 데이터
+fn mariadb_uncompress(data: &[u8]) -> Result<Vec<u8>> {
+    let invalid = || CdcError::BinlogParseError("Invalid MariaDB compressed data".to_string());
+
+    let (&header, rest) = data.split_first().ok_or_else(invalid)?;
+    let length_bytes = (header & 0x07) as usize;
+    if header & 0x80 == 0 || !(1..=4).contains(&length_bytes) {
+        return Err(invalid());
+    }
+
+    let length = rest
+        .get(..length_bytes)
+        .ok_or_else(invalid)?
+        .iter()
+        .fold(0usize, |length, &b| length << 8 | b as usize);
+
+    Compression::Zlib.decompress(&rest[length_bytes..], length)
 }
 
 /// LCB (Length-Coded Binary) 읽기
@@ -337,7 +733,7 @@ fn read_lcb(cursor: &mut Cursor<&[u8]>) -> Result<u64> {
     match byte {
         0..=0xfa => Ok(byte as u64),
         0xfb => Ok(0),
-        0xfc => Ok(cursor.read_u24::<LittleEndian>()? as u64),
+        0xfc => Ok(cursor.read_u16::<LittleEndian>()? as u64),
         0xfd => Ok(cursor.read_u24::<LittleEndian>()? as u64),
         0xfe => Ok(cursor.read_u64::<LittleEndian>()?),
         0xff => Err(CdcError::BinlogParseError("Invalid LCB value".to_string())),
@@ -345,7 +741,7 @@ fn read_lcb(cursor: &mut Cursor<&[u8]>) -> Result<u64> {
 }
 
 /// UUID 바이트 배열을 문자열로 변환
-fn format_uuid(bytes: &[u8; 16]) -> String {
+pub(crate) fn format_uuid(bytes: &[u8; 16]) -> String {
     format!(
         "{:02x}{:02x}{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}{:02x}{:02x}{:02x}{:02x}",
         bytes[0], bytes[1], bytes[2], bytes[3],
@@ -356,89 +752,510 @@ fn format_uuid(bytes: &[u8; 16]) -> String {
     )
 }
 
-/// 행 데이터 파싱
-fn parse_row_data(
-    cursor: &mut Cursor<&[u8]>,
-    column_count: usize,
-    present_bitmap: &[u8],
-) -> Result<Vec<Vec<CellValue>>> {
-    let mut rows = Vec::new();
-    let mut row = Vec::new();
+/// TABLE_MAP optional metadata 중 숫자 컬럼의 UNSIGNED 비트맵
+pub(crate) const TABLE_MAP_SIGNEDNESS: u8 = 1;
+
+/// DECIMAL 의 남은 자릿수 (0~8) 를 저장하는 바이트 수
+pub(crate) const DIG2BYTES: [usize; 10] = [0, 1, 1, 2, 2, 3, 3, 4, 4, 4];
+
+/// TABLE_MAP 에 기록되는 컬럼 타입별 메타데이터 길이
+pub(crate) fn column_meta_length(column_type: u8) -> usize {
+    match ColumnType::try_from(column_type) {
+        Ok(
+            ColumnType::MYSQL_TYPE_FLOAT
+            | ColumnType::MYSQL_TYPE_DOUBLE
+            | ColumnType::MYSQL_TYPE_TINY_BLOB
+            | ColumnType::MYSQL_TYPE_MEDIUM_BLOB
+            | ColumnType::MYSQL_TYPE_LONG_BLOB
+            | ColumnType::MYSQL_TYPE_BLOB
+            | ColumnType::MYSQL_TYPE_JSON
+            | ColumnType::MYSQL_TYPE_GEOMETRY
+            | ColumnType::MYSQL_TYPE_VECTOR
+            | ColumnType::MYSQL_TYPE_TIMESTAMP2
+            | ColumnType::MYSQL_TYPE_DATETIME2
+            | ColumnType::MYSQL_TYPE_TIME2,
+        ) => 1,
+        Ok(
+            ColumnType::MYSQL_TYPE_VARCHAR
+            | ColumnType::MYSQL_TYPE_VAR_STRING
+            | ColumnType::MYSQL_TYPE_STRING
+            | ColumnType::MYSQL_TYPE_ENUM
+            | ColumnType::MYSQL_TYPE_SET
+            | ColumnType::MYSQL_TYPE_NEWDECIMAL
+            | ColumnType::MYSQL_TYPE_BIT,
+        ) => 2,
+        _ => 0,
+    }
+}
 
-    for col_idx in 0..column_count {
-        let byte_idx = col_idx / 8;
-        let bit_idx = col_idx % 8;
+/// SIGNEDNESS 비트맵 (숫자 컬럼마다 1 bit, 최상위 비트부터) 을 컬럼별 UNSIGNED 여부로 변환
+fn unsigned_columns_from(column_types: &[u8], bitmap: &[u8]) -> Vec<bool> {
+    let mut numeric_index = 0;
+    column_types
+        .iter()
+        .map(|&column_type| {
+            if !is_numeric_type(column_type) {
+                return false;
+            }
+            let index = numeric_index;
+            numeric_index += 1;
+            bitmap
+                .get(index / 8)
+                .is_some_and(|byte| byte & (0x80 >> (index % 8)) != 0)
+        })
+        .collect()
+}
 
-        if byte_idx >= present_bitmap.len() {
-            row.push(CellValue::Null);
-            continue;
-        }
+/// SIGNEDNESS 비트맵에 포함되는 숫자 타입
+pub(crate) fn is_numeric_type(column_type: u8) -> bool {
+    matches!(
+        ColumnType::try_from(column_type),
+        Ok(ColumnType::MYSQL_TYPE_TINY
+            | ColumnType::MYSQL_TYPE_SHORT
+            | ColumnType::MYSQL_TYPE_INT24
+            | ColumnType::MYSQL_TYPE_LONG
+            | ColumnType::MYSQL_TYPE_LONGLONG
+            | ColumnType::MYSQL_TYPE_NEWDECIMAL
+            | ColumnType::MYSQL_TYPE_FLOAT
+            | ColumnType::MYSQL_TYPE_DOUBLE)
+    )
+}
 
-        let is_present = (present_bitmap[byte_idx] & (1 << bit_idx)) != 0;
+/// CHAR / ENUM / SET (MYSQL_TYPE_STRING) 메타데이터에서 실제 타입과 최대 길이
+///
+/// 최대 길이가 255 를 넘으면 상위 2 bit 가 첫 바이트의 0x30 비트에 반전되어 저장됩니다.
+pub(crate) fn string_type(meta: &[u8]) -> (u8, usize) {
+    let (&byte0, &byte1) = match meta {
+        [byte0, byte1, ..] => (byte0, byte1),
+        _ => return (ColumnType::MYSQL_TYPE_STRING as u8, 0),
+    };
+    if byte0 & 0x30 != 0x30 {
+        (byte0 | 0x30, byte1 as usize | (((byte0 & 0x30) ^ 0x30) as usize) << 4)
+    } else {
+        (byte0, byte1 as usize)
+    }
+}
 
-        if !is_present {
-            row.push(CellValue::Null);
-        } else {
-            // 컬럼 타입에 따라 파싱 (간단한 구현)
-            if let Ok(byte) = cursor.read_u8() {
-                match byte {
-                    0 => row.push(CellValue::Null),
-                    1 => {
-                        if let Ok(val) = cursor.read_u8() {
-                            row.push(CellValue::UInt8(val));
-                        }
-                    }
-                    2 => {
-                        if let Ok(val) = cursor.read_u16::<LittleEndian>() {
-                            row.push(CellValue::UInt16(val));
-                        }
-                    }
-                    4 => {
-                        if let Ok(val) = cursor.read_u32::<LittleEndian>() {
-                            row.push(CellValue::UInt32(val));
-                        }
-                    }
-                    _ => row.push(CellValue::Bytes(vec![byte])),
-                }
+/// 비트맵의 `index` 번째 bit (최하위 비트부터)
+pub(crate) fn bitmap_bit(bitmap: &[u8], index: usize) -> bool {
+    bitmap
+        .get(index / 8)
+        .is_some_and(|byte| byte & (1 << (index % 8)) != 0)
+}
+
+/// DECIMAL(precision, scale) 의 이진 형식 길이
+pub(crate) fn decimal_length(precision: u8, scale: u8) -> usize {
+    let integer = precision.saturating_sub(scale) as usize;
+    let scale = scale as usize;
+    integer / 9 * 4 + DIG2BYTES[integer % 9] + scale / 9 * 4 + DIG2BYTES[scale % 9]
+}
+
+/// 소수 초 자릿수 (fsp) 별 저장 바이트 수
+pub(crate) fn fraction_length(fsp: u8) -> usize {
+    (fsp.min(6) as usize).div_ceil(2)
+}
+
+fn le_uint(data: &[u8]) -> u64 {
+    data.iter().rev().fold(0, |value, &b| value << 8 | b as u64)
+}
+
+fn be_uint(data: &[u8]) -> u64 {
+    data.iter().fold(0, |value, &b| value << 8 | b as u64)
+}
+
+/// 소수 초 (big endian) 를 마이크로초로 변환
+fn fraction_micros(data: &[u8], fsp: u8) -> u32 {
+    let value = be_uint(data) as u32;
+    match fsp {
+        1 | 2 => value * 10000,
+        3 | 4 => value * 100,
+        _ => value,
+    }
+}
+
+/// `.123` 형식의 소수 초 (fsp 자리까지)
+fn format_fraction(micros: u32, fsp: u8) -> String {
+    match fsp {
+        1..=6 => format!(".{:06}", micros)[..=fsp as usize].to_string(),
+        _ => String::new(),
+    }
+}
+
+/// 날짜와 시각을 `CellValue::DateTime` 으로 (0000-00-00 같은 값은 문자열 그대로)
+fn datetime_value(date: (u32, u32, u32), time: (u32, u32, u32), micros: u32, fsp: u8) -> CellValue {
+    let (year, month, day) = date;
+    let (hour, minute, second) = time;
+    chrono::NaiveDate::from_ymd_opt(year as i32, month, day)
+        .and_then(|date| date.and_hms_micro_opt(hour, minute, second, micros))
+        .map(|datetime| CellValue::DateTime(datetime.and_utc()))
+        .unwrap_or_else(|| {
+            CellValue::String(format!(
+                "{:04}-{:02}-{:02} {:02}:{:02}:{:02}{}",
+                year,
+                month,
+                day,
+                hour,
+                minute,
+                second,
+                format_fraction(micros, fsp)
+            ))
+        })
+}
+
+/// DECIMAL 이진 형식 디코딩
+///
+/// 정수부와 소수부를 9 자리씩 4 바이트 (big endian) 에 저장하고 남은 자리는 `DIG2BYTES` 바이트에
+/// 저장합니다. 첫 바이트의 최상위 비트가 부호 (1 = 양수) 이며 음수는 모든 바이트가 반전됩니다.
+fn decode_decimal(data: &[u8], precision: u8, scale: u8) -> String {
+    let negative = data[0] & 0x80 == 0;
+    let mask = if negative { 0xff } else { 0 };
+    let mut bytes: Vec<u8> = data.iter().map(|b| b ^ mask).collect();
+    bytes[0] ^= 0x80;
+
+    let integer = precision.saturating_sub(scale) as usize;
+    let scale = scale as usize;
+    let mut rest = &bytes[..];
+    let mut take = |length: usize| {
+        let (group, tail) = rest.split_at(length);
+        rest = tail;
+        be_uint(group)
+    };
+
+    let mut integer_digits = String::new();
+    let leading = integer % 9;
+    if leading > 0 {
+        integer_digits.push_str(&take(DIG2BYTES[leading]).to_string());
+    }
+    for _ in 0..integer / 9 {
+        integer_digits.push_str(&format!("{:09}", take(4)));
+    }
+    let integer_digits = match integer_digits.trim_start_matches('0') {
+        "" => "0",
+        digits => digits,
+    };
+
+    let mut fraction_digits = String::new();
+    for _ in 0..scale / 9 {
+        fraction_digits.push_str(&format!("{:09}", take(4)));
+    }
+    let trailing = scale % 9;
+    if trailing > 0 {
+        fraction_digits
+            .push_str(&format!("{:0width$}", take(DIG2BYTES[trailing]), width = trailing));
+    }
+
+    let sign = if negative { "-" } else { "" };
+    match scale {
+        0 => format!("{}{}", sign, integer_digits),
+        _ => format!("{}{}.{}", sign, integer_digits, fraction_digits),
+    }
+}
+
+/// TIME2 디코딩 (3 바이트 정수부 + 소수 초, 0x800000 을 더해 부호 없이 저장)
+fn decode_time2(data: &[u8], fsp: u8) -> String {
+    let mut integer = be_uint(&data[..3]) as i64 - 0x800000;
+    let packed = match fsp {
+        1 | 2 => {
+            let mut fraction = data[3] as i64;
+            if integer < 0 && fraction != 0 {
+                integer += 1;
+                fraction -= 0x100;
             }
+            (integer << 24) + fraction * 10000
         }
+        3 | 4 => {
+            let mut fraction = be_uint(&data[3..5]) as i64;
+            if integer < 0 && fraction != 0 {
+                integer += 1;
+                fraction -= 0x10000;
+            }
+            (integer << 24) + fraction * 100
+        }
+        5 | 6 => be_uint(&data[..6]) as i64 - 0x800000000000,
+        _ => integer << 24,
+    };
+
+    let sign = if packed < 0 { "-" } else { "" };
+    let packed = packed.unsigned_abs();
+    let hms = packed >> 24;
+    format!(
+        "{}{:02}:{:02}:{:02}{}",
+        sign,
+        (hms >> 12) % (1 << 10),
+        (hms >> 6) % (1 << 6),
+        hms % (1 << 6),
+        format_fraction((packed % (1 << 24)) as u32, fsp)
+    )
+}
+
+/// 행 이미지 읽기 (TABLE_MAP 의 컬럼 타입과 메타데이터 사용)
+///
+/// BLOB / TEXT / VARCHAR / JSON 셀은 `rows_data` 를 복사 없이 공유하는 `Bytes` 입니다.
+struct RowImageReader<'a> {
+    data: &'a Bytes,
+    position: usize,
+    table_map: &'a TableMapData,
+}
+
+impl<'a> RowImageReader<'a> {
+    fn new(data: &'a Bytes, table_map: &'a TableMapData, column_count: u64) -> Result<Self> {
+        let columns = table_map.column_types.len();
+        if column_count as usize != columns || table_map.column_meta.len() != columns {
+            return Err(CdcError::BinlogParseError(format!(
+                "Rows event has {} columns but table map of {}.{} has {}",
+                column_count, table_map.database, table_map.table, columns
+            )));
+        }
+
+        Ok(RowImageReader {
+            data,
+            position: 0,
+            table_map,
+        })
     }
 
-    if !row.is_empty() {
-        rows.push(row);
+    fn is_empty(&self) -> bool {
+        self.position >= self.data.len()
     }
 
-    Ok(rows)
-}
+    fn take(&mut self, length: usize) -> Result<&'a [u8]> {
+        let data: &'a Bytes = self.data;
+        let end = self
+            .position
+            .checked_add(length)
+            .filter(|&end| end <= data.len())
+            .ok_or_else(|| CdcError::BinlogParseError("Truncated row image".to_string()))?;
+        let bytes = &data[self.position..end];
+        self.position = end;
+        Ok(bytes)
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// 길이 (`length_bytes` 바이트, little endian) + 값
+    fn read_bytes(&mut self, length_bytes: usize) -> Result<CellValue> {
+        let length = le_uint(self.take(length_bytes)?) as usize;
+        let value = self.take(length)?;
+        Ok(CellValue::Bytes(self.data.slice_ref(value)))
+    }
 
-    #[test]
-    fn test_verify_magic() {
-        let valid = vec![0xfe, 0x62, 0x69, 0x6e];
-        assert!(BinlogParser::verify_magic(&valid).is_ok());
+    /// `rows_data` 가 끝날 때까지 행을 읽음
+    fn read_rows(&mut self, present: &[u8]) -> Result<Vec<Vec<CellValue>>> {
+        let mut rows = Vec::new();
+        while !self.is_empty() {
+            rows.push(self.read_row(present)?);
+        }
+        Ok(rows)
+    }
 
-        let invalid = vec![0x00, 0x00, 0x00, 0x00];
-        assert!(BinlogParser::verify_magic(&invalid).is_err());
+    /// 변경 전 이미지 (`before`) 와 변경 후 이미지 (`after`) 가 번갈아 나오는 UPDATE 행
+    fn read_row_pairs(
+        &mut self,
+        before: &[u8],
+        after: &[u8],
+    ) -> Result<Vec<(Vec<CellValue>, Vec<CellValue>)>> {
+        let mut rows = Vec::new();
+        while !self.is_empty() {
+            let before = self.read_row(before)?;
+            rows.push((before, self.read_row(after)?));
+        }
+        Ok(rows)
     }
 
-    #[test]
-    fn test_format_uuid() {
-        let bytes = [0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0xde, 0xf0,
-                     0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0xde, 0xf0];
-        let uuid = format_uuid(&bytes);
-        assert!(uuid.contains('-'));
-        assert_eq!(uuid.len(), 36);
+    /// null 비트맵 (이미지에 있는 컬럼마다 1 bit) + NULL 이 아닌 컬럼 값
+    fn read_row(&mut self, present: &[u8]) -> Result<Vec<CellValue>> {
+        let start = self.position;
+        let columns = self.table_map.column_types.len();
+        let present_count = (0..columns).filter(|&i| bitmap_bit(present, i)).count();
+        let null_bitmap = self.take(present_count.div_ceil(8))?;
+
+        let mut row = Vec::with_capacity(columns);
+        let mut present_index = 0;
+        for column in 0..columns {
+            if !bitmap_bit(present, column) {
+                row.push(CellValue::Null);
+                continue;
+            }
+            let is_null = bitmap_bit(null_bitmap, present_index);
+            present_index += 1;
+            row.push(if is_null { CellValue::Null } else { self.read_cell(column)? });
+        }
+
+        if self.position == start {
+            return Err(CdcError::BinlogParseError("Empty row image".to_string()));
+        }
+        Ok(row)
     }
 
-    #[test]
-    fn test_binlog_client() {
-        let client = BinlogClient::new(1, "mysql-bin.000001".to_string());
-        let info = client.get_source_info();
-        assert_eq!(info.server_id, 1);
-        assert_eq!(info.binlog_filename, "mysql-bin.000001");
+    fn read_cell(&mut self, column: usize) -> Result<CellValue> {
+        let column_type = self.table_map.column_types[column];
+        let meta = &self.table_map.column_meta[column];
+        let unsigned = self.table_map.unsigned_columns.get(column).copied().unwrap_or(false);
+        let meta_byte = |index: usize| meta.get(index).copied().unwrap_or(0);
+
+        let value = match ColumnType::try_from(column_type) {
+            Ok(ColumnType::MYSQL_TYPE_NULL) => CellValue::Null,
+            Ok(ColumnType::MYSQL_TYPE_TINY) => {
+                let value = self.take(1)?[0];
+                match unsigned {
+                    true => CellValue::UInt8(value),
+                    false => CellValue::Int8(value as i8),
+                }
+            }
+            Ok(ColumnType::MYSQL_TYPE_SHORT) => {
+                let value = le_uint(self.take(2)?) as u16;
+                match unsigned {
+                    true => CellValue::UInt16(value),
+                    false => CellValue::Int16(value as i16),
+                }
+            }
+            Ok(ColumnType::MYSQL_TYPE_INT24) => {
+                let value = le_uint(self.take(3)?) as u32;
+                match unsigned {
+                    true => CellValue::UInt32(value),
+                    false => CellValue::Int32(((value << 8) as i32) >> 8),
+                }
+            }
+            Ok(ColumnType::MYSQL_TYPE_LONG) => {
+                let value = le_uint(self.take(4)?) as u32;
+                match unsigned {
+                    true => CellValue::UInt32(value),
+                    false => CellValue::Int32(value as i32),
+                }
+            }
+            Ok(ColumnType::MYSQL_TYPE_LONGLONG) => {
+                let value = le_uint(self.take(8)?);
+                match unsigned {
+                    true => CellValue::UInt64(value),
+                    false => CellValue::Int64(value as i64),
+                }
+            }
+            Ok(ColumnType::MYSQL_TYPE_FLOAT) => {
+                CellValue::Float(f32::from_bits(le_uint(self.take(4)?) as u32))
+            }
+            Ok(ColumnType::MYSQL_TYPE_DOUBLE) => {
+                CellValue::Double(f64::from_bits(le_uint(self.take(8)?)))
+            }
+            Ok(ColumnType::MYSQL_TYPE_YEAR) => match self.take(1)?[0] {
+                0 => CellValue::UInt16(0),
+                year => CellValue::UInt16(1900 + year as u16),
+            },
+            Ok(ColumnType::MYSQL_TYPE_NEWDECIMAL) => {
+                let (precision, scale) = (meta_byte(0), meta_byte(1));
+                let data = self.take(decimal_length(precision, scale))?;
+                if data.is_empty() {
+                    return Err(CdcError::BinlogParseError("Invalid decimal metadata".to_string()));
+                }
+                CellValue::Decimal(decode_decimal(data, precision, scale))
+            }
+            Ok(ColumnType::MYSQL_TYPE_DATE) => {
+                // day (5 bit) + month (4 bit) + year
+                let value = le_uint(self.take(3)?) as u32;
+                CellValue::Date(format!(
+                    "{:04}-{:02}-{:02}",
+                    value >> 9,
+                    (value >> 5) & 0x0f,
+                    value & 0x1f
+                ))
+            }
+            Ok(ColumnType::MYSQL_TYPE_TIME) => {
+                // HHMMSS (부호 있는 3 바이트)
+                let value = ((le_uint(self.take(3)?) as i32) << 8) >> 8;
+                let sign = if value < 0 { "-" } else { "" };
+                let value = value.unsigned_abs();
+                CellValue::Time(format!(
+                    "{}{:02}:{:02}:{:02}",
+                    sign,
+                    value / 10000,
+                    value / 100 % 100,
+                    value % 100
+                ))
+            }
+            Ok(ColumnType::MYSQL_TYPE_TIME2) => {
+                let fsp = meta_byte(0);
+                let data = self.take(3 + fraction_length(fsp))?;
+                CellValue::Time(decode_time2(data, fsp))
+            }
+            Ok(ColumnType::MYSQL_TYPE_TIMESTAMP) => {
+                let seconds = le_uint(self.take(4)?) as i64;
+                CellValue::DateTime(DateTime::from_timestamp(seconds, 0).unwrap_or_default())
+            }
+            Ok(ColumnType::MYSQL_TYPE_TIMESTAMP2) => {
+                // 초 (big endian) + 소수 초
+                let fsp = meta_byte(0);
+                let seconds = be_uint(self.take(4)?) as i64;
+                let micros = fraction_micros(self.take(fraction_length(fsp))?, fsp);
+                CellValue::DateTime(
+                    DateTime::from_timestamp(seconds, micros * 1000).unwrap_or_default(),
+                )
+            }
+            Ok(ColumnType::MYSQL_TYPE_DATETIME) => {
+                // YYYYMMDDhhmmss (8 바이트 정수)
+                let value = le_uint(self.take(8)?);
+                let (date, time) = ((value / 1_000_000) as u32, (value % 1_000_000) as u32);
+                datetime_value(
+                    (date / 10000, date / 100 % 100, date % 100),
+                    (time / 10000, time / 100 % 100, time % 100),
+                    0,
+                    0,
+                )
+            }
+            Ok(ColumnType::MYSQL_TYPE_DATETIME2) => {
+                // 부호 1 bit + year*13+month 17 bit + day 5 + hour 5 + minute 6 + second 6
+                let fsp = meta_byte(0);
+                let value = be_uint(self.take(5)?).wrapping_sub(0x80_0000_0000);
+                let micros = fraction_micros(self.take(fraction_length(fsp))?, fsp);
+                let (ymd, hms) = ((value >> 17) as u32, (value % (1 << 17)) as u32);
+                let year_month = ymd >> 5;
+                datetime_value(
+                    (year_month / 13, year_month % 13, ymd % (1 << 5)),
+                    (hms >> 12, (hms >> 6) % (1 << 6), hms % (1 << 6)),
+                    micros,
+                    fsp,
+                )
+            }
+            Ok(ColumnType::MYSQL_TYPE_VARCHAR | ColumnType::MYSQL_TYPE_VAR_STRING) => {
+                let max_length = meta_byte(0) as usize | (meta_byte(1) as usize) << 8;
+                self.read_bytes(if max_length > 255 { 2 } else { 1 })?
+            }
+            Ok(
+                ColumnType::MYSQL_TYPE_STRING
+                | ColumnType::MYSQL_TYPE_ENUM
+                | ColumnType::MYSQL_TYPE_SET,
+            ) => {
+                let (real_type, max_length) = string_type(meta);
+                match ColumnType::try_from(real_type) {
+                    // ENUM 은 1 부터 시작하는 값 번호, SET 은 값마다 1 bit
+                    Ok(ColumnType::MYSQL_TYPE_ENUM) => {
+                        CellValue::UInt16(le_uint(self.take(max_length.clamp(1, 2))?) as u16)
+                    }
+                    Ok(ColumnType::MYSQL_TYPE_SET) => {
+                        CellValue::UInt64(le_uint(self.take(max_length.clamp(1, 8))?))
+                    }
+                    _ => self.read_bytes(if max_length > 255 { 2 } else { 1 })?,
+                }
+            }
+            Ok(
+                ColumnType::MYSQL_TYPE_TINY_BLOB
+                | ColumnType::MYSQL_TYPE_MEDIUM_BLOB
+                | ColumnType::MYSQL_TYPE_LONG_BLOB
+                | ColumnType::MYSQL_TYPE_BLOB
+                | ColumnType::MYSQL_TYPE_JSON
+                | ColumnType::MYSQL_TYPE_GEOMETRY
+                | ColumnType::MYSQL_TYPE_VECTOR,
+            ) => self.read_bytes((meta_byte(0) as usize).clamp(1, 4))?,
+            Ok(ColumnType::MYSQL_TYPE_BIT) => {
+                // 남은 bit 수 + 바이트 수, big endian
+                let length = meta_byte(1) as usize + usize::from(meta_byte(0) > 0);
+                CellValue::UInt64(be_uint(self.take(length.min(8))?))
+            }
+            _ => {
+                return Err(CdcError::BinlogParseError(format!(
+                    "Unsupported column type {} in row image",
+                    column_type
+                )))
+            }
+        };
+
+        Ok(value)
     }
 }
 
@@ -456,7 +1273,7 @@ impl BinlogClient {
 
     /// Binlog 이벤트 스트림 시작
     pub async fn stream_events(&self) -> Result<mpsc::UnboundedReceiver<BinlogEvent>> {
-        let (tx, rx) = mpsc::unbounded_channel();
+        let (_tx, rx) = mpsc::unbounded_channel();
 
         // 실제 구현에서는 MySQL replication protocol로 이벤트 수신
         // 여기서는 구조만 제시
@@ -478,3 +1295,427 @@ impl BinlogClient {
         f(&mut info);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{format_description_body, RawEvent};
+
+    #[test]
+    fn test_verify_magic() {
+        let valid = vec![0xfe, 0x62, 0x69, 0x6e];
+        assert!(BinlogParser::verify_magic(&valid).is_ok());
+
+        let invalid = vec![0x00, 0x00, 0x00, 0x00];
+        assert!(BinlogParser::verify_magic(&invalid).is_err());
+    }
+
+    #[test]
+    fn test_format_uuid() {
+        let bytes = [0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0xde, 0xf0,
+                     0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0xde, 0xf0];
+        let uuid = format_uuid(&bytes);
+        assert!(uuid.contains('-'));
+        assert_eq!(uuid.len(), 36);
+    }
+
+    #[test]
+    fn test_parse_event_shares_buffer() {
+        let data = event(0xC8, &[7, 8, 9]); // 알 수 없는 타입
+
+        let event = BinlogParser::parse_event(data.clone()).unwrap();
+        match event.data {
+            BinlogEventData::Unknown(body) => {
+                assert_eq!(body, vec![7, 8, 9]);
+                assert_eq!(body.as_ptr(), data[EVENT_HEADER_SIZE..].as_ptr());
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+    }
+
+    /// 체크섬 없는 이벤트 (server_id 1)
+    fn event(event_type: u8, body: &[u8]) -> Bytes {
+        Bytes::from(RawEvent::new(event_type, body).build())
+    }
+
+    /// MariaDB 압축 형식 (0x80 | 4, big endian 길이, zlib)
+    fn mariadb_compress(data: &[u8]) -> Vec<u8> {
+        let mut compressed = vec![0x84];
+        compressed.extend_from_slice(&(data.len() as u32).to_be_bytes());
+        compressed.extend_from_slice(&Compression::Zlib.compress(data).unwrap());
+        compressed
+    }
+
+    #[test]
+    fn test_parse_mariadb_gtid_events() {
+        let mut body = 100u64.to_le_bytes().to_vec();
+        body.extend_from_slice(&1u32.to_le_bytes());
+        body.push(MARIADB_GTID_FL_STANDALONE | MARIADB_GTID_FL_GROUP_COMMIT_ID);
+        body.extend_from_slice(&7u64.to_le_bytes());
+
+        let gtid = RawEvent::new(162, &body).server_id(3).build();
+        let gtid = BinlogParser::parse_event(Bytes::from(gtid)).unwrap();
+        match gtid.data {
+            BinlogEventData::MariadbGtid(data) => {
+                assert_eq!(data.gtid, MariadbGtid::new(1, 3, 100));
+                assert!(data.standalone);
+                assert_eq!(data.commit_id, Some(7));
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+
+        let mut body = 2u32.to_le_bytes().to_vec();
+        for (domain, server, sequence) in [(0u32, 1u32, 10u64), (1, 2, 20)] {
+            body.extend_from_slice(&domain.to_le_bytes());
+            body.extend_from_slice(&server.to_le_bytes());
+            body.extend_from_slice(&sequence.to_le_bytes());
+        }
+        let list = BinlogParser::parse_event(event(163, &body)).unwrap();
+        match list.data {
+            BinlogEventData::MariadbGtidList(gtids) => {
+                assert_eq!(gtids, vec![MariadbGtid::new(0, 1, 10), MariadbGtid::new(1, 2, 20)]);
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+
+        let mut body = 16u32.to_le_bytes().to_vec();
+        body.extend_from_slice(b"mariadb-bin.0003");
+        let checkpoint = BinlogParser::parse_event(event(161, &body)).unwrap();
+        assert!(matches!(
+            checkpoint.data,
+            BinlogEventData::BinlogCheckpoint(name) if name == "mariadb-bin.0003"
+        ));
+    }
+
+    #[test]
+    fn test_parse_mariadb_compressed_events() {
+        // QUERY_COMPRESSED: 쿼리 텍스트만 압축
+        let mut body = vec![0u8; 13];
+        body[8] = 4; // db_len
+        body.extend_from_slice(b"shop\0");
+        body.extend_from_slice(&mariadb_compress(b"CREATE TABLE t (id INT)"));
+        let query = BinlogParser::parse_event(event(165, &body)).unwrap();
+        match query.data {
+            BinlogEventData::Query(data) => {
+                assert_eq!(data.database, "shop");
+                assert_eq!(data.query, "CREATE TABLE t (id INT)");
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+
+        // WRITE_ROWS_COMPRESSED_V1: 비트맵 이후 행 이미지만 압축, extra data 없음
+        let row_image = [0x00, 0xAB, 0xCD];
+        let mut body = vec![0u8; 6 + 2];
+        body.push(1); // column count
+        body.push(0x01); // columns present
+        body.extend_from_slice(&mariadb_compress(&row_image));
+        let rows = BinlogParser::parse_event(event(166, &body)).unwrap();
+        match rows.data {
+            BinlogEventData::WriteRows(data) => {
+                assert_eq!(data.column_count, 1);
+                assert_eq!(data.rows_data, row_image[..]);
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+
+        // 손상된 압축 헤더
+        let mut body = vec![0u8; 13];
+        body.push(0);
+        body.extend_from_slice(&[0x00, 0x01]);
+        assert!(BinlogParser::parse_event(event(165, &body)).is_err());
+    }
+
+    #[test]
+    fn test_parse_format_description_and_checksum() {
+        // 5.6.1 이전에는 체크섬 바이트가 없음
+        let old = BinlogParser::parse_format_description_event(&format_description_body(
+            "5.5.62-log",
+            None,
+        ))
+        .unwrap();
+        assert_eq!(old.server_version, "5.5.62-log");
+        assert_eq!(old.checksum, ChecksumAlgorithm::None);
+
+        // body 끝의 체크섬 4 바이트까지 포함
+        let with_checksum = |mut body: Vec<u8>| {
+            body.extend_from_slice(&[0u8; 4]);
+            body
+        };
+        let body = with_checksum(format_description_body("10.6.12-MariaDB-log", Some(1)));
+        let format = BinlogParser::parse_format_description_event(&body).unwrap();
+        assert_eq!(format.checksum, ChecksumAlgorithm::Crc32);
+
+        let body = with_checksum(format_description_body("8.0.35", Some(7)));
+        assert!(BinlogParser::parse_format_description_event(&body).is_err());
+
+        // CRC32 가 붙은 XID 이벤트
+        let mut data = RawEvent::new(16, &5u64.to_le_bytes()).crc(true).build();
+
+        let crc32 = ChecksumAlgorithm::Crc32;
+        let parsed = BinlogParser::parse_event_with_checksum(Bytes::from(data.clone()), crc32)
+            .unwrap();
+        assert!(matches!(parsed.data, BinlogEventData::Xid(5)));
+
+        data[EVENT_HEADER_SIZE] ^= 0xff;
+        let corrupted = Bytes::from(data);
+        assert!(BinlogParser::parse_event_with_checksum(corrupted, crc32).is_err());
+    }
+
+    /// `CREATE TABLE shop.orders (id INT UNSIGNED NOT NULL, name VARCHAR(32), price DECIMAL(10,2),
+    /// created DATETIME(3), note TEXT) CHARSET utf8mb4` 의 TABLE_MAP (table_id 108)
+    const ORDERS_TABLE_MAP: &[u8] = &[
+        0x6c, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, // table_id, flags
+        0x04, b's', b'h', b'o', b'p', 0x00, // 데이터베이스
+        0x06, b'o', b'r', b'd', b'e', b'r', b's', 0x00, // 테이블
+        0x05, 0x03, 0x0f, 0xf6, 0x12, 0xfc, // 컬럼 개수 + 타입
+        0x06, 0x80, 0x00, 0x0a, 0x02, 0x03, 0x02, // 메타데이터
+        0x1e, // nullable
+        0x01, 0x01, 0x80, // SIGNEDNESS (id 만 UNSIGNED)
+    ];
+
+    /// `INSERT INTO shop.orders VALUES (1, 'apple', 12.50, '2024-01-02 03:04:05.678', 'fresh'),
+    /// (2, NULL, -3.05, NULL, ''), (3, 'kiwi', 0.99, '1999-12-31 23:59:59', NULL)` 의 WRITE_ROWS v2
+    const ORDERS_WRITE_ROWS: &[u8] = &[
+        0x6c, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x02, 0x00, // table_id, flags, extra
+        0x05, 0x1f, // 컬럼 개수, 이미지에 있는 컬럼
+        // 1 번째 행
+        0x00,
+        0x01, 0x00, 0x00, 0x00,
+        0x05, b'a', b'p', b'p', b'l', b'e',
+        0x80, 0x00, 0x00, 0x0c, 0x32,
+        0x99, 0xb2, 0x44, 0x31, 0x05, 0x1a, 0x7c,
+        0x05, 0x00, b'f', b'r', b'e', b's', b'h',
+        // 2 번째 행 (name, created 가 NULL)
+        0x0a,
+        0x02, 0x00, 0x00, 0x00,
+        0x7f, 0xff, 0xff, 0xfc, 0xfa,
+        0x00, 0x00,
+        // 3 번째 행 (note 가 NULL)
+        0x10,
+        0x03, 0x00, 0x00, 0x00,
+        0x04, b'k', b'i', b'w', b'i',
+        0x80, 0x00, 0x00, 0x00, 0x63,
+        0x99, 0x63, 0xff, 0x7e, 0xfb, 0x00, 0x00,
+    ];
+
+    fn orders_table_map() -> TableMapData {
+        match BinlogParser::parse_event(event(19, ORDERS_TABLE_MAP)).unwrap().data {
+            BinlogEventData::TableMap(table_map) => table_map,
+            other => panic!("unexpected event: {:?}", other),
+        }
+    }
+
+    fn datetime(value: &str) -> CellValue {
+        let datetime = chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S%.f");
+        CellValue::DateTime(datetime.unwrap().and_utc())
+    }
+
+    fn bytes(value: &'static [u8]) -> CellValue {
+        CellValue::Bytes(Bytes::from_static(value))
+    }
+
+    #[test]
+    fn test_parse_table_map_metadata() {
+        let table_map = orders_table_map();
+        assert_eq!((table_map.database.as_str(), table_map.table.as_str()), ("shop", "orders"));
+        assert_eq!(table_map.column_types, [0x03, 0x0f, 0xf6, 0x12, 0xfc]);
+        assert_eq!(
+            table_map.column_meta,
+            [vec![], vec![0x80, 0x00], vec![0x0a, 0x02], vec![0x03], vec![0x02]]
+        );
+        assert_eq!(table_map.nullable_bitmap, [0x1e]);
+        assert_eq!(table_map.unsigned_columns, [true, false, false, false, false]);
+    }
+
+    #[test]
+    fn test_decode_write_rows() {
+        let data = event(30, ORDERS_WRITE_ROWS);
+        let mut event = BinlogParser::parse_event(data.clone()).unwrap();
+        BinlogParser::decode_rows(&mut event.data, &orders_table_map()).unwrap();
+        let BinlogEventData::WriteRows(rows) = event.data else {
+            panic!("expected write rows");
+        };
+
+        assert_eq!(
+            rows.rows,
+            [
+                vec![
+                    CellValue::UInt32(1),
+                    bytes(b"apple"),
+                    CellValue::Decimal("12.50".to_string()),
+                    datetime("2024-01-02 03:04:05.678"),
+                    bytes(b"fresh"),
+                ],
+                vec![
+                    CellValue::UInt32(2),
+                    CellValue::Null,
+                    CellValue::Decimal("-3.05".to_string()),
+                    CellValue::Null,
+                    bytes(b""),
+                ],
+                vec![
+                    CellValue::UInt32(3),
+                    bytes(b"kiwi"),
+                    CellValue::Decimal("0.99".to_string()),
+                    datetime("1999-12-31 23:59:59"),
+                    CellValue::Null,
+                ],
+            ]
+        );
+
+        // 문자열 / BLOB 셀은 이벤트 버퍼를 공유
+        let CellValue::Bytes(note) = &rows.rows[0][4] else {
+            panic!("expected bytes");
+        };
+        assert!(data.as_ptr_range().contains(&note.as_ptr()));
+    }
+
+    #[test]
+    fn test_decode_update_rows_minimal_image() {
+        // 변경 전은 id 만, 변경 후는 id 와 price
+        let body = [
+            &ORDERS_WRITE_ROWS[..10],
+            &[0x05, 0x01, 0x05],
+            &[0x00, 0x01, 0x00, 0x00, 0x00],
+            &[0x00, 0x01, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x0d, 0x00],
+            &[0x00, 0x02, 0x00, 0x00, 0x00],
+            &[0x02, 0x02, 0x00, 0x00, 0x00],
+        ]
+        .concat();
+        let mut event = BinlogParser::parse_event(event(31, &body)).unwrap();
+        BinlogParser::decode_rows(&mut event.data, &orders_table_map()).unwrap();
+        let BinlogEventData::UpdateRows(rows) = event.data else {
+            panic!("expected update rows");
+        };
+
+        let row = |id, price: Option<&str>| {
+            let mut row = vec![CellValue::Null; 5];
+            row[0] = CellValue::UInt32(id);
+            if let Some(price) = price {
+                row[2] = CellValue::Decimal(price.to_string());
+            }
+            row
+        };
+        assert_eq!(
+            rows.rows,
+            [
+                (row(1, None), row(1, Some("13.00"))),
+                (row(2, None), row(2, None)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decode_column_types() {
+        let types = [
+            (0x01, vec![]),           // TINYINT
+            (0x08, vec![]),           // BIGINT UNSIGNED
+            (0x09, vec![]),           // MEDIUMINT
+            (0x0d, vec![]),           // YEAR
+            (0x0a, vec![]),           // DATE
+            (0x13, vec![0]),          // TIME
+            (0x13, vec![3]),          // TIME(3)
+            (0x11, vec![0]),          // TIMESTAMP
+            (0xfe, vec![0xf7, 1]),    // ENUM
+            (0xfe, vec![0xf8, 1]),    // SET
+            (0xfe, vec![0xfe, 40]),   // CHAR(10)
+            (0x10, vec![4, 1]),       // BIT(12)
+            (0x05, vec![8]),          // DOUBLE
+            (0x04, vec![4]),          // FLOAT
+            (0xf5, vec![4]),          // JSON
+            (0xf6, vec![20, 10]),     // DECIMAL(20,10)
+            (0x0f, vec![0xb0, 0x04]), // VARCHAR(300)
+            (0x12, vec![6]),          // DATETIME(6)
+        ];
+        let table_map = TableMapData {
+            table_id: 1,
+            database: "test".to_string(),
+            table: "types".to_string(),
+            column_types: types.iter().map(|(column_type, _)| *column_type).collect(),
+            column_meta: types.iter().map(|(_, meta)| meta.clone()).collect(),
+            nullable_bitmap: vec![0xff; 3],
+            unsigned_columns: (0..types.len()).map(|i| i == 1).collect(),
+        };
+        let rows_data: &[&[u8]] = &[
+            &[0x00, 0x00, 0x00],
+            &[0xff],
+            &[0xff; 8],
+            &[0xff, 0xff, 0xff],
+            &[0x7c],
+            &[0x6f, 0xd0, 0x0f],
+            &[0x7f, 0xef, 0x7d],
+            &[0x80, 0xc8, 0xb8, 0x1e, 0xd2],
+            &[0x65, 0x53, 0xf1, 0x00],
+            &[0x02],
+            &[0x05],
+            &[0x02, b'o', b'k'],
+            &[0x0a, 0xbc],
+            &1.5f64.to_le_bytes(),
+            &2.5f32.to_le_bytes(),
+            &[0x03, 0x00, 0x00, 0x00, 0x00, 0x01, 0x02],
+            &[0x7e, 0xf2, 0x04, 0xc7, 0x2d, 0xff, 0x43, 0x9e, 0xb1, 0xf6],
+            &[0x03, 0x00, b'a', b'b', b'c'],
+            &[0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+        ];
+        let mut data = BinlogEventData::WriteRows(WriteRowsData {
+            table_id: 1,
+            flags: 0,
+            column_count: types.len() as u64,
+            columns_present: vec![0xff, 0xff, 0x03],
+            rows: Vec::new(),
+            rows_data: Bytes::from(rows_data.concat()),
+        });
+        BinlogParser::decode_rows(&mut data, &table_map).unwrap();
+        let BinlogEventData::WriteRows(rows) = data else {
+            panic!("expected write rows");
+        };
+
+        assert_eq!(
+            rows.rows,
+            [vec![
+                CellValue::Int8(-1),
+                CellValue::UInt64(u64::MAX),
+                CellValue::Int32(-1),
+                CellValue::UInt16(2024),
+                CellValue::Date("2024-03-15".to_string()),
+                CellValue::Time("-01:02:03".to_string()),
+                CellValue::Time("12:34:56.789".to_string()),
+                CellValue::DateTime(DateTime::from_timestamp(1_700_000_000, 0).unwrap()),
+                CellValue::UInt16(2),
+                CellValue::UInt64(5),
+                bytes(b"ok"),
+                CellValue::UInt64(0xabc),
+                CellValue::Double(1.5),
+                CellValue::Float(2.5),
+                bytes(&[0x00, 0x01, 0x02]),
+                CellValue::Decimal("-1234567890.0123456789".to_string()),
+                bytes(b"abc"),
+                CellValue::String("0000-00-00 00:00:00.000000".to_string()),
+            ]]
+        );
+    }
+
+    #[test]
+    fn test_decode_rows_errors() {
+        let mut table_map = orders_table_map();
+
+        // 잘린 행 이미지
+        let body = &ORDERS_WRITE_ROWS[..ORDERS_WRITE_ROWS.len() - 1];
+        let mut data = BinlogParser::parse_event(event(30, body)).unwrap().data;
+        assert!(BinlogParser::decode_rows(&mut data, &table_map).is_err());
+
+        // 컬럼 개수가 다른 TABLE_MAP
+        let mut data = BinlogParser::parse_event(event(30, ORDERS_WRITE_ROWS)).unwrap().data;
+        table_map.column_types.pop();
+        table_map.column_meta.pop();
+        assert!(BinlogParser::decode_rows(&mut data, &table_map).is_err());
+    }
+
+    #[test]
+    fn test_binlog_client() {
+        let client = BinlogClient::new(1, "mysql-bin.000001".to_string());
+        let info = client.get_source_info();
+        assert_eq!(info.server_id, 1);
+        assert_eq!(info.binlog_filename, "mysql-bin.000001");
+    }
+}
@@ -2,14 +2,26 @@
 //!
 //! mysql-binlog-connector-java를 참고하여 구현한 Rust binlog 스트리밍 클라이언트
 
-use crate::auth;
+use crate::archive::{ArchiveConfig, BinlogArchiver};
+use crate::auth::{self, AuthContext, AuthPlugin, HandshakeResponse};
+use crate::binlog::BinlogParser;
 use crate::connection::ConnectionConfig;
 use crate::error::{CdcError, Result};
-use crate::events::BinlogEvent;
+use crate::events::{
+    BinlogEvent, BinlogEventData, ChecksumAlgorithm, EventHeader, EventType, FailoverEventData,
+};
+use crate::failover::{self, FailoverPolicy, SourceHost};
+use crate::gtid::{GtidSet, MariadbGtidSet};
+use crate::offset::BinlogPosition;
 use crate::protocol::{self, GreetingPacket, PacketChannel};
+use crate::queue::{self, EventReceiver, EventSender, QueueConfig, QueueDepth};
+use crate::tls;
 use byteorder::{LittleEndian, WriteBytesExt};
+use chrono::{DateTime, Utc};
+use std::collections::VecDeque;
 use std::io::Write;
 use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
 use tracing::{debug, error, info, warn};
 
 /// COM_BINLOG_DUMP 명령어 코드
@@ -18,11 +30,192 @@ const COM_BINLOG_DUMP: u8 = 0x12;
 /// COM_BINLOG_DUMP_GTID 명령어 코드
 const COM_BINLOG_DUMP_GTID: u8 = 0x1e;
 
+/// COM_BINLOG_DUMP flag: binlog 끝에 도달하면 대기하지 않고 EOF 전송
+const BINLOG_DUMP_NON_BLOCK: u16 = 0x01;
+
+/// COM_BINLOG_DUMP_GTID flag: GTID 집합 데이터 포함
+const BINLOG_THROUGH_GTID: u16 = 0x04;
+
+/// semi-sync 이벤트 헤더 (0xEF + flag) 시작 바이트
+const SEMI_SYNC_INDICATOR: u8 = 0xEF;
+
+/// semi-sync flag: 이 이벤트 처리 후 ACK 필요
+const SEMI_SYNC_ACK_REQ: u8 = 0x01;
+
+/// MariaDB replica capability (MARIA_SLAVE_CAPABILITY_GTID): GTID 및 MariaDB 전용 이벤트 수신
+const MARIADB_SLAVE_CAPABILITY_GTID: u8 = 4;
+
+/// 스트리밍 중지 조건
+#[derive(Debug, Clone, PartialEq)]
+pub enum StopCondition {
+    /// 지정한 binlog 위치에 도달하면 중지
+    Position(BinlogPosition),
+    /// 지정한 GTID 트랜잭션이 커밋되면 중지
+    Gtid(String),
+    /// 이벤트 타임스탬프가 지정한 시각을 넘으면 중지 (해당 이벤트는 전달하지 않음)
+    Timestamp(DateTime<Utc>),
+}
+
+impl StopCondition {
+    fn is_position_reached(&self, position: &BinlogPosition) -> bool {
+        match self {
+            StopCondition::Position(stop) => position >= stop,
+            _ => false,
+        }
+    }
+
+    fn is_timestamp_reached(&self, header: &EventHeader) -> bool {
+        match self {
+            // timestamp 0 은 서버가 만든 가상 이벤트 (fake ROTATE, heartbeat)
+            StopCondition::Timestamp(stop) => {
+                header.timestamp != 0 && header.timestamp as i64 > stop.timestamp()
+            }
+            _ => false,
+        }
+    }
+}
+
+/// GTID 중지 조건 추적 (GTID 이벤트 이후 트랜잭션 종료 이벤트까지 대기)
+#[derive(Debug, Default)]
+struct StopTracker {
+    in_stop_transaction: bool,
+}
+
+impl StopTracker {
+    /// 이벤트를 전달한 뒤 스트리밍을 끝내야 하는지 여부
+    fn observe(
+        &mut self,
+        stop: &StopCondition,
+        event: &BinlogEvent,
+        position: &BinlogPosition,
+    ) -> bool {
+        match (stop, &event.data) {
+            (StopCondition::Position(_), _) => stop.is_position_reached(position),
+            (StopCondition::Gtid(gtid), BinlogEventData::Gtid(data)) => {
+                self.in_stop_transaction = data.gtid.eq_ignore_ascii_case(gtid);
+                false
+            }
+            (StopCondition::Gtid(gtid), BinlogEventData::MariadbGtid(data)) => {
+                self.in_stop_transaction = data.gtid.to_string() == *gtid;
+                // standalone 트랜잭션은 BEGIN 없이 다음 QUERY 하나로 끝남
+                false
+            }
+            (StopCondition::Gtid(_), data) => self.in_stop_transaction && ends_transaction(data),
+            _ => false,
+        }
+    }
+}
+
+/// XID 또는 BEGIN 이 아닌 QUERY (DDL, COMMIT) 로 트랜잭션이 끝남
+pub(crate) fn ends_transaction(data: &BinlogEventData) -> bool {
+    match data {
+        BinlogEventData::Xid(_) => true,
+        BinlogEventData::Query(query) => !query.query.eq_ignore_ascii_case("BEGIN"),
+        _ => false,
+    }
+}
+
+/// semi-sync ACK 전송 핸들
+///
+/// 컨슈머가 트랜잭션을 영구 저장한 뒤 해당 트랜잭션 이후의 binlog 위치로 `ack` 를 호출합니다.
+#[derive(Debug, Clone)]
+pub struct SemiSyncAck(mpsc::UnboundedSender<BinlogPosition>);
+
+impl SemiSyncAck {
+    /// `position` 까지의 트랜잭션 처리 완료를 서버에 알림
+    pub fn ack(&self, position: BinlogPosition) -> Result<()> {
+        self.0.send(position).map_err(|_| CdcError::ChannelClosed)
+    }
+}
+
+/// semi-sync 상태 (ACK 대기 중인 위치)
+///
+/// ACK 채널은 재연결 후에도 계속 사용하므로 빌려서 씁니다.
+struct SemiSync<'a> {
+    acks: &'a mut mpsc::UnboundedReceiver<BinlogPosition>,
+    pending: VecDeque<BinlogPosition>,
+}
+
+impl<'a> SemiSync<'a> {
+    fn new(acks: &'a mut mpsc::UnboundedReceiver<BinlogPosition>) -> Self {
+        SemiSync {
+            acks,
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// `position` 이하의 ACK 요청 중 가장 마지막 위치로 ACK 전송
+    async fn acknowledge(
+        &mut self,
+        channel: &mut PacketChannel,
+        position: BinlogPosition,
+    ) -> Result<()> {
+        let mut acked = None;
+        while self.pending.front().is_some_and(|pending| *pending <= position) {
+            acked = self.pending.pop_front();
+        }
+
+        if let Some(acked) = acked {
+            debug!("Sending semi-sync ACK for {}", acked);
+            let packet = BinlogClient::create_semi_sync_ack(&acked)?;
+            channel.write_out_of_band_packet(&packet).await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Binlog 스트림 (이벤트 수신 + 종료 위치)
+pub struct BinlogStream {
+    events: EventReceiver<BinlogEvent>,
+    handle: JoinHandle<Result<BinlogPosition>>,
+    ack: SemiSyncAck,
+}
+
+impl BinlogStream {
+    /// semi-sync ACK 핸들 (semi-sync 가 비활성화된 경우 ACK 는 무시됨)
+    pub fn acknowledger(&self) -> SemiSyncAck {
+        self.ack.clone()
+    }
+
+    /// 다음 이벤트 수신 (스트림이 끝나면 None)
+    pub async fn recv(&mut self) -> Option<BinlogEvent> {
+        self.events.recv().await
+    }
+
+    /// 아직 처리하지 않은 이벤트 수와 크기
+    pub fn depth(&self) -> QueueDepth {
+        self.events.depth()
+    }
+
+    /// 스트리밍 종료를 기다리고 마지막으로 처리한 위치 반환
+    pub async fn finish(self) -> Result<BinlogPosition> {
+        drop(self.events);
+        self.handle
+            .await
+            .map_err(|e| CdcError::Other(format!("Binlog streaming task failed: {}", e)))?
+    }
+}
+
+/// COM_BINLOG_DUMP 옵션
+#[derive(Debug, Clone, Default)]
+struct DumpOptions {
+    non_blocking: bool,
+    stop_condition: Option<StopCondition>,
+    semi_sync: bool,
+    queue: QueueConfig,
+    mariadb_gtid: Option<MariadbGtidSet>,
+    gtid_set: Option<GtidSet>,
+    archive: Option<ArchiveConfig>,
+    failover: FailoverPolicy,
+}
+
 /// Binlog 클라이언트
 pub struct BinlogClient {
     config: ConnectionConfig,
     binlog_filename: String,
     binlog_position: u64,
+    options: DumpOptions,
 }
 
 impl BinlogClient {
@@ -32,111 +225,322 @@ impl BinlogClient {
             config,
             binlog_filename,
             binlog_position,
+            options: DumpOptions::default(),
+        }
+    }
+
+    /// 현재 binlog 끝까지만 읽고 종료 (BINLOG_DUMP_NON_BLOCK)
+    pub fn non_blocking(mut self, non_blocking: bool) -> Self {
+        self.options.non_blocking = non_blocking;
+        self
+    }
+
+    /// 중지 조건 설정
+    pub fn stop_at(mut self, stop_condition: StopCondition) -> Self {
+        self.options.stop_condition = Some(stop_condition);
+        self
+    }
+
+    /// semi-sync replica 로 동작 (서버의 rpl_semi_sync_source_enabled 필요)
+    ///
+    /// ACK 는 `BinlogStream::acknowledger` 로 전송하므로 `stream` 으로 시작해야 합니다.
+    pub fn semi_sync(mut self, semi_sync: bool) -> Self {
+        self.options.semi_sync = semi_sync;
+        self
+    }
+
+    /// 이벤트 큐 크기 설정
+    ///
+    /// 큐가 가득 차면 소켓 읽기를 멈추므로 서버에는 TCP flow control 로 backpressure 가 전달됩니다.
+    pub fn queue(mut self, queue: QueueConfig) -> Self {
+        self.options.queue = queue;
+        self
+    }
+
+    /// `gtid_set` 에 없는 트랜잭션부터 읽기 (COM_BINLOG_DUMP_GTID)
+    ///
+    /// binlog 파일/위치 대신 GTID 로 덤프를 요청하므로 다른 서버로 장애 조치할 수 있습니다.
+    /// `ConnectionConfig::failover_hosts` 를 설정하면 연결이 끊겼을 때 다음 후보 서버로 전환하며,
+    /// 전환하면 `BinlogEventData::Failover` 이벤트를 먼저 전달합니다. 재시도 간격과 횟수는
+    /// `failover_policy` 를 따르며, 설정 에러 (`CdcError::ConfigError`) 는 전환하지 않고 바로 반환합니다.
+    /// 끊기기 전에 커밋되지 않은 트랜잭션은 새 서버에서 GTID 이벤트부터 다시 전달됩니다.
+    pub fn gtid_set(mut self, gtid_set: GtidSet) -> Self {
+        self.options.gtid_set = Some(gtid_set);
+        self
+    }
+
+    /// 장애 조치 재시도 간격과 최대 횟수 설정
+    pub fn failover_policy(mut self, policy: FailoverPolicy) -> Self {
+        self.options.failover = policy;
+        self
+    }
+
+    /// MariaDB GTID 위치에서 재개 (`@slave_connect_state`)
+    ///
+    /// 설정하면 binlog 파일/위치 대신 GTID 위치로 덤프를 요청합니다.
+    /// MariaDB 서버에서만 사용할 수 있습니다.
+    pub fn mariadb_gtid(mut self, gtid_set: MariadbGtidSet) -> Self {
+        self.options.mariadb_gtid = Some(gtid_set);
+        self
+    }
+
+    /// 받은 binlog 를 서버와 같은 파일로 보관 (`mysqlbinlog --raw`)
+    ///
+    /// 서버의 체크섬 설정 그대로 받으며, 보관 디렉터리에 파일이 있으면 생성자에 준 파일/위치 대신
+    /// 마지막 로컬 파일의 끝부터 이어 받습니다. 파일/위치로만 덤프하므로 GTID 시작 위치와 함께
+    /// 사용할 수 없습니다. 이벤트는 평소처럼 전달되므로 보관만 하려면 `archive_binlogs` 를 사용합니다.
+    pub fn archive(mut self, archive: ArchiveConfig) -> Self {
+        self.options.archive = Some(archive);
+        self
+    }
+
+    /// 보관만 수행 (이벤트는 버림), 스트리밍이 끝나면 마지막 위치 반환
+    pub async fn archive_binlogs(&self) -> Result<BinlogPosition> {
+        if self.options.archive.is_none() {
+            return Err(CdcError::Other("Archive directory is not configured".to_string()));
         }
+        let mut stream = self.stream().await?;
+        while stream.recv().await.is_some() {}
+        stream.finish().await
     }
 
     /// Binlog 스트리밍 시작
-    pub async fn start_streaming(&self) -> Result<mpsc::UnboundedReceiver<BinlogEvent>> {
-        let (tx, rx) = mpsc::unbounded_channel();
+    pub async fn start_streaming(&self) -> Result<EventReceiver<BinlogEvent>> {
+        let (tx, rx) = queue::bounded(self.options.queue);
+        let task = self.spawn_reader(tx, None);
+
+        tokio::spawn(async move {
+            match task.await {
+                Ok(Ok(position)) => info!("Binlog streaming ended at {}", position),
+                Ok(Err(e)) => error!("Binlog streaming error: {}", e),
+                Err(e) => error!("Binlog streaming task failed: {}", e),
+            }
+        });
+
+        Ok(rx)
+    }
+
+    /// Binlog 스트리밍 시작 (종료 위치를 받을 수 있는 스트림 반환)
+    pub async fn stream(&self) -> Result<BinlogStream> {
+        let (tx, events) = queue::bounded(self.options.queue);
+        let (ack_tx, ack_rx) = mpsc::unbounded_channel();
+        let handle = self.spawn_reader(tx, Some(ack_rx));
 
+        Ok(BinlogStream {
+            events,
+            handle,
+            ack: SemiSyncAck(ack_tx),
+        })
+    }
+
+    /// 백그라운드에서 binlog 이벤트 읽기
+    fn spawn_reader(
+        &self,
+        tx: EventSender<BinlogEvent>,
+        acks: Option<mpsc::UnboundedReceiver<BinlogPosition>>,
+    ) -> JoinHandle<Result<BinlogPosition>> {
         info!(
             "Starting binlog streaming from {}:{}",
             self.binlog_filename, self.binlog_position
         );
 
-        // MySQL 연결 설정
-        let connection_string = if let Some(ref db) = self.config.database {
-            format!(
-                "mysql://{}:{}@{}:{}/{}",
-                self.config.username,
-                self.config.password,
-                self.config.hostname,
-                self.config.port,
-                db
+        let config = self.config.clone();
+        let binlog_filename = self.binlog_filename.clone();
+        let binlog_position = self.binlog_position;
+        let options = self.options.clone();
+
+        tokio::spawn(Self::run(
+            config,
+            binlog_filename,
+            binlog_position,
+            options,
+            tx,
+            acks,
+        ))
+    }
+
+    /// 스트리밍 실행 (장애 조치 후보가 있으면 연결이 끊길 때마다 다음 서버로 전환)
+    async fn run(
+        config: ConnectionConfig,
+        binlog_filename: String,
+        binlog_position: u64,
+        mut options: DumpOptions,
+        tx: EventSender<BinlogEvent>,
+        mut acks: Option<mpsc::UnboundedReceiver<BinlogPosition>>,
+    ) -> Result<BinlogPosition> {
+        let mut gtid_set = options.gtid_set.take();
+
+        if options.archive.is_some() && (gtid_set.is_some() || options.mariadb_gtid.is_some()) {
+            return Err(CdcError::GtidError(
+                "Binlog archive resumes by file and position, not by GTID".to_string(),
+            ));
+        }
+
+        let candidates = config.candidates();
+        if candidates.len() == 1 {
+            return Self::read_binlog_events(
+                &config,
+                &binlog_filename,
+                binlog_position,
+                &options,
+                gtid_set.as_mut(),
+                &tx,
+                acks.as_mut(),
             )
-        } else {
-            format!(
-                "mysql://{}:{}@{}:{}",
-                self.config.username, self.config.password, self.config.hostname, self.config.port
+            .await;
+        }
+
+        // 파일/위치는 서버마다 다르므로 장애 조치는 GTID 로만 재개할 수 있음
+        let gtid_set = gtid_set.as_mut().ok_or_else(|| {
+            CdcError::GtidError(
+                "Failover requires a GTID start position (BinlogClient::gtid_set)".to_string(),
             )
-        };
+        })?;
 
-        let opts: mysql_async::Opts = connection_string
-            .parse()
-            .map_err(|_| CdcError::ConnectionError("Invalid connection string".to_string()))?;
+        // 진행 없이 실패한 연결 수 (후보 선택 실패는 한 바퀴 전체로 계산)
+        let policy = options.failover;
+        let max_failures = policy.max_rounds.saturating_mul(candidates.len() as u32);
+        let mut failures = 0u32;
+        let mut current: Option<SourceHost> = None;
+        loop {
+            let start = current.as_ref().map_or(0, |source| source.index + 1);
+            let processed = gtid_set.to_string();
 
-        // 백그라운드에서 binlog 이벤트 읽기
-        let binlog_filename = self.binlog_filename.clone();
-        let binlog_position = self.binlog_position;
-        let server_id = self.config.server_id;
+            let error = match failover::select_source(&candidates, start, gtid_set).await {
+                Ok(source) => {
+                    if let Some(previous) = current.as_ref() {
+                        if previous.index != source.index || previous.server_id != source.server_id
+                        {
+                            warn!(
+                                "Failing over from {} to {} at {}",
+                                previous.config.endpoint(),
+                                source.config.endpoint(),
+                                gtid_set
+                            );
+                            tx.send(Self::failover_event(previous, &source, gtid_set)).await?;
+                        }
+                    }
 
-        tokio::spawn(async move {
-            match Self::read_binlog_events(opts, server_id, binlog_filename, binlog_position, tx)
-                .await
-            {
-                Ok(_) => info!("Binlog streaming ended"),
-                Err(e) => error!("Binlog streaming error: {}", e),
+                    let result = Self::read_binlog_events(
+                        &source.config,
+                        &binlog_filename,
+                        binlog_position,
+                        &options,
+                        Some(gtid_set),
+                        &tx,
+                        acks.as_mut(),
+                    )
+                    .await;
+
+                    match result {
+                        Err(e) if e.is_retryable() => {
+                            warn!("Lost connection to {}: {}", source.config.endpoint(), e);
+                            current = Some(source);
+                            failures += 1;
+                            e
+                        }
+                        result => return result,
+                    }
+                }
+                Err(e) if e.is_retryable() => {
+                    failures += candidates.len() as u32;
+                    e
+                }
+                Err(e) => return Err(e),
+            };
+
+            // 트랜잭션을 처리했으면 새로 끊긴 것으로 보고 처음부터 계산
+            if gtid_set.to_string() != processed {
+                failures = 1;
+            }
+            if failures >= max_failures {
+                return Err(CdcError::ConnectionError(format!(
+                    "Giving up failover after {} rounds over {} sources: {}",
+                    policy.max_rounds,
+                    candidates.len(),
+                    error
+                )));
             }
-        });
 
-        Ok(rx)
+            let backoff = policy.backoff(failures);
+            debug!("Retrying binlog source in {:?}", backoff);
+            tokio::time::sleep(backoff).await;
+        }
+    }
+
+    /// 장애 조치 알림 이벤트 (header.server_id 는 새 서버)
+    fn failover_event(
+        previous: &SourceHost,
+        source: &SourceHost,
+        gtid_set: &GtidSet,
+    ) -> BinlogEvent {
+        BinlogEvent {
+            header: EventHeader {
+                timestamp: Utc::now().timestamp() as u32,
+                event_type: EventType::Unknown,
+                server_id: source.server_id,
+                event_length: 0,
+                next_pos: 0,
+                flags: 0,
+            },
+            data: BinlogEventData::Failover(FailoverEventData {
+                previous_host: previous.config.endpoint(),
+                previous_server_id: previous.server_id,
+                host: source.config.endpoint(),
+                server_id: source.server_id,
+                gtid_set: gtid_set.to_string(),
+            }),
+        }
     }
 
     /// Binlog 이벤트 읽기 (실제 구현)
+    ///
+    /// `gtid_set` 이 있으면 GTID 로 덤프하고, 전달을 끝낸 트랜잭션의 GTID 를 추가합니다.
     async fn read_binlog_events(
-        _opts: mysql_async::Opts,
-        server_id: u32,
-        binlog_filename: String,
+        config: &ConnectionConfig,
+        binlog_filename: &str,
         binlog_position: u64,
-        tx: mpsc::UnboundedSender<BinlogEvent>,
-    ) -> Result<()> {
-        // TODO: opts에서 호스트, 포트, 사용자명, 비밀번호 추출
-        // 지금은 하드코딩
-        let hostname = "localhost";
-        let port = 3306;
-        let username = "root";
-        let password = "rootpassword";
-        let database = Some("testdb");
+        options: &DumpOptions,
+        gtid_set: Option<&mut GtidSet>,
+        tx: &EventSender<BinlogEvent>,
+        acks: Option<&mut mpsc::UnboundedReceiver<BinlogPosition>>,
+    ) -> Result<BinlogPosition> {
+        info!("Connecting to {}", config.endpoint());
 
-        info!("Connecting to {}:{}", hostname, port);
-
-        // 1. TCP 소켓 열기
-        let mut channel = PacketChannel::connect(hostname, port).await?;
+        // 1. 소켓 열기 (Unix socket 또는 TCP)
+        let mut channel = match config.socket {
+            Some(ref path) => PacketChannel::connect_unix(path).await?,
+            None => PacketChannel::connect(&config.hostname, config.port).await?,
+        };
+        channel.set_max_allowed_packet(config.max_allowed_packet);
 
         // 2. MySQL 핸드셰이크 수신
         let greeting_packet = channel.read_packet().await?;
+        if protocol::is_error_packet(&greeting_packet) {
+            return Err(protocol::parse_error_packet(&greeting_packet));
+        }
         let greeting = GreetingPacket::parse(&greeting_packet)?;
 
         info!(
-            "MySQL Server version: {}, Thread ID: {}",
-            greeting.server_version, greeting.thread_id
+            "MySQL Server version: {} ({}), Thread ID: {}",
+            greeting.server_version, greeting.server_info, greeting.thread_id
         );
 
-        // 3. 인증
-        let auth_response = auth::create_handshake_response(
-            username,
-            password,
-            database,
-            &greeting.scramble,
-            greeting.server_collation,
-        )
-        .map_err(|e| CdcError::ConnectionError(format!("Failed to create auth response: {}", e)))?;
-
-        channel.write_packet(&auth_response, 1).await?;
+        // 3. TLS 협상 (ssl_mode)
+        let (mut channel, sequence) = Self::negotiate_tls(channel, &greeting, config).await?;
 
-        // 4. 인증 결과 확인
-        let auth_result = channel.read_packet().await?;
-        if protocol::is_error_packet(&auth_result) {
-            return Err(CdcError::ConnectionError(
-                "Authentication failed".to_string(),
-            ));
-        }
+        // 4. 인증
+        Self::authenticate(&mut channel, &greeting, config, sequence).await?;
 
         info!("Authentication successful");
 
         // 5. 체크섬 설정 (필수!)
-        // MySQL 서버의 binlog 체크섬을 비활성화하도록 요청
-        let checksum_query = b"SET @master_binlog_checksum='NONE'";
+        // MySQL 서버의 binlog 체크섬을 비활성화하도록 요청 (보관 모드는 서버 설정 그대로 받음)
+        let checksum_query: &[u8] = if options.archive.is_some() {
+            b"SET @master_binlog_checksum=@@global.binlog_checksum"
+        } else {
+            b"SET @master_binlog_checksum='NONE'"
+        };
         let mut query_packet = vec![0x03]; // COM_QUERY
         query_packet.extend_from_slice(checksum_query);
 
@@ -144,139 +548,549 @@ impl BinlogClient {
 
         // 응답 확인
         let checksum_result = channel.read_packet().await?;
-        if protocol::is_error_packet(&checksum_result) {
-            warn!("Failed to set binlog checksum to NONE, continuing anyway...");
+        if protocol::is_error_packet(&checksum_result) && options.archive.is_some() {
+            return Err(protocol::parse_error_packet(&checksum_result));
+        } else if protocol::is_error_packet(&checksum_result) {
+            warn!(
+                "Failed to set binlog checksum to NONE ({}), continuing anyway...",
+                protocol::parse_error_packet(&checksum_result)
+            );
+        } else if options.archive.is_some() {
+            info!("Binlog checksum kept as the server's binlog_checksum");
         } else {
             info!("Binlog checksum set to NONE");
         }
 
-        // 6. COM_BINLOG_DUMP 명령어 전송
-        let dump_command =
-            Self::create_binlog_dump_command(server_id, &binlog_filename, binlog_position)?;
+        // 6. semi-sync replica 등록
+        let semi_sync = match acks {
+            Some(acks) if options.semi_sync => {
+                Self::enable_semi_sync(&mut channel).await?.then(|| SemiSync::new(acks))
+            }
+            None if options.semi_sync => {
+                warn!("Semi-sync requires BinlogClient::stream for acknowledgements, ignoring");
+                None
+            }
+            _ => None,
+        };
 
-        channel.write_packet(&dump_command, 0).await?;
+        // 보관 모드는 마지막 로컬 파일의 끝부터 재개
+        let mut archiver = match &options.archive {
+            Some(archive) => {
+                let mut archiver = BinlogArchiver::open(archive.clone()).await?;
+                // 첫 가상 ROTATE 도 체크섬이 붙어 오므로 FORMAT_DESCRIPTION 전에 알아야 함
+                let rows = channel.query("SELECT @master_binlog_checksum").await?;
+                let value = rows.first().and_then(|row| row.first()).cloned().flatten();
+                if value.is_some_and(|value| value.eq_ignore_ascii_case("CRC32")) {
+                    archiver.set_checksum(ChecksumAlgorithm::Crc32);
+                }
+                Some(archiver)
+            }
+            None => None,
+        };
+        let resume = archiver.as_ref().and_then(|a| a.resume_position().cloned());
+        let (binlog_filename, binlog_position) = match &resume {
+            Some(resume) => (resume.filename.as_str(), resume.position),
+            None => (binlog_filename, binlog_position),
+        };
 
-        info!(
-            "Sent COM_BINLOG_DUMP: file={}, position={}",
-            binlog_filename, binlog_position
-        );
+        // 7. MariaDB replica 설정 (GTID 위치로 재개하면 파일/위치 대신 빈 파일명 + 4 사용)
+        let (binlog_filename, binlog_position) = if greeting.server_info.is_mariadb() {
+            if gtid_set.is_some() {
+                return Err(CdcError::GtidError(
+                    "MariaDB uses BinlogClient::mariadb_gtid instead of a MySQL GTID set"
+                        .to_string(),
+                ));
+            }
+            Self::prepare_mariadb_dump(&mut channel, options.mariadb_gtid.as_ref()).await?;
+            match options.mariadb_gtid {
+                Some(_) => ("", 4),
+                None => (binlog_filename, binlog_position),
+            }
+        } else if options.mariadb_gtid.is_some() {
+            return Err(CdcError::GtidError(format!(
+                "MariaDB GTID position requires a MariaDB server, got {}",
+                greeting.server_info
+            )));
+        } else {
+            (binlog_filename, binlog_position)
+        };
+
+        // 8. COM_BINLOG_DUMP / COM_BINLOG_DUMP_GTID 명령어 전송
+        let flags = if options.non_blocking { BINLOG_DUMP_NON_BLOCK } else { 0 };
+        let start = match gtid_set.as_deref() {
+            Some(gtid_set) => {
+                let dump_command =
+                    Self::create_binlog_dump_gtid_command(config.server_id, gtid_set, flags)?;
+                channel.write_packet(&dump_command, 0).await?;
+
+                info!(
+                    "Sent COM_BINLOG_DUMP_GTID: gtid_set={}, non_blocking={}",
+                    gtid_set, options.non_blocking
+                );
+
+                // 서버가 첫 이벤트로 보내는 ROTATE 로 파일명이 정해짐
+                BinlogPosition::new(String::new(), 4)
+            }
+            None => {
+                let dump_command = Self::create_binlog_dump_command(
+                    config.server_id,
+                    binlog_filename,
+                    binlog_position,
+                    flags,
+                )?;
+                channel.write_packet(&dump_command, 0).await?;
 
-        // 7. Binlog 이벤트 스트리밍
-        info!("Binlog event streaming started - reading events...");
+                info!(
+                    "Sent COM_BINLOG_DUMP: file={}, position={}, non_blocking={}",
+                    binlog_filename, binlog_position, options.non_blocking
+                );
+
+                BinlogPosition::new(binlog_filename.to_string(), binlog_position)
+            }
+        };
+
+        // 9. Binlog 이벤트 스트리밍
+        Self::stream_events(
+            &mut channel,
+            start,
+            options.stop_condition.as_ref(),
+            tx,
+            semi_sync,
+            gtid_set,
+            archiver.as_mut(),
+        )
+        .await
+    }
+
+    /// MariaDB 서버에 GTID capability 를 알리고 GTID 재개 위치 설정
+    ///
+    /// capability 를 알리지 않으면 서버가 MariaDB 전용 이벤트를 QUERY 등 호환 이벤트로 바꿔 보냅니다.
+    async fn prepare_mariadb_dump(
+        channel: &mut PacketChannel,
+        gtid_set: Option<&MariadbGtidSet>,
+    ) -> Result<()> {
+        channel
+            .query(&format!(
+                "SET @mariadb_slave_capability={}",
+                MARIADB_SLAVE_CAPABILITY_GTID
+            ))
+            .await?;
+
+        if let Some(gtid_set) = gtid_set {
+            channel
+                .query(&format!("SET @slave_connect_state='{}'", gtid_set))
+                .await?;
+            channel.query("SET @slave_gtid_strict_mode=0").await?;
+            channel.query("SET @slave_gtid_ignore_duplicates=0").await?;
+            info!("Resuming MariaDB binlog from GTID position {}", gtid_set);
+        }
+
+        Ok(())
+    }
+
+    /// 서버에서 semi-sync 가 활성화되어 있으면 replica 로 등록
+    async fn enable_semi_sync(channel: &mut PacketChannel) -> Result<bool> {
+        let rows = channel
+            .query(
+                "SHOW GLOBAL VARIABLES WHERE Variable_name IN \
+                 ('rpl_semi_sync_source_enabled', 'rpl_semi_sync_master_enabled')",
+            )
+            .await?;
+
+        let enabled = rows
+            .iter()
+            .any(|row| row.get(1).and_then(|v| v.as_deref()) == Some("ON"));
+        if !enabled {
+            warn!("Semi-sync is not enabled on the source, continuing asynchronously");
+            return Ok(false);
+        }
+
+        // 8.0.26 이전 서버는 rpl_semi_sync_slave 를 사용
+        channel
+            .query("SET @rpl_semi_sync_replica = 1, @rpl_semi_sync_slave = 1")
+            .await?;
+        info!("Registered as semi-sync replica");
+
+        Ok(true)
+    }
+
+    /// 서버가 보내는 binlog 이벤트를 파싱하여 전달
+    ///
+    /// EOF 패킷 (non-blocking 모드) 또는 중지 조건에 도달하면 마지막 위치를 반환합니다.
+    /// `executed` 가 있으면 전달을 끝낸 트랜잭션의 GTID 를 추가합니다.
+    /// `archiver` 가 있으면 체크섬을 포함한 이벤트를 그대로 기록하며, 파싱할 수 없는 이벤트는 에러입니다.
+    /// semi-sync 에서도 건너뛴 이벤트의 ACK 가 빠지지 않도록 파싱할 수 없는 이벤트는 에러입니다.
+    async fn stream_events(
+        channel: &mut PacketChannel,
+        start: BinlogPosition,
+        stop_condition: Option<&StopCondition>,
+        tx: &EventSender<BinlogEvent>,
+        mut semi_sync: Option<SemiSync<'_>>,
+        mut executed: Option<&mut GtidSet>,
+        mut archiver: Option<&mut BinlogArchiver>,
+    ) -> Result<BinlogPosition> {
+        let mut position = start;
+        let mut tracker = StopTracker::default();
+        let mut event_count = 0u64;
+        let mut pending_gtid: Option<String> = None;
+
+        if stop_condition.is_some_and(|stop| stop.is_position_reached(&position)) {
+            info!("Start position {} is already past the stop condition", position);
+            return Ok(position);
+        }
 
-        // 무한 루프로 이벤트 읽기
-        let mut event_count = 0;
         loop {
-            match channel.read_packet().await {
-                Ok(packet) => {
-                    // 에러 패킷 확인
-                    if protocol::is_error_packet(&packet) {
-                        error!("Received error packet from server");
-                        if packet.len() > 3 {
-                            let error_code = u16::from_le_bytes([packet[1], packet[2]]);
-                            let error_msg = String::from_utf8_lossy(&packet[9..]);
-                            error!("Error code: {}, message: {}", error_code, error_msg);
+            // 읽기 버퍼가 비지 않는 부하 상황에서도 ACK 가 밀리지 않도록 매 패킷마다 전송
+            if let Some(semi_sync) = semi_sync.as_mut() {
+                while let Ok(ack) = semi_sync.acks.try_recv() {
+                    semi_sync.acknowledge(channel, ack).await?;
+                }
+            }
+
+            let packet = match semi_sync.as_mut() {
+                // 패킷을 기다리는 동안에도 컨슈머의 ACK 를 바로 전송
+                Some(semi_sync) => match channel.try_read_packet()? {
+                    Some(packet) => packet,
+                    None => {
+                        tokio::select! {
+                            Some(ack) = semi_sync.acks.recv() => {
+                                semi_sync.acknowledge(channel, ack).await?;
+                            }
+                            filled = channel.fill_read_buffer() => filled?,
                         }
-                        break;
+                        continue;
                     }
+                },
+                None => channel.read_packet().await?,
+            };
 
-                    // EOF 패킷 확인 (0xFE, 패킷 길이 < 9)
-                    if !packet.is_empty() && packet[0] == 0xFE && packet.len() < 9 {
-                        info!("Received EOF packet - stream ended");
-                        break;
-                    }
+            // 에러 패킷 확인
+            if protocol::is_error_packet(&packet) {
+                let err = protocol::parse_error_packet(&packet);
+                error!("Received error packet from server: {}", err);
+                return Err(err);
+            }
 
-                    event_count += 1;
-
-                    // 패킷이 비어있지 않으면 binlog 이벤트
-                    if !packet.is_empty() {
-                        // 첫 바이트 0x00은 OK 표시, 실제 이벤트 데이터는 그 다음부터
-                        let event_data = if packet[0] == 0x00 && packet.len() > 1 {
-                            &packet[1..]
-                        } else {
-                            &packet[..]
-                        };
-
-                        if event_data.len() >= 19 {
-                            // Binlog 이벤트 헤더 파싱 (최소 19 bytes)
-                            let timestamp = u32::from_le_bytes([
-                                event_data[0],
-                                event_data[1],
-                                event_data[2],
-                                event_data[3],
-                            ]);
-                            let event_type = event_data[4];
-                            let server_id = u32::from_le_bytes([
-                                event_data[5],
-                                event_data[6],
-                                event_data[7],
-                                event_data[8],
-                            ]);
-                            let event_size = u32::from_le_bytes([
-                                event_data[9],
-                                event_data[10],
-                                event_data[11],
-                                event_data[12],
-                            ]);
-                            let log_pos = u32::from_le_bytes([
-                                event_data[13],
-                                event_data[14],
-                                event_data[15],
-                                event_data[16],
-                            ]);
-                            let flags = u16::from_le_bytes([event_data[17], event_data[18]]);
-
-                            info!(
-                                "📦 Event #{}: type={}, timestamp={}, server_id={}, size={}, pos={}, flags=0x{:04x}",
-                                event_count,
-                                event_type,
-                                timestamp,
-                                server_id,
-                                event_size,
-                                log_pos,
-                                flags
-                            );
+            // EOF 패킷 확인 (0xFE, 패킷 길이 < 9) - non-blocking 모드에서 binlog 끝에 도달
+            if packet.first() == Some(&0xFE) && packet.len() < 9 {
+                info!("Reached end of binlog at {} ({} events)", position, event_count);
+                return Ok(position);
+            }
 
-                            // 이벤트 타입별 추가 정보 출력
-                            if event_type == 2 {
-                                // QUERY_EVENT
-                                info!("   → QUERY_EVENT detected (likely DDL or BEGIN/COMMIT)");
-                            } else if event_type == 30 {
-                                // WRITE_ROWS_EVENT
-                                info!("   → WRITE_ROWS_EVENT detected (INSERT)");
-                            } else if event_type == 31 {
-                                // UPDATE_ROWS_EVENT
-                                info!("   → UPDATE_ROWS_EVENT detected (UPDATE)");
-                            } else if event_type == 32 {
-                                // DELETE_ROWS_EVENT
-                                info!("   → DELETE_ROWS_EVENT detected (DELETE)");
-                            } else if event_type == 19 {
-                                // TABLE_MAP_EVENT
-                                info!("   → TABLE_MAP_EVENT detected (table schema info)");
-                            }
+            // 첫 바이트 0x00은 OK 표시, 실제 이벤트 데이터는 그 다음부터
+            if packet.first() != Some(&0x00) {
+                return Err(CdcError::ProtocolError(
+                    "Unexpected packet in binlog stream".to_string(),
+                ));
+            }
 
-                            // Raw 데이터 출력 (처음 100바이트만)
-                            let display_len = std::cmp::min(100, event_data.len());
-                            debug!(
-                                "   Raw data (first {} bytes): {:02x?}",
-                                display_len,
-                                &event_data[..display_len]
-                            );
-                        }
-                    }
+            // semi-sync 헤더 (0xEF + flag)
+            let (event_data, need_ack) = match (&semi_sync, &packet[1..]) {
+                (Some(_), [SEMI_SYNC_INDICATOR, flag, ..]) => {
+                    (packet.slice(3..), flag & SEMI_SYNC_ACK_REQ != 0)
+                }
+                (Some(_), _) => {
+                    return Err(CdcError::ProtocolError(
+                        "Missing semi-sync header in binlog event".to_string(),
+                    ))
                 }
+                (None, _) => (packet.slice(1..), false),
+            };
+
+            let checksum = archiver.as_ref().map_or(ChecksumAlgorithm::None, |a| a.checksum());
+            let parsed = BinlogParser::parse_event_with_checksum(event_data.clone(), checksum);
+            let event = match parsed {
+                Ok(event) => event,
+                Err(e) if archiver.is_some() || semi_sync.is_some() => return Err(e),
                 Err(e) => {
-                    error!("Failed to read packet: {}", e);
-                    break;
+                    warn!("Failed to parse binlog event at {}: {}", position, e);
+                    continue;
+                }
+            };
+
+            debug!(
+                "Event: type={:?}, timestamp={}, server_id={}, size={}, next_pos={}",
+                event.header.event_type,
+                event.header.timestamp,
+                event.header.server_id,
+                event.header.event_length,
+                event.header.next_pos
+            );
+
+            if let Some(archiver) = archiver.as_deref_mut() {
+                archiver.write_event(&event_data, &event).await?;
+            }
+
+            // 타임스탬프 조건은 해당 이벤트를 전달하기 전에 검사
+            if stop_condition.is_some_and(|stop| stop.is_timestamp_reached(&event.header)) {
+                info!("Stop timestamp reached at {} ({} events)", position, event_count);
+                return Ok(position);
+            }
+
+            // 위치 갱신 (ROTATE 는 새 파일, 그 외에는 next_pos)
+            match &event.data {
+                BinlogEventData::Rotate(rotate) => {
+                    position =
+                        BinlogPosition::new(rotate.next_binlog_name.clone(), rotate.position);
+                }
+                _ if event.header.next_pos != 0 => {
+                    position.position = event.header.next_pos as u64;
+                }
+                _ => {}
+            }
+
+            if let Some(semi_sync) = semi_sync.as_mut().filter(|_| need_ack) {
+                semi_sync.pending.push_back(position.clone());
+            }
+
+            let finished = match stop_condition {
+                Some(stop) => tracker.observe(stop, &event, &position),
+                None => false,
+            };
+
+            let committed = match &event.data {
+                BinlogEventData::Gtid(data) => {
+                    pending_gtid = Some(data.gtid.clone());
+                    None
                 }
+                data if ends_transaction(data) => pending_gtid.take(),
+                _ => None,
+            };
+
+            event_count += 1;
+            if tx.send(event).await.is_err() {
+                info!("Binlog event receiver dropped, stopping at {}", position);
+                return Ok(position);
+            }
+
+            if let (Some(executed), Some(gtid)) = (executed.as_deref_mut(), committed) {
+                executed.add_gtid(&gtid)?;
+            }
+
+            if finished {
+                info!("Stop condition reached at {} ({} events)", position, event_count);
+                return Ok(position);
             }
         }
+    }
 
-        info!(
-            "Binlog streaming ended. Total events received: {}",
-            event_count
+    /// ssl_mode 에 따라 SSL request 를 보내고 채널을 TLS로 업그레이드
+    ///
+    /// 핸드셰이크 응답에 사용할 sequence 번호를 함께 반환합니다.
+    async fn negotiate_tls(
+        mut channel: PacketChannel,
+        greeting: &GreetingPacket,
+        config: &ConnectionConfig,
+    ) -> Result<(PacketChannel, u8)> {
+        if !config.ssl_mode.is_enabled() {
+            return Ok((channel, 1));
+        }
+
+        // Unix socket 은 이미 안전한 채널이므로 PREFERRED 에서는 TLS 생략
+        if channel.is_local() && !config.ssl_mode.is_required() {
+            return Ok((channel, 1));
+        }
+
+        if greeting.server_capabilities & auth::capabilities::SSL == 0 {
+            if config.ssl_mode.is_required() {
+                return Err(CdcError::ConfigError(format!(
+                    "Server does not support TLS but ssl_mode is {}",
+                    config.ssl_mode
+                )));
+            }
+            warn!("Server does not support TLS, continuing without encryption");
+            return Ok((channel, 1));
+        }
+
+        let tls_config = tls::build_client_config(config)?;
+        let server_name = tls::server_name(&config.hostname)?;
+
+        let ssl_request =
+            auth::create_ssl_request(config.database.as_deref(), greeting.server_collation)
+                .map_err(|e| CdcError::ConnectionError(format!("Failed to create SSL request: {}", e)))?;
+        channel.write_packet(&ssl_request, 1).await?;
+
+        let channel = channel.upgrade_to_tls(tls_config, server_name).await?;
+        info!("TLS established (ssl_mode={})", config.ssl_mode);
+
+        Ok((channel, 2))
+    }
+
+    /// 핸드셰이크 응답 전송 및 인증 플러그인 협상
+    ///
+    /// greeting 의 플러그인으로 시작하여 AuthSwitchRequest / AuthMoreData 를 처리합니다.
+    async fn authenticate(
+        channel: &mut PacketChannel,
+        greeting: &GreetingPacket,
+        config: &ConnectionConfig,
+        sequence: u8,
+    ) -> Result<()> {
+        let server_public_key = config.load_server_public_key()?;
+        let ctx = AuthContext {
+            password: &config.password,
+            secure_transport: channel.is_secure(),
+            encrypted_transport: channel.is_tls(),
+            allow_cleartext_password: config.allow_cleartext_password,
+            allow_public_key_retrieval: config.allow_public_key_retrieval,
+            server_public_key: server_public_key.as_deref(),
+        };
+
+        // 4.1 이전 인증 (CLIENT_SECURE_CONNECTION 없음) 은 지원하지 않음
+        if greeting.server_capabilities & auth::capabilities::SECURE_CONNECTION == 0 {
+            return Err(CdcError::ConfigError(format!(
+                "Server {} does not support secure authentication",
+                greeting.server_version
+            )));
+        }
+
+        let mut plugin = greeting.auth_plugin();
+        let mut scramble = greeting.scramble.clone();
+
+        debug!("Authenticating with {}", plugin.name());
+
+        let compression = config.compression.negotiate(greeting.server_capabilities);
+        if compression != config.compression {
+            warn!(
+                "Server does not support {:?} compression, using {:?}",
+                config.compression, compression
+            );
+        }
+
+        let mut extra_capabilities = compression.capability();
+        if channel.is_tls() {
+            extra_capabilities |= auth::capabilities::SSL;
+        }
+
+        // 서버가 CLIENT_CONNECT_ATTRS 를 지원할 때만 연결 속성 전송
+        let connect_attrs = config.connect_attributes();
+        let connect_attrs = (greeting.server_capabilities & auth::capabilities::CONNECT_ATTRS != 0)
+            .then_some(&connect_attrs);
+
+        let auth_data = ctx.initial_response(plugin, &scramble)?;
+        let handshake_response = HandshakeResponse {
+            username: &config.username,
+            auth_response: &auth_data,
+            database: config.database.as_deref(),
+            collation: greeting.server_collation,
+            plugin,
+            extra_capabilities,
+            connect_attrs,
+            zstd_level: compression.zstd_level(),
+        }
+        .encode()
+        .map_err(|e| CdcError::ConnectionError(format!("Failed to create auth response: {}", e)))?;
+
+        // greeting 이 sequence 0, 이후 요청/응답마다 1씩 증가
+        let mut sequence = sequence;
+        channel.write_packet(&handshake_response, sequence).await?;
+
+        loop {
+            let packet = channel.read_packet().await?;
+            sequence = sequence.wrapping_add(1);
+
+            if protocol::is_ok_packet(&packet) {
+                // 인증 OK 이후의 모든 패킷은 압축 프레임으로 전송됨
+                if compression.is_enabled() {
+                    debug!("Compression enabled: {:?}", compression);
+                    channel.enable_compression(compression);
+                }
+                return Ok(());
+            }
+            if protocol::is_error_packet(&packet) {
+                return Err(protocol::parse_error_packet(&packet));
+            }
+
+            let response = match packet.first() {
+                Some(&auth::AUTH_SWITCH_REQUEST) => {
+                    // plugin name (null-terminated) + plugin data
+                    let body = &packet[1..];
+                    let name_end = body.iter().position(|&b| b == 0).unwrap_or(body.len());
+                    let name = String::from_utf8_lossy(&body[..name_end]).to_string();
+                    plugin = AuthPlugin::from_name(&name).ok_or_else(|| {
+                        CdcError::ConfigError(format!("Unsupported auth plugin: {}", name))
+                    })?;
+
+                    let data = body.get(name_end + 1..).unwrap_or_default();
+                    scramble = data.strip_suffix(&[0]).unwrap_or(data).to_vec();
+
+                    debug!("Auth switch requested: {}", plugin.name());
+                    ctx.initial_response(plugin, &scramble)?
+                }
+                Some(&auth::AUTH_MORE_DATA) => {
+                    let data = &packet[1..];
+                    match (plugin, data.first()) {
+                        (AuthPlugin::CachingSha2Password, Some(&auth::CACHING_SHA2_FAST_AUTH_SUCCESS)) => {
+                            debug!("caching_sha2_password fast auth succeeded");
+                            continue;
+                        }
+                        (AuthPlugin::CachingSha2Password, Some(&auth::CACHING_SHA2_PERFORM_FULL_AUTH)) => {
+                            debug!("caching_sha2_password full auth requested");
+                            ctx.full_auth_response(&scramble)?
+                        }
+                        (AuthPlugin::CachingSha2Password, _) | (AuthPlugin::Sha256Password, _) => {
+                            // 서버가 보낸 RSA 공개키 (PEM)
+                            debug!("Received server public key");
+                            auth::encrypt_password_rsa(&config.password, &scramble, data)?
+                        }
+                        _ => {
+                            return Err(CdcError::ProtocolError(format!(
+                                "Unexpected auth data for {}",
+                                plugin.name()
+                            )));
+                        }
+                    }
+                }
+                _ => {
+                    return Err(CdcError::ProtocolError(
+                        "Unexpected packet during authentication".to_string(),
+                    ));
+                }
+            };
+
+            sequence = sequence.wrapping_add(1);
+            channel.write_packet(&response, sequence).await?;
+        }
+    }
+
+    /// semi-sync ACK 패킷 생성 (0xEF + binlog position (8) + binlog filename)
+    fn create_semi_sync_ack(position: &BinlogPosition) -> Result<Vec<u8>> {
+        let mut buffer = Vec::new();
+
+        buffer.write_u8(SEMI_SYNC_INDICATOR)?;
+        buffer.write_u64::<LittleEndian>(position.position)?;
+        buffer.write_all(position.filename.as_bytes())?;
+
+        Ok(buffer)
+    }
+
+    /// COM_BINLOG_DUMP_GTID 명령어 생성
+    ///
+    /// flags (2) + server_id (4) + 파일명 길이 (4, 0) + 위치 (8, 4) + GTID 데이터 길이 (4) + GTID 데이터
+    fn create_binlog_dump_gtid_command(
+        server_id: u32,
+        gtid_set: &GtidSet,
+        flags: u16,
+    ) -> Result<Vec<u8>> {
+        let gtid_data = gtid_set.encode()?;
+        let mut buffer = Vec::new();
+
+        buffer.write_u8(COM_BINLOG_DUMP_GTID)?;
+        buffer.write_u16::<LittleEndian>(flags | BINLOG_THROUGH_GTID)?;
+        buffer.write_u32::<LittleEndian>(server_id)?;
+
+        // 파일명 없이 GTID 로만 위치 지정
+        buffer.write_u32::<LittleEndian>(0)?;
+        buffer.write_u64::<LittleEndian>(4)?;
+
+        buffer.write_u32::<LittleEndian>(gtid_data.len() as u32)?;
+        buffer.write_all(&gtid_data)?;
+
+        debug!(
+            "Created COM_BINLOG_DUMP_GTID command: server_id={}, gtid_set={}",
+            server_id, gtid_set
         );
-        Ok(())
+
+        Ok(buffer)
     }
 
     /// COM_BINLOG_DUMP 명령어 생성
@@ -284,6 +1098,7 @@ impl BinlogClient {
         server_id: u32,
         binlog_filename: &str,
         binlog_position: u64,
+        flags: u16,
     ) -> Result<Vec<u8>> {
         let mut buffer = Vec::new();
 
@@ -293,8 +1108,8 @@ impl BinlogClient {
         // Binlog position (4 bytes, little-endian)
         buffer.write_u32::<LittleEndian>(binlog_position as u32)?;
 
-        // Flags (2 bytes) - 0 for non-blocking
-        buffer.write_u16::<LittleEndian>(0)?;
+        // Flags (2 bytes) - BINLOG_DUMP_NON_BLOCK 이면 binlog 끝에서 EOF 전송
+        buffer.write_u16::<LittleEndian>(flags)?;
 
         // Server ID (4 bytes)
         buffer.write_u32::<LittleEndian>(server_id)?;
@@ -314,13 +1129,468 @@ impl BinlogClient {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_fixtures::{format_description_body, rotate_body, RawEvent};
 
     #[test]
     fn test_create_binlog_dump_command() {
-        let cmd = BinlogClient::create_binlog_dump_command(1, "mysql-bin.000001", 4).unwrap();
+        let cmd = BinlogClient::create_binlog_dump_command(1, "mysql-bin.000001", 4, 0).unwrap();
 
         // COM_BINLOG_DUMP (1) + position (4) + flags (2) + server_id (4) + filename
         assert!(cmd.len() > 11);
         assert_eq!(cmd[0], COM_BINLOG_DUMP);
+
+        let flags = BINLOG_DUMP_NON_BLOCK;
+        let cmd = BinlogClient::create_binlog_dump_command(1, "mysql-bin.000001", 4, flags).unwrap();
+        assert_eq!(u16::from_le_bytes([cmd[5], cmd[6]]), BINLOG_DUMP_NON_BLOCK);
+    }
+
+    #[test]
+    fn test_create_binlog_dump_gtid_command() {
+        let gtid_set = GtidSet::parse("3e11fa47-71ca-11e1-9e33-c80aa9429562:1-5").unwrap();
+        let cmd = BinlogClient::create_binlog_dump_gtid_command(7, &gtid_set, 0).unwrap();
+
+        assert_eq!(cmd[0], COM_BINLOG_DUMP_GTID);
+        assert_eq!(u16::from_le_bytes([cmd[1], cmd[2]]), BINLOG_THROUGH_GTID);
+        assert_eq!(u32::from_le_bytes(cmd[3..7].try_into().unwrap()), 7);
+        // 빈 파일명 + 위치 4
+        assert_eq!(u32::from_le_bytes(cmd[7..11].try_into().unwrap()), 0);
+        assert_eq!(u64::from_le_bytes(cmd[11..19].try_into().unwrap()), 4);
+
+        let gtid_data = gtid_set.encode().unwrap();
+        assert_eq!(u32::from_le_bytes(cmd[19..23].try_into().unwrap()), gtid_data.len() as u32);
+        assert_eq!(&cmd[23..], &gtid_data[..]);
+    }
+
+    fn rotate_packet(filename: &str) -> Vec<u8> {
+        RawEvent::new(4, &rotate_body(filename, 4)).timestamp(0).packet()
+    }
+
+    fn xid_packet(timestamp: u32, next_pos: u32) -> Vec<u8> {
+        RawEvent::new(16, &7u64.to_le_bytes())
+            .timestamp(timestamp)
+            .next_pos(next_pos)
+            .packet()
+    }
+
+    fn gtid_packet(sequence: u64, next_pos: u32) -> Vec<u8> {
+        let mut body = vec![0x00];
+        body.extend_from_slice(&[0x11; 16]);
+        body.extend_from_slice(&sequence.to_le_bytes());
+        body.resize(42, 0);
+        RawEvent::new(33, &body).next_pos(next_pos).packet()
+    }
+
+    fn mariadb_gtid_packet(sequence: u64, next_pos: u32) -> Vec<u8> {
+        let mut body = sequence.to_le_bytes().to_vec();
+        body.extend_from_slice(&0u32.to_le_bytes()); // domain_id
+        body.push(0); // flags
+        RawEvent::new(162, &body).next_pos(next_pos).packet()
+    }
+
+    /// 패킷들을 서버 쪽에서 보내고 stream_events 실행
+    async fn run_stream(
+        packets: Vec<Vec<u8>>,
+        stop: Option<StopCondition>,
+        executed: Option<&mut GtidSet>,
+    ) -> (Result<BinlogPosition>, Vec<BinlogEvent>) {
+        run_stream_with(packets, stop, executed, None).await
+    }
+
+    async fn run_stream_with(
+        packets: Vec<Vec<u8>>,
+        stop: Option<StopCondition>,
+        executed: Option<&mut GtidSet>,
+        archiver: Option<&mut BinlogArchiver>,
+    ) -> (Result<BinlogPosition>, Vec<BinlogEvent>) {
+        let (client, server) = tokio::io::duplex(64 * 1024);
+        let mut channel = PacketChannel::from_stream(client);
+        let mut server = PacketChannel::from_stream(server);
+
+        let (tx, mut rx) = queue::bounded(QueueConfig::default());
+        let writer = async move {
+            for (i, packet) in packets.iter().enumerate() {
+                server.write_packet(packet, i as u8).await.unwrap();
+            }
+            server
+        };
+
+        let start = BinlogPosition::new("mysql-bin.000001".to_string(), 4);
+        let (result, _server) = tokio::join!(
+            BinlogClient::stream_events(
+                &mut channel,
+                start,
+                stop.as_ref(),
+                &tx,
+                None,
+                executed,
+                archiver
+            ),
+            writer
+        );
+
+        let mut events = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            events.push(event);
+        }
+        (result, events)
+    }
+
+    #[tokio::test]
+    async fn test_stream_ends_on_eof() {
+        let packets = vec![
+            rotate_packet("mysql-bin.000002"),
+            xid_packet(1_700_000_000, 150),
+            vec![0xFE, 0x00, 0x00, 0x02, 0x00],
+        ];
+
+        let (result, events) = run_stream(packets, None, None).await;
+        assert_eq!(result.unwrap(), BinlogPosition::new("mysql-bin.000002".to_string(), 150));
+        assert_eq!(events.len(), 2);
+        assert!(matches!(events[1].data, BinlogEventData::Xid(7)));
+    }
+
+    #[tokio::test]
+    async fn test_stream_stop_conditions() {
+        let packets = || {
+            vec![
+                gtid_packet(1, 100),
+                xid_packet(1_700_000_000, 200),
+                gtid_packet(2, 300),
+                xid_packet(1_700_000_100, 400),
+                vec![0xFE, 0x00, 0x00, 0x02, 0x00],
+            ]
+        };
+
+        let stop_position = BinlogPosition::new("mysql-bin.000001".to_string(), 200);
+        let stop = StopCondition::Position(stop_position);
+        let (result, events) = run_stream(packets(), Some(stop), None).await;
+        assert_eq!(result.unwrap().position, 200);
+        assert_eq!(events.len(), 2);
+
+        let stop = StopCondition::Gtid("11111111-1111-1111-1111-111111111111:2".to_string());
+        let (result, events) = run_stream(packets(), Some(stop), None).await;
+        assert_eq!(result.unwrap().position, 400);
+        assert_eq!(events.len(), 4);
+
+        let stop = StopCondition::Timestamp(DateTime::from_timestamp(1_700_000_050, 0).unwrap());
+        let (result, events) = run_stream(packets(), Some(stop), None).await;
+        assert_eq!(result.unwrap().position, 300);
+        assert_eq!(events.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_stream_tracks_committed_gtids() {
+        // 두 번째 트랜잭션은 커밋 전에 끊김
+        let packets = vec![
+            gtid_packet(1, 100),
+            xid_packet(1_700_000_000, 200),
+            gtid_packet(2, 300),
+            vec![0xFE, 0x00, 0x00, 0x02, 0x00],
+        ];
+
+        let uuid = "11111111-1111-1111-1111-111111111111";
+        let mut executed = GtidSet::new();
+        let (result, events) = run_stream(packets, None, Some(&mut executed)).await;
+        result.unwrap();
+        assert_eq!(events.len(), 3);
+        assert!(executed.contains(&format!("{}:1", uuid)));
+        assert!(!executed.contains(&format!("{}:2", uuid)));
+    }
+
+    #[tokio::test]
+    async fn test_stream_stop_at_mariadb_gtid() {
+        let packets = vec![
+            mariadb_gtid_packet(1, 100),
+            xid_packet(1_700_000_000, 200),
+            mariadb_gtid_packet(2, 300),
+            xid_packet(1_700_000_100, 400),
+            vec![0xFE, 0x00, 0x00, 0x02, 0x00],
+        ];
+
+        let stop = StopCondition::Gtid("0-1-1".to_string());
+        let (result, events) = run_stream(packets, Some(stop), None).await;
+        assert_eq!(result.unwrap().position, 200);
+        assert_eq!(events.len(), 2);
+        assert!(matches!(events[0].data, BinlogEventData::MariadbGtid(_)));
+    }
+
+    #[tokio::test]
+    async fn test_stream_archives_raw_events() {
+        // 체크섬을 붙인 패킷 (OK 바이트 + 이벤트 + CRC32)
+        let format = format_description_body("8.0.35", Some(1));
+        let packets = vec![
+            RawEvent::new(4, &rotate_body("mysql-bin.000001", 4))
+                .timestamp(0)
+                .crc(true)
+                .packet(),
+            RawEvent::new(15, &format)
+                .timestamp(0)
+                .next_pos(125)
+                .crc(true)
+                .packet(),
+            RawEvent::new(16, &7u64.to_le_bytes())
+                .next_pos(156)
+                .crc(true)
+                .packet(),
+            vec![0xFE, 0x00, 0x00, 0x02, 0x00],
+        ];
+
+        let dir = std::env::temp_dir()
+            .join(format!("binlog_client_archive_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let mut archiver = BinlogArchiver::open(ArchiveConfig::new(&dir)).await.unwrap();
+        archiver.set_checksum(ChecksumAlgorithm::Crc32);
+
+        let (result, events) =
+            run_stream_with(packets.clone(), None, None, Some(&mut archiver)).await;
+        assert_eq!(result.unwrap().position, 156);
+        assert!(matches!(events[2].data, BinlogEventData::Xid(7)));
+
+        let mut expected = crate::binlog::BINLOG_MAGIC.to_vec();
+        expected.extend_from_slice(&packets[1][1..]);
+        expected.extend_from_slice(&packets[2][1..]);
+        assert_eq!(std::fs::read(dir.join("mysql-bin.000001")).unwrap(), expected);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_semi_sync_ack() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let (client, mut server) = tokio::io::duplex(64 * 1024);
+        let mut channel = PacketChannel::from_stream(client);
+        let (tx, mut rx) = queue::bounded::<BinlogEvent>(QueueConfig::default());
+        let (ack_tx, mut ack_rx) = mpsc::unbounded_channel();
+        let ack = SemiSyncAck(ack_tx);
+
+        let server_side = async move {
+            // ACK 를 요청하는 XID 이벤트
+            let mut packet = vec![0x00, SEMI_SYNC_INDICATOR, SEMI_SYNC_ACK_REQ];
+            packet.extend_from_slice(&xid_packet(1_700_000_000, 200)[1..]);
+            let mut frame = (packet.len() as u32).to_le_bytes();
+            frame[3] = 0;
+            server.write_all(&frame).await.unwrap();
+            server.write_all(&packet).await.unwrap();
+
+            // ACK 는 sequence 0 의 새 패킷
+            let mut header = [0u8; 4];
+            server.read_exact(&mut header).await.unwrap();
+            assert_eq!(header[3], 0);
+            let mut ack = vec![0u8; header[0] as usize];
+            server.read_exact(&mut ack).await.unwrap();
+
+            // 서버는 원래 sequence 를 이어서 사용
+            server.write_all(&[5, 0, 0, 1, 0xFE, 0, 0, 2, 0]).await.unwrap();
+            ack
+        };
+
+        let consumer = async move {
+            let event = rx.recv().await.unwrap();
+            assert!(matches!(event.data, BinlogEventData::Xid(7)));
+            ack.ack(BinlogPosition::new("mysql-bin.000001".to_string(), 200)).unwrap();
+            ack
+        };
+
+        let start = BinlogPosition::new("mysql-bin.000001".to_string(), 4);
+        let semi_sync = Some(SemiSync::new(&mut ack_rx));
+        let (result, ack_packet, _ack) = tokio::join!(
+            BinlogClient::stream_events(&mut channel, start, None, &tx, semi_sync, None, None),
+            server_side,
+            consumer
+        );
+
+        assert_eq!(result.unwrap().position, 200);
+        let mut expected = vec![SEMI_SYNC_INDICATOR];
+        expected.extend_from_slice(&200u64.to_le_bytes());
+        expected.extend_from_slice(b"mysql-bin.000001");
+        assert_eq!(ack_packet, expected);
+    }
+
+    #[tokio::test]
+    async fn test_semi_sync_ack_under_load() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let (client, mut server) = tokio::io::duplex(64 * 1024);
+        let mut channel = PacketChannel::from_stream(client);
+        let config = QueueConfig { capacity: 1, max_bytes: None };
+        let (tx, mut rx) = queue::bounded::<BinlogEvent>(config);
+        let (ack_tx, mut ack_rx) = mpsc::unbounded_channel();
+        let ack = SemiSyncAck(ack_tx);
+
+        // ACK 를 요청하는 이벤트 뒤에 이벤트가 계속 이어져 읽기 버퍼가 비지 않음
+        let mut burst = Vec::new();
+        for (sequence, next_pos) in (0u8..).zip((200..=1000).step_by(100)) {
+            let mut packet = vec![0x00, SEMI_SYNC_INDICATOR, SEMI_SYNC_ACK_REQ];
+            packet.extend_from_slice(&xid_packet(1_700_000_000, next_pos)[1..]);
+            let mut frame = (packet.len() as u32).to_le_bytes();
+            frame[3] = sequence;
+            burst.extend_from_slice(&frame);
+            burst.extend_from_slice(&packet);
+        }
+        burst.extend_from_slice(&[5, 0, 0, 9, 0xFE, 0, 0, 2, 0]);
+
+        let server_side = async move {
+            server.write_all(&burst).await.unwrap();
+            let mut header = [0u8; 4];
+            server.read_exact(&mut header).await.unwrap();
+            let mut ack = vec![0u8; header[0] as usize];
+            server.read_exact(&mut ack).await.unwrap();
+            ack
+        };
+
+        let consumer = async move {
+            rx.recv().await.unwrap();
+            ack.ack(BinlogPosition::new("mysql-bin.000001".to_string(), 200)).unwrap();
+            for _ in 0..8 {
+                rx.recv().await.unwrap();
+            }
+        };
+
+        let start = BinlogPosition::new("mysql-bin.000001".to_string(), 4);
+        let semi_sync = Some(SemiSync::new(&mut ack_rx));
+        let stream =
+            BinlogClient::stream_events(&mut channel, start, None, &tx, semi_sync, None, None);
+        let joined = async { tokio::join!(stream, server_side, consumer) };
+        let (result, ack_packet, _) =
+            tokio::time::timeout(std::time::Duration::from_secs(5), joined)
+                .await
+                .expect("ACK should be sent before the stream ends");
+
+        assert_eq!(result.unwrap().position, 1000);
+        assert_eq!(&ack_packet[1..9], &200u64.to_le_bytes());
+    }
+
+    #[tokio::test]
+    async fn test_semi_sync_ack_with_compression() {
+        use crate::compression::{Compression, COMPRESSED_HEADER_SIZE};
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let (client, mut server) = tokio::io::duplex(64 * 1024);
+        let mut channel = PacketChannel::from_stream(client);
+        channel.enable_compression(Compression::Zlib);
+        let config = QueueConfig { capacity: 1, max_bytes: None };
+        let (tx, mut rx) = queue::bounded::<BinlogEvent>(config);
+        let (ack_tx, mut ack_rx) = mpsc::unbounded_channel();
+        let ack = SemiSyncAck(ack_tx);
+
+        let mut packets = Vec::new();
+        for (sequence, next_pos) in (0u8..).zip((200..=1000).step_by(100)) {
+            let mut packet = vec![0x00, SEMI_SYNC_INDICATOR, SEMI_SYNC_ACK_REQ];
+            packet.extend_from_slice(&xid_packet(1_700_000_000, next_pos)[1..]);
+            let mut frame = (packet.len() as u32).to_le_bytes();
+            frame[3] = sequence;
+            let mut framed = frame.to_vec();
+            framed.extend_from_slice(&packet);
+            packets.push(framed);
+        }
+        packets.push(vec![5, 0, 0, 9, 0xFE, 0, 0, 2, 0]);
+
+        // 압축 프레임 하나에 여러 패킷을 담아, ACK 전송 시점에도 압축 해제 버퍼가 비지 않음
+        let compressed_frame = |data: &[u8], sequence: u8| {
+            let compressed = Compression::Zlib.compress(data).unwrap();
+            let mut frame = (compressed.len() as u32).to_le_bytes();
+            frame[3] = sequence;
+            let mut frame = frame.to_vec();
+            frame.extend_from_slice(&(data.len() as u32).to_le_bytes()[..3]);
+            frame.extend_from_slice(&compressed);
+            frame
+        };
+
+        let server_side = async move {
+            server.write_all(&compressed_frame(&packets[..4].concat(), 0)).await.unwrap();
+            server.write_all(&compressed_frame(&packets[4..7].concat(), 1)).await.unwrap();
+
+            // ACK 는 압축 sequence 0 의 새 프레임
+            let mut header = [0u8; COMPRESSED_HEADER_SIZE];
+            server.read_exact(&mut header).await.unwrap();
+            assert_eq!(header[3], 0);
+            let mut ack = vec![0u8; header[0] as usize];
+            server.read_exact(&mut ack).await.unwrap();
+
+            // 서버는 자신의 압축 sequence 를 이어서 사용
+            server.write_all(&compressed_frame(&packets[7..].concat(), 2)).await.unwrap();
+            ack
+        };
+
+        let consumer = async move {
+            rx.recv().await.unwrap();
+            ack.ack(BinlogPosition::new("mysql-bin.000001".to_string(), 200)).unwrap();
+            for _ in 0..8 {
+                rx.recv().await.unwrap();
+            }
+        };
+
+        let start = BinlogPosition::new("mysql-bin.000001".to_string(), 4);
+        let semi_sync = Some(SemiSync::new(&mut ack_rx));
+        let stream =
+            BinlogClient::stream_events(&mut channel, start, None, &tx, semi_sync, None, None);
+        let joined = async { tokio::join!(stream, server_side, consumer) };
+        let (result, ack_packet, _) =
+            tokio::time::timeout(std::time::Duration::from_secs(5), joined)
+                .await
+                .expect("compressed stream should continue after the ACK");
+
+        assert_eq!(result.unwrap().position, 1000);
+        // 압축하지 않은 프레임 안의 sequence 0 패킷
+        assert_eq!(ack_packet[3], 0);
+        assert_eq!(&ack_packet[5..13], &200u64.to_le_bytes());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_clear_password_over_unix_socket_requires_opt_in() {
+        use crate::auth::capabilities;
+        use crate::server::ServerInfo;
+
+        let path = std::env::temp_dir().join(format!("mysql-cdc-{}.sock", uuid::Uuid::new_v4()));
+        let listener = tokio::net::UnixListener::bind(&path).unwrap();
+        let server = tokio::spawn(async move { listener.accept().await.unwrap() });
+
+        let mut channel = PacketChannel::connect_unix(&path).await.unwrap();
+        let _server = server.await.unwrap();
+        std::fs::remove_file(&path).ok();
+
+        let greeting = GreetingPacket {
+            protocol_version: 10,
+            server_version: "8.0.35".to_string(),
+            thread_id: 1,
+            scramble: vec![0x11; 20],
+            server_capabilities: capabilities::SECURE_CONNECTION | capabilities::PLUGIN_AUTH,
+            server_collation: 255,
+            server_status: 0,
+            auth_plugin_name: Some("mysql_clear_password".to_string()),
+            server_info: ServerInfo::detect("8.0.35"),
+        };
+        let config = ConnectionConfig {
+            password: "secret".to_string(),
+            ..ConnectionConfig::default()
+        };
+
+        // 비밀번호를 보내기 전에 거부
+        let result = BinlogClient::authenticate(&mut channel, &greeting, &config, 1).await;
+        assert!(matches!(result, Err(CdcError::ConfigError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_semi_sync_parse_error_is_fatal() {
+        let (client, mut server) = tokio::io::duplex(64 * 1024);
+        let mut channel = PacketChannel::from_stream(client);
+        let (tx, _rx) = queue::bounded::<BinlogEvent>(QueueConfig::default());
+        let (_ack_tx, mut ack_rx) = mpsc::unbounded_channel();
+
+        // ACK 를 요청하지만 헤더가 잘린 이벤트
+        let packet = [0x00, SEMI_SYNC_INDICATOR, SEMI_SYNC_ACK_REQ, 1, 2, 3];
+        let mut frame = (packet.len() as u32).to_le_bytes();
+        frame[3] = 0;
+        tokio::io::AsyncWriteExt::write_all(&mut server, &frame).await.unwrap();
+        tokio::io::AsyncWriteExt::write_all(&mut server, &packet).await.unwrap();
+
+        let start = BinlogPosition::new("mysql-bin.000001".to_string(), 4);
+        let semi_sync = Some(SemiSync::new(&mut ack_rx));
+        let stream =
+            BinlogClient::stream_events(&mut channel, start, None, &tx, semi_sync, None, None);
+        let result = tokio::time::timeout(std::time::Duration::from_secs(5), stream).await;
+        assert!(result.expect("stream should fail instead of waiting").is_err());
     }
 }
@@ -0,0 +1,500 @@
+//! 암호화된 binlog 파일 (`binlog_encryption=ON`, MySQL 8.0.14+)
+//!
+//! 파일은 512 바이트 암호화 헤더와 AES-256-CTR 로 암호화된 일반 binlog 로 이루어집니다.
+//! 헤더는 매직 (0xfd 'b' 'i' 'n'), 버전 (1), TLV 필드 (keyring 키 ID, 암호화된 파일 비밀번호,
+//! 비밀번호 IV) 순서입니다.
+//!
+//! 파일 비밀번호는 keyring 의 replication master key 로 AES-256-CBC (패딩 없음) 복호화하고,
+//! SHA-512(파일 비밀번호) 의 앞 32 바이트를 파일 키, 다음 16 바이트를 CTR IV 로 사용합니다
+//! (`EVP_BytesToKey(aes-256-ctr, sha512, 1)` 과 같음).
+//! binlog 위치 (next_pos, 덤프 위치) 는 헤더를 뺀 평문 기준입니다.
+
+use crate::binlog::BINLOG_MAGIC;
+use crate::error::{CdcError, Result};
+use aes::cipher::{BlockDecrypt, BlockEncrypt, KeyInit, KeyIvInit, StreamCipher, StreamCipherSeek};
+use aes::Aes256;
+use rand::RngCore;
+use sha2::{Digest, Sha512};
+use std::collections::HashMap;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// 암호화된 binlog 파일의 매직 넘버
+pub const ENCRYPTED_BINLOG_MAGIC: [u8; 4] = [0xfd, 0x62, 0x69, 0x6e];
+
+/// 암호화 헤더 크기 (평문 binlog 는 이 위치부터 시작)
+pub const ENCRYPTION_HEADER_SIZE: u64 = 512;
+
+/// 지원하는 헤더 버전
+const HEADER_VERSION: u8 = 1;
+
+/// 헤더 TLV 필드 타입 (0 은 끝)
+const FIELD_KEY_ID: u8 = 1;
+const FIELD_ENCRYPTED_PASSWORD: u8 = 2;
+const FIELD_IV: u8 = 3;
+
+/// 파일 비밀번호 길이
+const PASSWORD_LENGTH: usize = 32;
+
+/// AES 블록 / IV 길이
+const BLOCK_SIZE: usize = 16;
+
+/// replication master key 길이 (AES-256)
+const MASTER_KEY_LENGTH: usize = 32;
+
+type Aes256Ctr = ctr::Ctr128BE<Aes256>;
+
+/// 파일 앞의 매직 넘버가 암호화된 binlog 인지 여부
+pub fn is_encrypted(magic: &[u8]) -> bool {
+    magic.starts_with(&ENCRYPTED_BINLOG_MAGIC)
+}
+
+/// 암호화 헤더 (Rpl_encryption_header_v1)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncryptionHeader {
+    pub version: u8,
+    /// keyring 의 replication master key ID (예: "MySQLReplicationKey_<server_uuid>_1")
+    pub key_id: String,
+    /// master key 로 암호화된 파일 비밀번호
+    pub encrypted_password: [u8; PASSWORD_LENGTH],
+    /// 파일 비밀번호 복호화용 IV
+    pub iv: [u8; BLOCK_SIZE],
+}
+
+impl EncryptionHeader {
+    /// 파일 앞 512 바이트 파싱
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        let invalid = |reason: &str| {
+            CdcError::BinlogParseError(format!("Invalid binlog encryption header: {}", reason))
+        };
+
+        if data.len() < ENCRYPTION_HEADER_SIZE as usize {
+            return Err(invalid("too short"));
+        }
+        if !is_encrypted(data) {
+            return Err(invalid("bad magic"));
+        }
+        let version = data[4];
+        if version != HEADER_VERSION {
+            return Err(invalid(&format!("unsupported version {}", version)));
+        }
+
+        let mut key_id = None;
+        let mut encrypted_password = None;
+        let mut iv = None;
+
+        let mut fields = &data[5..ENCRYPTION_HEADER_SIZE as usize];
+        while let Some((&field, rest)) = fields.split_first() {
+            let length = match field {
+                0 => break,
+                FIELD_KEY_ID => {
+                    let (&length, rest) = rest.split_first().ok_or_else(|| invalid("key id"))?;
+                    let value = rest
+                        .get(..length as usize)
+                        .ok_or_else(|| invalid("key id"))?;
+                    key_id = Some(String::from_utf8_lossy(value).to_string());
+                    1 + length as usize
+                }
+                FIELD_ENCRYPTED_PASSWORD => {
+                    let value = rest.first_chunk().ok_or_else(|| invalid("file password"))?;
+                    encrypted_password = Some(*value);
+                    PASSWORD_LENGTH
+                }
+                FIELD_IV => {
+                    iv = Some(*rest.first_chunk().ok_or_else(|| invalid("iv"))?);
+                    BLOCK_SIZE
+                }
+                other => return Err(invalid(&format!("unknown field type {}", other))),
+            };
+            fields = &rest[length..];
+        }
+
+        Ok(EncryptionHeader {
+            version,
+            key_id: key_id.ok_or_else(|| invalid("missing key id"))?,
+            encrypted_password: encrypted_password.ok_or_else(|| invalid("missing password"))?,
+            iv: iv.ok_or_else(|| invalid("missing iv"))?,
+        })
+    }
+
+    /// 512 바이트 헤더로 직렬화 (남는 부분은 0)
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        let key_id = self.key_id.as_bytes();
+        let length = u8::try_from(key_id.len())
+            .map_err(|_| CdcError::InvalidEvent(format!("Key id too long: {}", self.key_id)))?;
+
+        let mut data = Vec::with_capacity(ENCRYPTION_HEADER_SIZE as usize);
+        data.extend_from_slice(&ENCRYPTED_BINLOG_MAGIC);
+        data.push(self.version);
+        data.extend_from_slice(&[FIELD_KEY_ID, length]);
+        data.extend_from_slice(key_id);
+        data.push(FIELD_ENCRYPTED_PASSWORD);
+        data.extend_from_slice(&self.encrypted_password);
+        data.push(FIELD_IV);
+        data.extend_from_slice(&self.iv);
+        data.resize(ENCRYPTION_HEADER_SIZE as usize, 0);
+        Ok(data)
+    }
+
+    /// master key 로 파일 비밀번호 복호화 (AES-256-CBC, 패딩 없음)
+    pub fn decrypt_password(&self, master_key: &[u8]) -> Result<[u8; PASSWORD_LENGTH]> {
+        let cipher = master_cipher(master_key)?;
+        let mut password = self.encrypted_password;
+        let mut previous = self.iv;
+        for block in password.chunks_exact_mut(BLOCK_SIZE) {
+            let encrypted: [u8; BLOCK_SIZE] = block.try_into().unwrap_or_default();
+            cipher.decrypt_block(block.into());
+            xor(block, &previous);
+            previous = encrypted;
+        }
+        Ok(password)
+    }
+}
+
+/// replication master key 목록
+///
+/// component_keyring_file 의 JSON 파일을 읽거나 키를 직접 넣을 수 있습니다.
+/// (keyring_file 플러그인의 바이너리 형식은 지원하지 않음)
+#[derive(Debug, Clone, Default)]
+pub struct Keyring {
+    keys: HashMap<String, Vec<u8>>,
+    /// 키 ID 와 무관하게 사용할 키
+    default_key: Option<Vec<u8>>,
+}
+
+impl Keyring {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 헤더의 키 ID 와 무관하게 `key` 를 사용하는 keyring
+    pub fn from_key(key: impl Into<Vec<u8>>) -> Self {
+        Keyring {
+            keys: HashMap::new(),
+            default_key: Some(key.into()),
+        }
+    }
+
+    /// 키 ID 로 키 추가
+    pub fn with_key(mut self, key_id: &str, key: impl Into<Vec<u8>>) -> Self {
+        self.keys.insert(key_id.to_string(), key.into());
+        self
+    }
+
+    /// component_keyring_file 데이터 파일 읽기
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path).map_err(|e| {
+            CdcError::IoError(format!("Failed to read keyring {}: {}", path.display(), e))
+        })?;
+        Self::from_json(&json)
+    }
+
+    /// `{"version": "1.0", "elements": [{"data_id": .., "data_type": "AES", "data": "<hex>"}]}`
+    pub fn from_json(json: &str) -> Result<Self> {
+        let invalid = |reason: &str| CdcError::Other(format!("Invalid keyring: {}", reason));
+
+        let value: serde_json::Value = serde_json::from_str(json)?;
+        let elements = value["elements"]
+            .as_array()
+            .ok_or_else(|| invalid("missing elements"))?;
+
+        let mut keyring = Keyring::new();
+        for element in elements {
+            let key_id = element["data_id"]
+                .as_str()
+                .ok_or_else(|| invalid("missing data_id"))?;
+            let data = element["data"]
+                .as_str()
+                .ok_or_else(|| invalid("missing data"))?;
+            let key =
+                decode_hex(data).ok_or_else(|| invalid(&format!("bad data for {}", key_id)))?;
+            keyring.keys.insert(key_id.to_string(), key);
+        }
+        Ok(keyring)
+    }
+
+    /// 키 ID 의 키 (없으면 기본 키)
+    pub fn get(&self, key_id: &str) -> Option<&[u8]> {
+        self.keys
+            .get(key_id)
+            .or(self.default_key.as_ref())
+            .map(Vec::as_slice)
+    }
+}
+
+/// 암호화된 binlog 를 평문 binlog 처럼 읽는 리더
+///
+/// 위치와 `Seek` 는 헤더를 뺀 평문 기준이므로 `BinlogFileReader::new` 에 그대로 넘길 수 있습니다.
+pub struct DecryptingReader<R> {
+    inner: R,
+    cipher: Aes256Ctr,
+    header: EncryptionHeader,
+    position: u64,
+}
+
+impl<R: Read + Seek> DecryptingReader<R> {
+    /// 헤더를 읽고 keyring 의 master key 로 파일 키 준비
+    pub fn new(mut inner: R, keyring: &Keyring) -> Result<Self> {
+        inner.seek(SeekFrom::Start(0))?;
+        let mut data = vec![0u8; ENCRYPTION_HEADER_SIZE as usize];
+        inner.read_exact(&mut data).map_err(|e| {
+            CdcError::BinlogParseError(format!("Failed to read binlog encryption header: {}", e))
+        })?;
+        let header = EncryptionHeader::parse(&data)?;
+
+        let master_key = keyring
+            .get(&header.key_id)
+            .ok_or_else(|| CdcError::Other(format!("Keyring has no key {}", header.key_id)))?;
+        let password = header.decrypt_password(master_key)?;
+
+        Ok(DecryptingReader {
+            inner,
+            cipher: file_cipher(&password),
+            header,
+            position: 0,
+        })
+    }
+
+    pub fn header(&self) -> &EncryptionHeader {
+        &self.header
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for DecryptingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.cipher.apply_keystream(&mut buf[..read]);
+        self.position += read as u64;
+        Ok(read)
+    }
+}
+
+impl<R: Seek> Seek for DecryptingReader<R> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let target = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::Current(delta) => self.position.checked_add_signed(delta),
+            SeekFrom::End(delta) => {
+                let end = self.inner.seek(SeekFrom::End(0))?;
+                end.saturating_sub(ENCRYPTION_HEADER_SIZE)
+                    .checked_add_signed(delta)
+            }
+        };
+        let target = target.ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Seek before start of encrypted binlog",
+            )
+        })?;
+
+        self.inner
+            .seek(SeekFrom::Start(target + ENCRYPTION_HEADER_SIZE))?;
+        self.cipher.try_seek(target).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Seek beyond encrypted binlog keystream",
+            )
+        })?;
+        self.position = target;
+        Ok(target)
+    }
+}
+
+/// 평문 binlog 를 암호화된 파일로 변환 (테스트 fixture 작성용, 비밀번호와 IV 는 무작위)
+pub fn encrypt_binlog(binlog: &[u8], key_id: &str, master_key: &[u8]) -> Result<Vec<u8>> {
+    if !binlog.starts_with(BINLOG_MAGIC) {
+        return Err(CdcError::BinlogParseError(
+            "Invalid binlog magic number".to_string(),
+        ));
+    }
+
+    let mut password = [0u8; PASSWORD_LENGTH];
+    let mut iv = [0u8; BLOCK_SIZE];
+    rand::thread_rng().fill_bytes(&mut password);
+    rand::thread_rng().fill_bytes(&mut iv);
+
+    // AES-256-CBC (패딩 없음)
+    let cipher = master_cipher(master_key)?;
+    let mut encrypted_password = password;
+    let mut previous = iv;
+    for block in encrypted_password.chunks_exact_mut(BLOCK_SIZE) {
+        xor(block, &previous);
+        cipher.encrypt_block(block.into());
+        previous = block.try_into().unwrap_or_default();
+    }
+
+    let header = EncryptionHeader {
+        version: HEADER_VERSION,
+        key_id: key_id.to_string(),
+        encrypted_password,
+        iv,
+    };
+    let mut data = header.encode()?;
+    let start = data.len();
+    data.extend_from_slice(binlog);
+    file_cipher(&password).apply_keystream(&mut data[start..]);
+    Ok(data)
+}
+
+fn master_cipher(master_key: &[u8]) -> Result<Aes256> {
+    if master_key.len() != MASTER_KEY_LENGTH {
+        return Err(CdcError::Other(format!(
+            "Replication master key must be {} bytes, got {}",
+            MASTER_KEY_LENGTH,
+            master_key.len()
+        )));
+    }
+    Ok(Aes256::new(master_key.into()))
+}
+
+/// SHA-512(비밀번호) = 파일 키 (32) + IV (16) + 미사용 (16)
+fn file_cipher(password: &[u8]) -> Aes256Ctr {
+    let digest = Sha512::digest(password);
+    Aes256Ctr::new(digest[..32].into(), digest[32..48].into())
+}
+
+fn xor(block: &mut [u8], mask: &[u8]) {
+    block
+        .iter_mut()
+        .zip(mask)
+        .for_each(|(byte, mask)| *byte ^= mask);
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binlog_file::{BinlogFileReader, FIRST_EVENT_POSITION};
+    use crate::binlog_writer::BinlogFileBuilder;
+    use crate::events::{BinlogEventData, QueryEventData};
+    use std::io::Cursor;
+
+    const KEY_ID: &str = "MySQLReplicationKey_3e11fa47-71ca-11e1-9e33-c80aa9429562_1";
+    const MASTER_KEY: [u8; 32] = [0x5a; 32];
+
+    fn plain_binlog() -> Vec<u8> {
+        let query = |sql: &str| {
+            BinlogEventData::Query(QueryEventData {
+                thread_id: 3,
+                exec_time: 0,
+                database: "shop".to_string(),
+                query: sql.to_string(),
+            })
+        };
+        BinlogFileBuilder::new()
+            .event(query("BEGIN"))
+            .event(query("INSERT INTO t VALUES (1)"))
+            .event(BinlogEventData::Xid(5))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_encryption_header() {
+        let header = EncryptionHeader {
+            version: 1,
+            key_id: KEY_ID.to_string(),
+            encrypted_password: [7; 32],
+            iv: [9; 16],
+        };
+        let data = header.encode().unwrap();
+        assert_eq!(data.len(), 512);
+        assert!(is_encrypted(&data));
+        assert_eq!(EncryptionHeader::parse(&data).unwrap(), header);
+
+        let mut bad_version = data.clone();
+        bad_version[4] = 2;
+        assert!(EncryptionHeader::parse(&bad_version).is_err());
+        assert!(EncryptionHeader::parse(&data[..100]).is_err());
+
+        // 암호화한 비밀번호는 같은 master key 로만 복원
+        let encrypted = encrypt_binlog(&plain_binlog(), KEY_ID, &MASTER_KEY).unwrap();
+        let header = EncryptionHeader::parse(&encrypted).unwrap();
+        let password = header.decrypt_password(&MASTER_KEY).unwrap();
+        assert_ne!(password, header.decrypt_password(&[0x11; 32]).unwrap());
+        assert!(header.decrypt_password(&[0x11; 16]).is_err());
+    }
+
+    #[test]
+    fn test_read_encrypted_binlog() {
+        let plain = plain_binlog();
+        let encrypted = encrypt_binlog(&plain, KEY_ID, &MASTER_KEY).unwrap();
+        assert_eq!(encrypted.len(), plain.len() + 512);
+        assert_ne!(&encrypted[512..], &plain[..]);
+
+        let keyring = Keyring::new().with_key(KEY_ID, MASTER_KEY);
+        let reader = DecryptingReader::new(Cursor::new(encrypted.clone()), &keyring).unwrap();
+        let events: Vec<_> = BinlogFileReader::new(reader)
+            .unwrap()
+            .map(|e| e.unwrap())
+            .collect();
+        let expected: Vec<_> = BinlogFileReader::new(Cursor::new(plain.clone()))
+            .unwrap()
+            .map(|e| e.unwrap())
+            .collect();
+        assert_eq!(events.len(), 4);
+        assert_eq!(events[0].offset, FIRST_EVENT_POSITION);
+        let offsets = |events: &[crate::binlog_file::BinlogFileEvent]| {
+            events.iter().map(|e| e.offset).collect::<Vec<_>>()
+        };
+        assert_eq!(offsets(&events), offsets(&expected));
+
+        // 블록 경계가 아닌 평문 위치로 이동
+        let reader = DecryptingReader::new(Cursor::new(encrypted.clone()), &keyring).unwrap();
+        let mut reader = BinlogFileReader::new(reader).unwrap();
+        reader.seek(events[2].offset).unwrap();
+        let insert = reader.next_event().unwrap().unwrap();
+        assert!(matches!(&insert.event.data,
+            BinlogEventData::Query(q) if q.query == "INSERT INTO t VALUES (1)"));
+
+        // 암호화된 파일을 그대로 열거나 다른 키를 쓰면 에러
+        let err = BinlogFileReader::new(Cursor::new(encrypted.clone()))
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("encrypted"));
+        let wrong = Keyring::from_key([0x11; 32]);
+        let reader = DecryptingReader::new(Cursor::new(encrypted.clone()), &wrong).unwrap();
+        assert!(BinlogFileReader::new(reader).is_err());
+        let other = Keyring::new().with_key("MySQLReplicationKey_other_1", MASTER_KEY);
+        assert!(DecryptingReader::new(Cursor::new(encrypted), &other).is_err());
+    }
+
+    #[test]
+    fn test_keyring_file() {
+        let hex: String = MASTER_KEY.iter().map(|b| format!("{:02x}", b)).collect();
+        let json = format!(
+            r#"{{"version": "1.0", "elements": [{{"user": "", "data_id": "{}",
+                "data_type": "AES", "data": "{}", "extension": []}}]}}"#,
+            KEY_ID, hex
+        );
+        let dir = std::env::temp_dir().join(format!("binlog_encryption_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("component_keyring_file"), &json).unwrap();
+        let encrypted = encrypt_binlog(&plain_binlog(), KEY_ID, &MASTER_KEY).unwrap();
+        std::fs::write(dir.join("mysql-bin.000001"), &encrypted).unwrap();
+
+        let keyring = Keyring::from_file(dir.join("component_keyring_file")).unwrap();
+        assert_eq!(keyring.get(KEY_ID), Some(&MASTER_KEY[..]));
+        assert_eq!(keyring.get("missing"), None);
+
+        let reader = BinlogFileReader::open_encrypted(dir.join("mysql-bin.000001"), &keyring);
+        assert_eq!(reader.unwrap().count(), 4);
+
+        assert!(Keyring::from_json(r#"{"elements": [{"data_id": "k", "data": "zz"}]}"#).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,461 @@
+//! 디스크의 binlog 파일 읽기
+//!
+//! 백업 등으로 받은 `mysql-bin.000123` 파일을 스트리밍과 같은 파서로 디코딩합니다.
+//! 파일 끝의 이벤트가 아직 다 기록되지 않았으면 (서버가 쓰는 중) 그 이벤트 앞에서 멈추고,
+//! 파일이 커진 뒤 다시 읽으면 같은 위치부터 이어서 읽습니다.
+//! 암호화된 파일 (`binlog_encryption=ON`) 은 `open_encrypted` 로 엽니다.
+
+use crate::binlog::{BinlogParser, BINLOG_MAGIC, EVENT_HEADER_SIZE};
+use crate::binlog_encryption::{is_encrypted, DecryptingReader, Keyring};
+use crate::error::{CdcError, Result};
+use crate::events::{BinlogEvent, BinlogEventData, ChecksumAlgorithm};
+use bytes::Bytes;
+use futures_util::stream::{self, Stream};
+use std::fs::File;
+use std::io::{BufReader, ErrorKind, Read, Seek, SeekFrom};
+use std::path::Path;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt};
+
+/// 첫 이벤트 (FORMAT_DESCRIPTION) 위치
+pub const FIRST_EVENT_POSITION: u64 = BINLOG_MAGIC.len() as u64;
+
+/// 파일 안의 위치와 함께 읽은 이벤트
+#[derive(Debug, Clone)]
+pub struct BinlogFileEvent {
+    /// 이벤트가 시작하는 파일 위치
+    pub offset: u64,
+    pub event: BinlogEvent,
+}
+
+impl BinlogFileEvent {
+    /// 다음 이벤트 위치
+    pub fn next_offset(&self) -> u64 {
+        self.offset + self.event.header.event_length as u64
+    }
+}
+
+/// 동기/비동기 리더가 공유하는 상태
+#[derive(Debug)]
+struct ReaderState {
+    position: u64,
+    checksum: ChecksumAlgorithm,
+    /// FORMAT_DESCRIPTION 이벤트를 읽었는지 여부
+    format_loaded: bool,
+    /// 파일 끝에 덜 기록된 이벤트가 있는지 여부
+    partial: bool,
+    failed: bool,
+}
+
+impl ReaderState {
+    fn new() -> Self {
+        ReaderState {
+            position: FIRST_EVENT_POSITION,
+            checksum: ChecksumAlgorithm::None,
+            format_loaded: false,
+            partial: false,
+            failed: false,
+        }
+    }
+
+    /// 이벤트 헤더의 event_length 확인
+    fn event_length(&self, header: &[u8]) -> Result<usize> {
+        let length = u32::from_le_bytes([header[9], header[10], header[11], header[12]]) as usize;
+        if length < EVENT_HEADER_SIZE {
+            return Err(CdcError::InvalidEvent(format!(
+                "Invalid event length {} at {}",
+                length, self.position
+            )));
+        }
+        Ok(length)
+    }
+
+    /// 읽은 이벤트 바이트 파싱 후 위치 이동
+    fn decode(&mut self, data: Vec<u8>) -> Result<BinlogFileEvent> {
+        let offset = self.position;
+        let event = BinlogParser::parse_event_with_checksum(Bytes::from(data), self.checksum)
+            .map_err(|e| CdcError::BinlogParseError(format!("{} (at {})", e, offset)))?;
+
+        if let BinlogEventData::FormatDescription(format) = &event.data {
+            self.checksum = format.checksum;
+            self.format_loaded = true;
+        }
+
+        self.position = offset + event.header.event_length as u64;
+        self.partial = false;
+        Ok(BinlogFileEvent { offset, event })
+    }
+}
+
+/// 매직 넘버 확인
+fn check_magic(magic: &[u8], read: usize) -> Result<()> {
+    if read < BINLOG_MAGIC.len() {
+        return Err(CdcError::BinlogParseError("Invalid binlog: too short".to_string()));
+    }
+    if is_encrypted(magic) {
+        return Err(CdcError::BinlogParseError(
+            "Binlog is encrypted; use BinlogFileReader::open_encrypted with a keyring".to_string(),
+        ));
+    }
+    BinlogParser::verify_magic(magic)
+}
+
+/// `buf` 를 채울 때까지 읽고 읽은 바이트 수 반환 (파일 끝이면 더 적음)
+fn read_full<R: Read>(reader: &mut R, buf: &mut [u8]) -> Result<usize> {
+    let mut read = 0;
+    while read < buf.len() {
+        match reader.read(&mut buf[read..]) {
+            Ok(0) => break,
+            Ok(n) => read += n,
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(read)
+}
+
+async fn read_full_async<R: AsyncRead + Unpin>(reader: &mut R, buf: &mut [u8]) -> Result<usize> {
+    let mut read = 0;
+    while read < buf.len() {
+        match reader.read(&mut buf[read..]).await? {
+            0 => break,
+            n => read += n,
+        }
+    }
+    Ok(read)
+}
+
+/// binlog 파일 리더 (동기, `Iterator`)
+pub struct BinlogFileReader<R = BufReader<File>> {
+    reader: R,
+    state: ReaderState,
+}
+
+impl BinlogFileReader<BufReader<File>> {
+    /// 파일 열기 (매직 넘버 확인)
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file = File::open(path).map_err(|e| {
+            CdcError::IoError(format!("Failed to open binlog {}: {}", path.display(), e))
+        })?;
+        Self::new(BufReader::new(file))
+    }
+}
+
+impl BinlogFileReader<DecryptingReader<BufReader<File>>> {
+    /// 암호화된 binlog 파일 열기 (keyring 의 replication master key 로 복호화)
+    ///
+    /// 위치는 암호화 헤더를 뺀 평문 기준입니다.
+    pub fn open_encrypted(path: impl AsRef<Path>, keyring: &Keyring) -> Result<Self> {
+        let path = path.as_ref();
+        let file = File::open(path).map_err(|e| {
+            CdcError::IoError(format!("Failed to open binlog {}: {}", path.display(), e))
+        })?;
+        Self::new(DecryptingReader::new(BufReader::new(file), keyring)?)
+    }
+}
+
+impl<R: Read + Seek> BinlogFileReader<R> {
+    /// 파일 처음부터 읽는 리더 생성 (매직 넘버 확인)
+    pub fn new(mut reader: R) -> Result<Self> {
+        reader.seek(SeekFrom::Start(0))?;
+        let mut magic = [0u8; 4];
+        let read = read_full(&mut reader, &mut magic)?;
+        check_magic(&magic, read)?;
+
+        Ok(BinlogFileReader {
+            reader,
+            state: ReaderState::new(),
+        })
+    }
+
+    /// 다음 이벤트를 읽을 위치
+    pub fn position(&self) -> u64 {
+        self.state.position
+    }
+
+    /// 이벤트 체크섬 알고리즘 (FORMAT_DESCRIPTION 이벤트를 읽기 전에는 None)
+    pub fn checksum(&self) -> ChecksumAlgorithm {
+        self.state.checksum
+    }
+
+    /// 파일 끝에 덜 기록된 이벤트가 남아 있는지 여부
+    pub fn has_partial_event(&self) -> bool {
+        self.state.partial
+    }
+
+    /// `position` 으로 이동 (이벤트 시작 위치여야 함)
+    ///
+    /// 체크섬 알고리즘을 알기 위해 FORMAT_DESCRIPTION 이벤트를 먼저 읽습니다.
+    pub fn seek(&mut self, position: u64) -> Result<()> {
+        if position < FIRST_EVENT_POSITION {
+            return Err(CdcError::InvalidEvent(format!("Invalid binlog position {}", position)));
+        }
+
+        if !self.state.format_loaded {
+            self.state.position = FIRST_EVENT_POSITION;
+            self.reader.seek(SeekFrom::Start(FIRST_EVENT_POSITION))?;
+            if self.next_event()?.is_none() {
+                return Err(CdcError::BinlogParseError(
+                    "Format description event is not available".to_string(),
+                ));
+            }
+        }
+
+        self.reader.seek(SeekFrom::Start(position))?;
+        self.state.position = position;
+        self.state.partial = false;
+        Ok(())
+    }
+
+    /// 다음 이벤트 읽기
+    ///
+    /// 파일 끝이거나 마지막 이벤트가 덜 기록되었으면 None 을 반환하고 위치는 그대로 둡니다.
+    pub fn next_event(&mut self) -> Result<Option<BinlogFileEvent>> {
+        let mut data = vec![0u8; EVENT_HEADER_SIZE];
+        let read = read_full(&mut self.reader, &mut data)?;
+        if read < EVENT_HEADER_SIZE {
+            return self.rewind(read);
+        }
+
+        let length = self.state.event_length(&data)?;
+        data.resize(length, 0);
+        let read = EVENT_HEADER_SIZE + read_full(&mut self.reader, &mut data[EVENT_HEADER_SIZE..])?;
+        if read < length {
+            return self.rewind(read);
+        }
+
+        self.state.decode(data).map(Some)
+    }
+
+    /// 덜 기록된 이벤트 앞으로 되돌아감
+    fn rewind(&mut self, read: usize) -> Result<Option<BinlogFileEvent>> {
+        self.state.partial = read > 0;
+        self.reader.seek(SeekFrom::Start(self.state.position))?;
+        Ok(None)
+    }
+}
+
+impl<R: Read + Seek> Iterator for BinlogFileReader<R> {
+    type Item = Result<BinlogFileEvent>;
+
+    /// 에러가 나면 에러를 한 번 반환하고 끝남
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.state.failed {
+            return None;
+        }
+        match self.next_event() {
+            Ok(event) => event.map(Ok),
+            Err(e) => {
+                self.state.failed = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+/// binlog 파일 리더 (비동기, `Stream`)
+pub struct AsyncBinlogFileReader<R = tokio::io::BufReader<tokio::fs::File>> {
+    reader: R,
+    state: ReaderState,
+}
+
+impl AsyncBinlogFileReader<tokio::io::BufReader<tokio::fs::File>> {
+    /// 파일 열기 (매직 넘버 확인)
+    pub async fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file = tokio::fs::File::open(path).await.map_err(|e| {
+            CdcError::IoError(format!("Failed to open binlog {}: {}", path.display(), e))
+        })?;
+        Self::new(tokio::io::BufReader::new(file)).await
+    }
+}
+
+impl<R: AsyncRead + AsyncSeek + Unpin> AsyncBinlogFileReader<R> {
+    /// 파일 처음부터 읽는 리더 생성 (매직 넘버 확인)
+    pub async fn new(mut reader: R) -> Result<Self> {
+        reader.seek(SeekFrom::Start(0)).await?;
+        let mut magic = [0u8; 4];
+        let read = read_full_async(&mut reader, &mut magic).await?;
+        check_magic(&magic, read)?;
+
+        Ok(AsyncBinlogFileReader {
+            reader,
+            state: ReaderState::new(),
+        })
+    }
+
+    /// 다음 이벤트를 읽을 위치
+    pub fn position(&self) -> u64 {
+        self.state.position
+    }
+
+    /// 파일 끝에 덜 기록된 이벤트가 남아 있는지 여부
+    pub fn has_partial_event(&self) -> bool {
+        self.state.partial
+    }
+
+    /// `position` 으로 이동 (`BinlogFileReader::seek` 참고)
+    pub async fn seek(&mut self, position: u64) -> Result<()> {
+        if position < FIRST_EVENT_POSITION {
+            return Err(CdcError::InvalidEvent(format!("Invalid binlog position {}", position)));
+        }
+
+        if !self.state.format_loaded {
+            self.state.position = FIRST_EVENT_POSITION;
+            self.reader.seek(SeekFrom::Start(FIRST_EVENT_POSITION)).await?;
+            if self.next_event().await?.is_none() {
+                return Err(CdcError::BinlogParseError(
+                    "Format description event is not available".to_string(),
+                ));
+            }
+        }
+
+        self.reader.seek(SeekFrom::Start(position)).await?;
+        self.state.position = position;
+        self.state.partial = false;
+        Ok(())
+    }
+
+    /// 다음 이벤트 읽기 (`BinlogFileReader::next_event` 참고)
+    pub async fn next_event(&mut self) -> Result<Option<BinlogFileEvent>> {
+        let mut data = vec![0u8; EVENT_HEADER_SIZE];
+        let read = read_full_async(&mut self.reader, &mut data).await?;
+        if read < EVENT_HEADER_SIZE {
+            return self.rewind(read).await;
+        }
+
+        let length = self.state.event_length(&data)?;
+        data.resize(length, 0);
+        let read = EVENT_HEADER_SIZE
+            + read_full_async(&mut self.reader, &mut data[EVENT_HEADER_SIZE..]).await?;
+        if read < length {
+            return self.rewind(read).await;
+        }
+
+        self.state.decode(data).map(Some)
+    }
+
+    async fn rewind(&mut self, read: usize) -> Result<Option<BinlogFileEvent>> {
+        self.state.partial = read > 0;
+        self.reader.seek(SeekFrom::Start(self.state.position)).await?;
+        Ok(None)
+    }
+
+    /// 파일 끝까지 이벤트를 전달하는 스트림 (에러가 나면 에러를 한 번 전달하고 끝남)
+    pub fn into_stream(self) -> impl Stream<Item = Result<BinlogFileEvent>> {
+        stream::unfold(Some(self), |reader| async move {
+            let mut reader = reader?;
+            match reader.next_event().await {
+                Ok(Some(event)) => Some((Ok(event), Some(reader))),
+                Ok(None) => None,
+                Err(e) => Some((Err(e), None)),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::EventType;
+    use crate::test_fixtures::{format_description, RawEvent};
+    use futures_util::StreamExt;
+    use std::io::Cursor;
+
+    /// 매직 + FORMAT_DESCRIPTION + QUERY + XID
+    fn binlog_file() -> Vec<u8> {
+        let mut file = BINLOG_MAGIC.to_vec();
+        file.extend(format_description("8.0.35", 0));
+
+        // 고정 헤더 13 바이트 + 빈 데이터베이스명의 null
+        let mut query = vec![0u8; 14];
+        query.extend_from_slice(b"BEGIN");
+        let query = RawEvent::new(2, &query).crc(true);
+        let xid_pos = file.len() + query.build().len();
+        file.extend(query.next_pos(xid_pos as u32).build());
+        let xid = 9u64.to_le_bytes();
+        let xid = RawEvent::new(16, &xid).crc(true);
+        let end = xid_pos + xid.build().len();
+        file.extend(xid.next_pos(end as u32).build());
+        file
+    }
+
+    #[test]
+    fn test_read_events_with_offsets() {
+        let reader = BinlogFileReader::new(Cursor::new(binlog_file())).unwrap();
+        let events: Vec<BinlogFileEvent> = reader.map(|e| e.unwrap()).collect();
+
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0].offset, FIRST_EVENT_POSITION);
+        assert!(matches!(
+            &events[0].event.data,
+            BinlogEventData::FormatDescription(f) if f.checksum == ChecksumAlgorithm::Crc32
+        ));
+        // CRC32 가 제외된 쿼리
+        assert!(matches!(&events[1].event.data, BinlogEventData::Query(q) if q.query == "BEGIN"));
+        assert_eq!(events[2].offset, events[1].next_offset());
+        assert!(matches!(events[2].event.data, BinlogEventData::Xid(9)));
+
+        assert!(BinlogFileReader::new(Cursor::new(vec![0u8; 4])).is_err());
+    }
+
+    #[test]
+    fn test_seek_and_partial_event() {
+        let file = binlog_file();
+        let xid_offset = {
+            let reader = BinlogFileReader::new(Cursor::new(file.clone())).unwrap();
+            reader.last().unwrap().unwrap().offset
+        };
+
+        // 새 리더에서 바로 이동 (FORMAT_DESCRIPTION 을 먼저 읽어 체크섬 확인)
+        let mut reader = BinlogFileReader::new(Cursor::new(file.clone())).unwrap();
+        reader.seek(xid_offset).unwrap();
+        assert_eq!(reader.checksum(), ChecksumAlgorithm::Crc32);
+        let xid = reader.next_event().unwrap().unwrap();
+        assert_eq!(xid.event.header.event_type, EventType::XidEvent);
+
+        // 마지막 이벤트가 덜 기록된 파일
+        let truncated = file[..file.len() - 3].to_vec();
+        let mut reader = BinlogFileReader::new(Cursor::new(truncated)).unwrap();
+        assert_eq!(reader.by_ref().count(), 2);
+        assert!(reader.has_partial_event());
+        assert_eq!(reader.position(), xid_offset);
+
+        // 체크섬 불일치
+        let mut corrupted = file.clone();
+        let last = corrupted.len() - 6;
+        corrupted[last] ^= 0xff;
+        let results: Vec<_> = BinlogFileReader::new(Cursor::new(corrupted)).unwrap().collect();
+        assert_eq!(results.len(), 3);
+        assert!(results[2].is_err());
+    }
+
+    #[tokio::test]
+    async fn test_async_stream_and_growing_file() {
+        let file = binlog_file();
+        let path = std::env::temp_dir().join(format!("binlog_file_test_{}", std::process::id()));
+
+        // 처음에는 마지막 이벤트가 덜 기록됨
+        std::fs::write(&path, &file[..file.len() - 3]).unwrap();
+        let mut reader = AsyncBinlogFileReader::open(&path).await.unwrap();
+        let mut count = 0;
+        while reader.next_event().await.unwrap().is_some() {
+            count += 1;
+        }
+        assert_eq!(count, 2);
+        assert!(reader.has_partial_event());
+
+        // 나머지가 기록되면 이어서 읽음
+        std::fs::write(&path, &file).unwrap();
+        let xid = reader.next_event().await.unwrap().unwrap();
+        assert!(matches!(xid.event.data, BinlogEventData::Xid(9)));
+        assert!(!reader.has_partial_event());
+
+        let reader = AsyncBinlogFileReader::open(&path).await.unwrap();
+        let events: Vec<_> = reader.into_stream().collect().await;
+        assert_eq!(events.len(), 3);
+        assert!(events.iter().all(|e| e.is_ok()));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,450 @@
+//! 여러 binlog 파일을 이어서 읽기
+//!
+//! `mysql-bin.index` 파일이나 디렉터리 목록에서 파일을 찾아 `BinlogPosition::file_sequence`
+//! 순서로 읽습니다. ROTATE / STOP 이벤트나 (비정상 종료로) 파일 끝을 만나면 다음 파일로 넘어가므로
+//! 라이브 서버 없이 보관된 binlog 를 스트리밍과 같은 순서로 재생할 수 있습니다.
+//!
+//! relay log (`relay-bin.*`) 도 같은 방식으로 읽습니다. relay log 에는 replica 가 기록한 이벤트와
+//! source 에서 받은 이벤트가 섞여 있으므로 파일 전환은 replica 자신의 ROTATE / STOP 으로만 하고,
+//! source 의 ROTATE 는 `source_position` 추적에 사용합니다.
+
+use crate::binlog_file::{BinlogFileReader, FIRST_EVENT_POSITION};
+use crate::error::{CdcError, Result};
+use crate::events::{BinlogEvent, BinlogEventData, EventType};
+use crate::offset::BinlogPosition;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 순서가 정해진 binlog 파일 목록
+#[derive(Debug, Clone, Default)]
+pub struct BinlogIndex {
+    files: Vec<PathBuf>,
+}
+
+impl BinlogIndex {
+    /// 파일 목록을 시퀀스 번호 순서로 정렬하여 생성 (중복 제거)
+    pub fn new(mut files: Vec<PathBuf>) -> Self {
+        files.sort_by_key(|path| (file_sequence(path), file_name(path)));
+        files.dedup();
+        BinlogIndex { files }
+    }
+
+    /// `mysql-bin.index` / `relay-bin.index` 파일 읽기
+    ///
+    /// 상대 경로나 더 이상 없는 절대 경로 (다른 곳으로 옮긴 보관본) 는 인덱스 파일의
+    /// 디렉터리에서 같은 파일명을 찾습니다.
+    pub fn from_index_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path).map_err(|e| {
+            CdcError::IoError(format!(
+                "Failed to read binlog index {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        let dir = path.parent().unwrap_or_else(|| Path::new("."));
+
+        let files = content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                let entry = Path::new(line);
+                if entry.is_absolute() && entry.exists() {
+                    entry.to_path_buf()
+                } else {
+                    dir.join(entry.file_name().unwrap_or(entry.as_os_str()))
+                }
+            })
+            .collect();
+
+        Ok(Self::new(files))
+    }
+
+    /// 디렉터리에서 `<basename>.<시퀀스>` 파일 찾기 (예: `mysql-bin`, `relay-bin`)
+    pub fn from_directory(dir: impl AsRef<Path>, basename: &str) -> Result<Self> {
+        let dir = dir.as_ref();
+        let entries = fs::read_dir(dir).map_err(|e| {
+            CdcError::IoError(format!(
+                "Failed to list binlog directory {}: {}",
+                dir.display(),
+                e
+            ))
+        })?;
+
+        let mut files = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            let name = file_name(&path);
+            let is_binlog = name
+                .strip_prefix(basename)
+                .and_then(|rest| rest.strip_prefix('.'))
+                .is_some_and(|sequence| {
+                    !sequence.is_empty() && sequence.bytes().all(|b| b.is_ascii_digit())
+                });
+            if is_binlog && path.is_file() {
+                files.push(path);
+            }
+        }
+
+        Ok(Self::new(files))
+    }
+
+    /// 정렬된 파일 경로
+    pub fn files(&self) -> &[PathBuf] {
+        &self.files
+    }
+
+    /// 파일명 (경로 제외) 으로 위치 찾기
+    pub fn position_of(&self, name: &str) -> Option<usize> {
+        self.files.iter().position(|path| file_name(path) == name)
+    }
+
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+fn file_sequence(path: &Path) -> Option<u64> {
+    BinlogPosition::new(file_name(path), 0).file_sequence()
+}
+
+/// 파일 위치와 함께 읽은 이벤트
+#[derive(Debug, Clone)]
+pub struct BinlogIndexEvent {
+    /// 이벤트가 시작하는 파일명과 위치
+    pub position: BinlogPosition,
+    pub event: BinlogEvent,
+}
+
+/// 인덱스의 파일들을 이어서 읽는 리더 (`Iterator`)
+pub struct BinlogIndexReader {
+    index: BinlogIndex,
+    current: usize,
+    reader: BinlogFileReader,
+    /// 현재 파일을 기록한 서버 (첫 FORMAT_DESCRIPTION 의 server_id)
+    local_server_id: Option<u32>,
+    /// relay log 의 source binlog 위치
+    source: Option<BinlogPosition>,
+    failed: bool,
+}
+
+impl BinlogIndexReader {
+    /// 첫 파일부터 읽기
+    pub fn new(index: BinlogIndex) -> Result<Self> {
+        if index.is_empty() {
+            return Err(CdcError::IoError("Binlog index has no files".to_string()));
+        }
+        let reader = BinlogFileReader::open(&index.files[0])?;
+
+        Ok(BinlogIndexReader {
+            index,
+            current: 0,
+            reader,
+            local_server_id: None,
+            source: None,
+            failed: false,
+        })
+    }
+
+    /// `position` (파일명 + 이벤트 시작 위치) 부터 읽기
+    pub fn open_at(index: BinlogIndex, position: &BinlogPosition) -> Result<Self> {
+        let current = index.position_of(&position.filename).ok_or_else(|| {
+            CdcError::IoError(format!("Binlog {} is not in the index", position.filename))
+        })?;
+
+        let mut reader = Self::new(index)?;
+        reader.open_file(current)?;
+        if position.position > FIRST_EVENT_POSITION {
+            // 파일 전환 판단에 필요한 FORMAT_DESCRIPTION 의 server_id 를 먼저 읽음
+            if let Some(format) = reader.reader.next_event()? {
+                reader.local_server_id = Some(format.event.header.server_id);
+            }
+            reader.reader.seek(position.position)?;
+        }
+        Ok(reader)
+    }
+
+    /// 다음 이벤트를 읽을 위치
+    pub fn position(&self) -> BinlogPosition {
+        BinlogPosition::new(self.current_file(), self.reader.position())
+    }
+
+    /// 마지막으로 읽은 이벤트 다음의 source binlog 위치 (relay log 에서만 알 수 있음)
+    pub fn source_position(&self) -> Option<&BinlogPosition> {
+        self.source.as_ref()
+    }
+
+    /// 현재 읽는 파일명
+    pub fn current_file(&self) -> String {
+        file_name(&self.index.files[self.current])
+    }
+
+    /// 다음 이벤트 읽기
+    ///
+    /// 마지막 파일의 끝이면 None 을 반환하며, 파일이 커진 뒤 다시 호출하면 이어서 읽습니다.
+    pub fn next_event(&mut self) -> Result<Option<BinlogIndexEvent>> {
+        loop {
+            let Some(file_event) = self.reader.next_event()? else {
+                let has_next = self.current + 1 < self.index.len();
+                if self.reader.has_partial_event() || !has_next {
+                    return Ok(None);
+                }
+                // ROTATE 없이 끝난 파일 (서버 비정상 종료)
+                self.open_file(self.current + 1)?;
+                continue;
+            };
+
+            let position = BinlogPosition::new(self.current_file(), file_event.offset);
+            let event = file_event.event;
+
+            if event.header.event_type == EventType::FormatDescriptionEvent {
+                self.local_server_id.get_or_insert(event.header.server_id);
+            }
+            let local = self.local_server_id == Some(event.header.server_id);
+
+            let ends_file = matches!(
+                event.header.event_type,
+                EventType::RotateEvent | EventType::StopEvent
+            );
+            if local && ends_file {
+                if self.current + 1 < self.index.len() {
+                    self.open_file(self.current + 1)?;
+                }
+            } else if !local {
+                self.track_source(&event);
+            }
+
+            return Ok(Some(BinlogIndexEvent { position, event }));
+        }
+    }
+
+    /// source 에서 받은 이벤트로 source binlog 위치 갱신
+    fn track_source(&mut self, event: &BinlogEvent) {
+        if let BinlogEventData::Rotate(rotate) = &event.data {
+            self.source = Some(BinlogPosition::new(
+                rotate.next_binlog_name.clone(),
+                rotate.position,
+            ));
+        } else if let Some(source) = self.source.as_mut() {
+            if event.header.next_pos != 0 {
+                source.position = event.header.next_pos as u64;
+            }
+        }
+    }
+
+    fn open_file(&mut self, index: usize) -> Result<()> {
+        self.reader = BinlogFileReader::open(&self.index.files[index])?;
+        self.current = index;
+        self.local_server_id = None;
+        Ok(())
+    }
+}
+
+impl Iterator for BinlogIndexReader {
+    type Item = Result<BinlogIndexEvent>;
+
+    /// 에러가 나면 에러를 한 번 반환하고 끝남
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        match self.next_event() {
+            Ok(event) => event.map(Ok),
+            Err(e) => {
+                self.failed = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binlog::BINLOG_MAGIC;
+    use crate::test_fixtures::{format_description_body, rotate_body, RawEvent};
+
+    /// 테스트용 binlog 파일 내용 (체크섬 없는 5.5 형식)
+    struct FileBuilder {
+        data: Vec<u8>,
+    }
+
+    impl FileBuilder {
+        fn new(server_id: u32) -> Self {
+            let mut builder = FileBuilder {
+                data: BINLOG_MAGIC.to_vec(),
+            };
+            builder.event(15, server_id, 0, &format_description_body("5.5.62", None));
+            builder
+        }
+
+        fn event(
+            &mut self,
+            event_type: u8,
+            server_id: u32,
+            next_pos: u32,
+            body: &[u8],
+        ) -> &mut Self {
+            let event = RawEvent::new(event_type, body)
+                .server_id(server_id)
+                .next_pos(next_pos);
+            self.data.extend(event.build());
+            self
+        }
+
+        fn xid(&mut self, server_id: u32, xid: u64, next_pos: u32) -> &mut Self {
+            self.event(16, server_id, next_pos, &xid.to_le_bytes())
+        }
+
+        fn rotate(&mut self, server_id: u32, name: &str, position: u64) -> &mut Self {
+            self.event(4, server_id, 0, &rotate_body(name, position))
+        }
+
+        fn write(&self, dir: &Path, name: &str) -> PathBuf {
+            let path = dir.join(name);
+            fs::write(&path, &self.data).unwrap();
+            path
+        }
+    }
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("binlog_index_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn xids(reader: BinlogIndexReader) -> Vec<(String, u64)> {
+        reader
+            .map(|event| event.unwrap())
+            .filter_map(|e| match e.event.data {
+                BinlogEventData::Xid(xid) => Some((e.position.filename, xid)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_index_order_and_directory() {
+        let dir = test_dir("order");
+        for name in ["mysql-bin.000010", "mysql-bin.000002", "mysql-bin.000009"] {
+            FileBuilder::new(1).write(&dir, name);
+        }
+        fs::write(dir.join("mysql-bin.index"), "").unwrap();
+        fs::write(dir.join("relay-bin.000001"), BINLOG_MAGIC).unwrap();
+
+        let index = BinlogIndex::from_directory(&dir, "mysql-bin").unwrap();
+        let names: Vec<String> = index.files().iter().map(|p| file_name(p)).collect();
+        assert_eq!(
+            names,
+            ["mysql-bin.000002", "mysql-bin.000009", "mysql-bin.000010"]
+        );
+
+        // 인덱스 파일의 절대 경로가 없으면 인덱스 디렉터리에서 찾음
+        let content = "/var/lib/mysql/mysql-bin.000010\n./mysql-bin.000009\n\n";
+        fs::write(dir.join("mysql-bin.index"), content).unwrap();
+        let index = BinlogIndex::from_index_file(dir.join("mysql-bin.index")).unwrap();
+        assert_eq!(
+            index.files(),
+            [dir.join("mysql-bin.000009"), dir.join("mysql-bin.000010")]
+        );
+        assert_eq!(index.position_of("mysql-bin.000010"), Some(1));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_traverse_rotated_files() {
+        let dir = test_dir("rotate");
+        FileBuilder::new(1)
+            .xid(1, 1, 0)
+            .rotate(1, "mysql-bin.000002", 4)
+            .write(&dir, "mysql-bin.000001");
+        // STOP 으로 끝난 파일
+        FileBuilder::new(1)
+            .xid(1, 2, 0)
+            .event(3, 1, 0, &[])
+            .write(&dir, "mysql-bin.000002");
+        // 비정상 종료로 ROTATE 가 없는 파일
+        FileBuilder::new(1)
+            .xid(1, 3, 0)
+            .write(&dir, "mysql-bin.000003");
+        FileBuilder::new(1)
+            .xid(1, 4, 0)
+            .write(&dir, "mysql-bin.000004");
+
+        let index = BinlogIndex::from_directory(&dir, "mysql-bin").unwrap();
+        let reader = BinlogIndexReader::new(index.clone()).unwrap();
+        let expected = [
+            ("mysql-bin.000001".to_string(), 1),
+            ("mysql-bin.000002".to_string(), 2),
+            ("mysql-bin.000003".to_string(), 3),
+            ("mysql-bin.000004".to_string(), 4),
+        ];
+        assert_eq!(xids(reader), expected);
+
+        // 두 번째 파일 중간 (XID) 부터 재개해도 STOP 에서 다음 파일로 넘어감
+        let resume = BinlogIndexReader::new(index.clone())
+            .unwrap()
+            .map(|event| event.unwrap())
+            .find(|e| matches!(e.event.data, BinlogEventData::Xid(2)))
+            .unwrap()
+            .position;
+        assert!(resume.position > FIRST_EVENT_POSITION);
+        let mut reader = BinlogIndexReader::open_at(index, &resume).unwrap();
+        let first = reader.next_event().unwrap().unwrap();
+        assert_eq!(first.position, resume);
+        assert_eq!(xids(reader), expected[2..]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_relay_log_tracks_source_position() {
+        let dir = test_dir("relay");
+        // replica (server_id 2) 의 relay log 에 source (server_id 1) 이벤트가 섞임
+        FileBuilder::new(2)
+            .rotate(1, "mysql-bin.000007", 4)
+            .xid(1, 1, 500)
+            .rotate(1, "mysql-bin.000008", 4)
+            .xid(1, 2, 300)
+            .rotate(2, "relay-bin.000002", 4)
+            .write(&dir, "relay-bin.000001");
+        FileBuilder::new(2)
+            .xid(1, 3, 900)
+            .write(&dir, "relay-bin.000002");
+
+        let index = BinlogIndex::from_directory(&dir, "relay-bin").unwrap();
+        let mut reader = BinlogIndexReader::new(index).unwrap();
+        let mut seen = Vec::new();
+        while let Some(event) = reader.next_event().unwrap() {
+            if let BinlogEventData::Xid(xid) = event.event.data {
+                seen.push((
+                    event.position.filename,
+                    xid,
+                    reader.source_position().cloned(),
+                ));
+            }
+        }
+
+        let source = |name: &str, pos| Some(BinlogPosition::new(name.to_string(), pos));
+        assert_eq!(seen[0].2, source("mysql-bin.000007", 500));
+        assert_eq!(seen[1].2, source("mysql-bin.000008", 300));
+        assert_eq!(seen[2].0, "relay-bin.000002");
+        assert_eq!(seen[2].2, source("mysql-bin.000008", 900));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,1302 @@
+//! Binlog 이벤트 인코더 (`BinlogParser` 의 역변환)
+//!
+//! `BinlogEvent` 를 v4 이벤트 바이트로 직렬화하고, 헤더의 event_length / next_pos 와
+//! CRC32 체크섬을 채웁니다. `BinlogFileBuilder` 로 매직 넘버와 FORMAT_DESCRIPTION 으로 시작하는
+//! 파일 전체를 만들 수 있어 MySQL 없이 파서 round-trip 테스트와 fixture 를 만들 수 있습니다.
+//!
+//! 행 이벤트의 `rows` 는 앞서 인코딩한 TABLE_MAP 의 컬럼 타입과 메타데이터로 인코딩합니다.
+//! `rows` 가 비어 있으면 `rows_data` 를 그대로 기록합니다. TABLE_MAP 이 없거나 값이 컬럼 타입과
+//! 맞지 않으면 `CdcError::InvalidEvent` 를 반환합니다.
+
+use crate::binlog::{
+    bitmap_bit, decimal_length, fraction_length, is_numeric_type, string_type, BINLOG_MAGIC,
+    CHECKSUM_VERSION, DIG2BYTES, EVENT_HEADER_SIZE, TABLE_MAP_SIGNEDNESS,
+};
+use crate::error::{CdcError, Result};
+use crate::events::*;
+use crate::gtid;
+use crate::server::ServerVersion;
+use byteorder::{BigEndian, LittleEndian, WriteBytesExt};
+use bytes::Bytes;
+use chrono::{Datelike, Timelike};
+use mysql_async::consts::ColumnType;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// MySQL 8.0 이벤트 타입별 post-header 길이 (타입 1~40)
+const POST_HEADER_LENGTHS: [u8; 40] = [
+    56, 13, 0, 8, 0, 0, 0, 0, 4, 0, 4, 0, 0, 0, 97, 0, 4, 26, 8, 0, 0, 0, 8, 8, 8, 2, 0, 0, 0, 10,
+    10, 10, 42, 42, 0, 18, 52, 0, 10, 40,
+];
+
+/// 이벤트 인코더
+///
+/// 인코딩한 TABLE_MAP 을 table_id 별로 기억해 이후 행 이벤트의 `rows` 를 인코딩합니다.
+#[derive(Debug, Clone, Default)]
+pub struct BinlogEncoder {
+    checksum: ChecksumAlgorithm,
+    tables: HashMap<u64, TableMapData>,
+}
+
+impl BinlogEncoder {
+    /// `checksum` 은 FORMAT_DESCRIPTION 이외 이벤트에 붙일 체크섬
+    pub fn new(checksum: ChecksumAlgorithm) -> Self {
+        BinlogEncoder {
+            checksum,
+            tables: HashMap::new(),
+        }
+    }
+
+    /// 이벤트 인코딩 (헤더 + body + 체크섬)
+    ///
+    /// `position` 은 이벤트가 기록될 파일 위치로 next_pos 는 `position` + 이벤트 길이가 됩니다.
+    /// 0 이면 next_pos 도 0 (서버가 보내는 가상 이벤트) 입니다.
+    /// event_type 은 데이터에 맞게 정해지며, 행 이벤트는 헤더가 v1 타입이면 v1 으로 기록합니다.
+    pub fn encode(&mut self, event: &BinlogEvent, position: u64) -> Result<Vec<u8>> {
+        let (event_type, body) = encode_body(&event.header, &event.data, &self.tables)?;
+
+        // FORMAT_DESCRIPTION 은 자신에 기록된 알고리즘으로 체크섬을 붙임
+        let checksum = match &event.data {
+            BinlogEventData::FormatDescription(format) if has_checksum_byte(format) => {
+                ChecksumAlgorithm::Crc32
+            }
+            BinlogEventData::FormatDescription(_) => ChecksumAlgorithm::None,
+            _ => self.checksum,
+        };
+
+        let length = EVENT_HEADER_SIZE + body.len() + checksum.checksum_len();
+        let next_pos = match position {
+            0 => 0,
+            position => position + length as u64,
+        };
+        let next_pos = u32::try_from(next_pos).map_err(|_| {
+            CdcError::InvalidEvent(format!("Event position {} exceeds 4GB", next_pos))
+        })?;
+
+        let mut data = Vec::with_capacity(length);
+        data.write_u32::<LittleEndian>(event.header.timestamp)?;
+        data.write_u8(event_type as u8)?;
+        data.write_u32::<LittleEndian>(event.header.server_id)?;
+        data.write_u32::<LittleEndian>(length as u32)?;
+        data.write_u32::<LittleEndian>(next_pos)?;
+        data.write_u16::<LittleEndian>(event.header.flags)?;
+        data.extend_from_slice(&body);
+
+        if checksum == ChecksumAlgorithm::Crc32 {
+            let crc = crc32fast::hash(&data);
+            data.write_u32::<LittleEndian>(crc)?;
+        }
+
+        if let BinlogEventData::TableMap(table_map) = &event.data {
+            self.tables.insert(table_map.table_id, table_map.clone());
+        }
+
+        Ok(data)
+    }
+}
+
+/// 5.6.1 이후 서버의 FORMAT_DESCRIPTION 은 알고리즘 바이트와 체크섬을 항상 가짐
+fn has_checksum_byte(format: &FormatDescriptionData) -> bool {
+    ServerVersion::parse(&format.server_version).is_some_and(|v| v >= CHECKSUM_VERSION)
+}
+
+/// 이벤트 데이터의 타입과 body
+fn encode_body(
+    header: &EventHeader,
+    data: &BinlogEventData,
+    tables: &HashMap<u64, TableMapData>,
+) -> Result<(EventType, Vec<u8>)> {
+    let mut body = Vec::new();
+
+    let event_type = match data {
+        BinlogEventData::FormatDescription(format) => {
+            body.write_u16::<LittleEndian>(format.binlog_version)?;
+            let version = format.server_version.as_bytes();
+            if version.len() > 50 {
+                return Err(CdcError::InvalidEvent(format!(
+                    "Server version is too long: {}",
+                    format.server_version
+                )));
+            }
+            body.extend_from_slice(version);
+            body.resize(2 + 50, 0);
+            body.write_u32::<LittleEndian>(format.create_timestamp)?;
+            body.write_u8(format.header_length)?;
+            body.extend_from_slice(&POST_HEADER_LENGTHS);
+            if has_checksum_byte(format) {
+                body.write_u8(match format.checksum {
+                    ChecksumAlgorithm::None => 0,
+                    ChecksumAlgorithm::Crc32 => 1,
+                })?;
+            }
+            EventType::FormatDescriptionEvent
+        }
+        BinlogEventData::TableMap(table_map) => {
+            body.write_u48::<LittleEndian>(table_map.table_id)?;
+            body.write_u16::<LittleEndian>(0)?;
+            write_name(&mut body, &table_map.database)?;
+            write_name(&mut body, &table_map.table)?;
+            write_lcb(&mut body, table_map.column_types.len() as u64)?;
+            body.extend_from_slice(&table_map.column_types);
+            let metadata = table_map.column_meta.concat();
+            write_lcb(&mut body, metadata.len() as u64)?;
+            body.extend_from_slice(&metadata);
+            let nullable_len = table_map.column_types.len().div_ceil(8);
+            let mut nullable = table_map.nullable_bitmap.clone();
+            nullable.resize(nullable_len, 0);
+            body.extend_from_slice(&nullable);
+            write_signedness(&mut body, table_map)?;
+            EventType::TableMapEvent
+        }
+        BinlogEventData::WriteRows(rows) => {
+            let v1 = header.event_type == EventType::WriteRowsEventV1;
+            write_rows_header(&mut body, rows.table_id, rows.flags, v1, rows.column_count)?;
+            body.extend_from_slice(&rows.columns_present);
+            if rows.rows.is_empty() {
+                body.extend_from_slice(&rows.rows_data);
+            } else {
+                let mut writer =
+                    RowImageWriter::new(&mut body, tables, rows.table_id, rows.column_count)?;
+                for row in &rows.rows {
+                    writer.write_row(&rows.columns_present, row)?;
+                }
+            }
+            if v1 {
+                EventType::WriteRowsEventV1
+            } else {
+                EventType::WriteRowsEvent
+            }
+        }
+        BinlogEventData::UpdateRows(rows) => {
+            let v1 = header.event_type == EventType::UpdateRowsEventV1;
+            write_rows_header(&mut body, rows.table_id, rows.flags, v1, rows.column_count)?;
+            body.extend_from_slice(&rows.columns_present);
+            body.extend_from_slice(&rows.columns_changed);
+            if rows.rows.is_empty() {
+                body.extend_from_slice(&rows.rows_data);
+            } else {
+                let mut writer =
+                    RowImageWriter::new(&mut body, tables, rows.table_id, rows.column_count)?;
+                for (before, after) in &rows.rows {
+                    writer.write_row(&rows.columns_present, before)?;
+                    writer.write_row(&rows.columns_changed, after)?;
+                }
+            }
+            if v1 {
+                EventType::UpdateRowsEventV1
+            } else {
+                EventType::UpdateRowsEvent
+            }
+        }
+        BinlogEventData::DeleteRows(rows) => {
+            let v1 = header.event_type == EventType::DeleteRowsEventV1;
+            write_rows_header(&mut body, rows.table_id, rows.flags, v1, rows.column_count)?;
+            body.extend_from_slice(&rows.columns_present);
+            if rows.rows.is_empty() {
+                body.extend_from_slice(&rows.rows_data);
+            } else {
+                let mut writer =
+                    RowImageWriter::new(&mut body, tables, rows.table_id, rows.column_count)?;
+                for row in &rows.rows {
+                    writer.write_row(&rows.columns_present, row)?;
+                }
+            }
+            if v1 {
+                EventType::DeleteRowsEventV1
+            } else {
+                EventType::DeleteRowsEvent
+            }
+        }
+        BinlogEventData::Query(query) => {
+            body.write_u32::<LittleEndian>(query.thread_id)?;
+            body.write_u32::<LittleEndian>(query.exec_time)?;
+            let database = query.database.as_bytes();
+            let db_len = u8::try_from(database.len()).map_err(|_| {
+                CdcError::InvalidEvent(format!("Database name is too long: {}", query.database))
+            })?;
+            body.write_u8(db_len)?;
+            body.write_u16::<LittleEndian>(0)?; // error_code
+            body.write_u16::<LittleEndian>(0)?; // status variables
+            body.extend_from_slice(database);
+            body.write_u8(0)?;
+            body.extend_from_slice(query.query.as_bytes());
+            EventType::QueryEvent
+        }
+        BinlogEventData::Rotate(rotate) => {
+            body.write_u64::<LittleEndian>(rotate.position)?;
+            body.extend_from_slice(rotate.next_binlog_name.as_bytes());
+            EventType::RotateEvent
+        }
+        BinlogEventData::Gtid(data) => {
+            let (uuid, sequence) = data
+                .gtid
+                .rsplit_once(':')
+                .and_then(|(uuid, sequence)| Some((uuid, sequence.parse::<u64>().ok()?)))
+                .ok_or_else(|| CdcError::GtidError(format!("Invalid GTID: {}", data.gtid)))?;
+            body.write_u8(if data.committed { 0 } else { 1 })?;
+            body.extend_from_slice(&gtid::parse_uuid(uuid)?);
+            body.write_u64::<LittleEndian>(sequence)?;
+            // logical clock (type 2 + last_committed + sequence_number)
+            body.write_u8(2)?;
+            body.write_u64::<LittleEndian>(0)?;
+            body.write_u64::<LittleEndian>(0)?;
+            EventType::GtidEvent
+        }
+        BinlogEventData::Xid(xid) => {
+            body.write_u64::<LittleEndian>(*xid)?;
+            EventType::XidEvent
+        }
+        BinlogEventData::Unknown(data) => {
+            body.extend_from_slice(data);
+            header.event_type
+        }
+        _ => {
+            return Err(CdcError::InvalidEvent(format!(
+                "Encoding is not supported for this event data (type {:?})",
+                header.event_type
+            )))
+        }
+    };
+
+    Ok((event_type, body))
+}
+
+/// 길이 1 바이트 + 이름 + null
+fn write_name(body: &mut Vec<u8>, name: &str) -> Result<()> {
+    let len = u8::try_from(name.len())
+        .map_err(|_| CdcError::InvalidEvent(format!("Name is too long: {}", name)))?;
+    body.write_u8(len)?;
+    body.extend_from_slice(name.as_bytes());
+    body.write_u8(0)?;
+    Ok(())
+}
+
+/// 행 이벤트 공통 헤더 (table_id, flags, v2 extra data, 컬럼 개수)
+fn write_rows_header(
+    body: &mut Vec<u8>,
+    table_id: u64,
+    flags: u16,
+    v1: bool,
+    column_count: u64,
+) -> Result<()> {
+    body.write_u48::<LittleEndian>(table_id)?;
+    body.write_u16::<LittleEndian>(flags)?;
+    if !v1 {
+        // extra data 길이 (자신 2 바이트 포함)
+        body.write_u16::<LittleEndian>(2)?;
+    }
+    write_lcb(body, column_count)
+}
+
+/// LCB (Length-Coded Binary) 쓰기
+fn write_lcb(body: &mut Vec<u8>, value: u64) -> Result<()> {
+    match value {
+        0..=0xfa => body.write_u8(value as u8)?,
+        0xfb..=0xffff => {
+            body.write_u8(0xfc)?;
+            body.write_u16::<LittleEndian>(value as u16)?;
+        }
+        0x1_0000..=0xff_ffff => {
+            body.write_u8(0xfd)?;
+            body.write_u24::<LittleEndian>(value as u32)?;
+        }
+        _ => {
+            body.write_u8(0xfe)?;
+            body.write_u64::<LittleEndian>(value)?;
+        }
+    }
+    Ok(())
+}
+
+/// optional metadata 의 SIGNEDNESS (숫자 컬럼마다 1 bit, MSB 부터) 쓰기
+///
+/// UNSIGNED 컬럼이 없으면 쓰지 않습니다.
+fn write_signedness(body: &mut Vec<u8>, table_map: &TableMapData) -> Result<()> {
+    if !table_map.unsigned_columns.iter().any(|&unsigned| unsigned) {
+        return Ok(());
+    }
+    let numeric: Vec<bool> = table_map
+        .column_types
+        .iter()
+        .enumerate()
+        .filter(|(_, &column_type)| is_numeric_type(column_type))
+        .map(|(i, _)| table_map.unsigned_columns.get(i).copied().unwrap_or(false))
+        .collect();
+    let mut bitmap = vec![0u8; numeric.len().div_ceil(8)];
+    for (index, _) in numeric.iter().enumerate().filter(|(_, &unsigned)| unsigned) {
+        bitmap[index / 8] |= 0x80 >> (index % 8);
+    }
+    body.write_u8(TABLE_MAP_SIGNEDNESS)?;
+    write_lcb(body, bitmap.len() as u64)?;
+    body.extend_from_slice(&bitmap);
+    Ok(())
+}
+
+/// 행 이미지 쓰기 (`BinlogParser::decode_rows` 의 역변환, 앞서 인코딩한 TABLE_MAP 사용)
+struct RowImageWriter<'a> {
+    body: &'a mut Vec<u8>,
+    table_map: &'a TableMapData,
+}
+
+impl<'a> RowImageWriter<'a> {
+    fn new(
+        body: &'a mut Vec<u8>,
+        tables: &'a HashMap<u64, TableMapData>,
+        table_id: u64,
+        column_count: u64,
+    ) -> Result<Self> {
+        let table_map = tables.get(&table_id).ok_or_else(|| {
+            CdcError::InvalidEvent(format!(
+                "Rows event for table_id {} has rows but no TABLE_MAP was encoded",
+                table_id
+            ))
+        })?;
+        if table_map.column_types.len() as u64 != column_count {
+            return Err(CdcError::InvalidEvent(format!(
+                "Rows event has {} columns but table map of {}.{} has {}",
+                column_count,
+                table_map.database,
+                table_map.table,
+                table_map.column_types.len()
+            )));
+        }
+
+        Ok(RowImageWriter { body, table_map })
+    }
+
+    /// null 비트맵 (이미지에 있는 컬럼마다 1 bit) + NULL 이 아닌 컬럼 값
+    fn write_row(&mut self, present: &[u8], row: &[CellValue]) -> Result<()> {
+        let columns = self.table_map.column_types.len();
+        if row.len() != columns {
+            return Err(CdcError::InvalidEvent(format!(
+                "Row has {} values but table map of {}.{} has {} columns",
+                row.len(),
+                self.table_map.database,
+                self.table_map.table,
+                columns
+            )));
+        }
+
+        let present: Vec<usize> = (0..columns).filter(|&i| bitmap_bit(present, i)).collect();
+        let mut null_bitmap = vec![0u8; present.len().div_ceil(8)];
+        for (index, &column) in present.iter().enumerate() {
+            if row[column] == CellValue::Null {
+                null_bitmap[index / 8] |= 1 << (index % 8);
+            }
+        }
+        self.body.extend_from_slice(&null_bitmap);
+
+        for column in present {
+            if row[column] != CellValue::Null {
+                self.write_cell(column, &row[column])?;
+            }
+        }
+        Ok(())
+    }
+
+    fn write_cell(&mut self, column: usize, value: &CellValue) -> Result<()> {
+        let column_type = self.table_map.column_types[column];
+        let meta = self.table_map.column_meta.get(column).map_or(&[][..], Vec::as_slice);
+        let meta_byte = |index: usize| meta.get(index).copied().unwrap_or(0);
+        let mismatch = || {
+            CdcError::InvalidEvent(format!(
+                "Cannot encode {:?} as column type {} of {}.{}",
+                value, column_type, self.table_map.database, self.table_map.table
+            ))
+        };
+
+        let body = &mut *self.body;
+        match ColumnType::try_from(column_type) {
+            Ok(ColumnType::MYSQL_TYPE_NULL) => {}
+            Ok(ColumnType::MYSQL_TYPE_TINY) => write_int(body, value, 1).ok_or_else(mismatch)?,
+            Ok(ColumnType::MYSQL_TYPE_SHORT) => write_int(body, value, 2).ok_or_else(mismatch)?,
+            Ok(ColumnType::MYSQL_TYPE_INT24) => write_int(body, value, 3).ok_or_else(mismatch)?,
+            Ok(ColumnType::MYSQL_TYPE_LONG) => write_int(body, value, 4).ok_or_else(mismatch)?,
+            Ok(ColumnType::MYSQL_TYPE_LONGLONG) => {
+                write_int(body, value, 8).ok_or_else(mismatch)?
+            }
+            Ok(ColumnType::MYSQL_TYPE_FLOAT) => match *value {
+                CellValue::Float(v) => body.write_f32::<LittleEndian>(v)?,
+                CellValue::Double(v) => body.write_f32::<LittleEndian>(v as f32)?,
+                _ => return Err(mismatch()),
+            },
+            Ok(ColumnType::MYSQL_TYPE_DOUBLE) => match *value {
+                CellValue::Double(v) => body.write_f64::<LittleEndian>(v)?,
+                CellValue::Float(v) => body.write_f64::<LittleEndian>(v as f64)?,
+                _ => return Err(mismatch()),
+            },
+            Ok(ColumnType::MYSQL_TYPE_YEAR) => {
+                let year = integer(value).ok_or_else(mismatch)?;
+                body.write_u8(if year == 0 { 0 } else { year.saturating_sub(1900) as u8 })?;
+            }
+            Ok(ColumnType::MYSQL_TYPE_NEWDECIMAL) => {
+                let CellValue::Decimal(decimal) = value else {
+                    return Err(mismatch());
+                };
+                let data = encode_decimal(decimal, meta_byte(0), meta_byte(1)).ok_or_else(mismatch)?;
+                body.extend_from_slice(&data);
+            }
+            Ok(ColumnType::MYSQL_TYPE_DATE) => {
+                let CellValue::Date(date) = value else {
+                    return Err(mismatch());
+                };
+                let (year, month, day) = parse_date(date).ok_or_else(mismatch)?;
+                body.write_u24::<LittleEndian>(year << 9 | month << 5 | day)?;
+            }
+            Ok(ColumnType::MYSQL_TYPE_TIME) => {
+                let (negative, (hour, minute, second), _) =
+                    time_parts(value).ok_or_else(mismatch)?;
+                let value = (hour * 10000 + minute * 100 + second) as i32;
+                body.write_i24::<LittleEndian>(if negative { -value } else { value })?;
+            }
+            Ok(ColumnType::MYSQL_TYPE_TIME2) => {
+                let fsp = meta_byte(0);
+                let (negative, (hour, minute, second), micros) =
+                    time_parts(value).ok_or_else(mismatch)?;
+                let hms = (hour << 12 | minute << 6 | second) as i64;
+                let packed = (hms << 24) + truncate_micros(micros, fsp) as i64;
+                write_time2(body, if negative { -packed } else { packed }, fsp);
+            }
+            Ok(ColumnType::MYSQL_TYPE_TIMESTAMP) => {
+                let CellValue::DateTime(datetime) = value else {
+                    return Err(mismatch());
+                };
+                body.write_u32::<LittleEndian>(datetime.timestamp() as u32)?;
+            }
+            Ok(ColumnType::MYSQL_TYPE_TIMESTAMP2) => {
+                let CellValue::DateTime(datetime) = value else {
+                    return Err(mismatch());
+                };
+                let fsp = meta_byte(0);
+                body.write_u32::<BigEndian>(datetime.timestamp() as u32)?;
+                write_fraction(body, datetime.timestamp_subsec_micros(), fsp);
+            }
+            Ok(ColumnType::MYSQL_TYPE_DATETIME) => {
+                let ((year, month, day), (hour, minute, second), _) =
+                    datetime_parts(value).ok_or_else(mismatch)?;
+                let date = (year * 10000 + month * 100 + day) as u64;
+                let time = (hour * 10000 + minute * 100 + second) as u64;
+                body.write_u64::<LittleEndian>(date * 1_000_000 + time)?;
+            }
+            Ok(ColumnType::MYSQL_TYPE_DATETIME2) => {
+                let fsp = meta_byte(0);
+                let ((year, month, day), (hour, minute, second), micros) =
+                    datetime_parts(value).ok_or_else(mismatch)?;
+                let ymd = ((year * 13 + month) << 5 | day) as u64;
+                let hms = (hour << 12 | minute << 6 | second) as u64;
+                let packed = (ymd << 17 | hms) + 0x80_0000_0000;
+                body.extend_from_slice(&packed.to_be_bytes()[3..]);
+                write_fraction(body, micros, fsp);
+            }
+            Ok(ColumnType::MYSQL_TYPE_VARCHAR | ColumnType::MYSQL_TYPE_VAR_STRING) => {
+                let max_length = meta_byte(0) as usize | (meta_byte(1) as usize) << 8;
+                let length_bytes = if max_length > 255 { 2 } else { 1 };
+                write_bytes(body, value, length_bytes).ok_or_else(mismatch)?;
+            }
+            Ok(
+                ColumnType::MYSQL_TYPE_STRING
+                | ColumnType::MYSQL_TYPE_ENUM
+                | ColumnType::MYSQL_TYPE_SET,
+            ) => {
+                let (real_type, max_length) = string_type(meta);
+                match ColumnType::try_from(real_type) {
+                    Ok(ColumnType::MYSQL_TYPE_ENUM) => {
+                        write_int(body, value, max_length.clamp(1, 2)).ok_or_else(mismatch)?
+                    }
+                    Ok(ColumnType::MYSQL_TYPE_SET) => {
+                        write_int(body, value, max_length.clamp(1, 8)).ok_or_else(mismatch)?
+                    }
+                    _ => {
+                        let length_bytes = if max_length > 255 { 2 } else { 1 };
+                        write_bytes(body, value, length_bytes).ok_or_else(mismatch)?
+                    }
+                }
+            }
+            Ok(
+                ColumnType::MYSQL_TYPE_TINY_BLOB
+                | ColumnType::MYSQL_TYPE_MEDIUM_BLOB
+                | ColumnType::MYSQL_TYPE_LONG_BLOB
+                | ColumnType::MYSQL_TYPE_BLOB
+                | ColumnType::MYSQL_TYPE_JSON
+                | ColumnType::MYSQL_TYPE_GEOMETRY
+                | ColumnType::MYSQL_TYPE_VECTOR,
+            ) => {
+                let length_bytes = (meta_byte(0) as usize).clamp(1, 4);
+                write_bytes(body, value, length_bytes).ok_or_else(mismatch)?
+            }
+            Ok(ColumnType::MYSQL_TYPE_BIT) => {
+                let length = (meta_byte(1) as usize + usize::from(meta_byte(0) > 0)).min(8);
+                let bits = integer(value).ok_or_else(mismatch)?;
+                body.extend_from_slice(&bits.to_be_bytes()[8 - length..]);
+            }
+            _ => return Err(mismatch()),
+        }
+        Ok(())
+    }
+}
+
+/// 정수 값 (2 의 보수 비트)
+fn integer(value: &CellValue) -> Option<u64> {
+    Some(match *value {
+        CellValue::Int8(v) => v as u64,
+        CellValue::Int16(v) => v as u64,
+        CellValue::Int32(v) => v as u64,
+        CellValue::Int64(v) => v as u64,
+        CellValue::UInt8(v) => v as u64,
+        CellValue::UInt16(v) => v as u64,
+        CellValue::UInt32(v) => v as u64,
+        CellValue::UInt64(v) => v,
+        _ => return None,
+    })
+}
+
+/// 정수의 하위 `length` 바이트 (little endian)
+fn write_int(body: &mut Vec<u8>, value: &CellValue, length: usize) -> Option<()> {
+    body.extend_from_slice(&integer(value)?.to_le_bytes()[..length]);
+    Some(())
+}
+
+/// 길이 (`length_bytes` 바이트, little endian) + 값
+fn write_bytes(body: &mut Vec<u8>, value: &CellValue, length_bytes: usize) -> Option<()> {
+    let value = match value {
+        CellValue::Bytes(bytes) => &bytes[..],
+        CellValue::String(string) => string.as_bytes(),
+        _ => return None,
+    };
+    if length_bytes < 8 && value.len() >> (length_bytes * 8) != 0 {
+        return None;
+    }
+    body.extend_from_slice(&(value.len() as u64).to_le_bytes()[..length_bytes]);
+    body.extend_from_slice(value);
+    Some(())
+}
+
+/// 소수 초를 fsp 자리에서 자른 마이크로초
+fn truncate_micros(micros: u32, fsp: u8) -> u32 {
+    let unit = 10u32.pow(6 - fsp.min(6) as u32);
+    micros / unit * unit
+}
+
+/// 소수 초 (fsp 별 단위, big endian)
+fn write_fraction(body: &mut Vec<u8>, micros: u32, fsp: u8) {
+    let value = match fsp {
+        1 | 2 => micros / 10000,
+        3 | 4 => micros / 100,
+        _ => micros,
+    };
+    let length = fraction_length(fsp);
+    body.extend_from_slice(&value.to_be_bytes()[4 - length..]);
+}
+
+/// TIME2 쓰기 (정수부는 부호 있는 shift, 소수부는 C 와 같은 나머지)
+fn write_time2(body: &mut Vec<u8>, packed: i64, fsp: u8) {
+    let integer = ((packed >> 24) + 0x800000) as u32;
+    let fraction = packed % (1 << 24);
+    match fsp {
+        1 | 2 => {
+            body.extend_from_slice(&integer.to_be_bytes()[1..]);
+            body.push((fraction / 10000) as u8);
+        }
+        3 | 4 => {
+            body.extend_from_slice(&integer.to_be_bytes()[1..]);
+            body.extend_from_slice(&((fraction / 100) as u16).to_be_bytes());
+        }
+        5 | 6 => {
+            let value = (packed + (0x800000 << 24)) as u64;
+            body.extend_from_slice(&value.to_be_bytes()[2..]);
+        }
+        _ => body.extend_from_slice(&integer.to_be_bytes()[1..]),
+    }
+}
+
+/// 소수 초 문자열 (최대 6 자리) 을 마이크로초로
+fn parse_micros(fraction: &str) -> Option<u32> {
+    if fraction.len() > 6 || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    format!("{:0<6}", fraction).parse().ok()
+}
+
+/// `YYYY-MM-DD`
+fn parse_date(date: &str) -> Option<(u32, u32, u32)> {
+    let mut parts = date.split('-').map(|part| part.parse::<u32>().ok());
+    let date = (parts.next()??, parts.next()??, parts.next()??);
+    parts.next().is_none().then_some(date)
+}
+
+/// `HH:MM:SS[.ffffff]`
+fn parse_time(time: &str) -> Option<((u32, u32, u32), u32)> {
+    let (time, fraction) = time.split_once('.').unwrap_or((time, ""));
+    let mut parts = time.split(':').map(|part| part.parse::<u32>().ok());
+    let time = (parts.next()??, parts.next()??, parts.next()??);
+    parts.next().is_none().then_some((time, parse_micros(fraction)?))
+}
+
+/// TIME 값 (부호, 시/분/초, 마이크로초)
+fn time_parts(value: &CellValue) -> Option<(bool, (u32, u32, u32), u32)> {
+    let CellValue::Time(time) = value else {
+        return None;
+    };
+    let (negative, time) = match time.strip_prefix('-') {
+        Some(time) => (true, time),
+        None => (false, time.as_str()),
+    };
+    let (time, micros) = parse_time(time)?;
+    Some((negative, time, micros))
+}
+
+/// (년, 월, 일), (시, 분, 초), 마이크로초
+type DateTimeParts = ((u32, u32, u32), (u32, u32, u32), u32);
+
+/// DATETIME 값 (0000-00-00 같은 값은 문자열)
+fn datetime_parts(value: &CellValue) -> Option<DateTimeParts> {
+    match value {
+        CellValue::DateTime(datetime) => Some((
+            (datetime.year() as u32, datetime.month(), datetime.day()),
+            (datetime.hour(), datetime.minute(), datetime.second()),
+            datetime.timestamp_subsec_micros(),
+        )),
+        CellValue::String(datetime) => {
+            let (date, time) = datetime.split_once(' ')?;
+            let (time, micros) = parse_time(time)?;
+            Some((parse_date(date)?, time, micros))
+        }
+        _ => None,
+    }
+}
+
+/// DECIMAL 이진 형식 (`BinlogParser` 의 디코딩과 같은 9 자리 묶음, 자릿수가 넘치면 None)
+fn encode_decimal(value: &str, precision: u8, scale: u8) -> Option<Vec<u8>> {
+    let (negative, digits) = match value.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, value),
+    };
+    let (integer_digits, fraction_digits) = digits.split_once('.').unwrap_or((digits, ""));
+    if !integer_digits
+        .bytes()
+        .chain(fraction_digits.bytes())
+        .all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+
+    let integer = precision.saturating_sub(scale) as usize;
+    let scale = scale as usize;
+    let integer_digits = integer_digits.trim_start_matches('0');
+    if integer_digits.len() > integer || fraction_digits.len() > scale {
+        return None;
+    }
+    let integer_digits = format!("{:0>width$}", integer_digits, width = integer);
+    let fraction_digits = format!("{:0<width$}", fraction_digits, width = scale);
+
+    let mut data = Vec::with_capacity(decimal_length(precision, scale as u8));
+    let mut push = |digits: &str, length: usize| {
+        let value = digits.parse::<u32>().unwrap_or(0);
+        data.extend_from_slice(&value.to_be_bytes()[4 - length..]);
+    };
+    let leading = integer % 9;
+    if leading > 0 {
+        push(&integer_digits[..leading], DIG2BYTES[leading]);
+    }
+    for group in integer_digits.as_bytes()[leading..].chunks(9) {
+        push(std::str::from_utf8(group).ok()?, 4);
+    }
+    let trailing = scale % 9;
+    for group in fraction_digits.as_bytes()[..scale - trailing].chunks(9) {
+        push(std::str::from_utf8(group).ok()?, 4);
+    }
+    if trailing > 0 {
+        push(&fraction_digits[scale - trailing..], DIG2BYTES[trailing]);
+    }
+
+    *data.first_mut()? ^= 0x80;
+    if negative {
+        data.iter_mut().for_each(|b| *b ^= 0xff);
+    }
+    Some(data)
+}
+
+/// 매직 넘버 + FORMAT_DESCRIPTION + 이벤트들로 binlog 파일 만들기
+#[derive(Debug, Clone)]
+pub struct BinlogFileBuilder {
+    server_version: String,
+    server_id: u32,
+    timestamp: u32,
+    checksum: ChecksumAlgorithm,
+    events: Vec<BinlogEvent>,
+}
+
+impl Default for BinlogFileBuilder {
+    fn default() -> Self {
+        BinlogFileBuilder {
+            server_version: "8.0.35".to_string(),
+            server_id: 1,
+            timestamp: 0,
+            checksum: ChecksumAlgorithm::Crc32,
+            events: Vec::new(),
+        }
+    }
+}
+
+impl BinlogFileBuilder {
+    /// 8.0.35, server_id 1, CRC32 파일
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// FORMAT_DESCRIPTION 에 기록할 서버 버전 (5.6.1 이전이면 체크섬 없음)
+    pub fn server_version(mut self, server_version: &str) -> Self {
+        self.server_version = server_version.to_string();
+        self
+    }
+
+    /// 이후 추가하는 이벤트의 server_id
+    pub fn server_id(mut self, server_id: u32) -> Self {
+        self.server_id = server_id;
+        self
+    }
+
+    /// 이후 추가하는 이벤트의 타임스탬프
+    pub fn timestamp(mut self, timestamp: u32) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
+    pub fn checksum(mut self, checksum: ChecksumAlgorithm) -> Self {
+        self.checksum = checksum;
+        self
+    }
+
+    /// 현재 server_id / 타임스탬프로 이벤트 추가
+    ///
+    /// 타입은 데이터에 맞게 정해집니다.
+    pub fn event(self, data: BinlogEventData) -> Self {
+        self.event_with_type(EventType::Unknown, data)
+    }
+
+    /// 타입을 지정하여 이벤트 추가 (v1 행 이벤트, `Unknown` body 의 이벤트 등)
+    pub fn event_with_type(mut self, event_type: EventType, data: BinlogEventData) -> Self {
+        let header = EventHeader {
+            timestamp: self.timestamp,
+            event_type,
+            server_id: self.server_id,
+            event_length: 0,
+            next_pos: 0,
+            flags: 0,
+        };
+        self.events.push(BinlogEvent { header, data });
+        self
+    }
+
+    /// 다음 파일로 넘어가는 ROTATE 추가
+    pub fn rotate(self, next_binlog_name: &str) -> Self {
+        self.event(BinlogEventData::Rotate(RotateEventData {
+            next_binlog_name: next_binlog_name.to_string(),
+            position: BINLOG_MAGIC.len() as u64,
+        }))
+    }
+
+    /// 파일 내용 생성
+    pub fn build(&self) -> Result<Vec<u8>> {
+        let checksum = match ServerVersion::parse(&self.server_version) {
+            Some(version) if version >= CHECKSUM_VERSION => self.checksum,
+            _ => ChecksumAlgorithm::None,
+        };
+        let format = BinlogEvent {
+            header: EventHeader {
+                timestamp: self
+                    .events
+                    .first()
+                    .map_or(self.timestamp, |e| e.header.timestamp),
+                event_type: EventType::FormatDescriptionEvent,
+                server_id: self
+                    .events
+                    .first()
+                    .map_or(self.server_id, |e| e.header.server_id),
+                event_length: 0,
+                next_pos: 0,
+                flags: 0,
+            },
+            data: BinlogEventData::FormatDescription(FormatDescriptionData {
+                binlog_version: 4,
+                server_version: self.server_version.clone(),
+                create_timestamp: 0,
+                header_length: EVENT_HEADER_SIZE as u8,
+                checksum,
+            }),
+        };
+
+        let mut encoder = BinlogEncoder::new(checksum);
+        let mut file = BINLOG_MAGIC.to_vec();
+        for event in std::iter::once(&format).chain(&self.events) {
+            let data = encoder.encode(event, file.len() as u64)?;
+            file.extend_from_slice(&data);
+        }
+        Ok(file)
+    }
+
+    /// 파일로 저장
+    pub fn write_to(&self, path: impl AsRef<Path>) -> Result<()> {
+        std::fs::write(path, self.build()?)?;
+        Ok(())
+    }
+
+    /// 이벤트 스트림용 `Bytes` (파일 내용과 같음)
+    pub fn build_bytes(&self) -> Result<Bytes> {
+        self.build().map(Bytes::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binlog::BinlogParser;
+    use crate::binlog_file::BinlogFileReader;
+    use std::io::Cursor;
+
+    const UUID: &str = "3e11fa47-71ca-11e1-9e33-c80aa9429562";
+
+    fn header(event_type: EventType) -> EventHeader {
+        EventHeader {
+            timestamp: 1_700_000_000,
+            event_type,
+            server_id: 7,
+            event_length: 0,
+            next_pos: 0,
+            flags: 0,
+        }
+    }
+
+    fn round_trip(event_type: EventType, data: BinlogEventData, crc: bool) -> BinlogEvent {
+        let checksum = if crc {
+            ChecksumAlgorithm::Crc32
+        } else {
+            ChecksumAlgorithm::None
+        };
+        let event = BinlogEvent {
+            header: header(event_type),
+            data,
+        };
+        let bytes = BinlogEncoder::new(checksum).encode(&event, 1000).unwrap();
+
+        let parsed =
+            BinlogParser::parse_event_with_checksum(Bytes::from(bytes.clone()), checksum).unwrap();
+        assert_eq!(parsed.header.event_length as usize, bytes.len());
+        assert_eq!(parsed.header.next_pos as usize, 1000 + bytes.len());
+        assert_eq!(parsed.header.timestamp, 1_700_000_000);
+        assert_eq!(parsed.header.server_id, 7);
+        parsed
+    }
+
+    #[test]
+    fn test_round_trip_events() {
+        for crc in [false, true] {
+            let query = QueryEventData {
+                thread_id: 12,
+                exec_time: 1,
+                database: "shop".to_string(),
+                query: "CREATE TABLE t (id INT)".to_string(),
+            };
+            let parsed = round_trip(EventType::Unknown, BinlogEventData::Query(query), crc);
+            assert!(matches!(
+                parsed.data,
+                BinlogEventData::Query(q) if q.database == "shop" && q.thread_id == 12
+                    && q.query == "CREATE TABLE t (id INT)"
+            ));
+
+            let gtid = GtidEventData {
+                gtid: format!("{}:42", UUID),
+                committed: true,
+            };
+            let parsed = round_trip(EventType::Unknown, BinlogEventData::Gtid(gtid), crc);
+            assert!(matches!(
+                parsed.data,
+                BinlogEventData::Gtid(g) if g.gtid == format!("{}:42", UUID) && g.committed
+            ));
+
+            let rotate = RotateEventData {
+                next_binlog_name: "mysql-bin.000002".to_string(),
+                position: 4,
+            };
+            let parsed = round_trip(EventType::Unknown, BinlogEventData::Rotate(rotate), crc);
+            assert!(matches!(
+                parsed.data,
+                BinlogEventData::Rotate(r) if r.next_binlog_name == "mysql-bin.000002"
+            ));
+
+            let parsed = round_trip(EventType::Unknown, BinlogEventData::Xid(99), crc);
+            assert!(matches!(parsed.data, BinlogEventData::Xid(99)));
+        }
+
+        let table_map = TableMapData {
+            table_id: 0x0102_0304_0506,
+            database: "shop".to_string(),
+            table: "orders".to_string(),
+            column_types: vec![3, 15, 246],
+            column_meta: vec![vec![], vec![0x40, 0x00], vec![10, 2]],
+            nullable_bitmap: vec![0b110],
+            unsigned_columns: Vec::new(),
+        };
+        let parsed = round_trip(
+            EventType::Unknown,
+            BinlogEventData::TableMap(table_map),
+            true,
+        );
+        assert!(matches!(
+            parsed.data,
+            BinlogEventData::TableMap(t) if t.table_id == 0x0102_0304_0506 && t.table == "orders"
+                && t.column_types == [3, 15, 246] && t.nullable_bitmap == [0b110]
+                && t.column_meta == [vec![], vec![0x40, 0x00], vec![10, 2]]
+        ));
+    }
+
+    /// INT, SMALLINT 컬럼 테이블
+    fn table_map(columns: usize) -> TableMapData {
+        TableMapData {
+            table_id: 77,
+            database: "shop".to_string(),
+            table: "orders".to_string(),
+            column_types: [3, 2][..columns].to_vec(),
+            column_meta: vec![Vec::new(); columns],
+            nullable_bitmap: vec![0],
+            unsigned_columns: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_round_trip_rows_events() {
+        // null 비트맵 + INT 5 + SMALLINT 10000
+        let rows_data = Bytes::from_static(&[0, 5, 0, 0, 0, 0x10, 0x27]);
+        for event_type in [EventType::WriteRowsEvent, EventType::WriteRowsEventV1] {
+            let write = WriteRowsData {
+                table_id: 77,
+                flags: 1,
+                column_count: 2,
+                columns_present: vec![0b11],
+                rows: Vec::new(),
+                rows_data: rows_data.clone(),
+            };
+            let parsed = round_trip(event_type, BinlogEventData::WriteRows(write), true);
+            assert_eq!(parsed.header.event_type, event_type);
+            let mut data = parsed.data;
+            BinlogParser::decode_rows(&mut data, &table_map(2)).unwrap();
+            let BinlogEventData::WriteRows(rows) = data else {
+                panic!("expected write rows");
+            };
+            assert_eq!((rows.table_id, rows.flags, rows.column_count), (77, 1, 2));
+            assert_eq!(rows.rows_data, rows_data);
+            assert_eq!(rows.rows, [vec![CellValue::Int32(5), CellValue::Int16(10000)]]);
+        }
+
+        let update = UpdateRowsData {
+            table_id: 77,
+            flags: 0,
+            column_count: 1,
+            columns_present: vec![1],
+            columns_changed: vec![1],
+            rows: Vec::new(),
+            rows_data: Bytes::from_static(&[0, 5, 0, 0, 0, 0, 6, 0, 0, 0]),
+        };
+        let parsed = round_trip(
+            EventType::Unknown,
+            BinlogEventData::UpdateRows(update),
+            false,
+        );
+        let mut data = parsed.data;
+        BinlogParser::decode_rows(&mut data, &table_map(1)).unwrap();
+        let BinlogEventData::UpdateRows(update) = data else {
+            panic!("expected update rows");
+        };
+        assert_eq!(update.rows, [(vec![CellValue::Int32(5)], vec![CellValue::Int32(6)])]);
+
+        let delete = DeleteRowsData {
+            table_id: 77,
+            flags: 0,
+            column_count: 1,
+            columns_present: vec![1],
+            rows: Vec::new(),
+            rows_data: Bytes::from_static(&[0, 5, 0, 0, 0]),
+        };
+        let parsed = round_trip(
+            EventType::Unknown,
+            BinlogEventData::DeleteRows(delete),
+            false,
+        );
+        assert_eq!(parsed.header.event_type, EventType::DeleteRowsEvent);
+
+        // 인코딩할 수 없는 이벤트
+        let event = BinlogEvent {
+            header: header(EventType::Unknown),
+            data: BinlogEventData::RowsQuery("INSERT".to_string()),
+        };
+        assert!(BinlogEncoder::default().encode(&event, 4).is_err());
+    }
+
+    #[test]
+    fn test_write_lcb() {
+        for (value, expected) in [
+            (0xfa, vec![0xfa]),
+            (0xfb, vec![0xfc, 0xfb, 0x00]),
+            (0xffff, vec![0xfc, 0xff, 0xff]),
+            (0x1_0000, vec![0xfd, 0x00, 0x00, 0x01]),
+            (0xff_ffff, vec![0xfd, 0xff, 0xff, 0xff]),
+            (0x100_0000, vec![0xfe, 0, 0, 0, 1, 0, 0, 0, 0]),
+        ] {
+            let mut body = Vec::new();
+            write_lcb(&mut body, value).unwrap();
+            assert_eq!(body, expected, "value {:#x}", value);
+        }
+
+        // 컬럼이 251 개 이상이면 컬럼 개수가 0xfc 로 기록됨
+        let table_map = TableMapData {
+            table_id: 1,
+            database: "shop".to_string(),
+            table: "wide".to_string(),
+            column_types: vec![3; 300],
+            column_meta: vec![Vec::new(); 300],
+            nullable_bitmap: vec![0xff; 38],
+            unsigned_columns: Vec::new(),
+        };
+        let parsed = round_trip(
+            EventType::Unknown,
+            BinlogEventData::TableMap(table_map.clone()),
+            true,
+        );
+        assert!(matches!(
+            parsed.data,
+            BinlogEventData::TableMap(t) if t.column_types == table_map.column_types
+                && t.column_meta == table_map.column_meta
+                && t.nullable_bitmap == table_map.nullable_bitmap
+        ));
+    }
+
+    /// 여러 타입의 컬럼 (TINYINT UNSIGNED ~ SMALLINT)
+    fn mixed_table_map() -> TableMapData {
+        let columns: [(u8, &[u8]); 19] = [
+            (1, &[]),
+            (8, &[]),
+            (4, &[4]),
+            (5, &[8]),
+            (246, &[12, 4]),
+            (10, &[]),
+            (19, &[3]),
+            (18, &[6]),
+            (17, &[2]),
+            (13, &[]),
+            (15, &[0x2c, 0x01]),
+            (254, &[0xfe, 10]),
+            (254, &[0xf7, 1]),
+            (252, &[2]),
+            (245, &[4]),
+            (16, &[3, 1]),
+            (9, &[]),
+            (18, &[0]),
+            (2, &[]),
+        ];
+        let mut unsigned_columns = vec![false; columns.len()];
+        unsigned_columns[0] = true;
+        TableMapData {
+            table_id: 91,
+            database: "shop".to_string(),
+            table: "mixed".to_string(),
+            column_types: columns.iter().map(|(column_type, _)| *column_type).collect(),
+            column_meta: columns.iter().map(|(_, meta)| meta.to_vec()).collect(),
+            nullable_bitmap: vec![0xff, 0xff, 0x07],
+            unsigned_columns,
+        }
+    }
+
+    fn mixed_row(id: u8) -> Vec<CellValue> {
+        let datetime = chrono::NaiveDate::from_ymd_opt(2024, 2, 29)
+            .unwrap()
+            .and_hms_micro_opt(12, 34, 56, 123_456)
+            .unwrap()
+            .and_utc();
+        let timestamp = chrono::DateTime::from_timestamp(1_700_000_000, 120_000_000).unwrap();
+        vec![
+            CellValue::UInt8(200 + id),
+            CellValue::Int64(-5_000_000_000),
+            CellValue::Float(1.5),
+            CellValue::Double(-2.25),
+            CellValue::Decimal("-1234.5600".to_string()),
+            CellValue::Date("2024-02-29".to_string()),
+            CellValue::Time("-12:34:56.789".to_string()),
+            CellValue::DateTime(datetime),
+            CellValue::DateTime(timestamp),
+            CellValue::UInt16(2024),
+            CellValue::Bytes(Bytes::from_static(b"hello")),
+            CellValue::Bytes(Bytes::from_static(b"ab")),
+            CellValue::UInt16(2),
+            CellValue::Bytes(Bytes::from(vec![b'x'; 300])),
+            CellValue::Bytes(Bytes::from_static(br#"{"a":1}"#)),
+            CellValue::UInt64(0x5a5),
+            CellValue::Int32(-8_000_000),
+            CellValue::String("0000-00-00 00:00:00".to_string()),
+            if id == 0 {
+                CellValue::Null
+            } else {
+                CellValue::Int16(-7)
+            },
+        ]
+    }
+
+    /// TABLE_MAP 과 행 이벤트를 같은 인코더로 인코딩하고 다시 파싱해 행 디코딩
+    fn encode_rows(data: BinlogEventData) -> (TableMapData, BinlogEventData) {
+        let mut encoder = BinlogEncoder::new(ChecksumAlgorithm::Crc32);
+        let mut parse = |data: BinlogEventData| {
+            let event = BinlogEvent {
+                header: header(EventType::Unknown),
+                data,
+            };
+            let bytes = encoder.encode(&event, 4).unwrap();
+            BinlogParser::parse_event_with_checksum(Bytes::from(bytes), ChecksumAlgorithm::Crc32)
+                .unwrap()
+                .data
+        };
+        let BinlogEventData::TableMap(table_map) =
+            parse(BinlogEventData::TableMap(mixed_table_map()))
+        else {
+            panic!("expected table map");
+        };
+        let mut data = parse(data);
+        BinlogParser::decode_rows(&mut data, &table_map).unwrap();
+        (table_map, data)
+    }
+
+    #[test]
+    fn test_encode_rows() {
+        let rows = vec![mixed_row(0), mixed_row(1)];
+        let (table_map, data) = encode_rows(BinlogEventData::WriteRows(WriteRowsData {
+            table_id: 91,
+            flags: 0,
+            column_count: 19,
+            columns_present: vec![0xff, 0xff, 0x07],
+            rows: rows.clone(),
+            rows_data: Bytes::new(),
+        }));
+        // SIGNEDNESS optional metadata
+        assert_eq!(table_map.unsigned_columns, mixed_table_map().unsigned_columns);
+        let BinlogEventData::WriteRows(write) = data else {
+            panic!("expected write rows");
+        };
+        assert_eq!(write.rows, rows);
+
+        // minimal 이미지: before 는 첫 컬럼만, after 는 마지막 컬럼만
+        let mut before = vec![CellValue::Null; 19];
+        before[0] = CellValue::UInt8(7);
+        let mut after = vec![CellValue::Null; 19];
+        after[18] = CellValue::Int16(300);
+        let (_, data) = encode_rows(BinlogEventData::UpdateRows(UpdateRowsData {
+            table_id: 91,
+            flags: 0,
+            column_count: 19,
+            columns_present: vec![0x01, 0x00, 0x00],
+            columns_changed: vec![0x00, 0x00, 0x04],
+            rows: vec![(before.clone(), after.clone())],
+            rows_data: Bytes::new(),
+        }));
+        let BinlogEventData::UpdateRows(update) = data else {
+            panic!("expected update rows");
+        };
+        assert_eq!(update.rows, [(before, after)]);
+        // null 비트맵 1 바이트 + TINYINT, null 비트맵 1 바이트 + SMALLINT
+        assert_eq!(update.rows_data[..], [0, 7, 0, 0x2c, 0x01]);
+
+        let (_, data) = encode_rows(BinlogEventData::DeleteRows(DeleteRowsData {
+            table_id: 91,
+            flags: 0,
+            column_count: 19,
+            columns_present: vec![0xff, 0xff, 0x07],
+            rows: vec![mixed_row(1)],
+            rows_data: Bytes::new(),
+        }));
+        let BinlogEventData::DeleteRows(delete) = data else {
+            panic!("expected delete rows");
+        };
+        assert_eq!(delete.rows, [mixed_row(1)]);
+    }
+
+    #[test]
+    fn test_encode_rows_errors() {
+        let write = |table_id: u64, row: Vec<CellValue>| BinlogEvent {
+            header: header(EventType::Unknown),
+            data: BinlogEventData::WriteRows(WriteRowsData {
+                table_id,
+                flags: 0,
+                column_count: 19,
+                columns_present: vec![0xff, 0xff, 0x07],
+                rows: vec![row],
+                rows_data: Bytes::new(),
+            }),
+        };
+        let mut encoder = BinlogEncoder::default();
+
+        // TABLE_MAP 을 인코딩하기 전
+        let error = encoder.encode(&write(91, mixed_row(1)), 4).unwrap_err();
+        assert!(matches!(error, CdcError::InvalidEvent(_)));
+
+        let table_map = BinlogEvent {
+            header: header(EventType::Unknown),
+            data: BinlogEventData::TableMap(mixed_table_map()),
+        };
+        encoder.encode(&table_map, 4).unwrap();
+        assert!(encoder.encode(&write(91, mixed_row(1)), 4).is_ok());
+        assert!(encoder.encode(&write(92, mixed_row(1)), 4).is_err());
+
+        let mut short_row = mixed_row(1);
+        short_row.pop();
+        let mut wrong_type = mixed_row(1);
+        wrong_type[10] = CellValue::Int32(5);
+        let mut wide_decimal = mixed_row(1);
+        wide_decimal[4] = CellValue::Decimal("123456789.0".to_string());
+        let mut long_string = mixed_row(1);
+        long_string[11] = CellValue::Bytes(Bytes::from(vec![b'x'; 256]));
+        for row in [short_row, wrong_type, wide_decimal, long_string] {
+            let error = encoder.encode(&write(91, row), 4).unwrap_err();
+            assert!(matches!(error, CdcError::InvalidEvent(_)));
+        }
+    }
+
+    #[test]
+    fn test_file_builder() {
+        for (version, checksum) in [
+            ("8.0.35", ChecksumAlgorithm::Crc32),
+            ("8.0.35", ChecksumAlgorithm::None),
+            ("5.5.62", ChecksumAlgorithm::None),
+        ] {
+            let file = BinlogFileBuilder::new()
+                .server_version(version)
+                .checksum(checksum)
+                .server_id(3)
+                .timestamp(1_700_000_000)
+                .event(BinlogEventData::Xid(1))
+                .event_with_type(EventType::StopEvent, BinlogEventData::Unknown(Bytes::new()))
+                .build()
+                .unwrap();
+
+            let reader = BinlogFileReader::new(Cursor::new(file.clone())).unwrap();
+            let events: Vec<_> = reader.map(|e| e.unwrap()).collect();
+            assert_eq!(events.len(), 3);
+            let BinlogEventData::FormatDescription(format) = &events[0].event.data else {
+                panic!("expected format description");
+            };
+            assert_eq!(format.checksum, checksum);
+            assert_eq!(format.server_version, version);
+            assert!(matches!(events[1].event.data, BinlogEventData::Xid(1)));
+            assert_eq!(events[2].event.header.event_type, EventType::StopEvent);
+
+            // next_pos 는 다음 이벤트 위치, 마지막 이벤트의 next_pos 는 파일 크기
+            for pair in events.windows(2) {
+                assert_eq!(pair[0].event.header.next_pos as u64, pair[1].offset);
+            }
+            assert_eq!(events[2].event.header.next_pos as usize, file.len());
+        }
+
+        // 8.0 FORMAT_DESCRIPTION 은 121 바이트 (체크섬 포함)
+        let file = BinlogFileBuilder::new()
+            .rotate("mysql-bin.000002")
+            .build()
+            .unwrap();
+        assert_eq!(u32::from_le_bytes(file[13..17].try_into().unwrap()), 121);
+    }
+}
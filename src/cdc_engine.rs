@@ -5,13 +5,17 @@
 //! 2. Binlog 스트리밍 (이후 변경 사항 추적)
 //! 3. 상태 복원 시 놓친 이벤트 처리
 
-use crate::connection::{ConnectionConfig, MySqlConnection};
+use crate::binlog::BinlogParser;
+use crate::connection::{BinlogStatus, ConnectionConfig, MySqlConnection};
 use crate::error::{CdcError, Result};
 use crate::events::*;
-use crate::offset::{BinlogOffset, ProcessingState, SourceInfo};
-use chrono::Utc;
-use std::collections::HashMap;
-use tokio::sync::mpsc;
+use crate::offset::{BinlogOffset, ProcessingState};
+use crate::queue::{self, EventReceiver, EventSender, QueueConfig, QueueDepth, QueueMonitor};
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use tokio::task::JoinHandle;
 use tracing::{debug, info, warn};
 
 /// CDC 엔진 설정
@@ -23,8 +27,17 @@ pub struct CdcConfig {
     pub snapshot_mode: SnapshotMode,
     pub include_ddl: bool,
     pub gtid_filter: Option<String>,
+    /// binlog 이벤트 / ChangeEvent 큐 크기
+    pub queue: QueueConfig,
+    /// 스냅샷 시작 시 binlog 위치를 맞추는 잠금 방식
+    pub snapshot_locking: SnapshotLocking,
+    /// 스냅샷에서 기본 키 순서로 한 번에 읽는 행 수
+    pub snapshot_chunk_size: usize,
 }
 
+/// 기본 스냅샷 청크 크기
+pub const DEFAULT_SNAPSHOT_CHUNK_SIZE: usize = 10_000;
+
 /// 스냅샷 모드
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SnapshotMode {
@@ -38,8 +51,39 @@ pub enum SnapshotMode {
     Incremental,
 }
 
+/// 스냅샷 잠금 방식
+///
+/// 스냅샷의 행과 스트리밍 시작 위치가 같은 시점이어야 변경이 빠지거나 중복되지 않습니다.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SnapshotLocking {
+    /// `FLUSH TABLES WITH READ LOCK` (RELOAD 권한 필요)
+    ///
+    /// 위치 기록과 트랜잭션 시작 동안 모든 쓰기를 막습니다. 위치가 정확합니다.
+    #[default]
+    Global,
+    /// `LOCK INSTANCE FOR BACKUP` (MySQL 8.0+, BACKUP_ADMIN 권한 필요)
+    ///
+    /// 스냅샷이 끝날 때까지 DDL 만 막고 DML 은 막지 않으므로 위치는 `None` 과 같은 방식으로 맞춥니다.
+    Backup,
+    /// 캡처할 테이블만 `LOCK TABLES ... READ` (LOCK TABLES 권한 필요)
+    ///
+    /// 트랜잭션을 시작하면 잠금이 풀리므로 별도 연결에서 잠급니다. 다른 테이블의 쓰기는 막지 않으며
+    /// 캡처하는 테이블 기준으로 위치가 정확합니다.
+    Tables,
+    /// 잠금 없음
+    ///
+    /// 트랜잭션 시작 전후의 binlog 위치 / GTID 가 같으면 (그 사이 커밋 없음) 정확합니다.
+    /// 몇 번 재시도해도 다르면 시작 전 위치부터 스트리밍하므로, 그 사이 커밋된 변경은
+    /// 스냅샷 행과 binlog 이벤트로 두 번 나올 수 있지만 빠지지는 않습니다 (at-least-once).
+    None,
+}
+
+/// 잠금 없는 스냅샷에서 전후 위치를 맞추는 최대 시도 횟수
+const SNAPSHOT_RECONCILE_ATTEMPTS: u32 = 5;
+
 /// 테이블 메타데이터 캐시
 #[derive(Debug, Clone)]
+#[allow(dead_code)]
 struct TableMetadata {
     database: String,
     table: String,
@@ -48,16 +92,139 @@ struct TableMetadata {
     primary_key: Vec<String>,
 }
 
+impl TableMetadata {
+    /// "db.table"
+    fn key(&self) -> String {
+        format!("{}.{}", self.database, self.table)
+    }
+}
+
 /// CDC 이벤트 수신자
-pub type CdcEventReceiver = mpsc::UnboundedReceiver<ChangeEvent>;
+pub type CdcEventReceiver = EventReceiver<ChangeEvent>;
+
+/// 스냅샷 스트림 (행 수신 + 스냅샷 결과)
+///
+/// 스냅샷이 중간에 실패해도 수신은 성공했을 때처럼 None 으로 끝나므로 `finish` 로 결과를 확인합니다.
+pub struct SnapshotStream {
+    events: CdcEventReceiver,
+    /// 스냅샷을 건너뛰면 None
+    handle: Option<JoinHandle<Result<()>>>,
+    ack: SnapshotAck,
+}
+
+impl SnapshotStream {
+    /// 처리 완료 알림 핸들 (ACK 한 행까지만 오프셋에 진행 상황이 기록됨)
+    pub fn acknowledger(&self) -> SnapshotAck {
+        self.ack.clone()
+    }
+
+    /// 다음 행 수신 (스냅샷이 끝나거나 실패하면 None)
+    pub async fn recv(&mut self) -> Option<ChangeEvent> {
+        self.events.recv().await
+    }
+
+    /// 아직 처리하지 않은 행 수와 크기
+    pub fn depth(&self) -> QueueDepth {
+        self.events.depth()
+    }
+
+    /// 스냅샷 종료를 기다리고 결과 반환 (남은 행은 버림)
+    pub async fn finish(self) -> Result<()> {
+        drop(self.events);
+        match self.handle {
+            Some(handle) => handle
+                .await
+                .map_err(|e| CdcError::Other(format!("Snapshot task failed: {}", e)))?,
+            None => Ok(()),
+        }
+    }
+}
+
+/// 스냅샷 ACK 핸들
+///
+/// 컨슈머가 행을 영구 저장한 뒤 받은 순서대로 처리한 행 수로 `ack` 를 호출합니다. 청크의 행을 모두
+/// ACK 해야 그 청크의 마지막 키가 오프셋에 기록되므로, 재개하면 ACK 하지 않은 청크부터 다시 읽습니다.
+#[derive(Clone)]
+pub struct SnapshotAck(Arc<Mutex<SnapshotCheckpoints>>);
+
+impl SnapshotAck {
+    /// 받은 순서대로 `rows` 개 행의 처리 완료를 알림
+    pub fn ack(&self, rows: usize) {
+        let mut checkpoints = self.0.lock();
+        checkpoints.acked += rows;
+        checkpoints.apply();
+    }
+}
+
+/// ACK 를 기다리는 스냅샷 진행 상황
+struct SnapshotCheckpoints {
+    offset: Arc<Mutex<BinlogOffset>>,
+    /// ACK 한 행 수 (누적)
+    acked: usize,
+    /// (보낸 행 수, 그 행까지 ACK 되면 기록할 진행 상황)
+    pending: VecDeque<(usize, SnapshotCheckpoint)>,
+}
+
+enum SnapshotCheckpoint {
+    /// 테이블의 마지막 청크 키
+    Chunk(String, Vec<CellValue>),
+    /// 테이블 완료
+    Table(String),
+    /// 스냅샷 완료
+    Completed,
+}
+
+impl SnapshotCheckpoints {
+    fn new(offset: Arc<Mutex<BinlogOffset>>) -> Self {
+        SnapshotCheckpoints {
+            offset,
+            acked: 0,
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// `sent` 번째 행까지 ACK 되면 기록할 진행 상황 추가
+    fn push(&mut self, sent: usize, checkpoint: SnapshotCheckpoint) {
+        self.pending.push_back((sent, checkpoint));
+        self.apply();
+    }
+
+    /// ACK 된 진행 상황을 오프셋에 기록
+    fn apply(&mut self) {
+        let mut offset = self.offset.lock();
+        let acked = self
+            .pending
+            .iter()
+            .take_while(|(sent, _)| *sent <= self.acked)
+            .count();
+        for (_, checkpoint) in self.pending.drain(..acked) {
+            match checkpoint {
+                SnapshotCheckpoint::Chunk(table, key) => {
+                    offset.snapshot_progress.entry(table).or_default().last_key = Some(key);
+                }
+                SnapshotCheckpoint::Table(table) => {
+                    offset.snapshot_progress.entry(table).or_default().completed = true;
+                }
+                SnapshotCheckpoint::Completed => {
+                    offset.snapshot_completed = true;
+                    offset.snapshot_progress.clear();
+                }
+            }
+        }
+    }
+}
 
 /// MySQL CDC 엔진
 pub struct CdcEngine {
     config: CdcConfig,
     conn: Option<MySqlConnection>,
-    offset: BinlogOffset,
+    /// 스냅샷 태스크가 청크마다 진행 상황을 기록하므로 공유
+    offset: Arc<Mutex<BinlogOffset>>,
+    /// `with_offset` 으로 복원한 오프셋 (시작 시 현재 위치로 덮어쓰지 않음)
+    offset_restored: bool,
     state: ProcessingState,
     table_metadata: HashMap<String, TableMetadata>,
+    binlog_queue: Option<QueueMonitor>,
 }
 
 impl CdcEngine {
@@ -67,12 +234,21 @@ impl CdcEngine {
         CdcEngine {
             config,
             conn: None,
-            offset,
+            offset: Arc::new(Mutex::new(offset)),
+            offset_restored: false,
             state: ProcessingState::Snapshotting,
             table_metadata: HashMap::new(),
+            binlog_queue: None,
         }
     }
 
+    /// 저장한 오프셋에서 재개 (중단된 스냅샷은 ACK 한 마지막 청크 다음부터 이어서 읽음)
+    pub fn with_offset(mut self, offset: BinlogOffset) -> Self {
+        self.offset = Arc::new(Mutex::new(offset));
+        self.offset_restored = true;
+        self
+    }
+
     /// 엔진 초기화 및 연결
     pub async fn start(&mut self) -> Result<()> {
         info!("Starting CDC Engine");
@@ -95,9 +271,11 @@ impl CdcEngine {
             binlog_status.file, binlog_status.position
         );
 
-        self.offset
-            .update_position(binlog_status.file, binlog_status.position);
-        self.offset.gtid_set = binlog_status.executed_gtid_set;
+        if !self.offset_restored {
+            let mut offset = self.offset.lock();
+            offset.update_position(binlog_status.file, binlog_status.position);
+            offset.gtid_set = binlog_status.executed_gtid_set;
+        }
 
         // 테이블 메타데이터 로드
         self.load_table_metadata(&mut conn).await?;
@@ -143,7 +321,7 @@ impl CdcEngine {
                             columns.iter().map(|c| c.column_type.clone()).collect();
                         let primary_key: Vec<String> = columns
                             .iter()
-                            .filter(|c| c.is_key)
+                            .filter(|c| c.is_primary_key)
                             .map(|c| c.name.clone())
                             .collect();
 
@@ -173,256 +351,1013 @@ impl CdcEngine {
     }
 
     /// 스냅샷 처리 (초기 데이터 읽기)
-    pub async fn snapshot(&mut self) -> Result<CdcEventReceiver> {
-        let (tx, rx) = mpsc::unbounded_channel();
+    ///
+    /// `snapshot_locking` 잠금을 잡은 채 binlog 위치와 GTID 를 기록하고 consistent snapshot
+    /// 트랜잭션을 시작한 뒤 잠금을 풉니다. 행은 백그라운드에서 기본 키 순서의 청크로 읽어
+    /// `OperationType::Read` 이벤트로 보내고, 이후 `stream_binlog` 는 기록한 위치부터 시작합니다.
+    /// 오프셋에 청크 진행 상황이 있으면 (`with_offset`) ACK 한 마지막 청크 다음부터 이어서 읽습니다.
+    /// 읽는 중의 에러는 `SnapshotStream::finish` 로 받습니다.
+    pub async fn snapshot(&mut self) -> Result<SnapshotStream> {
+        let (tx, events) = queue::bounded(self.config.queue);
+        let checkpoints = Arc::new(Mutex::new(SnapshotCheckpoints::new(self.offset.clone())));
+        let skipped = SnapshotStream {
+            events,
+            handle: None,
+            ack: SnapshotAck(checkpoints.clone()),
+        };
 
-        if self.config.snapshot_mode == SnapshotMode::Never {
-            info!("Snapshot mode is NEVER, skipping snapshot");
-            return Ok(rx);
+        match self.config.snapshot_mode {
+            SnapshotMode::Never => {
+                info!("Snapshot mode is NEVER, skipping snapshot");
+                return Ok(skipped);
+            }
+            SnapshotMode::SchemaOnly => {
+                info!("Snapshot mode is SCHEMA_ONLY, skipping table data");
+                return Ok(skipped);
+            }
+            SnapshotMode::Initial | SnapshotMode::Incremental => {}
         }
 
-        info!("Starting snapshot");
+        let offset = self.offset.lock().clone();
+        if offset.snapshot_completed {
+            info!("Snapshot already completed, skipping");
+            return Ok(skipped);
+        }
 
-        for metadata in self.table_metadata.values() {
-            let query = format!("SELECT * FROM `{}`.`{}`", metadata.database, metadata.table);
+        let mut tables: Vec<TableMetadata> = self.table_metadata.values().cloned().collect();
+        tables.sort_by(|a, b| (&a.database, &a.table).cmp(&(&b.database, &b.table)));
 
-            // 실제 구현에서는 MySQL 쿼리 실행하여 행 읽기
-            debug!("Snapshot query: {}", query);
+        let mut conn = MySqlConnection::connect(self.config.connection.clone()).await?;
+        conn.execute("SET SESSION TRANSACTION ISOLATION LEVEL REPEATABLE READ")
+            .await?;
 
-            // 각 행에 대해 ChangeEvent 생성
-            // 생략: 실제 행 데이터는 쿼리 결과에서 읽음
+        // 중단된 스냅샷은 잠금 없이 새 트랜잭션에서 남은 청크를 읽고, 처음 기록한 위치부터 스트리밍
+        // (그 사이 변경은 binlog 로 다시 적용되므로 결과는 같음)
+        let resume = offset
+            .snapshot_progress
+            .values()
+            .any(|p| p.completed || p.last_key.is_some());
+        let locking = if resume {
+            info!(
+                "Resuming snapshot, streaming will start from {}",
+                offset.binlog_position
+            );
+            conn.execute("START TRANSACTION WITH CONSISTENT SNAPSHOT")
+                .await?;
+            None
+        } else {
+            let locking = self.config.snapshot_locking;
+            info!("Starting snapshot (locking: {:?})", locking);
+            let binlog_status = self.lock_snapshot(&mut conn, locking, &tables).await?;
 
             info!(
-                "Snapshot complete for {}.{}",
-                metadata.database, metadata.table
+                "Snapshot at binlog {}:{} (GTID {})",
+                binlog_status.file, binlog_status.position, binlog_status.executed_gtid_set
             );
-        }
+            let mut offset = self.offset.lock();
+            offset.update_position(binlog_status.file, binlog_status.position);
+            offset.gtid_set = binlog_status.executed_gtid_set;
+            offset.snapshot_progress.clear();
+            Some(locking)
+        };
 
-        Ok(rx)
+        let reader = SnapshotReader {
+            conn,
+            chunk_size: self.config.snapshot_chunk_size.max(1),
+            timestamp: Utc::now(),
+            offset: self.offset.clone(),
+            checkpoints,
+            sent: 0,
+            tx,
+        };
+        Ok(SnapshotStream {
+            handle: Some(tokio::spawn(reader.read(tables, locking))),
+            ..skipped
+        })
+    }
+
+    /// 잠금 방식에 맞게 스냅샷 트랜잭션을 시작하고 스트리밍 시작 위치 반환
+    ///
+    /// 잠금은 위치 기록과 트랜잭션 시작 동안만 유지합니다 (실패해도 풀기, `Backup` 은 스냅샷 끝까지).
+    async fn lock_snapshot(
+        &self,
+        conn: &mut MySqlConnection,
+        locking: SnapshotLocking,
+        tables: &[TableMetadata],
+    ) -> Result<BinlogStatus> {
+        let binlog_status = match locking {
+            SnapshotLocking::Global => {
+                conn.execute("FLUSH TABLES WITH READ LOCK").await?;
+                let locked = Self::start_consistent_snapshot(conn).await;
+                let unlocked = conn.execute("UNLOCK TABLES").await;
+                let binlog_status = locked?;
+                unlocked?;
+                binlog_status
+            }
+            SnapshotLocking::Tables if !tables.is_empty() => {
+                let mut lock_conn =
+                    MySqlConnection::connect(self.config.connection.clone()).await?;
+                lock_conn.execute(&lock_tables_statement(tables)).await?;
+                // 트랜잭션을 시작하면 같은 연결의 LOCK TABLES 가 풀리므로 다른 연결에서 시작
+                let locked = async {
+                    let binlog_status = lock_conn.get_binlog_status().await?;
+                    conn.execute("START TRANSACTION WITH CONSISTENT SNAPSHOT")
+                        .await?;
+                    Ok::<_, CdcError>(binlog_status)
+                }
+                .await;
+                let unlocked = lock_conn.execute("UNLOCK TABLES").await;
+                lock_conn.close().await?;
+                let binlog_status = locked?;
+                unlocked?;
+                binlog_status
+            }
+            SnapshotLocking::Tables => Self::start_consistent_snapshot(conn).await?,
+            // 백업 잠금은 스냅샷이 끝날 때까지 유지 (연결이 끊어져도 풀림)
+            SnapshotLocking::Backup => {
+                conn.execute("LOCK INSTANCE FOR BACKUP").await?;
+                match Self::reconcile_snapshot(conn).await {
+                    Ok(binlog_status) => binlog_status,
+                    Err(e) => {
+                        if let Err(unlock) = conn.execute("UNLOCK INSTANCE").await {
+                            warn!("Failed to release the backup lock: {}", unlock);
+                        }
+                        return Err(e);
+                    }
+                }
+            }
+            SnapshotLocking::None => Self::reconcile_snapshot(conn).await?,
+        };
+        Ok(binlog_status)
+    }
+
+    /// 잠금 상태에서 binlog 위치를 기록하고 consistent snapshot 트랜잭션 시작
+    async fn start_consistent_snapshot(conn: &mut MySqlConnection) -> Result<BinlogStatus> {
+        let binlog_status = conn.get_binlog_status().await?;
+        conn.execute("START TRANSACTION WITH CONSISTENT SNAPSHOT")
+            .await?;
+        Ok(binlog_status)
+    }
+
+    /// 잠금 없이 트랜잭션을 시작하고 전후 binlog 위치가 같아질 때까지 재시도
+    ///
+    /// 끝까지 다르면 시작 전 위치를 돌려줍니다 (그 사이 변경은 중복될 수 있음).
+    async fn reconcile_snapshot(conn: &mut MySqlConnection) -> Result<BinlogStatus> {
+        let mut attempt = 1;
+        loop {
+            let before = conn.get_binlog_status().await?;
+            conn.execute("START TRANSACTION WITH CONSISTENT SNAPSHOT")
+                .await?;
+            let after = conn.get_binlog_status().await?;
+
+            if same_binlog_status(&before, &after) {
+                return Ok(before);
+            }
+            if attempt == SNAPSHOT_RECONCILE_ATTEMPTS {
+                warn!(
+                    "Binlog moved from {}:{} to {}:{} while starting the snapshot, \
+                     changes in between may be delivered twice",
+                    before.file, before.position, after.file, after.position
+                );
+                return Ok(before);
+            }
+
+            debug!(
+                "Binlog moved while starting the snapshot (attempt {}), retrying",
+                attempt
+            );
+            conn.execute("ROLLBACK").await?;
+            attempt += 1;
+        }
     }
 
     /// Binlog 스트리밍 시작
     pub async fn stream_binlog(&mut self) -> Result<CdcEventReceiver> {
         use crate::binlog_client::BinlogClient;
 
+        let offset = self.offset.lock().clone();
         info!(
             "Starting binlog streaming from {}:{}",
-            self.offset.binlog_position.filename, self.offset.binlog_position.position
+            offset.binlog_position.filename, offset.binlog_position.position
         );
 
         // BinlogClient 생성
-        let client = BinlogClient::new(
+        let mut client = BinlogClient::new(
             self.config.connection.clone(),
-            self.offset.binlog_position.filename.clone(),
-            self.offset.binlog_position.position,
-        );
+            offset.binlog_position.filename,
+            offset.binlog_position.position,
+        )
+        .queue(self.config.queue);
+
+        // 장애 조치는 GTID 로만 재개할 수 있음
+        if !self.config.connection.failover_hosts.is_empty() {
+            client = client.gtid_set(offset.gtid_set);
+        }
 
         // Binlog 이벤트 수신 시작
         let binlog_rx = client.start_streaming().await?;
+        self.binlog_queue = Some(binlog_rx.monitor());
 
         // ChangeEvent로 변환하는 채널
-        let (tx, rx) = mpsc::unbounded_channel();
+        let (tx, rx) = queue::bounded(self.config.queue);
 
         // 백그라운드에서 BinlogEvent를 ChangeEvent로 변환
-        let table_metadata = self.table_metadata.clone();
-        let databases = self.config.databases.clone();
-        let include_ddl = self.config.include_ddl;
-
-        tokio::spawn(async move {
-            Self::process_binlog_events(binlog_rx, tx, table_metadata, databases, include_ddl)
-                .await;
-        });
+        let converter = EventConverter::new(&self.config, self.table_metadata.clone());
+        tokio::spawn(Self::process_binlog_events(binlog_rx, tx, converter));
 
         Ok(rx)
     }
 
     /// Binlog 이벤트를 처리하여 ChangeEvent로 변환
     async fn process_binlog_events(
-        mut binlog_rx: mpsc::UnboundedReceiver<crate::events::BinlogEvent>,
-        tx: mpsc::UnboundedSender<ChangeEvent>,
-        _table_metadata: std::collections::HashMap<String, TableMetadata>,
-        _databases: Vec<String>,
-        _include_ddl: bool,
+        mut binlog_rx: EventReceiver<BinlogEvent>,
+        tx: EventSender<ChangeEvent>,
+        mut converter: EventConverter,
     ) {
-        while let Some(_event) = binlog_rx.recv().await {
-            // TODO: BinlogEvent를 ChangeEvent로 변환
-            // 실제 구현에서는 이벤트 타입에 따라 처리:
-            // - TABLE_MAP: 테이블 메타데이터 업데이트
-            // - WRITE_ROWS: INSERT 이벤트
-            // - UPDATE_ROWS: UPDATE 이벤트
-            // - DELETE_ROWS: DELETE 이벤트
-            // - QUERY: DDL 이벤트
-            // - GTID: GTID 업데이트
+        while let Some(event) = binlog_rx.recv().await {
+            for change in converter.convert(&event) {
+                if tx.send(change).await.is_err() {
+                    debug!("ChangeEvent receiver dropped, stopping binlog processing");
+                    return;
+                }
+            }
+        }
+    }
+
+    /// 현재 오프셋 반환 (스냅샷 중이면 청크 진행 상황 포함)
+    pub fn get_offset(&self) -> BinlogOffset {
+        self.offset.lock().clone()
+    }
+
+    /// 오프셋 저장
+    pub fn save_offset(&mut self) -> Result<()> {
+        // 실제 구현에서는 Kafka/파일 등에 저장
+        debug!("Saving offset: {}", self.offset.lock().binlog_position);
+        Ok(())
+    }
+
+    /// 아직 변환하지 않은 binlog 이벤트 큐 깊이 (스트리밍 시작 전에는 None)
+    ///
+    /// ChangeEvent 큐 깊이는 `CdcEventReceiver::depth` 로 조회합니다.
+    pub fn binlog_queue_depth(&self) -> Option<QueueDepth> {
+        self.binlog_queue.as_ref().map(QueueMonitor::depth)
+    }
+
+    /// 상태 조회
+    pub fn get_state(&self) -> ProcessingState {
+        self.state
+    }
+
+    /// 엔진 종료
+    pub async fn stop(&mut self) -> Result<()> {
+        info!("Stopping CDC Engine");
+
+        if let Some(mut conn) = self.conn.take() {
+            conn.close().await?;
+        }
+
+        self.state = ProcessingState::Stopped;
+        info!("CDC Engine stopped");
+        Ok(())
+    }
+}
+
+/// 스냅샷 트랜잭션 안에서 테이블을 기본 키 순서의 청크로 읽어 전송 (스냅샷 태스크가 소유)
+///
+/// 청크의 행이 모두 ACK 되면 마지막 키를 오프셋에 기록하므로 중단되면 그 다음 청크부터 이어서 읽습니다.
+/// 기본 키가 없는 테이블은 한 번의 조회로 한 행씩 읽고, 중단되면 처음부터 다시 읽습니다.
+struct SnapshotReader {
+    conn: MySqlConnection,
+    chunk_size: usize,
+    /// 스냅샷 행의 타임스탬프 (스냅샷 시작 시각)
+    timestamp: DateTime<Utc>,
+    /// 재개할 때 읽는 진행 상황 (기록은 `checkpoints` 에서 ACK 후)
+    offset: Arc<Mutex<BinlogOffset>>,
+    checkpoints: Arc<Mutex<SnapshotCheckpoints>>,
+    /// 보낸 행 수 (누적)
+    sent: usize,
+    tx: EventSender<ChangeEvent>,
+}
+
+impl SnapshotReader {
+    /// 모든 테이블을 읽고 트랜잭션 종료 (`locking` 은 이번에 잡은 잠금, 재개 시 None)
+    ///
+    /// 백업 잠금은 실패해도 해제합니다.
+    async fn read(
+        mut self,
+        tables: Vec<TableMetadata>,
+        locking: Option<SnapshotLocking>,
+    ) -> Result<()> {
+        let read = self.read_tables(&tables).await;
+        let unlocked = match locking {
+            Some(SnapshotLocking::Backup) => self.conn.execute("UNLOCK INSTANCE").await,
+            _ => Ok(()),
+        };
+        read?;
+        unlocked?;
+
+        self.checkpoint(SnapshotCheckpoint::Completed);
+        info!("Snapshot completed");
+        self.conn.close().await
+    }
+
+    /// 남은 테이블을 모두 읽고 트랜잭션 커밋
+    async fn read_tables(&mut self, tables: &[TableMetadata]) -> Result<()> {
+        for metadata in tables {
+            let progress = self
+                .offset
+                .lock()
+                .snapshot_progress
+                .get(&metadata.key())
+                .cloned()
+                .unwrap_or_default();
+            if progress.completed {
+                debug!("Snapshot already completed for {}", metadata.key());
+                continue;
+            }
+
+            let count = self.read_table(metadata, progress.last_key).await?;
+            self.checkpoint(SnapshotCheckpoint::Table(metadata.key()));
+            info!("Snapshot complete for {} ({} rows)", metadata.key(), count);
+        }
+
+        self.conn.execute("COMMIT").await
+    }
+
+    /// `last_key` 다음부터 테이블 끝까지 읽고 읽은 행 수 반환
+    async fn read_table(
+        &mut self,
+        metadata: &TableMetadata,
+        mut last_key: Option<Vec<CellValue>>,
+    ) -> Result<usize> {
+        let key_columns: Vec<usize> = metadata
+            .primary_key
+            .iter()
+            .filter_map(|key| metadata.columns.iter().position(|c| c == key))
+            .collect();
+        let chunked = !key_columns.is_empty() && key_columns.len() == metadata.primary_key.len();
+
+        // 기본 키가 없으면 결과를 한 행씩 읽어 큐의 backpressure 로 메모리를 제한
+        if !chunked {
+            let query = format!("SELECT * FROM `{}`.`{}`", metadata.database, metadata.table);
+            debug!("Snapshot query: {}", query);
+            let mut rows = self.conn.query_stream(&query).await?;
+            let mut count = 0;
+            while let Some(row) = rows.next().await? {
+                self.tx.send(snapshot_event(metadata, &row, self.timestamp)).await?;
+                self.sent += 1;
+                count += 1;
+            }
+            return Ok(count);
+        }
+
+        let mut count = 0;
+        loop {
+            let (query, params) = chunk_query(metadata, last_key.as_deref(), self.chunk_size);
+            debug!("Snapshot query: {} {:?}", query, params);
+            let rows = self.conn.exec_values(&query, &params).await?;
+            let read = rows.len();
+            if let Some(row) = rows.last() {
+                let key = key_columns.iter().map(|&i| row[i].clone()).collect();
+                last_key = Some(key);
+            }
+
+            for row in rows {
+                self.tx.send(snapshot_event(metadata, &row, self.timestamp)).await?;
+                self.sent += 1;
+            }
+            count += read;
+
+            if read < self.chunk_size {
+                return Ok(count);
+            }
+
+            // 청크의 행이 모두 ACK 되면 체크포인트
+            if let Some(key) = &last_key {
+                self.checkpoint(SnapshotCheckpoint::Chunk(metadata.key(), key.clone()));
+            }
+        }
+    }
+
+    /// 지금까지 보낸 행이 모두 ACK 되면 기록할 진행 상황
+    fn checkpoint(&self, checkpoint: SnapshotCheckpoint) {
+        self.checkpoints.lock().push(self.sent, checkpoint);
+    }
+}
+
+/// 스냅샷으로 읽은 행 하나의 READ 이벤트
+fn snapshot_event(
+    metadata: &TableMetadata,
+    row: &[CellValue],
+    timestamp: DateTime<Utc>,
+) -> ChangeEvent {
+    ChangeEvent {
+        gtid: None,
+        op: OperationType::Read,
+        timestamp,
+        database: metadata.database.clone(),
+        table: metadata.table.clone(),
+        before: None,
+        after: Some(row_to_map(row, metadata)),
+        query: None,
+    }
+}
+
+/// BinlogEvent 를 ChangeEvent 로 변환 (스트리밍 태스크가 소유)
+///
+/// TABLE_MAP 으로 table_id 와 테이블을 연결하고, 설정의 데이터베이스 / 테이블 / DDL 필터를 적용합니다.
+struct EventConverter {
+    table_metadata: HashMap<String, TableMetadata>,
+    databases: Vec<String>,
+    tables: Option<Vec<String>>,
+    include_ddl: bool,
+    /// TABLE_MAP 의 table_id -> TABLE_MAP 과 테이블 (필터에 걸린 테이블은 없음)
+    tables_by_id: HashMap<u64, (TableMapData, TableMetadata)>,
+    /// 진행 중인 트랜잭션의 GTID
+    gtid: Option<String>,
+}
+
+impl EventConverter {
+    fn new(config: &CdcConfig, table_metadata: HashMap<String, TableMetadata>) -> Self {
+        EventConverter {
+            table_metadata,
+            databases: config.databases.clone(),
+            tables: config.tables.clone(),
+            include_ddl: config.include_ddl,
+            tables_by_id: HashMap::new(),
+            gtid: None,
+        }
+    }
+
+    /// 이벤트 하나를 변환 (행 / DDL 이벤트가 아니거나 필터에 걸리면 비어 있음)
+    fn convert(&mut self, event: &BinlogEvent) -> Vec<ChangeEvent> {
+        let header = &event.header;
+        match &event.data {
+            BinlogEventData::Gtid(data) => {
+                self.gtid = Some(data.gtid.clone());
+                Vec::new()
+            }
+            BinlogEventData::MariadbGtid(data) => {
+                self.gtid = Some(data.gtid.to_string());
+                Vec::new()
+            }
+            BinlogEventData::TableMap(data) => {
+                self.handle_table_map(data);
+                Vec::new()
+            }
+            BinlogEventData::WriteRows(WriteRowsData { table_id, .. })
+            | BinlogEventData::UpdateRows(UpdateRowsData { table_id, .. })
+            | BinlogEventData::DeleteRows(DeleteRowsData { table_id, .. }) => {
+                self.rows_to_change_events(header, &event.data, *table_id)
+            }
+            BinlogEventData::Query(data) => {
+                let change = self.query_to_change_event(header, data);
+                // BEGIN 이 아니면 (COMMIT, DDL) 트랜잭션 끝
+                if !data.query.eq_ignore_ascii_case("BEGIN") {
+                    self.gtid = None;
+                }
+                change.into_iter().collect()
+            }
+            BinlogEventData::Xid(_) => {
+                self.gtid = None;
+                Vec::new()
+            }
+            _ => Vec::new(),
         }
     }
 
+    fn database_included(&self, database: &str) -> bool {
+        self.databases.is_empty() || self.databases.iter().any(|d| d == database)
+    }
+
+    fn table_included(&self, database: &str, table: &str) -> bool {
+        self.database_included(database)
+            && self
+                .tables
+                .as_ref()
+                .is_none_or(|tables| tables.iter().any(|t| t == table))
+    }
+
     /// 테이블 맵 이벤트 처리
-    #[allow(dead_code)]
     fn handle_table_map(&mut self, data: &TableMapData) {
         debug!("Table map event: {}.{}", data.database, data.table);
-        // 메타데이터 업데이트
+
+        if !self.table_included(&data.database, &data.table) {
+            self.tables_by_id.remove(&data.table_id);
+            return;
+        }
+
+        // 시작 후 만들어지거나 바뀐 테이블은 컬럼 이름을 몰라 위치로 이름 붙임 (@1, @2, ..)
+        let table_key = format!("{}.{}", data.database, data.table);
+        let cached = self.table_metadata.get(&table_key);
+        if cached.is_some_and(|m| m.columns.len() != data.column_types.len()) {
+            warn!(
+                "Column count of {} changed to {}, dropping cached metadata",
+                table_key,
+                data.column_types.len()
+            );
+            self.table_metadata.remove(&table_key);
+        }
+        let metadata = self
+            .table_metadata
+            .get(&table_key)
+            .cloned()
+            .unwrap_or_else(|| {
+                warn!(
+                    "No metadata for {}, using positional column names",
+                    table_key
+                );
+                TableMetadata {
+                    database: data.database.clone(),
+                    table: data.table.clone(),
+                    columns: (1..=data.column_types.len())
+                        .map(|i| format!("@{}", i))
+                        .collect(),
+                    column_types: Vec::new(),
+                    primary_key: Vec::new(),
+                }
+            });
+        self.tables_by_id.insert(data.table_id, (data.clone(), metadata));
+    }
+
+    /// 행 이벤트를 TABLE_MAP 으로 디코딩한 뒤 ChangeEvent 로 변환
+    fn rows_to_change_events(
+        &self,
+        header: &EventHeader,
+        data: &BinlogEventData,
+        table_id: u64,
+    ) -> Vec<ChangeEvent> {
+        let Some((table_map, table)) = self.tables_by_id.get(&table_id) else {
+            return Vec::new();
+        };
+
+        let mut data = data.clone();
+        if let Err(e) = BinlogParser::decode_rows(&mut data, table_map) {
+            warn!("Failed to decode rows of {}.{}: {}", table.database, table.table, e);
+            return Vec::new();
+        }
+
+        match &data {
+            BinlogEventData::WriteRows(data) => self.write_rows_to_change_event(header, data, table),
+            BinlogEventData::UpdateRows(data) => {
+                self.update_rows_to_change_event(header, data, table)
+            }
+            BinlogEventData::DeleteRows(data) => {
+                self.delete_rows_to_change_event(header, data, table)
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// 현재 GTID 와 이벤트 헤더의 타임스탬프로 ChangeEvent 생성
+    fn change_event(
+        &self,
+        header: &EventHeader,
+        op: OperationType,
+        table: &TableMetadata,
+    ) -> ChangeEvent {
+        ChangeEvent {
+            gtid: self.gtid.clone(),
+            op,
+            timestamp: event_timestamp(header),
+            database: table.database.clone(),
+            table: table.table.clone(),
+            before: None,
+            after: None,
+            query: None,
+        }
     }
 
     /// WRITE_ROWS 이벤트를 ChangeEvent로 변환
-    #[allow(dead_code)]
     fn write_rows_to_change_event(
         &self,
+        header: &EventHeader,
         data: &WriteRowsData,
         table: &TableMetadata,
     ) -> Vec<ChangeEvent> {
         data.rows
             .iter()
-            .map(|row| {
-                let mut after = HashMap::new();
-                for (i, col_name) in table.columns.iter().enumerate() {
-                    if i < row.len() {
-                        after.insert(col_name.clone(), row[i].clone());
-                    }
-                }
-
-                ChangeEvent {
-                    gtid: None,
-                    op: OperationType::Insert,
-                    timestamp: Utc::now(),
-                    database: table.database.clone(),
-                    table: table.table.clone(),
-                    before: None,
-                    after: Some(after),
-                    query: None,
-                }
+            .map(|row| ChangeEvent {
+                after: Some(row_to_map(row, table)),
+                ..self.change_event(header, OperationType::Insert, table)
             })
             .collect()
     }
 
     /// UPDATE_ROWS 이벤트를 ChangeEvent로 변환
-    #[allow(dead_code)]
     fn update_rows_to_change_event(
         &self,
+        header: &EventHeader,
         data: &UpdateRowsData,
         table: &TableMetadata,
     ) -> Vec<ChangeEvent> {
         data.rows
             .iter()
-            .map(|(before_row, after_row)| {
-                let mut before = HashMap::new();
-                let mut after = HashMap::new();
-
-                for (i, col_name) in table.columns.iter().enumerate() {
-                    if i < before_row.len() {
-                        before.insert(col_name.clone(), before_row[i].clone());
-                    }
-                    if i < after_row.len() {
-                        after.insert(col_name.clone(), after_row[i].clone());
-                    }
-                }
-
-                ChangeEvent {
-                    gtid: None,
-                    op: OperationType::Update,
-                    timestamp: Utc::now(),
-                    database: table.database.clone(),
-                    table: table.table.clone(),
-                    before: Some(before),
-                    after: Some(after),
-                    query: None,
-                }
+            .map(|(before_row, after_row)| ChangeEvent {
+                before: Some(row_to_map(before_row, table)),
+                after: Some(row_to_map(after_row, table)),
+                ..self.change_event(header, OperationType::Update, table)
             })
             .collect()
     }
 
     /// DELETE_ROWS 이벤트를 ChangeEvent로 변환
-    #[allow(dead_code)]
     fn delete_rows_to_change_event(
         &self,
+        header: &EventHeader,
         data: &DeleteRowsData,
         table: &TableMetadata,
     ) -> Vec<ChangeEvent> {
         data.rows
             .iter()
-            .map(|row| {
-                let mut before = HashMap::new();
-                for (i, col_name) in table.columns.iter().enumerate() {
-                    if i < row.len() {
-                        before.insert(col_name.clone(), row[i].clone());
-                    }
-                }
-
-                ChangeEvent {
-                    gtid: None,
-                    op: OperationType::Delete,
-                    timestamp: Utc::now(),
-                    database: table.database.clone(),
-                    table: table.table.clone(),
-                    before: Some(before),
-                    after: None,
-                    query: None,
-                }
+            .map(|row| ChangeEvent {
+                before: Some(row_to_map(row, table)),
+                ..self.change_event(header, OperationType::Delete, table)
             })
             .collect()
     }
 
     /// 쿼리 이벤트를 ChangeEvent로 변환 (DDL)
-    #[allow(dead_code)]
-    fn query_to_change_event(&self, data: &QueryEventData) -> Option<ChangeEvent> {
-        if !self.config.include_ddl {
+    ///
+    /// DDL 이 바꾸는 테이블의 캐시된 메타데이터는 필터와 관계없이 버립니다.
+    fn query_to_change_event(
+        &mut self,
+        header: &EventHeader,
+        data: &QueryEventData,
+    ) -> Option<ChangeEvent> {
+        // DDL 쿼리 감지
+        let upper_query = data.query.trim_start().to_uppercase();
+        if !upper_query.starts_with("CREATE")
+            && !upper_query.starts_with("ALTER")
+            && !upper_query.starts_with("DROP")
+        {
             return None;
         }
 
-        // DDL 쿼리 감지
-        let upper_query = data.query.to_uppercase();
-        if upper_query.starts_with("CREATE")
-            || upper_query.starts_with("ALTER")
-            || upper_query.starts_with("DROP")
-        {
-            return Some(ChangeEvent {
-                gtid: None,
-                op: OperationType::Ddl,
-                timestamp: Utc::now(),
-                database: data.database.clone(),
-                table: String::new(),
-                before: None,
-                after: None,
-                query: Some(data.query.clone()),
-            });
+        // 스키마가 붙지 않은 이름만 세션 기본 스키마의 것
+        let (database, table) = match DdlTarget::parse(&data.query) {
+            DdlTarget::Database(database) => {
+                self.table_metadata.retain(|_, m| m.database != database);
+                (database, None)
+            }
+            DdlTarget::Table(database, table) => {
+                let database = database.unwrap_or_else(|| data.database.clone());
+                self.table_metadata.remove(&format!("{}.{}", database, table));
+                (database, Some(table))
+            }
+            DdlTarget::Other(database) => (database.unwrap_or_else(|| data.database.clone()), None),
+        };
+
+        if !self.include_ddl || !self.database_included(&database) {
+            return None;
         }
 
-        None
+        Some(ChangeEvent {
+            gtid: self.gtid.clone(),
+            op: OperationType::Ddl,
+            timestamp: event_timestamp(header),
+            database,
+            table: table.unwrap_or_default(),
+            before: None,
+            after: None,
+            query: Some(data.query.clone()),
+        })
     }
+}
 
-    /// 현재 오프셋 반환
-    pub fn get_offset(&self) -> &BinlogOffset {
-        &self.offset
-    }
+/// DDL 문장이 바꾸는 대상 (스키마는 이름에 붙은 경우에만 있음)
+#[derive(Debug, PartialEq)]
+enum DdlTarget {
+    /// CREATE / ALTER / DROP DATABASE (SCHEMA)
+    Database(String),
+    /// 테이블, 뷰, 인덱스가 속한 테이블
+    Table(Option<String>, String),
+    /// 프로시저, 트리거 등 테이블이 아닌 객체이거나 대상을 알 수 없는 문장
+    Other(Option<String>),
+}
 
-    /// 오프셋 저장
-    pub fn save_offset(&mut self) -> Result<()> {
-        // 실제 구현에서는 Kafka/파일 등에 저장
-        debug!("Saving offset: {}", self.offset.binlog_position);
-        Ok(())
+impl DdlTarget {
+    fn parse(query: &str) -> DdlTarget {
+        let tokens = sql_tokens(query);
+        let keyword = |i: usize, word: &str| {
+            matches!(tokens.get(i), Some(SqlToken::Word(w)) if w.eq_ignore_ascii_case(word))
+        };
+        let name = |i: usize| match tokens.get(i) {
+            Some(SqlToken::Word(name) | SqlToken::Quoted(name)) => Some(name.clone()),
+            _ => None,
+        };
+
+        // 객체 종류 키워드 (CREATE OR REPLACE DEFINER=.. VIEW 처럼 앞에 수식어가 올 수 있음)
+        const KINDS: [&str; 9] = [
+            "TABLE", "VIEW", "INDEX", "DATABASE", "SCHEMA", "PROCEDURE", "FUNCTION", "TRIGGER",
+            "EVENT",
+        ];
+        let Some(kind) = (1..tokens.len()).find(|&i| KINDS.iter().any(|k| keyword(i, k))) else {
+            return DdlTarget::Other(None);
+        };
+
+        // CREATE INDEX idx ON tbl / DROP INDEX idx ON tbl
+        let mut i = kind;
+        if keyword(kind, "INDEX") {
+            match (kind..tokens.len()).find(|&j| keyword(j, "ON")) {
+                Some(on) => i = on,
+                None => return DdlTarget::Other(None),
+            }
+        }
+        i += 1;
+        while keyword(i, "IF") || keyword(i, "NOT") || keyword(i, "EXISTS") {
+            i += 1;
+        }
+
+        let Some(first) = name(i) else {
+            return DdlTarget::Other(None);
+        };
+        let (schema, object) = match (tokens.get(i + 1), name(i + 2)) {
+            (Some(SqlToken::Symbol('.')), Some(object)) => (Some(first), object),
+            _ => (None, first),
+        };
+
+        if keyword(kind, "DATABASE") || keyword(kind, "SCHEMA") {
+            DdlTarget::Database(object)
+        } else if keyword(kind, "TABLE") || keyword(kind, "VIEW") || keyword(kind, "INDEX") {
+            DdlTarget::Table(schema, object)
+        } else {
+            DdlTarget::Other(schema)
+        }
     }
+}
 
-    /// 상태 조회
-    pub fn get_state(&self) -> ProcessingState {
-        self.state
+/// DDL 대상 이름을 찾는 데 필요한 만큼만 나눈 SQL 토큰
+#[derive(Debug, PartialEq)]
+enum SqlToken {
+    Word(String),
+    /// 백틱으로 감싼 식별자 (키워드로 보지 않음)
+    Quoted(String),
+    Symbol(char),
+}
+
+fn sql_tokens(query: &str) -> Vec<SqlToken> {
+    let mut tokens = Vec::new();
+    let mut chars = query.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '`' | '\'' | '"' => {
+                // 같은 따옴표 두 개는 escape
+                let mut value = String::new();
+                while let Some(next) = chars.next() {
+                    if next != c {
+                        value.push(next);
+                    } else if chars.peek() == Some(&c) {
+                        chars.next();
+                        value.push(c);
+                    } else {
+                        break;
+                    }
+                }
+                if c == '`' {
+                    tokens.push(SqlToken::Quoted(value));
+                } else {
+                    tokens.push(SqlToken::Symbol(c));
+                }
+            }
+            c if c.is_alphanumeric() || c == '_' || c == '$' => {
+                let mut word = c.to_string();
+                while let Some(&next) = chars.peek() {
+                    if !(next.is_alphanumeric() || next == '_' || next == '$') {
+                        break;
+                    }
+                    word.push(next);
+                    chars.next();
+                }
+                tokens.push(SqlToken::Word(word));
+            }
+            c => tokens.push(SqlToken::Symbol(c)),
+        }
     }
+    tokens
+}
 
-    /// 엔진 종료
-    pub async fn stop(&mut self) -> Result<()> {
-        info!("Stopping CDC Engine");
+/// 캡처할 테이블을 모두 읽기 잠금하는 문장
+fn lock_tables_statement(tables: &[TableMetadata]) -> String {
+    let tables: Vec<String> = tables
+        .iter()
+        .map(|t| format!("`{}`.`{}` READ", t.database, t.table))
+        .collect();
+    format!("LOCK TABLES {}", tables.join(", "))
+}
 
-        if let Some(mut conn) = self.conn.take() {
-            conn.close().await?;
+/// 기본 키 순서로 `last_key` 다음 청크를 읽는 문장과 바인딩할 키 값 (복합 키는 row constructor 비교)
+fn chunk_query(
+    table: &TableMetadata,
+    last_key: Option<&[CellValue]>,
+    limit: usize,
+) -> (String, Vec<CellValue>) {
+    let key: Vec<String> = table
+        .primary_key
+        .iter()
+        .map(|c| format!("`{}`", c))
+        .collect();
+    let key = key.join(", ");
+
+    let mut query = format!("SELECT * FROM `{}`.`{}`", table.database, table.table);
+    let mut params = Vec::new();
+    if let Some(last_key) = last_key {
+        let mut placeholders = Vec::new();
+        for (column, value) in table.primary_key.iter().zip(last_key) {
+            let column_type = table
+                .columns
+                .iter()
+                .position(|c| c == column)
+                .and_then(|i| table.column_types.get(i))
+                .map_or("", String::as_str);
+            let (placeholder, param) = key_param(column_type, value);
+            placeholders.push(placeholder);
+            params.push(param);
+        }
+        if table.primary_key.len() == 1 {
+            query.push_str(&format!(" WHERE {} > {}", key, placeholders[0]));
+        } else {
+            query.push_str(&format!(" WHERE ({}) > ({})", key, placeholders.join(", ")));
         }
+    }
+    query.push_str(&format!(" ORDER BY {} LIMIT {}", key, limit));
+    (query, params)
+}
 
-        self.state = ProcessingState::Stopped;
-        info!("CDC Engine stopped");
-        Ok(())
+/// 키 컬럼 하나의 자리 표시자와 바인딩할 값
+///
+/// ENUM 은 정렬 순서인 인덱스로 비교하고 (문자열과 비교하면 이름 순서), DECIMAL 은 문자열을 컬럼과 같은
+/// 정밀도로 바꿔 비교합니다 (그대로 두면 double 로 비교).
+fn key_param(column_type: &str, value: &CellValue) -> (String, CellValue) {
+    if let CellValue::String(name) = value {
+        let index =
+            enum_values(column_type).and_then(|values| values.iter().position(|v| v == name));
+        if let Some(index) = index {
+            return ("?".to_string(), CellValue::UInt64(index as u64 + 1));
+        }
+    }
+
+    let precision = column_type
+        .strip_prefix("decimal(")
+        .and_then(|rest| rest.split_once(')'))
+        .and_then(|(precision, _)| precision.split_once(','))
+        .and_then(|(m, d)| Some((m.trim().parse::<u32>().ok()?, d.trim().parse::<u32>().ok()?)));
+    match precision {
+        Some((m, d)) => (format!("CAST(? AS DECIMAL({}, {}))", m, d), value.clone()),
+        None => ("?".to_string(), value.clone()),
+    }
+}
+
+/// `enum('a','b')` 형식 컬럼 타입의 값 목록 (작은따옴표는 '' 로 escape)
+fn enum_values(column_type: &str) -> Option<Vec<String>> {
+    let list = column_type.strip_prefix("enum(")?.strip_suffix(')')?;
+
+    let mut values = Vec::new();
+    let mut value = String::new();
+    let mut quoted = false;
+    let mut chars = list.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' if quoted && chars.peek() == Some(&'\'') => {
+                chars.next();
+                value.push('\'');
+            }
+            '\'' if quoted => {
+                quoted = false;
+                values.push(std::mem::take(&mut value));
+            }
+            '\'' => quoted = true,
+            c if quoted => value.push(c),
+            _ => {}
+        }
     }
+    Some(values)
+}
+
+/// 두 시점 사이에 커밋된 트랜잭션이 없는지 여부
+fn same_binlog_status(before: &BinlogStatus, after: &BinlogStatus) -> bool {
+    before.file == after.file
+        && before.position == after.position
+        && before.executed_gtid_set.to_string() == after.executed_gtid_set.to_string()
+}
+
+/// 행 값을 컬럼 이름으로 매핑
+fn row_to_map(row: &[CellValue], table: &TableMetadata) -> HashMap<String, CellValue> {
+    table
+        .columns
+        .iter()
+        .cloned()
+        .zip(row.iter().cloned())
+        .collect()
+}
+
+/// 이벤트 헤더의 타임스탬프 (서버에서 실행된 시각)
+fn event_timestamp(header: &EventHeader) -> DateTime<Utc> {
+    DateTime::from_timestamp(header.timestamp as i64, 0).unwrap_or_default()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::binlog_writer::BinlogFileBuilder;
+    use crate::fake_server::FakeServer;
+    use bytes::Bytes;
+    use std::time::Duration;
+
+    const UUID: &str = "3e11fa47-71ca-11e1-9e33-c80aa9429562";
+    const ORDERS_QUERY: &str = "SELECT * FROM `shop`.`orders` ORDER BY `id` LIMIT 10000";
+
+    fn config(tables: Option<Vec<String>>) -> CdcConfig {
+        CdcConfig {
+            connection: ConnectionConfig::default(),
+            databases: vec!["shop".to_string()],
+            tables,
+            snapshot_mode: SnapshotMode::Never,
+            include_ddl: true,
+            gtid_filter: None,
+            queue: QueueConfig::default(),
+            snapshot_locking: SnapshotLocking::Global,
+            snapshot_chunk_size: DEFAULT_SNAPSHOT_CHUNK_SIZE,
+        }
+    }
+
+    fn orders_metadata() -> HashMap<String, TableMetadata> {
+        let metadata = TableMetadata {
+            database: "shop".to_string(),
+            table: "orders".to_string(),
+            columns: vec!["id".to_string(), "amount".to_string()],
+            column_types: vec!["int".to_string(), "int".to_string()],
+            primary_key: vec!["id".to_string()],
+        };
+        HashMap::from([("shop.orders".to_string(), metadata)])
+    }
+
+    fn event(timestamp: u32, data: BinlogEventData) -> BinlogEvent {
+        BinlogEvent {
+            header: EventHeader {
+                timestamp,
+                event_type: EventType::Unknown,
+                server_id: 1,
+                event_length: 0,
+                next_pos: 0,
+                flags: 0,
+            },
+            data,
+        }
+    }
+
+    fn table_map(table_id: u64, database: &str, table: &str) -> BinlogEventData {
+        BinlogEventData::TableMap(TableMapData {
+            table_id,
+            database: database.to_string(),
+            table: table.to_string(),
+            column_types: vec![3, 3],
+            column_meta: vec![vec![], vec![]],
+            nullable_bitmap: vec![0],
+            unsigned_columns: Vec::new(),
+        })
+    }
+
+    fn query(database: &str, sql: &str) -> BinlogEventData {
+        BinlogEventData::Query(QueryEventData {
+            thread_id: 1,
+            exec_time: 0,
+            database: database.to_string(),
+            query: sql.to_string(),
+        })
+    }
+
+    /// INT 컬럼 (8 개 이하) 만 있는 행 이미지 (행마다 null 비트맵 + 값)
+    fn rows_data(rows: &[&[i32]]) -> Bytes {
+        let mut data = Vec::new();
+        for row in rows {
+            data.push(0);
+            for value in *row {
+                data.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+        Bytes::from(data)
+    }
+
+    fn write_rows(table_id: u64, rows: &[&[i32]]) -> BinlogEventData {
+        let columns = rows[0].len();
+        BinlogEventData::WriteRows(WriteRowsData {
+            table_id,
+            flags: 0,
+            column_count: columns as u64,
+            columns_present: vec![(1 << columns) - 1],
+            rows: Vec::new(),
+            rows_data: rows_data(rows),
+        })
+    }
+
+    fn value(row: &Option<HashMap<String, CellValue>>, column: &str) -> Option<i32> {
+        match row.as_ref()?.get(column)? {
+            CellValue::Int32(v) => Some(*v),
+            _ => None,
+        }
+    }
 
     #[test]
     fn test_cdc_config() {
@@ -433,9 +1368,663 @@ mod tests {
             snapshot_mode: SnapshotMode::Initial,
             include_ddl: true,
             gtid_filter: None,
+            queue: QueueConfig::default(),
+            snapshot_locking: SnapshotLocking::Global,
+            snapshot_chunk_size: DEFAULT_SNAPSHOT_CHUNK_SIZE,
         };
 
         let engine = CdcEngine::new(config);
         assert_eq!(engine.state, ProcessingState::Snapshotting);
+        assert_eq!(engine.binlog_queue_depth(), None);
+    }
+
+    #[test]
+    fn test_event_converter() {
+        let mut converter =
+            EventConverter::new(&config(Some(vec!["orders".to_string()])), orders_metadata());
+        let gtid = format!("{}:5", UUID);
+
+        let events = [
+            event(
+                100,
+                BinlogEventData::Gtid(GtidEventData {
+                    gtid: gtid.clone(),
+                    committed: true,
+                }),
+            ),
+            event(100, query("shop", "BEGIN")),
+            event(100, table_map(10, "shop", "orders")),
+            event(100, table_map(11, "shop", "other")),
+            event(100, write_rows(10, &[&[1, 7], &[2, 8]])),
+            event(100, write_rows(11, &[&[3, 9]])),
+            event(
+                101,
+                BinlogEventData::UpdateRows(UpdateRowsData {
+                    table_id: 10,
+                    flags: 0,
+                    column_count: 2,
+                    columns_present: vec![0b11],
+                    columns_changed: vec![0b11],
+                    rows: Vec::new(),
+                    rows_data: rows_data(&[&[1, 7], &[1, 70]]),
+                }),
+            ),
+            event(
+                102,
+                BinlogEventData::DeleteRows(DeleteRowsData {
+                    table_id: 10,
+                    flags: 0,
+                    column_count: 2,
+                    columns_present: vec![0b11],
+                    rows: Vec::new(),
+                    rows_data: rows_data(&[&[2, 8]]),
+                }),
+            ),
+            event(102, BinlogEventData::Xid(9)),
+            event(
+                103,
+                query("shop", "ALTER TABLE orders ADD COLUMN note TEXT"),
+            ),
+            event(103, query("crm", "DROP TABLE users")),
+            event(103, query("shop", "INSERT INTO orders VALUES (3, 9)")),
+        ];
+        let changes: Vec<_> = events.iter().flat_map(|e| converter.convert(e)).collect();
+
+        let ops: Vec<_> = changes.iter().map(|c| c.op).collect();
+        assert_eq!(
+            ops,
+            [
+                OperationType::Insert,
+                OperationType::Insert,
+                OperationType::Update,
+                OperationType::Delete,
+                OperationType::Ddl,
+            ]
+        );
+        assert!(changes[..4]
+            .iter()
+            .all(|c| c.gtid.as_deref() == Some(gtid.as_str())
+                && c.database == "shop"
+                && c.table == "orders"));
+        assert_eq!(changes[0].timestamp.timestamp(), 100);
+        assert_eq!(
+            (
+                value(&changes[1].after, "id"),
+                value(&changes[1].after, "amount")
+            ),
+            (Some(2), Some(8))
+        );
+        assert_eq!(value(&changes[2].before, "amount"), Some(7));
+        assert_eq!(value(&changes[2].after, "amount"), Some(70));
+        assert_eq!(value(&changes[3].before, "id"), Some(2));
+        assert!(changes[3].after.is_none());
+
+        // XID 뒤의 DDL 은 새 트랜잭션 (GTID 이벤트가 없었으므로 None)
+        assert_eq!(changes[4].gtid, None);
+        assert_eq!(changes[4].timestamp.timestamp(), 103);
+        assert!(changes[4]
+            .query
+            .as_deref()
+            .unwrap()
+            .starts_with("ALTER TABLE"));
+
+        // include_ddl 이 꺼져 있으면 DDL 없음
+        let mut config = config(None);
+        config.include_ddl = false;
+        let mut converter = EventConverter::new(&config, HashMap::new());
+        assert!(converter.convert(&events[9]).is_empty());
+
+        // 메타데이터가 없는 테이블은 위치로 컬럼 이름 붙임
+        converter.convert(&event(0, table_map(12, "shop", "new_table")));
+        let changes = converter.convert(&event(0, write_rows(12, &[&[4, 5]])));
+        assert_eq!(changes[0].table, "new_table");
+        assert_eq!(
+            (
+                value(&changes[0].after, "@1"),
+                value(&changes[0].after, "@2")
+            ),
+            (Some(4), Some(5))
+        );
+    }
+
+    #[test]
+    fn test_ddl_target_schema() {
+        let mut converter = EventConverter::new(&config(None), orders_metadata());
+
+        // 다른 세션 스키마에서 필터에 포함된 스키마의 테이블을 바꿈
+        let changes = converter.convert(&event(
+            0,
+            query("crm", "ALTER TABLE `shop`.`orders` ADD COLUMN note TEXT"),
+        ));
+        assert_eq!(changes.len(), 1);
+        assert_eq!(
+            (changes[0].database.as_str(), changes[0].table.as_str()),
+            ("shop", "orders")
+        );
+
+        // 필터에 포함된 세션 스키마에서 다른 스키마의 테이블을 바꿈
+        let drop = query("shop", "DROP TABLE IF EXISTS other.t");
+        let changes = converter.convert(&event(0, drop));
+        assert!(changes.is_empty());
+
+        // 스키마가 붙지 않은 이름은 세션 스키마
+        let changes = converter.convert(&event(0, query("shop", "CREATE INDEX i ON t (a)")));
+        assert_eq!(
+            (changes[0].database.as_str(), changes[0].table.as_str()),
+            ("shop", "t")
+        );
+
+        assert_eq!(
+            DdlTarget::parse("CREATE DATABASE IF NOT EXISTS `a``b`"),
+            DdlTarget::Database("a`b".to_string())
+        );
+        assert_eq!(
+            DdlTarget::parse("CREATE DEFINER=`root`@`%` VIEW app.v AS SELECT 1"),
+            DdlTarget::Table(Some("app".to_string()), "v".to_string())
+        );
+        assert_eq!(
+            DdlTarget::parse("DROP PROCEDURE app.p"),
+            DdlTarget::Other(Some("app".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_schema_change_drops_metadata() {
+        let mut converter = EventConverter::new(&config(None), orders_metadata());
+        let table_map = |columns: usize| {
+            BinlogEventData::TableMap(TableMapData {
+                table_id: 10,
+                database: "shop".to_string(),
+                table: "orders".to_string(),
+                column_types: vec![3; columns],
+                column_meta: vec![vec![]; columns],
+                nullable_bitmap: vec![0],
+                unsigned_columns: Vec::new(),
+            })
+        };
+
+        // 컬럼 수가 캐시와 다르면 위치로 이름 붙임
+        converter.convert(&event(0, table_map(3)));
+        let changes = converter.convert(&event(0, write_rows(10, &[&[1, 2, 3]])));
+        assert_eq!(value(&changes[0].after, "@3"), Some(3));
+        assert_eq!(value(&changes[0].after, "id"), None);
+
+        // DDL 뒤에는 컬럼 수가 같아도 캐시된 이름을 쓰지 않음
+        let mut converter = EventConverter::new(&config(None), orders_metadata());
+        let alter = query("shop", "ALTER TABLE orders DROP COLUMN amount, ADD note INT");
+        converter.convert(&event(0, alter));
+        converter.convert(&event(0, table_map(2)));
+        let changes = converter.convert(&event(0, write_rows(10, &[&[1, 2]])));
+        assert_eq!(value(&changes[0].after, "@2"), Some(2));
+        assert_eq!(value(&changes[0].after, "amount"), None);
+    }
+
+    #[tokio::test]
+    async fn test_stream_binlog_change_events() {
+        let file = BinlogFileBuilder::new()
+            .timestamp(1_700_000_000)
+            .event(BinlogEventData::Gtid(GtidEventData {
+                gtid: format!("{}:1", UUID),
+                committed: true,
+            }))
+            .event(query("shop", "BEGIN"))
+            .event(table_map(42, "shop", "orders"))
+            .event(BinlogEventData::WriteRows(WriteRowsData {
+                table_id: 42,
+                flags: 0,
+                column_count: 2,
+                columns_present: vec![0b11],
+                rows: Vec::new(),
+                rows_data: rows_data(&[&[5, 6]]),
+            }))
+            .event(BinlogEventData::Xid(3))
+            .build()
+            .unwrap();
+        let server = FakeServer::builder()
+            .binlog("mysql-bin.000001", file)
+            .start()
+            .await
+            .unwrap();
+
+        let mut config = config(None);
+        config.connection = server.connection_config();
+        let mut engine = CdcEngine::new(config);
+        engine.table_metadata = orders_metadata();
+        engine
+            .offset
+            .lock()
+            .update_position("mysql-bin.000001".to_string(), 4);
+
+        let mut rx = engine.stream_binlog().await.unwrap();
+        let change = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(change.op, OperationType::Insert);
+        assert_eq!(
+            (change.database.as_str(), change.table.as_str()),
+            ("shop", "orders")
+        );
+        assert_eq!(change.gtid, Some(format!("{}:1", UUID)));
+        assert_eq!(change.timestamp.timestamp(), 1_700_000_000);
+        assert_eq!(value(&change.after, "id"), Some(5));
+        assert_eq!(value(&change.after, "amount"), Some(6));
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_handoff() {
+        let file = BinlogFileBuilder::new()
+            .event(query("shop", "CREATE TABLE orders (id INT, amount INT)"))
+            .build()
+            .unwrap();
+        let end = file.len() as u64;
+        let row = |id: &str, amount: &str| vec![Some(id.to_string()), Some(amount.to_string())];
+        let server = FakeServer::builder()
+            .binlog("mysql-bin.000001", file)
+            .variable("gtid_executed", &format!("{}:1-7", UUID))
+            .query_result(
+                ORDERS_QUERY,
+                &["id", "amount"],
+                vec![row("1", "10"), row("2", "20")],
+            )
+            .start()
+            .await
+            .unwrap();
+
+        let mut config = config(None);
+        config.connection = server.connection_config();
+        config.snapshot_mode = SnapshotMode::Initial;
+        let mut engine = CdcEngine::new(config);
+        engine.table_metadata = orders_metadata();
+
+        let mut snapshot = engine.snapshot().await.unwrap();
+        let mut rows = Vec::new();
+        while let Some(change) = snapshot.recv().await {
+            rows.push(change);
+        }
+        snapshot.finish().await.unwrap();
+        assert_eq!(rows.len(), 2);
+        assert!(rows
+            .iter()
+            .all(|r| r.op == OperationType::Read && r.before.is_none()));
+        assert!(matches!(rows[1].after.as_ref().unwrap()["amount"],
+            CellValue::String(ref v) if v == "20"));
+
+        // 잠금 안에서 기록한 위치와 GTID
+        let offset = engine.get_offset();
+        assert_eq!(offset.binlog_position.filename, "mysql-bin.000001");
+        assert_eq!(offset.binlog_position.position, end);
+        assert_eq!(offset.gtid_set.to_string(), format!("{}:1-7", UUID));
+
+        let queries = server.queries();
+        let index = |sql: &str| queries.iter().position(|q| q == sql).unwrap();
+        assert!(index("FLUSH TABLES WITH READ LOCK") < index("SHOW MASTER STATUS"));
+        assert!(index("SHOW MASTER STATUS") < index("START TRANSACTION WITH CONSISTENT SNAPSHOT"));
+        assert!(index("START TRANSACTION WITH CONSISTENT SNAPSHOT") < index("UNLOCK TABLES"));
+        assert!(index("UNLOCK TABLES") < index(ORDERS_QUERY));
+        assert!(index(ORDERS_QUERY) < index("COMMIT"));
+
+        // 스트리밍은 기록한 위치부터
+        let _binlog_rx = engine.stream_binlog().await.unwrap();
+        let mut dumps = server.dump_requests();
+        for _ in 0..100 {
+            if !dumps.is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            dumps = server.dump_requests();
+        }
+        assert_eq!(
+            (dumps[0].filename.as_str(), dumps[0].position),
+            ("mysql-bin.000001", end)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_releases_lock_on_error() {
+        let server = FakeServer::builder()
+            .query_error(
+                "START TRANSACTION WITH CONSISTENT SNAPSHOT",
+                1205,
+                "Lock wait timeout",
+            )
+            .start()
+            .await
+            .unwrap();
+
+        let mut config = config(None);
+        config.connection = server.connection_config();
+        config.snapshot_mode = SnapshotMode::Initial;
+        let mut engine = CdcEngine::new(config);
+        engine.table_metadata = orders_metadata();
+
+        assert!(engine.snapshot().await.is_err());
+        assert_eq!(server.queries().last().unwrap(), "UNLOCK TABLES");
+
+        // 스냅샷을 건너뛰는 모드는 서버에 접속하지 않음
+        let mut engine = CdcEngine::new(CdcConfig {
+            snapshot_mode: SnapshotMode::SchemaOnly,
+            ..engine.config.clone()
+        });
+        let connections = server.connections();
+        let mut snapshot = engine.snapshot().await.unwrap();
+        assert!(snapshot.recv().await.is_none());
+        snapshot.finish().await.unwrap();
+        assert_eq!(server.connections(), connections);
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_releases_backup_lock_on_error() {
+        let backup = |server: &FakeServer| {
+            let mut config = config(None);
+            config.connection = server.connection_config();
+            config.snapshot_mode = SnapshotMode::Initial;
+            config.snapshot_locking = SnapshotLocking::Backup;
+            let mut engine = CdcEngine::new(config);
+            engine.table_metadata = orders_metadata();
+            engine
+        };
+
+        // 트랜잭션 시작 실패
+        let server = FakeServer::builder()
+            .query_error(
+                "START TRANSACTION WITH CONSISTENT SNAPSHOT",
+                1205,
+                "Lock wait timeout",
+            )
+            .start()
+            .await
+            .unwrap();
+        assert!(backup(&server).snapshot().await.is_err());
+        assert_eq!(server.queries().last().unwrap(), "UNLOCK INSTANCE");
+
+        // 행을 읽는 중 실패
+        let server = FakeServer::builder()
+            .binlog(
+                "mysql-bin.000001",
+                BinlogFileBuilder::new().build().unwrap(),
+            )
+            .query_error(ORDERS_QUERY, 1205, "Lock wait timeout")
+            .start()
+            .await
+            .unwrap();
+        let mut snapshot = backup(&server).snapshot().await.unwrap();
+        assert!(snapshot.recv().await.is_none());
+        assert!(snapshot.finish().await.is_err());
+
+        let queries = server.queries();
+        let index = |sql: &str| queries.iter().position(|q| q == sql);
+        assert!(index(ORDERS_QUERY).is_some());
+        assert!(index(ORDERS_QUERY) < index("UNLOCK INSTANCE"));
+        assert_eq!(index("COMMIT"), None);
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_locking() {
+        const START: &str = "START TRANSACTION WITH CONSISTENT SNAPSHOT";
+        const STATUS: &str = "SHOW MASTER STATUS";
+
+        for locking in [
+            SnapshotLocking::Backup,
+            SnapshotLocking::Tables,
+            SnapshotLocking::None,
+        ] {
+            let file = BinlogFileBuilder::new().build().unwrap();
+            let end = file.len() as u64;
+            let server = FakeServer::builder()
+                .binlog("mysql-bin.000001", file)
+                .query_result(
+                    ORDERS_QUERY,
+                    &["id", "amount"],
+                    vec![vec![Some("1".to_string()), Some("10".to_string())]],
+                )
+                .start()
+                .await
+                .unwrap();
+
+            let mut config = config(None);
+            config.connection = server.connection_config();
+            config.snapshot_mode = SnapshotMode::Initial;
+            config.snapshot_locking = locking;
+            let mut engine = CdcEngine::new(config);
+            engine.table_metadata = orders_metadata();
+
+            let mut snapshot = engine.snapshot().await.unwrap();
+            let mut rows = 0;
+            while snapshot.recv().await.is_some() {
+                rows += 1;
+            }
+            snapshot.finish().await.unwrap();
+            assert_eq!(rows, 1, "{:?}", locking);
+            assert_eq!(engine.get_offset().binlog_position.position, end);
+
+            let queries = server.queries();
+            let index = |sql: &str| queries.iter().position(|q| q == sql);
+            let count = |sql: &str| queries.iter().filter(|q| *q == sql).count();
+            assert_eq!(index("FLUSH TABLES WITH READ LOCK"), None);
+            match locking {
+                SnapshotLocking::Backup => {
+                    // DDL 만 막으므로 전후 위치 비교, 잠금은 스냅샷이 끝난 뒤 해제
+                    let lock = index("LOCK INSTANCE FOR BACKUP");
+                    assert!(lock.is_some() && lock < index(STATUS));
+                    assert_eq!(count(STATUS), 2);
+                    assert!(index("COMMIT") < index("UNLOCK INSTANCE"));
+                }
+                SnapshotLocking::Tables => {
+                    // 잠금 연결과 스냅샷 연결
+                    assert_eq!(server.connections(), 2);
+                    let lock = index("LOCK TABLES `shop`.`orders` READ");
+                    assert!(lock.is_some() && lock < index(STATUS));
+                    assert!(index(STATUS) < index(START));
+                    assert!(index(START) < index("UNLOCK TABLES"));
+                    assert_eq!(count(STATUS), 1);
+                }
+                _ => {
+                    assert!(queries.iter().all(|q| !q.contains("LOCK")));
+                    assert_eq!(count(STATUS), 2);
+                    assert!(index(STATUS) < index(START));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_same_binlog_status() {
+        let status = |file: &str, position, gtid: &str| BinlogStatus {
+            file: file.to_string(),
+            position,
+            binlog_do_db: None,
+            binlog_ignore_db: None,
+            executed_gtid_set: crate::gtid::GtidSet::parse(gtid).unwrap(),
+        };
+        let gtid = format!("{}:1-7", UUID);
+        let before = status("mysql-bin.000001", 400, &gtid);
+
+        assert!(same_binlog_status(
+            &before,
+            &status("mysql-bin.000001", 400, &gtid)
+        ));
+        assert!(!same_binlog_status(
+            &before,
+            &status("mysql-bin.000001", 520, &gtid)
+        ));
+        assert!(!same_binlog_status(
+            &before,
+            &status("mysql-bin.000002", 400, &gtid)
+        ));
+        let moved = format!("{}:1-8", UUID);
+        assert!(!same_binlog_status(
+            &before,
+            &status("mysql-bin.000001", 400, &moved)
+        ));
+    }
+
+    #[test]
+    fn test_chunk_query() {
+        let mut metadata = orders_metadata().remove("shop.orders").unwrap();
+        let (query, params) = chunk_query(&metadata, None, 100);
+        assert_eq!(
+            query,
+            "SELECT * FROM `shop`.`orders` ORDER BY `id` LIMIT 100"
+        );
+        assert!(params.is_empty());
+
+        // 키 값은 리터럴 대신 바인딩 (BIGINT UNSIGNED 도 정확하게 비교)
+        let (query, params) = chunk_query(&metadata, Some(&[CellValue::UInt64(u64::MAX)]), 100);
+        assert_eq!(
+            query,
+            "SELECT * FROM `shop`.`orders` WHERE `id` > ? ORDER BY `id` LIMIT 100"
+        );
+        assert!(matches!(params[..], [CellValue::UInt64(u64::MAX)]));
+
+        // 복합 키는 row constructor 로 비교, ENUM 은 인덱스로 비교
+        metadata.columns.push("region".to_string());
+        metadata
+            .column_types
+            .push("enum('us','it''s','eu')".to_string());
+        metadata.primary_key = vec!["region".to_string(), "id".to_string()];
+        let last_key = [CellValue::String("it's".to_string()), CellValue::UInt64(7)];
+        let (query, params) = chunk_query(&metadata, Some(&last_key), 10);
+        assert_eq!(
+            query,
+            "SELECT * FROM `shop`.`orders` WHERE (`region`, `id`) > (?, ?) \
+             ORDER BY `region`, `id` LIMIT 10"
+        );
+        assert!(matches!(
+            params[..],
+            [CellValue::UInt64(2), CellValue::UInt64(7)]
+        ));
+
+        // DECIMAL 은 컬럼 정밀도로 바꿔 비교
+        metadata.column_types[1] = "decimal(10,2) unsigned".to_string();
+        metadata.primary_key = vec!["amount".to_string()];
+        let (query, params) =
+            chunk_query(&metadata, Some(&[CellValue::Decimal("1.50".into())]), 10);
+        assert_eq!(
+            query,
+            "SELECT * FROM `shop`.`orders` WHERE `amount` > CAST(? AS DECIMAL(10, 2)) \
+             ORDER BY `amount` LIMIT 10"
+        );
+        assert!(matches!(&params[..], [CellValue::Decimal(d)] if d == "1.50"));
+
+        assert_eq!(
+            enum_values("enum('a','b''c','')"),
+            Some(vec!["a".to_string(), "b'c".to_string(), String::new()])
+        );
+        assert_eq!(enum_values("varchar(10)"), None);
+    }
+
+    #[tokio::test]
+    async fn test_chunked_snapshot_resume() {
+        let row = |id: &str| vec![Some(id.to_string()), Some("10".to_string())];
+        // 가짜 서버의 결과 컬럼은 문자열이므로 키도 문자열로 바인딩됨 ('2')
+        let chunk = |after: Option<&str>| match after {
+            Some(id) => format!(
+                "SELECT * FROM `shop`.`orders` WHERE `id` > '{}' ORDER BY `id` LIMIT 2",
+                id
+            ),
+            None => "SELECT * FROM `shop`.`orders` ORDER BY `id` LIMIT 2".to_string(),
+        };
+        let mut tables = orders_metadata();
+        tables.insert(
+            "shop.logs".to_string(),
+            TableMetadata {
+                database: "shop".to_string(),
+                table: "logs".to_string(),
+                columns: vec!["message".to_string()],
+                column_types: vec!["text".to_string()],
+                primary_key: Vec::new(),
+            },
+        );
+        let engine = |server: &FakeServer| {
+            let mut config = config(None);
+            config.connection = server.connection_config();
+            config.snapshot_mode = SnapshotMode::Initial;
+            config.snapshot_chunk_size = 2;
+            config
+        };
+        // 처음 `acked` 개 행만 처리 완료
+        let drain = |mut snapshot: SnapshotStream, acked: usize| async move {
+            let ack = snapshot.acknowledger();
+            let mut tables = Vec::new();
+            while let Some(change) = snapshot.recv().await {
+                if tables.len() < acked {
+                    ack.ack(1);
+                }
+                tables.push(change.table);
+            }
+            (tables, snapshot.finish().await)
+        };
+
+        // 세 번째 청크에서 실패, 컨슈머는 두 번째 청크의 마지막 행을 처리하기 전에 중단
+        let file = BinlogFileBuilder::new().build().unwrap();
+        let end = file.len() as u64;
+        let server = FakeServer::builder()
+            .binlog("mysql-bin.000001", file)
+            .query_result(
+                "SELECT * FROM `shop`.`logs`",
+                &["message"],
+                vec![vec![Some("hello".to_string())]],
+            )
+            .query_result(&chunk(None), &["id", "amount"], vec![row("1"), row("2")])
+            .query_result(
+                &chunk(Some("2")),
+                &["id", "amount"],
+                vec![row("3"), row("4")],
+            )
+            .query_error(&chunk(Some("4")), 2013, "Lost connection to MySQL server")
+            .start()
+            .await
+            .unwrap();
+        let mut first = CdcEngine::new(engine(&server));
+        first.table_metadata = tables.clone();
+        let (rows, result) = drain(first.snapshot().await.unwrap(), 4).await;
+        assert_eq!(rows, ["logs", "orders", "orders", "orders", "orders"]);
+        assert!(
+            matches!(result, Err(CdcError::QueryError(ref e)) if e.contains("Lost connection"))
+        );
+
+        let offset = first.get_offset();
+        assert!(!offset.snapshot_completed);
+        assert!(offset.snapshot_progress["shop.logs"].completed);
+        let orders = &offset.snapshot_progress["shop.orders"];
+        assert!(!orders.completed);
+        assert!(matches!(orders.last_key.as_deref(),
+            Some([CellValue::String(id)]) if id == "2"));
+
+        // 재시작: 끝난 테이블은 건너뛰고 ACK 하지 않은 청크부터 다시, 스트리밍 위치는 그대로
+        let server = FakeServer::builder()
+            .query_result(
+                &chunk(Some("2")),
+                &["id", "amount"],
+                vec![row("3"), row("4")],
+            )
+            .query_result(&chunk(Some("4")), &["id", "amount"], vec![row("5")])
+            .start()
+            .await
+            .unwrap();
+        let mut second = CdcEngine::new(engine(&server)).with_offset(offset);
+        second.table_metadata = tables;
+        let (rows, result) = drain(second.snapshot().await.unwrap(), usize::MAX).await;
+        assert_eq!(rows, ["orders", "orders", "orders"]);
+        result.unwrap();
+
+        let queries = server.queries();
+        assert!(!queries
+            .iter()
+            .any(|q| q.contains("LOCK") || q == "SHOW MASTER STATUS"));
+        assert!(!queries.iter().any(|q| q.contains("`logs`")));
+        let offset = second.get_offset();
+        assert!(offset.snapshot_completed);
+        assert!(offset.snapshot_progress.is_empty());
+        assert_eq!(offset.binlog_position.position, end);
+
+        // 완료된 스냅샷은 다시 읽지 않음
+        let connections = server.connections();
+        let mut third = CdcEngine::new(engine(&server)).with_offset(offset);
+        let (rows, result) = drain(third.snapshot().await.unwrap(), 0).await;
+        assert!(rows.is_empty());
+        result.unwrap();
+        assert_eq!(server.connections(), connections);
     }
 }
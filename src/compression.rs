@@ -0,0 +1,149 @@
+//! MySQL 압축 프로토콜 (CLIENT_COMPRESS, CLIENT_ZSTD_COMPRESSION_ALGORITHM)
+//!
+//! 인증이 끝난 뒤 모든 패킷은 7 바이트 헤더를 가진 압축 프레임으로 감싸집니다.
+//!   - Compressed payload length (3 bytes)
+//!   - Compressed sequence id (1 byte)
+//!   - Uncompressed payload length (3 bytes, 0 이면 압축하지 않은 payload)
+//!
+//! 압축 프레임의 payload 에는 일반 MySQL 패킷(4 바이트 헤더 포함)이 하나 이상 들어갑니다.
+
+use crate::auth::capabilities;
+use crate::error::{CdcError, Result};
+use std::io::{Read, Write};
+
+/// 압축 프레임 헤더 크기
+pub const COMPRESSED_HEADER_SIZE: usize = 7;
+
+/// 이보다 작은 payload 는 압축하지 않고 전송 (MySQL MIN_COMPRESS_LENGTH)
+pub const MIN_COMPRESS_LENGTH: usize = 50;
+
+/// 기본 zstd 압축 레벨 (MySQL 기본값과 동일)
+pub const DEFAULT_ZSTD_LEVEL: u8 = 3;
+
+/// 압축 알고리즘
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Compression {
+    /// 압축하지 않음
+    #[default]
+    Disabled,
+    /// zlib (CLIENT_COMPRESS)
+    Zlib,
+    /// zstd (CLIENT_ZSTD_COMPRESSION_ALGORITHM), 레벨 1-22
+    Zstd { level: u8 },
+}
+
+impl Compression {
+    /// 서버 capability 에 맞춰 실제로 사용할 알고리즘 결정
+    ///
+    /// zstd 를 지원하지 않는 서버에서는 zlib 으로, 압축을 지원하지 않는 서버에서는
+    /// 압축 없이 연결합니다.
+    pub fn negotiate(self, server_capabilities: u32) -> Compression {
+        let zstd = server_capabilities & capabilities::ZSTD_COMPRESSION_ALGORITHM != 0;
+        let zlib = server_capabilities & capabilities::COMPRESS != 0;
+
+        match self {
+            Compression::Zstd { .. } if zstd => self,
+            Compression::Zstd { .. } | Compression::Zlib if zlib => Compression::Zlib,
+            _ => Compression::Disabled,
+        }
+    }
+
+    /// 핸드셰이크 응답에 설정할 capability flag
+    pub fn capability(&self) -> u32 {
+        match self {
+            Compression::Disabled => 0,
+            Compression::Zlib => capabilities::COMPRESS,
+            Compression::Zstd { .. } => capabilities::ZSTD_COMPRESSION_ALGORITHM,
+        }
+    }
+
+    /// 핸드셰이크 응답 끝에 붙는 zstd 압축 레벨
+    pub fn zstd_level(&self) -> Option<u8> {
+        match self {
+            Compression::Zstd { level } => Some(*level),
+            _ => None,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        *self != Compression::Disabled
+    }
+
+    /// payload 압축
+    pub fn compress(&self, data: &[u8]) -> Result<Vec<u8>> {
+        match self {
+            Compression::Disabled => Ok(data.to_vec()),
+            Compression::Zlib => {
+                let mut encoder =
+                    flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(data)?;
+                Ok(encoder.finish()?)
+            }
+            Compression::Zstd { level } => Ok(zstd::bulk::compress(data, *level as i32)?),
+        }
+    }
+
+    /// payload 압축 해제
+    pub fn decompress(&self, data: &[u8], uncompressed_length: usize) -> Result<Vec<u8>> {
+        let decompressed = match self {
+            Compression::Disabled => data.to_vec(),
+            Compression::Zlib => {
+                // 헤더의 길이보다 1 바이트만 더 읽어 길이 불일치를 감지 (메모리 사용량 제한)
+                let mut decoder = flate2::read::ZlibDecoder::new(data)
+                    .take(uncompressed_length as u64 + 1);
+                let mut buffer = Vec::with_capacity(uncompressed_length);
+                decoder.read_to_end(&mut buffer)?;
+                buffer
+            }
+            Compression::Zstd { .. } => zstd::bulk::decompress(data, uncompressed_length)?,
+        };
+
+        if decompressed.len() != uncompressed_length {
+            return Err(CdcError::ProtocolError(format!(
+                "Decompressed length mismatch: expected {}, got {}",
+                uncompressed_length,
+                decompressed.len()
+            )));
+        }
+
+        Ok(decompressed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_negotiate() {
+        let both = capabilities::COMPRESS | capabilities::ZSTD_COMPRESSION_ALGORITHM;
+        let zstd = Compression::Zstd { level: 5 };
+
+        assert_eq!(zstd.negotiate(both), zstd);
+        assert_eq!(zstd.negotiate(capabilities::COMPRESS), Compression::Zlib);
+        assert_eq!(Compression::Zlib.negotiate(0), Compression::Disabled);
+        assert_eq!(Compression::Disabled.negotiate(both), Compression::Disabled);
+    }
+
+    #[test]
+    fn test_compress_roundtrip() {
+        let data = b"binlog event payload ".repeat(100);
+
+        for compression in [Compression::Zlib, Compression::Zstd { level: DEFAULT_ZSTD_LEVEL }] {
+            let compressed = compression.compress(&data).unwrap();
+            assert!(compressed.len() < data.len());
+            assert_eq!(compression.decompress(&compressed, data.len()).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn test_decompress_length_mismatch() {
+        let data = vec![0u8; 1 << 20];
+
+        for compression in [Compression::Zlib, Compression::Zstd { level: DEFAULT_ZSTD_LEVEL }] {
+            let compressed = compression.compress(&data).unwrap();
+            assert!(compression.decompress(&compressed, 100).is_err());
+            assert!(compression.decompress(&compressed, data.len() + 1).is_err());
+        }
+    }
+}
@@ -1,9 +1,19 @@
 //! MySQL 데이터베이스 연결 관리
 
+use crate::compression::Compression;
 use crate::error::{CdcError, Result};
-use crate::gtid::GtidSet;
+use crate::events::CellValue;
+use crate::gtid::{GtidSet, MariadbGtidSet};
+use crate::protocol::DEFAULT_MAX_ALLOWED_PACKET;
+use crate::server::ServerInfo;
+use crate::tls::{self, SslMode};
 use mysql_async::prelude::*;
-use mysql_async::{Conn, Opts, OptsBuilder};
+use futures_util::StreamExt;
+use mysql_async::{Conn, Opts, OptsBuilder, ResultSetStream, TextProtocol};
+use tracing::{debug, warn};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::PathBuf;
 use std::time::Duration;
 
 /// MySQL 연결 설정
@@ -11,11 +21,74 @@ use std::time::Duration;
 pub struct ConnectionConfig {
     pub hostname: String,
     pub port: u16,
+    /// Unix domain socket 경로 (설정 시 hostname/port 대신 사용)
+    pub socket: Option<PathBuf>,
     pub username: String,
     pub password: String,
     pub database: Option<String>,
     pub server_id: u32,
     pub timeout: Duration,
+    /// replication 채널에서 허용하는 최대 패킷 크기 (바이트)
+    pub max_allowed_packet: usize,
+    /// sha256_password / caching_sha2_password 용 서버 RSA 공개키 (PEM 파일 경로)
+    pub server_public_key_path: Option<PathBuf>,
+    /// 공개키가 설정되지 않은 경우 서버에 공개키를 요청할지 여부
+    pub allow_public_key_retrieval: bool,
+    /// TLS 가 아닌 채널 (Unix socket 포함) 에서 mysql_clear_password 전송 허용 여부
+    pub allow_cleartext_password: bool,
+    /// TLS 모드 (MySQL `--ssl-mode`)
+    pub ssl_mode: SslMode,
+    /// CA 인증서 (PEM 파일 경로)
+    pub ssl_ca: Option<PathBuf>,
+    /// 클라이언트 인증서 (PEM 파일 경로)
+    pub ssl_cert: Option<PathBuf>,
+    /// 클라이언트 개인키 (PEM 파일 경로)
+    pub ssl_key: Option<PathBuf>,
+    /// 압축 프로토콜 (서버가 지원하지 않으면 zstd -> zlib -> 비압축 순으로 대체)
+    pub compression: Compression,
+    /// 추가 연결 속성 (performance_schema.session_connect_attrs, 기본 속성보다 우선)
+    pub connect_attrs: HashMap<String, String>,
+    /// 장애 조치 후보 서버 (hostname/port 다음 순서로 시도, GTID 모드 필요)
+    pub failover_hosts: Vec<HostAddress>,
+}
+
+/// 서버 주소 (host:port)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostAddress {
+    pub hostname: String,
+    pub port: u16,
+}
+
+impl HostAddress {
+    pub fn new(hostname: impl Into<String>, port: u16) -> Self {
+        HostAddress {
+            hostname: hostname.into(),
+            port,
+        }
+    }
+}
+
+/// "host" 또는 "host:port" (포트 생략 시 3306)
+impl std::str::FromStr for HostAddress {
+    type Err = CdcError;
+
+    fn from_str(address: &str) -> Result<Self> {
+        match address.rsplit_once(':') {
+            Some((hostname, port)) => {
+                let port = port.parse().map_err(|_| {
+                    CdcError::ConfigError(format!("Invalid address: {}", address))
+                })?;
+                Ok(HostAddress::new(hostname, port))
+            }
+            None => Ok(HostAddress::new(address, 3306)),
+        }
+    }
+}
+
+impl fmt::Display for HostAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.hostname, self.port)
+    }
 }
 
 impl Default for ConnectionConfig {
@@ -23,11 +96,23 @@ impl Default for ConnectionConfig {
         ConnectionConfig {
             hostname: "localhost".to_string(),
             port: 3306,
+            socket: None,
             username: "root".to_string(),
             password: String::new(),
             database: None,
             server_id: 1,
             timeout: Duration::from_secs(30),
+            max_allowed_packet: DEFAULT_MAX_ALLOWED_PACKET,
+            server_public_key_path: None,
+            allow_public_key_retrieval: false,
+            allow_cleartext_password: false,
+            ssl_mode: SslMode::default(),
+            ssl_ca: None,
+            ssl_cert: None,
+            ssl_key: None,
+            compression: Compression::default(),
+            connect_attrs: HashMap::new(),
+            failover_hosts: Vec::new(),
         }
     }
 }
@@ -41,7 +126,65 @@ impl ConnectionConfig {
         }
     }
 
-    fn build_opts(&self) -> Result<Opts> {
+    /// 설정된 서버 공개키 파일 읽기
+    pub fn load_server_public_key(&self) -> Result<Option<Vec<u8>>> {
+        match self.server_public_key_path {
+            Some(ref path) => std::fs::read(path).map(Some).map_err(|e| {
+                CdcError::ConfigError(format!(
+                    "Failed to read server public key {}: {}",
+                    path.display(),
+                    e
+                ))
+            }),
+            None => Ok(None),
+        }
+    }
+
+    /// 핸드셰이크에 보낼 연결 속성
+    ///
+    /// `_client_name`, `_client_version`, `_pid`, `program_name` 에 사용자 속성을 더합니다.
+    /// replication 연결과 메타데이터 연결 모두 같은 속성을 보냅니다.
+    pub fn connect_attributes(&self) -> BTreeMap<String, String> {
+        let program_name = std::env::current_exe()
+            .ok()
+            .and_then(|path| path.file_stem().map(|name| name.to_string_lossy().to_string()))
+            .unwrap_or_else(|| env!("CARGO_PKG_NAME").to_string());
+
+        let mut attrs = BTreeMap::from([
+            ("_client_name".to_string(), env!("CARGO_PKG_NAME").to_string()),
+            ("_client_version".to_string(), env!("CARGO_PKG_VERSION").to_string()),
+            ("_pid".to_string(), std::process::id().to_string()),
+            ("program_name".to_string(), program_name),
+        ]);
+        attrs.extend(self.connect_attrs.clone());
+
+        attrs
+    }
+
+    /// 장애 조치 후보 설정 목록 (자기 자신이 첫 번째)
+    ///
+    /// 후보 서버는 hostname/port 만 바뀌고 Unix socket 은 사용하지 않습니다.
+    pub fn candidates(&self) -> Vec<ConnectionConfig> {
+        let failover = self.failover_hosts.iter().map(|host| ConnectionConfig {
+            hostname: host.hostname.clone(),
+            port: host.port,
+            socket: None,
+            failover_hosts: Vec::new(),
+            ..self.clone()
+        });
+
+        std::iter::once(self.clone()).chain(failover).collect()
+    }
+
+    /// 로그 출력용 연결 대상
+    pub fn endpoint(&self) -> String {
+        match self.socket {
+            Some(ref path) => path.display().to_string(),
+            None => format!("{}:{}", self.hostname, self.port),
+        }
+    }
+
+    fn build_opts(&self, ssl_opts: Option<mysql_async::SslOpts>) -> Result<Opts> {
         let connection_string = if let Some(ref db) = self.database {
             format!(
                 "mysql://{}:{}@{}:{}/{}",
@@ -54,53 +197,190 @@ impl ConnectionConfig {
             )
         };
 
-        connection_string
+        let opts: Opts = connection_string
             .parse()
-            .map_err(|_| CdcError::ConnectionError("Failed to parse connection string".to_string()))
+            .map_err(|_| CdcError::ConnectionError("Failed to parse connection string".to_string()))?;
+
+        let socket = self.socket.as_ref().map(|path| path.to_string_lossy().to_string());
+
+        // mysql_async 는 zlib 만 지원하므로 메타데이터 연결은 zstd 설정 시에도 zlib 사용
+        let compression = self
+            .compression
+            .is_enabled()
+            .then(mysql_async::Compression::default);
+
+        Ok(OptsBuilder::from_opts(opts)
+            .ssl_opts(ssl_opts)
+            .socket(socket)
+            .compression(compression)
+            .connect_attributes(self.connect_attributes().into_iter().collect::<HashMap<_, _>>())
+            .into())
     }
 }
 
+/// mysql_async 연결 에러 변환 (서버 ERR 패킷은 에러 코드별 CdcError 로 변환)
+fn connect_error(err: mysql_async::Error) -> CdcError {
+    match err {
+        mysql_async::Error::Server(e) => CdcError::from_server_error(e.code, &e.state, &e.message),
+        e @ mysql_async::Error::Driver(mysql_async::DriverError::NoClientSslFlagFromServer) => {
+            CdcError::ConfigError(format!("Failed to connect to MySQL: {}", e))
+        }
+        e => CdcError::ConnectionError(format!("Failed to connect to MySQL: {}", e)),
+    }
+}
+
+/// version, version_comment, aurora_version 변수로 서버 종류 감지
+async fn detect_server_info(conn: &mut Conn) -> Result<ServerInfo> {
+    let result: Vec<(String, String)> = conn
+        .query(
+            "SHOW GLOBAL VARIABLES WHERE Variable_name IN \
+             ('version', 'version_comment', 'aurora_version')",
+        )
+        .await
+        .map_err(|e| CdcError::QueryError(format!("Failed to query server version: {}", e)))?;
+
+    Ok(ServerInfo::from_variables(
+        result.iter().map(|(name, value)| (name.as_str(), value.as_str())),
+    ))
+}
+
 /// MySQL 연결 래퍼
 pub struct MySqlConnection {
     conn: Conn,
     config: ConnectionConfig,
+    server_info: ServerInfo,
 }
 
 impl MySqlConnection {
     pub async fn connect(config: ConnectionConfig) -> Result<Self> {
-        let opts = config.build_opts()?;
-        let pool = mysql_async::Pool::new(opts);
+        let opts = config.build_opts(tls::mysql_async_ssl_opts(&config))?;
 
-        let conn = pool
-            .get_conn()
-            .await
-            .map_err(|e| CdcError::ConnectionError(format!("Failed to connect to MySQL: {}", e)))?;
+        let mut conn = match Conn::new(opts).await {
+            Ok(conn) => conn,
+            // PREFERRED: 서버가 TLS를 지원하지 않을 때만 평문으로 재시도
+            // (replication 채널과 동일, TLS 협상 실패는 그대로 에러)
+            Err(mysql_async::Error::Driver(mysql_async::DriverError::NoClientSslFlagFromServer))
+                if config.ssl_mode == SslMode::Preferred =>
+            {
+                warn!("Server does not support TLS, continuing without encryption");
+                Conn::new(config.build_opts(None)?).await.map_err(connect_error)?
+            }
+            Err(e) => return Err(connect_error(e)),
+        };
+
+        let server_info = detect_server_info(&mut conn).await?;
+        debug!("Connected to {}", server_info);
+
+        Ok(MySqlConnection {
+            conn,
+            config,
+            server_info,
+        })
+    }
 
-        Ok(MySqlConnection { conn, config })
+    /// 서버 종류와 버전
+    pub fn server_info(&self) -> &ServerInfo {
+        &self.server_info
     }
 
     /// Binlog 상태 조회
+    ///
+    /// 서버 버전에 맞는 명령을 사용하며, Executed_Gtid_Set 컬럼이 없는 서버
+    /// (MySQL 5.5 이하, MariaDB) 는 빈 GTID 집합을, 파싱할 수 없는 GTID 집합은 에러를 반환합니다.
     pub async fn get_binlog_status(&mut self) -> Result<BinlogStatus> {
-        let result: Vec<(String, u64, String, String, String)> = self.conn
-            .query("SHOW MASTER STATUS")
+        let rows = self.query_rows(self.server_info.binlog_status_query(), "binlog status").await?;
+
+        let row = rows
+            .first()
+            .ok_or_else(|| CdcError::QueryError("No binlog status available".to_string()))?;
+
+        BinlogStatus::from_row(row)
+    }
+
+    /// binlog 파일 목록 조회 (SHOW BINARY LOGS)
+    pub async fn get_binary_logs(&mut self) -> Result<Vec<BinaryLogFile>> {
+        self.query_rows(self.server_info.binary_logs_query(), "binary logs")
+            .await?
+            .iter()
+            .map(BinaryLogFile::from_row)
+            .collect()
+    }
+
+    /// 이 서버에 연결된 replica 목록 조회
+    pub async fn get_replicas(&mut self) -> Result<Vec<ReplicaHost>> {
+        self.query_rows(self.server_info.replicas_query(), "replicas")
+            .await?
+            .iter()
+            .map(ReplicaHost::from_row)
+            .collect()
+    }
+
+    /// 이 서버의 replication 상태 조회 (replica 가 아니면 빈 목록, multi-source 는 채널별)
+    pub async fn get_replica_status(&mut self) -> Result<Vec<ReplicaStatus>> {
+        self.query_rows(self.server_info.replica_status_query(), "replica status")
+            .await?
+            .iter()
+            .map(ReplicaStatus::from_row)
+            .collect()
+    }
+
+    /// 컬럼 이름과 함께 결과 조회
+    async fn query_rows(&mut self, query: &str, what: &str) -> Result<Vec<ResultRow>> {
+        let rows: Vec<mysql_async::Row> = self.conn
+            .query(query)
             .await
-            .map_err(|e| CdcError::QueryError(format!("Failed to query binlog status: {}", e)))?;
+            .map_err(|e| CdcError::QueryError(format!("Failed to query {}: {}", what, e)))?;
 
-        if result.is_empty() {
-            return Err(CdcError::QueryError("No binlog status available".to_string()));
-        }
+        Ok(rows.into_iter().map(ResultRow::from).collect())
+    }
 
-        let (file, position, binlog_do_db, binlog_ignore_db, executed_gtid_set) = result[0].clone();
+    /// 결과 없는 문장 실행 (잠금, 트랜잭션 등)
+    pub async fn execute(&mut self, statement: &str) -> Result<()> {
+        self.conn
+            .query_drop(statement)
+            .await
+            .map_err(|e| CdcError::QueryError(format!("Failed to execute {}: {}", statement, e)))
+    }
 
-        Ok(BinlogStatus {
-            file: file.clone(),
-            position,
-            binlog_do_db: if binlog_do_db.is_empty() { None } else { Some(binlog_do_db) },
-            binlog_ignore_db: if binlog_ignore_db.is_empty() { None } else { Some(binlog_ignore_db) },
-            executed_gtid_set: GtidSet::parse(&executed_gtid_set).unwrap_or_default(),
+    /// 조회 결과를 컬럼 타입에 맞는 `CellValue` 로 읽기
+    pub async fn query_values(&mut self, query: &str) -> Result<Vec<Vec<CellValue>>> {
+        let rows: Vec<mysql_async::Row> = self.conn
+            .query(query)
+            .await
+            .map_err(|e| CdcError::QueryError(format!("Failed to query {}: {}", query, e)))?;
+
+        Ok(rows.into_iter().map(row_values).collect())
+    }
+
+    /// 조회 결과를 `query_values` 와 같은 값으로 한 행씩 읽기 (결과 전체를 메모리에 모으지 않음)
+    pub async fn query_stream(&mut self, query: &str) -> Result<ValueStream<'_>> {
+        let error = |e| CdcError::QueryError(format!("Failed to query {}: {}", query, e));
+        let result = self.conn.query_iter(query.to_string()).await.map_err(error)?;
+        let rows = result.stream_and_drop::<mysql_async::Row>().await.map_err(error)?;
+
+        Ok(ValueStream {
+            query: query.to_string(),
+            rows,
         })
     }
 
+    /// `?` 에 `params` 를 바인딩한 prepared statement 로 조회 (결과는 `query_values` 와 같은 값)
+    ///
+    /// 값을 쿼리 문자열에 넣지 않으므로 정수가 정확하게 비교되고 sql_mode 의 escape 규칙에 영향받지 않습니다.
+    pub async fn exec_values(
+        &mut self,
+        statement: &str,
+        params: &[CellValue],
+    ) -> Result<Vec<Vec<CellValue>>> {
+        let params: Vec<mysql_async::Value> = params.iter().map(param_value).collect();
+        let rows: Vec<mysql_async::Row> = self.conn
+            .exec(statement, params)
+            .await
+            .map_err(|e| CdcError::QueryError(format!("Failed to query {}: {}", statement, e)))?;
+
+        Ok(rows.into_iter().map(row_values).collect())
+    }
+
     /// GTID 모드 활성 여부 확인
     pub async fn is_gtid_mode_enabled(&mut self) -> Result<bool> {
         let result: Vec<(String, String)> = self.conn
@@ -125,6 +405,34 @@ impl MySqlConnection {
         GtidSet::parse(&result[0].0)
     }
 
+    /// purge 된 GTID 집합 조회 (binlog 에 더 이상 없는 트랜잭션)
+    pub async fn get_purged_gtid_set(&mut self) -> Result<GtidSet> {
+        let result: Vec<(String,)> = self.conn
+            .query("SELECT @@global.gtid_purged")
+            .await
+            .map_err(|e| CdcError::QueryError(format!("Failed to query gtid_purged: {}", e)))?;
+
+        if result.is_empty() {
+            return Ok(GtidSet::new());
+        }
+
+        GtidSet::parse(&result[0].0)
+    }
+
+    /// MariaDB 현재 binlog GTID 위치 조회 (`@@gtid_binlog_pos`)
+    pub async fn get_mariadb_gtid_position(&mut self) -> Result<MariadbGtidSet> {
+        let result: Vec<(String,)> = self.conn
+            .query("SELECT @@global.gtid_binlog_pos")
+            .await
+            .map_err(|e| CdcError::QueryError(format!("Failed to query gtid_binlog_pos: {}", e)))?;
+
+        if result.is_empty() {
+            return Ok(MariadbGtidSet::new());
+        }
+
+        MariadbGtidSet::parse(&result[0].0)
+    }
+
     /// MySQL 서버 정보 조회
     pub async fn get_server_id(&mut self) -> Result<u32> {
         let result: Vec<(u32,)> = self.conn
@@ -179,6 +487,7 @@ impl MySqlConnection {
                 column_type,
                 nullable: is_nullable == "YES",
                 is_key: !column_key.is_empty(),
+                is_primary_key: column_key == "PRI",
                 extra,
             })
             .collect())
@@ -211,6 +520,204 @@ impl MySqlConnection {
     }
 }
 
+/// 텍스트 프로토콜 값을 컬럼 타입에 맞게 변환 (해석할 수 없으면 문자열)
+fn cell_value(value: mysql_async::Value, column: &mysql_async::Column) -> CellValue {
+    use mysql_async::consts::{ColumnFlags, ColumnType};
+    use mysql_async::Value;
+
+    // 바이너리 프로토콜 (prepared statement) 값은 서버가 텍스트 프로토콜로 보내는 형식으로 바꿔
+    // 같은 CellValue 가 되게 함
+    let fraction = |micros: u32| match column.decimals() {
+        decimals @ 1..=6 => format!(".{:06}", micros)[..=decimals as usize].to_string(),
+        _ => String::new(),
+    };
+    let bytes = match value {
+        Value::NULL => return CellValue::Null,
+        Value::Bytes(bytes) => bytes,
+        Value::Int(v) => v.to_string().into_bytes(),
+        Value::UInt(v) => v.to_string().into_bytes(),
+        Value::Float(v) => v.to_string().into_bytes(),
+        Value::Double(v) => v.to_string().into_bytes(),
+        Value::Date(year, month, day, hour, minute, second, micros) => {
+            let date = format!("{:04}-{:02}-{:02}", year, month, day);
+            if column.column_type() == ColumnType::MYSQL_TYPE_DATE {
+                date.into_bytes()
+            } else {
+                let time = format!("{:02}:{:02}:{:02}", hour, minute, second);
+                format!("{} {}{}", date, time, fraction(micros)).into_bytes()
+            }
+        }
+        Value::Time(negative, days, hours, minutes, seconds, micros) => {
+            let hours = days * 24 + hours as u32;
+            let sign = if negative { "-" } else { "" };
+            let time = format!("{}{:02}:{:02}:{:02}", sign, hours, minutes, seconds);
+            format!("{}{}", time, fraction(micros)).into_bytes()
+        }
+    };
+
+    // character set 63 = binary (BLOB, BINARY, VARBINARY)
+    if column.character_set() == 63 && !column.flags().contains(ColumnFlags::NUM_FLAG) {
+        return CellValue::Bytes(bytes.into());
+    }
+
+    let text = String::from_utf8_lossy(&bytes).to_string();
+    let parsed = match column.column_type() {
+        ColumnType::MYSQL_TYPE_TINY
+        | ColumnType::MYSQL_TYPE_SHORT
+        | ColumnType::MYSQL_TYPE_INT24
+        | ColumnType::MYSQL_TYPE_LONG
+        | ColumnType::MYSQL_TYPE_LONGLONG
+        | ColumnType::MYSQL_TYPE_YEAR => {
+            if column.flags().contains(ColumnFlags::UNSIGNED_FLAG) {
+                text.parse().ok().map(CellValue::UInt64)
+            } else {
+                text.parse().ok().map(CellValue::Int64)
+            }
+        }
+        ColumnType::MYSQL_TYPE_FLOAT | ColumnType::MYSQL_TYPE_DOUBLE => {
+            text.parse().ok().map(CellValue::Double)
+        }
+        ColumnType::MYSQL_TYPE_DECIMAL | ColumnType::MYSQL_TYPE_NEWDECIMAL => {
+            Some(CellValue::Decimal(text.clone()))
+        }
+        ColumnType::MYSQL_TYPE_DATE => Some(CellValue::Date(text.clone())),
+        ColumnType::MYSQL_TYPE_TIME => Some(CellValue::Time(text.clone())),
+        ColumnType::MYSQL_TYPE_DATETIME | ColumnType::MYSQL_TYPE_TIMESTAMP => {
+            chrono::NaiveDateTime::parse_from_str(&text, "%Y-%m-%d %H:%M:%S%.f")
+                .ok()
+                .map(|dt| CellValue::DateTime(dt.and_utc()))
+        }
+        ColumnType::MYSQL_TYPE_JSON => serde_json::from_str(&text).ok().map(CellValue::Json),
+        _ => None,
+    };
+
+    parsed.unwrap_or(CellValue::String(text))
+}
+
+/// 행의 값을 컬럼 타입에 맞는 `CellValue` 로 변환
+fn row_values(row: mysql_async::Row) -> Vec<CellValue> {
+    let columns = row.columns();
+    row.unwrap()
+        .into_iter()
+        .zip(columns.iter())
+        .map(|(value, column)| cell_value(value, column))
+        .collect()
+}
+
+/// prepared statement 에 바인딩할 값 (정수 / 실수 / 시각은 그 타입 그대로)
+fn param_value(value: &CellValue) -> mysql_async::Value {
+    use chrono::{Datelike, Timelike};
+    use mysql_async::Value;
+
+    match value {
+        CellValue::Null => Value::NULL,
+        CellValue::Int8(v) => Value::Int(*v as i64),
+        CellValue::Int16(v) => Value::Int(*v as i64),
+        CellValue::Int32(v) => Value::Int(*v as i64),
+        CellValue::Int64(v) => Value::Int(*v),
+        CellValue::UInt8(v) => Value::UInt(*v as u64),
+        CellValue::UInt16(v) => Value::UInt(*v as u64),
+        CellValue::UInt32(v) => Value::UInt(*v as u64),
+        CellValue::UInt64(v) => Value::UInt(*v),
+        CellValue::Float(v) => Value::Float(*v),
+        CellValue::Double(v) => Value::Double(*v),
+        CellValue::DateTime(v) => Value::Date(
+            v.year() as u16,
+            v.month() as u8,
+            v.day() as u8,
+            v.hour() as u8,
+            v.minute() as u8,
+            v.second() as u8,
+            v.timestamp_subsec_micros(),
+        ),
+        CellValue::Bytes(v) => Value::Bytes(v.to_vec()),
+        CellValue::Decimal(v) | CellValue::String(v) | CellValue::Date(v) | CellValue::Time(v) => {
+            Value::Bytes(v.as_bytes().to_vec())
+        }
+        CellValue::Json(v) => Value::Bytes(v.to_string().into_bytes()),
+    }
+}
+
+/// `MySqlConnection::query_stream` 의 결과 (행을 읽는 동안 연결을 빌림)
+pub struct ValueStream<'a> {
+    query: String,
+    /// 결과셋이 없는 문장이면 None
+    rows: Option<ResultSetStream<'a, 'a, 'static, mysql_async::Row, TextProtocol>>,
+}
+
+impl ValueStream<'_> {
+    /// 다음 행 읽기 (결과 끝이면 None)
+    pub async fn next(&mut self) -> Result<Option<Vec<CellValue>>> {
+        let Some(rows) = self.rows.as_mut() else {
+            return Ok(None);
+        };
+
+        match rows.next().await {
+            Some(Ok(row)) => Ok(Some(row_values(row))),
+            Some(Err(e)) => Err(CdcError::QueryError(format!(
+                "Failed to query {}: {}",
+                self.query, e
+            ))),
+            None => Ok(None),
+        }
+    }
+}
+
+/// 컬럼 이름으로 값을 찾는 결과 행
+///
+/// SHOW 명령의 결과 컬럼은 서버 버전마다 개수와 이름이 달라 위치 대신 이름으로 읽습니다.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ResultRow {
+    columns: Vec<String>,
+    values: Vec<Option<String>>,
+}
+
+impl ResultRow {
+    pub fn new(columns: Vec<String>, values: Vec<Option<String>>) -> Self {
+        ResultRow { columns, values }
+    }
+
+    /// 이름 후보 중 처음 찾은 컬럼 값 (대소문자 무시, NULL 이면 None)
+    pub fn get(&self, names: &[&str]) -> Option<&str> {
+        names.iter().find_map(|name| {
+            let index = self.columns.iter().position(|c| c.eq_ignore_ascii_case(name))?;
+            self.values.get(index)?.as_deref()
+        })
+    }
+
+    /// 빈 문자열을 None 으로 취급
+    fn get_non_empty(&self, names: &[&str]) -> Option<String> {
+        self.get(names).filter(|v| !v.is_empty()).map(str::to_string)
+    }
+
+    fn require(&self, names: &[&str]) -> Result<&str> {
+        self.get(names)
+            .ok_or_else(|| CdcError::QueryError(format!("Missing column {}", names[0])))
+    }
+
+    fn parse<T: std::str::FromStr>(&self, names: &[&str]) -> Result<T> {
+        let value = self.require(names)?;
+        value.trim().parse().map_err(|_| {
+            CdcError::QueryError(format!("Invalid value for column {}: {}", names[0], value))
+        })
+    }
+}
+
+impl From<mysql_async::Row> for ResultRow {
+    fn from(row: mysql_async::Row) -> Self {
+        let columns = row
+            .columns_ref()
+            .iter()
+            .map(|column| column.name_str().to_string())
+            .collect();
+        let values = (0..row.len())
+            .map(|i| row.get_opt::<Option<String>, _>(i).and_then(|v| v.ok()).flatten())
+            .collect();
+
+        ResultRow { columns, values }
+    }
+}
+
 /// Binlog 상태
 #[derive(Debug, Clone)]
 pub struct BinlogStatus {
@@ -221,6 +728,111 @@ pub struct BinlogStatus {
     pub executed_gtid_set: GtidSet,
 }
 
+impl BinlogStatus {
+    /// SHOW MASTER STATUS / SHOW BINARY LOG STATUS / SHOW BINLOG STATUS 결과 행
+    pub fn from_row(row: &ResultRow) -> Result<Self> {
+        let executed_gtid_set = match row.get(&["Executed_Gtid_Set"]) {
+            // 여러 UUID 는 줄바꿈과 함께 출력됨
+            Some(gtid_set) => GtidSet::parse(&gtid_set.replace('\n', ""))?,
+            None => GtidSet::new(),
+        };
+
+        Ok(BinlogStatus {
+            file: row.require(&["File"])?.to_string(),
+            position: row.parse(&["Position"])?,
+            binlog_do_db: row.get_non_empty(&["Binlog_Do_DB"]),
+            binlog_ignore_db: row.get_non_empty(&["Binlog_Ignore_DB"]),
+            executed_gtid_set,
+        })
+    }
+}
+
+/// binlog 파일 (SHOW BINARY LOGS)
+#[derive(Debug, Clone, PartialEq)]
+pub struct BinaryLogFile {
+    pub name: String,
+    pub size: u64,
+    /// 암호화 여부 (MySQL 8.0.14 이상만 제공)
+    pub encrypted: Option<bool>,
+}
+
+impl BinaryLogFile {
+    pub fn from_row(row: &ResultRow) -> Result<Self> {
+        Ok(BinaryLogFile {
+            name: row.require(&["Log_name"])?.to_string(),
+            size: row.parse(&["File_size"])?,
+            encrypted: row.get(&["Encrypted"]).map(|v| v.eq_ignore_ascii_case("Yes")),
+        })
+    }
+}
+
+/// 연결된 replica (SHOW REPLICAS / SHOW SLAVE HOSTS)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplicaHost {
+    pub server_id: u32,
+    pub host: Option<String>,
+    pub port: u16,
+    pub source_id: u32,
+    /// replica server_uuid (MariaDB 는 제공하지 않음)
+    pub replica_uuid: Option<String>,
+}
+
+impl ReplicaHost {
+    pub fn from_row(row: &ResultRow) -> Result<Self> {
+        Ok(ReplicaHost {
+            server_id: row.parse(&["Server_Id"])?,
+            host: row.get_non_empty(&["Host"]),
+            port: row.parse(&["Port"])?,
+            source_id: row.parse(&["Source_Id", "Master_Id"])?,
+            replica_uuid: row.get_non_empty(&["Replica_UUID", "Slave_UUID"]),
+        })
+    }
+}
+
+/// replica 상태 (SHOW REPLICA STATUS / SHOW SLAVE STATUS)
+///
+/// 8.0.22 이후 MySQL 은 Source/Replica, 이전 버전과 MariaDB 는 Master/Slave 컬럼 이름을 사용합니다.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplicaStatus {
+    /// multi-source replication 채널 (MySQL Channel_Name, MariaDB Connection_name)
+    pub channel: Option<String>,
+    pub source_host: String,
+    pub source_port: u16,
+    pub io_running: bool,
+    pub sql_running: bool,
+    /// SQL 스레드가 실행한 source binlog 위치
+    pub exec_source_log_file: String,
+    pub exec_source_log_pos: u64,
+    /// 지연 시간 (초, 복제가 멈춰 있으면 None)
+    pub seconds_behind_source: Option<u64>,
+    /// 실행한 GTID (MySQL Executed_Gtid_Set, MariaDB Gtid_Slave_Pos)
+    pub executed_gtid_set: Option<String>,
+    pub last_error: Option<String>,
+}
+
+impl ReplicaStatus {
+    pub fn from_row(row: &ResultRow) -> Result<Self> {
+        Ok(ReplicaStatus {
+            channel: row.get_non_empty(&["Channel_Name", "Connection_name"]),
+            source_host: row.require(&["Source_Host", "Master_Host"])?.to_string(),
+            source_port: row.parse(&["Source_Port", "Master_Port"])?,
+            io_running: row.get(&["Replica_IO_Running", "Slave_IO_Running"]) == Some("Yes"),
+            sql_running: row.get(&["Replica_SQL_Running", "Slave_SQL_Running"]) == Some("Yes"),
+            exec_source_log_file: row
+                .require(&["Relay_Source_Log_File", "Relay_Master_Log_File"])?
+                .to_string(),
+            exec_source_log_pos: row.parse(&["Exec_Source_Log_Pos", "Exec_Master_Log_Pos"])?,
+            seconds_behind_source: row
+                .get(&["Seconds_Behind_Source", "Seconds_Behind_Master"])
+                .and_then(|v| v.parse().ok()),
+            executed_gtid_set: row
+                .get_non_empty(&["Executed_Gtid_Set", "Gtid_Slave_Pos"])
+                .map(|v| v.replace('\n', "")),
+            last_error: row.get_non_empty(&["Last_Error"]),
+        })
+    }
+}
+
 /// 테이블 컬럼 정보
 #[derive(Debug, Clone)]
 pub struct ColumnInfo {
@@ -228,6 +840,8 @@ pub struct ColumnInfo {
     pub column_type: String,
     pub nullable: bool,
     pub is_key: bool,
+    /// 기본 키 컬럼 여부 (`is_key` 는 UNIQUE / 일반 인덱스 컬럼도 포함)
+    pub is_primary_key: bool,
     pub extra: String,
 }
 
@@ -248,4 +862,324 @@ mod tests {
         assert_eq!(config.hostname, "127.0.0.1");
         assert_eq!(config.username, "root");
     }
+
+    /// 기록한 결과 행 (컬럼 이름, 값), "NULL" 은 NULL
+    fn recorded(row: &[(&str, &str)]) -> ResultRow {
+        ResultRow::new(
+            row.iter().map(|(c, _)| c.to_string()).collect(),
+            row.iter()
+                .map(|(_, v)| (*v != "NULL").then(|| v.to_string()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_binlog_status_matrix() {
+        let gtid = "3e11fa47-71ca-11e1-9e33-c80aa9429562:1-5";
+        let cases = [
+            // MySQL 5.5 SHOW MASTER STATUS (4 컬럼)
+            (
+                recorded(&[
+                    ("File", "mysql-bin.000003"),
+                    ("Position", "73"),
+                    ("Binlog_Do_DB", "test"),
+                    ("Binlog_Ignore_DB", "manual,mysql"),
+                ]),
+                "mysql-bin.000003",
+                73,
+                Some("test"),
+                "",
+            ),
+            // MySQL 5.7 SHOW MASTER STATUS
+            (
+                recorded(&[
+                    ("File", "mysql-bin.000012"),
+                    ("Position", "154"),
+                    ("Binlog_Do_DB", ""),
+                    ("Binlog_Ignore_DB", ""),
+                    ("Executed_Gtid_Set", gtid),
+                ]),
+                "mysql-bin.000012",
+                154,
+                None,
+                gtid,
+            ),
+            // MySQL 8.4 SHOW BINARY LOG STATUS (여러 UUID 는 줄바꿈 포함)
+            (
+                recorded(&[
+                    ("File", "binlog.000042"),
+                    ("Position", "1577"),
+                    ("Binlog_Do_DB", ""),
+                    ("Binlog_Ignore_DB", ""),
+                    (
+                        "Executed_Gtid_Set",
+                        "3e11fa47-71ca-11e1-9e33-c80aa9429562:1-5,\n\
+                         4e11fa47-71ca-11e1-9e33-c80aa9429562:1-2",
+                    ),
+                ]),
+                "binlog.000042",
+                1577,
+                None,
+                "3e11fa47-71ca-11e1-9e33-c80aa9429562:1-5,4e11fa47-71ca-11e1-9e33-c80aa9429562:1-2",
+            ),
+            // MariaDB 10.4 SHOW MASTER STATUS
+            (
+                recorded(&[
+                    ("File", "mariadb-bin.000003"),
+                    ("Position", "1024"),
+                    ("Binlog_Do_DB", ""),
+                    ("Binlog_Ignore_DB", ""),
+                ]),
+                "mariadb-bin.000003",
+                1024,
+                None,
+                "",
+            ),
+            // MariaDB 10.11 SHOW BINLOG STATUS
+            (
+                recorded(&[
+                    ("File", "mariadb-bin.000007"),
+                    ("Position", "342"),
+                    ("Binlog_Do_DB", ""),
+                    ("Binlog_Ignore_DB", ""),
+                ]),
+                "mariadb-bin.000007",
+                342,
+                None,
+                "",
+            ),
+        ];
+
+        for (row, file, position, do_db, gtid_set) in cases {
+            let status = BinlogStatus::from_row(&row).unwrap();
+            assert_eq!(status.file, file);
+            assert_eq!(status.position, position);
+            assert_eq!(status.binlog_do_db.as_deref(), do_db);
+            assert_eq!(status.executed_gtid_set.to_string(), gtid_set, "{}", file);
+        }
+
+        assert!(BinlogStatus::from_row(&recorded(&[("File", "binlog.000001")])).is_err());
+
+        // 잘못된 GTID 집합을 빈 집합으로 취급하지 않음
+        let malformed = recorded(&[
+            ("File", "binlog.000001"),
+            ("Position", "4"),
+            ("Executed_Gtid_Set", "3e11fa47-71ca-11e1-9e33-c80aa9429562:x-5"),
+        ]);
+        assert!(BinlogStatus::from_row(&malformed).is_err());
+    }
+
+    #[test]
+    fn test_binary_logs_and_replicas_matrix() {
+        // MySQL 5.7 (암호화 컬럼 없음), MySQL 8.0.14+
+        let mysql57 = recorded(&[("Log_name", "mysql-bin.000001"), ("File_size", "177")]);
+        let mysql80 = recorded(&[
+            ("Log_name", "binlog.000002"),
+            ("File_size", "3093"),
+            ("Encrypted", "Yes"),
+        ]);
+        assert_eq!(BinaryLogFile::from_row(&mysql57).unwrap().encrypted, None);
+        assert_eq!(
+            BinaryLogFile::from_row(&mysql80).unwrap(),
+            BinaryLogFile {
+                name: "binlog.000002".to_string(),
+                size: 3093,
+                encrypted: Some(true),
+            }
+        );
+
+        let uuid = "e1c2a3b4-71ca-11e1-9e33-c80aa9429562";
+        let cases = [
+            // MySQL 5.7 SHOW SLAVE HOSTS
+            recorded(&[
+                ("Server_id", "2"),
+                ("Host", ""),
+                ("Port", "3306"),
+                ("Master_id", "1"),
+                ("Slave_UUID", uuid),
+            ]),
+            // MySQL 8.4 SHOW REPLICAS
+            recorded(&[
+                ("Server_Id", "2"),
+                ("Host", ""),
+                ("Port", "3306"),
+                ("Source_Id", "1"),
+                ("Replica_UUID", uuid),
+            ]),
+        ];
+        for row in cases {
+            let replica = ReplicaHost::from_row(&row).unwrap();
+            assert_eq!(replica.server_id, 2);
+            assert_eq!(replica.host, None);
+            assert_eq!(replica.source_id, 1);
+            assert_eq!(replica.replica_uuid.as_deref(), Some(uuid));
+        }
+
+        // MariaDB SHOW REPLICA HOSTS (UUID 없음)
+        let mariadb = recorded(&[
+            ("Server_id", "3"),
+            ("Host", "replica-1"),
+            ("Port", "3306"),
+            ("Master_id", "1"),
+        ]);
+        let replica = ReplicaHost::from_row(&mariadb).unwrap();
+        assert_eq!(replica.host.as_deref(), Some("replica-1"));
+        assert_eq!(replica.replica_uuid, None);
+    }
+
+    #[test]
+    fn test_replica_status_matrix() {
+        // MySQL 5.7 SHOW SLAVE STATUS
+        let mysql57 = recorded(&[
+            ("Master_Host", "source-1"),
+            ("Master_Port", "3306"),
+            ("Relay_Master_Log_File", "mysql-bin.000012"),
+            ("Slave_IO_Running", "Yes"),
+            ("Slave_SQL_Running", "Yes"),
+            ("Last_Error", ""),
+            ("Exec_Master_Log_Pos", "154"),
+            ("Seconds_Behind_Master", "0"),
+            ("Executed_Gtid_Set", "3e11fa47-71ca-11e1-9e33-c80aa9429562:1-5"),
+            ("Channel_Name", ""),
+        ]);
+        // MySQL 8.4 SHOW REPLICA STATUS
+        let mysql84 = recorded(&[
+            ("Source_Host", "source-1"),
+            ("Source_Port", "3306"),
+            ("Relay_Source_Log_File", "mysql-bin.000012"),
+            ("Replica_IO_Running", "Yes"),
+            ("Replica_SQL_Running", "Yes"),
+            ("Last_Error", ""),
+            ("Exec_Source_Log_Pos", "154"),
+            ("Seconds_Behind_Source", "0"),
+            ("Executed_Gtid_Set", "3e11fa47-71ca-11e1-9e33-c80aa9429562:1-5"),
+            ("Channel_Name", ""),
+        ]);
+        let expected = ReplicaStatus {
+            channel: None,
+            source_host: "source-1".to_string(),
+            source_port: 3306,
+            io_running: true,
+            sql_running: true,
+            exec_source_log_file: "mysql-bin.000012".to_string(),
+            exec_source_log_pos: 154,
+            seconds_behind_source: Some(0),
+            executed_gtid_set: Some("3e11fa47-71ca-11e1-9e33-c80aa9429562:1-5".to_string()),
+            last_error: None,
+        };
+        assert_eq!(ReplicaStatus::from_row(&mysql57).unwrap(), expected);
+        assert_eq!(ReplicaStatus::from_row(&mysql84).unwrap(), expected);
+
+        // MariaDB 10.11 SHOW REPLICA STATUS (멈춘 replica)
+        let mariadb = recorded(&[
+            ("Connection_name", "east"),
+            ("Master_Host", "source-2"),
+            ("Master_Port", "3307"),
+            ("Relay_Master_Log_File", "mariadb-bin.000007"),
+            ("Slave_IO_Running", "No"),
+            ("Slave_SQL_Running", "No"),
+            ("Last_Error", "Error 'Duplicate entry' on query"),
+            ("Exec_Master_Log_Pos", "342"),
+            ("Seconds_Behind_Master", "NULL"),
+            ("Gtid_Slave_Pos", "0-1-100"),
+        ]);
+        let status = ReplicaStatus::from_row(&mariadb).unwrap();
+        assert_eq!(status.channel.as_deref(), Some("east"));
+        assert!(!status.io_running && !status.sql_running);
+        assert_eq!(status.seconds_behind_source, None);
+        assert_eq!(status.executed_gtid_set.as_deref(), Some("0-1-100"));
+        assert!(status.last_error.is_some());
+    }
+
+    #[test]
+    fn test_failover_candidates() {
+        let mut config = ConnectionConfig::new("primary", "repl");
+        config.socket = Some(PathBuf::from("/var/run/mysqld/mysqld.sock"));
+        config.failover_hosts = vec![
+            "replica-1:3307".parse().unwrap(),
+            "replica-2".parse().unwrap(),
+        ];
+        assert!("replica-3:port".parse::<HostAddress>().is_err());
+
+        let candidates = config.candidates();
+        assert_eq!(candidates.len(), 3);
+        assert_eq!(candidates[0].socket, config.socket);
+        assert_eq!(candidates[1].endpoint(), "replica-1:3307");
+        assert_eq!(candidates[2].endpoint(), "replica-2:3306");
+        assert_eq!(candidates[2].username, "repl");
+        assert!(candidates[1].failover_hosts.is_empty());
+    }
+
+    #[test]
+    fn test_connect_attributes() {
+        let mut config = ConnectionConfig::default();
+        config.connect_attrs.insert("program_name".to_string(), "orders-cdc".to_string());
+        config.connect_attrs.insert("pipeline".to_string(), "nightly".to_string());
+
+        let attrs = config.connect_attributes();
+        assert_eq!(attrs["_client_name"], "rust_mysql");
+        assert_eq!(attrs["_pid"], std::process::id().to_string());
+        assert_eq!(attrs["program_name"], "orders-cdc");
+        assert_eq!(attrs["pipeline"], "nightly");
+    }
+
+    #[test]
+    fn test_cell_value() {
+        use mysql_async::consts::{ColumnFlags, ColumnType};
+        use mysql_async::{Column, Value};
+
+        let text = |s: &str| Value::Bytes(s.as_bytes().to_vec());
+        let column = |column_type| Column::new(column_type).with_character_set(255);
+
+        let int = column(ColumnType::MYSQL_TYPE_LONG);
+        assert!(matches!(cell_value(text("-5"), &int), CellValue::Int64(-5)));
+        let unsigned = int.clone().with_flags(ColumnFlags::UNSIGNED_FLAG);
+        assert!(matches!(cell_value(text("18446744073709551615"), &unsigned),
+            CellValue::UInt64(u64::MAX)));
+        assert!(matches!(cell_value(Value::NULL, &int), CellValue::Null));
+
+        let decimal = column(ColumnType::MYSQL_TYPE_NEWDECIMAL);
+        assert!(matches!(cell_value(text("1.50"), &decimal),
+            CellValue::Decimal(ref d) if d == "1.50"));
+
+        let datetime = column(ColumnType::MYSQL_TYPE_DATETIME);
+        let value = cell_value(text("2024-03-01 12:30:00.250000"), &datetime);
+        let CellValue::DateTime(dt) = value else {
+            panic!("expected datetime");
+        };
+        assert_eq!(dt.timestamp_subsec_millis(), 250);
+        // 0000-00-00 같은 값은 문자열 그대로
+        let zero = cell_value(text("0000-00-00 00:00:00"), &datetime);
+        assert!(matches!(zero, CellValue::String(_)));
+
+        let json = column(ColumnType::MYSQL_TYPE_JSON);
+        assert!(matches!(cell_value(text(r#"{"a": 1}"#), &json),
+            CellValue::Json(ref v) if v["a"] == 1));
+
+        let blob = Column::new(ColumnType::MYSQL_TYPE_BLOB).with_character_set(63);
+        assert!(matches!(cell_value(text("\x00\x01"), &blob),
+            CellValue::Bytes(ref b) if b.len() == 2));
+        let varchar = column(ColumnType::MYSQL_TYPE_VAR_STRING);
+        assert!(matches!(cell_value(text("hello"), &varchar),
+            CellValue::String(ref v) if v == "hello"));
+
+        // 바이너리 프로토콜 값은 텍스트 프로토콜과 같은 CellValue
+        assert!(matches!(cell_value(Value::Int(-5), &int), CellValue::Int64(-5)));
+        assert!(matches!(cell_value(Value::UInt(u64::MAX), &unsigned),
+            CellValue::UInt64(u64::MAX)));
+        let float = column(ColumnType::MYSQL_TYPE_FLOAT);
+        assert!(matches!(cell_value(Value::Float(0.1), &float), CellValue::Double(v) if v == 0.1));
+        let datetime = datetime.with_decimals(3);
+        let value = cell_value(Value::Date(2024, 3, 1, 12, 30, 0, 250_000), &datetime);
+        assert!(matches!(value, CellValue::DateTime(dt) if dt.timestamp_subsec_millis() == 250));
+        let date = column(ColumnType::MYSQL_TYPE_DATE);
+        assert!(matches!(cell_value(Value::Date(2024, 3, 1, 0, 0, 0, 0), &date),
+            CellValue::Date(ref v) if v == "2024-03-01"));
+        let time = column(ColumnType::MYSQL_TYPE_TIME);
+        assert!(matches!(cell_value(Value::Time(true, 1, 2, 0, 5, 0), &time),
+            CellValue::Time(ref v) if v == "-26:00:05"));
+
+        assert_eq!(param_value(&CellValue::UInt64(u64::MAX)), Value::UInt(u64::MAX));
+        assert_eq!(param_value(&CellValue::String("it's".to_string())), Value::from("it's"));
+    }
 }
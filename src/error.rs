@@ -8,6 +8,10 @@ pub enum CdcError {
     #[error("MySQL 연결 에러: {0}")]
     ConnectionError(String),
 
+    /// 다시 연결해도 해결되지 않는 설정 / 인증 에러 (인증서 경로, 공개키, 평문 비밀번호 거부 등)
+    #[error("연결 설정 에러: {0}")]
+    ConfigError(String),
+
     #[error("Binlog 파싱 에러: {0}")]
     BinlogParseError(String),
 
@@ -26,6 +30,25 @@ pub enum CdcError {
     #[error("프로토콜 에러: {0}")]
     ProtocolError(String),
 
+    #[error("Binlog 을 읽을 수 없음 (purged 또는 존재하지 않는 파일): {0}")]
+    BinlogPurged(String),
+
+    #[error("접근 거부: {0}")]
+    AccessDenied(String),
+
+    #[error("server_id 충돌: {0}")]
+    ServerIdConflict(String),
+
+    #[error("서버 연결 수 초과: {0}")]
+    TooManyConnections(String),
+
+    #[error("MySQL 서버 에러 {code} ({sql_state}): {message}")]
+    ServerError {
+        code: u16,
+        sql_state: String,
+        message: String,
+    },
+
     #[error("직렬화 에러: {0}")]
     SerializationError(#[from] serde_json::Error),
 
@@ -45,4 +68,112 @@ impl From<io::Error> for CdcError {
     }
 }
 
+/// MySQL 서버 에러 코드
+pub mod error_codes {
+    /// ER_CON_COUNT_ERROR
+    pub const TOO_MANY_CONNECTIONS: u16 = 1040;
+    /// ER_DBACCESS_DENIED_ERROR
+    pub const DBACCESS_DENIED: u16 = 1044;
+    /// ER_ACCESS_DENIED_ERROR
+    pub const ACCESS_DENIED: u16 = 1045;
+    /// ER_TOO_MANY_USER_CONNECTIONS
+    pub const TOO_MANY_USER_CONNECTIONS: u16 = 1203;
+    /// ER_SPECIFIC_ACCESS_DENIED_ERROR (예: REPLICATION SLAVE 권한 없음)
+    pub const SPECIFIC_ACCESS_DENIED: u16 = 1227;
+    /// ER_MASTER_FATAL_ERROR_READING_BINLOG
+    pub const FATAL_ERROR_READING_BINLOG: u16 = 1236;
+    /// ER_ACCESS_DENIED_NO_PASSWORD_ERROR
+    pub const ACCESS_DENIED_NO_PASSWORD: u16 = 1698;
+}
+
+impl CdcError {
+    /// 서버 에러 코드를 용도별 에러로 변환
+    ///
+    /// 1236 은 binlog purge 와 server_id 충돌 모두에 사용되므로 메시지로 구분합니다.
+    pub fn from_server_error(code: u16, sql_state: &str, message: &str) -> Self {
+        let description = format!("ERROR {} ({}): {}", code, sql_state, message);
+
+        match code {
+            error_codes::FATAL_ERROR_READING_BINLOG if is_server_id_conflict(message) => {
+                CdcError::ServerIdConflict(description)
+            }
+            error_codes::FATAL_ERROR_READING_BINLOG => CdcError::BinlogPurged(description),
+            error_codes::ACCESS_DENIED
+            | error_codes::DBACCESS_DENIED
+            | error_codes::SPECIFIC_ACCESS_DENIED
+            | error_codes::ACCESS_DENIED_NO_PASSWORD => CdcError::AccessDenied(description),
+            error_codes::TOO_MANY_CONNECTIONS | error_codes::TOO_MANY_USER_CONNECTIONS => {
+                CdcError::TooManyConnections(description)
+            }
+            _ => CdcError::ServerError {
+                code,
+                sql_state: sql_state.to_string(),
+                message: message.to_string(),
+            },
+        }
+    }
+
+    /// 잠시 후 재연결하면 해결될 수 있는 에러인지 여부
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            CdcError::ConnectionError(_)
+                | CdcError::IoError(_)
+                | CdcError::Timeout
+                | CdcError::TooManyConnections(_)
+        )
+    }
+}
+
+/// "A slave with the same server_uuid/server_id as this slave has connected to the master"
+fn is_server_id_conflict(message: &str) -> bool {
+    let message = message.to_ascii_lowercase();
+    message.contains("same server_uuid/server_id") || message.contains("same server_id")
+}
+
 pub type Result<T> = std::result::Result<T, CdcError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_server_error() {
+        assert!(matches!(
+            CdcError::from_server_error(
+                1236,
+                "HY000",
+                "Could not find first log file name in binary log index file"
+            ),
+            CdcError::BinlogPurged(_)
+        ));
+        assert!(matches!(
+            CdcError::from_server_error(
+                1236,
+                "HY000",
+                "A replica with the same server_uuid/server_id as this replica has connected to the source"
+            ),
+            CdcError::ServerIdConflict(_)
+        ));
+        assert!(matches!(
+            CdcError::from_server_error(1045, "28000", "Access denied for user 'root'@'localhost'"),
+            CdcError::AccessDenied(_)
+        ));
+        assert!(matches!(
+            CdcError::from_server_error(1040, "08004", "Too many connections"),
+            CdcError::TooManyConnections(_)
+        ));
+        assert!(matches!(
+            CdcError::from_server_error(1064, "42000", "You have an error in your SQL syntax"),
+            CdcError::ServerError { code: 1064, .. }
+        ));
+    }
+
+    #[test]
+    fn test_is_retryable() {
+        assert!(CdcError::TooManyConnections(String::new()).is_retryable());
+        assert!(!CdcError::AccessDenied(String::new()).is_retryable());
+        assert!(!CdcError::BinlogPurged(String::new()).is_retryable());
+        assert!(!CdcError::ConfigError(String::new()).is_retryable());
+    }
+}
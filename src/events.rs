@@ -1,5 +1,7 @@
 //! MySQL Binlog 이벤트 타입 및 데이터 구조 정의
 
+use crate::gtid::MariadbGtid;
+use bytes::Bytes;
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
 use std::collections::HashMap;
@@ -10,10 +12,16 @@ use std::collections::HashMap;
 pub enum EventType {
     /// 알 수 없는 이벤트
     Unknown = 0,
+    /// 정지 이벤트 (서버 종료로 binlog 파일이 끝남)
+    StopEvent = 3,
     /// 로테이션 이벤트 (새 binlog 파일)
     RotateEvent = 4,
+    /// 포맷 설명 이벤트 (binlog 파일의 첫 이벤트)
+    FormatDescriptionEvent = 15,
     /// 쿼리 이벤트 (DDL, DML)
     QueryEvent = 2,
+    /// XID 이벤트 (트랜잭션 커밋)
+    XidEvent = 16,
     /// 테이블 맵 이벤트 (스키마 정보)
     TableMapEvent = 19,
     /// WRITE_ROWS 이벤트 (INSERT)
@@ -22,29 +30,86 @@ pub enum EventType {
     UpdateRowsEvent = 31,
     /// DELETE_ROWS 이벤트 (DELETE)
     DeleteRowsEvent = 32,
+    /// Heartbeat 이벤트 (파일에 기록되지 않음)
+    HeartbeatLogEvent = 27,
     /// GTID 이벤트 (Global Transaction ID)
     GtidEvent = 33,
     /// 익명 GTID 이벤트
     AnonymousGtidEvent = 34,
+    /// 이전 파일까지의 GTID 집합
+    PreviousGtidsEvent = 35,
     /// Rows Query 이벤트 (원본 쿼리)
     RowsQueryEvent = 36,
     /// 트랜잭션 페이로드 이벤트
     TransactionPayloadEvent = 38,
+    /// Heartbeat 이벤트 v2 (8.0.26+)
+    HeartbeatLogEventV2 = 41,
+    /// WRITE_ROWS v1 이벤트 (MariaDB, MySQL 5.5 이하)
+    WriteRowsEventV1 = 23,
+    /// UPDATE_ROWS v1 이벤트
+    UpdateRowsEventV1 = 24,
+    /// DELETE_ROWS v1 이벤트
+    DeleteRowsEventV1 = 25,
+    /// MariaDB ANNOTATE_ROWS 이벤트 (원본 쿼리)
+    AnnotateRowsEvent = 160,
+    /// MariaDB BINLOG_CHECKPOINT 이벤트
+    BinlogCheckpointEvent = 161,
+    /// MariaDB GTID 이벤트 (트랜잭션 시작)
+    MariadbGtidEvent = 162,
+    /// MariaDB GTID_LIST 이벤트 (파일 시작 시점의 GTID 위치)
+    MariadbGtidListEvent = 163,
+    /// MariaDB START_ENCRYPTION 이벤트
+    StartEncryptionEvent = 164,
+    /// MariaDB 압축 QUERY 이벤트
+    QueryCompressedEvent = 165,
+    /// MariaDB 압축 WRITE_ROWS v1 이벤트
+    WriteRowsCompressedEventV1 = 166,
+    /// MariaDB 압축 UPDATE_ROWS v1 이벤트
+    UpdateRowsCompressedEventV1 = 167,
+    /// MariaDB 압축 DELETE_ROWS v1 이벤트
+    DeleteRowsCompressedEventV1 = 168,
+    /// MariaDB 압축 WRITE_ROWS 이벤트
+    WriteRowsCompressedEvent = 169,
+    /// MariaDB 압축 UPDATE_ROWS 이벤트
+    UpdateRowsCompressedEvent = 170,
+    /// MariaDB 압축 DELETE_ROWS 이벤트
+    DeleteRowsCompressedEvent = 171,
 }
 
 impl EventType {
     pub fn from_u8(val: u8) -> Self {
         match val {
+            3 => EventType::StopEvent,
             4 => EventType::RotateEvent,
+            15 => EventType::FormatDescriptionEvent,
             2 => EventType::QueryEvent,
+            16 => EventType::XidEvent,
             19 => EventType::TableMapEvent,
             30 => EventType::WriteRowsEvent,
             31 => EventType::UpdateRowsEvent,
             32 => EventType::DeleteRowsEvent,
             33 => EventType::GtidEvent,
             34 => EventType::AnonymousGtidEvent,
+            35 => EventType::PreviousGtidsEvent,
+            27 => EventType::HeartbeatLogEvent,
+            41 => EventType::HeartbeatLogEventV2,
             36 => EventType::RowsQueryEvent,
             38 => EventType::TransactionPayloadEvent,
+            23 => EventType::WriteRowsEventV1,
+            24 => EventType::UpdateRowsEventV1,
+            25 => EventType::DeleteRowsEventV1,
+            160 => EventType::AnnotateRowsEvent,
+            161 => EventType::BinlogCheckpointEvent,
+            162 => EventType::MariadbGtidEvent,
+            163 => EventType::MariadbGtidListEvent,
+            164 => EventType::StartEncryptionEvent,
+            165 => EventType::QueryCompressedEvent,
+            166 => EventType::WriteRowsCompressedEventV1,
+            167 => EventType::UpdateRowsCompressedEventV1,
+            168 => EventType::DeleteRowsCompressedEventV1,
+            169 => EventType::WriteRowsCompressedEvent,
+            170 => EventType::UpdateRowsCompressedEvent,
+            171 => EventType::DeleteRowsCompressedEvent,
             _ => EventType::Unknown,
         }
     }
@@ -82,6 +147,9 @@ pub struct TableMapData {
     pub column_meta: Vec<Vec<u8>>,
     /// nullable 비트맵
     pub nullable_bitmap: Vec<u8>,
+    /// 컬럼별 UNSIGNED 여부 (optional metadata 의 SIGNEDNESS, 없으면 비어 있음)
+    #[serde(default)]
+    pub unsigned_columns: Vec<bool>,
 }
 
 /// WRITE_ROWS 이벤트 데이터
@@ -95,8 +163,11 @@ pub struct WriteRowsData {
     pub column_count: u64,
     /// 사용된 컬럼 비트맵
     pub columns_present: Vec<u8>,
-    /// 행 데이터들
+    /// 행 데이터들 (`BinlogParser::decode_rows` 가 TABLE_MAP 으로 채움)
     pub rows: Vec<Vec<CellValue>>,
+    /// 행 이미지 원본 (이벤트 버퍼를 복사 없이 공유)
+    #[serde(skip)]
+    pub rows_data: Bytes,
 }
 
 /// UPDATE_ROWS 이벤트 데이터
@@ -112,8 +183,11 @@ pub struct UpdateRowsData {
     pub columns_present: Vec<u8>,
     /// 변경된 컬럼 비트맵
     pub columns_changed: Vec<u8>,
-    /// 변경 전후 데이터 쌍들
+    /// 변경 전후 데이터 쌍들 (`BinlogParser::decode_rows` 가 TABLE_MAP 으로 채움)
     pub rows: Vec<(Vec<CellValue>, Vec<CellValue>)>,
+    /// 행 이미지 원본 (이벤트 버퍼를 복사 없이 공유)
+    #[serde(skip)]
+    pub rows_data: Bytes,
 }
 
 /// DELETE_ROWS 이벤트 데이터
@@ -127,12 +201,15 @@ pub struct DeleteRowsData {
     pub column_count: u64,
     /// 사용된 컬럼 비트맵
     pub columns_present: Vec<u8>,
-    /// 행 데이터들
+    /// 행 데이터들 (`BinlogParser::decode_rows` 가 TABLE_MAP 으로 채움)
     pub rows: Vec<Vec<CellValue>>,
+    /// 행 이미지 원본 (이벤트 버퍼를 복사 없이 공유)
+    #[serde(skip)]
+    pub rows_data: Bytes,
 }
 
 /// 셀 값 (다양한 MySQL 타입 지원)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum CellValue {
     Null,
     Int8(i8),
@@ -146,7 +223,8 @@ pub enum CellValue {
     Float(f32),
     Double(f64),
     String(String),
-    Bytes(Vec<u8>),
+    /// BLOB / TEXT / VARCHAR / JSON 등의 값 (패킷 버퍼를 복사 없이 공유)
+    Bytes(Bytes),
     DateTime(DateTime<Utc>),
     Date(String),
     Time(String),
@@ -177,6 +255,62 @@ pub struct GtidEventData {
     pub committed: bool,
 }
 
+/// MariaDB GTID 이벤트 데이터
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MariadbGtidEventData {
+    /// GTID (server_id 는 이벤트 헤더의 값)
+    pub gtid: MariadbGtid,
+    /// BEGIN/COMMIT 없이 단일 이벤트로 끝나는 트랜잭션 (DDL 등)
+    pub standalone: bool,
+    /// group commit ID (있는 경우)
+    pub commit_id: Option<u64>,
+}
+
+/// MariaDB START_ENCRYPTION 이벤트 데이터 (이후 이벤트는 암호화되어 기록됨)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StartEncryptionData {
+    /// 암호화 방식 (1 = AES)
+    pub scheme: u8,
+    /// 키 버전
+    pub key_version: u32,
+    /// nonce
+    pub nonce: [u8; 12],
+}
+
+/// 이벤트 체크섬 알고리즘 (binlog_checksum)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChecksumAlgorithm {
+    #[default]
+    None,
+    /// 이벤트 끝에 CRC32 (4 bytes)
+    Crc32,
+}
+
+impl ChecksumAlgorithm {
+    /// 이벤트 끝에 붙는 체크섬 길이
+    pub fn checksum_len(&self) -> usize {
+        match self {
+            ChecksumAlgorithm::None => 0,
+            ChecksumAlgorithm::Crc32 => 4,
+        }
+    }
+}
+
+/// 포맷 설명 이벤트 데이터
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FormatDescriptionData {
+    /// binlog 형식 버전 (MySQL 5.0 이상은 4)
+    pub binlog_version: u16,
+    /// binlog 를 기록한 서버 버전
+    pub server_version: String,
+    /// 파일 생성 시각
+    pub create_timestamp: u32,
+    /// 이벤트 헤더 길이 (19)
+    pub header_length: u8,
+    /// 이후 이벤트의 체크섬 알고리즘
+    pub checksum: ChecksumAlgorithm,
+}
+
 /// 쿼리 이벤트 데이터
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QueryEventData {
@@ -199,9 +333,25 @@ pub struct RotateEventData {
     pub position: u64,
 }
 
+/// 장애 조치 알림 (클라이언트가 만드는 이벤트, 서버가 보내지 않음)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FailoverEventData {
+    /// 이전 서버 (host:port)
+    pub previous_host: String,
+    /// 이전 서버의 server_id
+    pub previous_server_id: u32,
+    /// 새 서버 (host:port)
+    pub host: String,
+    /// 새 서버의 server_id
+    pub server_id: u32,
+    /// 새 서버에서 재개한 GTID 집합 (이 집합 이후의 트랜잭션부터 전달)
+    pub gtid_set: String,
+}
+
 /// 모든 Binlog 이벤트를 포함하는 열거형
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum BinlogEventData {
+    FormatDescription(FormatDescriptionData),
     TableMap(TableMapData),
     WriteRows(WriteRowsData),
     UpdateRows(UpdateRowsData),
@@ -209,8 +359,18 @@ pub enum BinlogEventData {
     Query(QueryEventData),
     Rotate(RotateEventData),
     Gtid(GtidEventData),
+    Xid(u64),
     RowsQuery(String),
-    Unknown(Vec<u8>),
+    MariadbGtid(MariadbGtidEventData),
+    /// MariaDB GTID_LIST (domain/server 별 마지막 GTID)
+    MariadbGtidList(Vec<MariadbGtid>),
+    /// MariaDB BINLOG_CHECKPOINT (crash recovery 에 필요한 가장 오래된 binlog 파일명)
+    BinlogCheckpoint(String),
+    StartEncryption(StartEncryptionData),
+    /// 다른 서버로 장애 조치 (header.server_id 는 새 서버)
+    Failover(FailoverEventData),
+    /// 파싱하지 않는 이벤트의 body (이벤트 버퍼를 복사 없이 공유)
+    Unknown(Bytes),
 }
 
 /// 완성된 Binlog 이벤트
@@ -227,7 +387,7 @@ pub struct BinlogEvent {
 pub struct ChangeEvent {
     /// GTID (있는 경우)
     pub gtid: Option<String>,
-    /// 연산 타입 (INSERT, UPDATE, DELETE, DDL)
+    /// 연산 타입 (INSERT, UPDATE, DELETE, DDL, READ)
     pub op: OperationType,
     /// 타임스탬프
     pub timestamp: DateTime<Utc>,
@@ -250,6 +410,8 @@ pub enum OperationType {
     Update,
     Delete,
     Ddl,
+    /// 스냅샷으로 읽은 기존 행
+    Read,
 }
 
 impl OperationType {
@@ -259,6 +421,7 @@ impl OperationType {
             OperationType::Update => "UPDATE",
             OperationType::Delete => "DELETE",
             OperationType::Ddl => "DDL",
+            OperationType::Read => "READ",
         }
     }
 }
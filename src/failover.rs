@@ -0,0 +1,169 @@
+//! 여러 후보 서버 사이의 장애 조치 (GTID 기반)
+//!
+//! 연결이 끊기면 후보 목록에서 다음 서버를 골라 마지막으로 처리한 GTID 집합부터
+//! COM_BINLOG_DUMP_GTID 로 재개합니다. 후보 서버가 처리한 트랜잭션을 모두 가지고 있지 않거나
+//! 아직 받지 않은 트랜잭션을 이미 purge 했으면 트랜잭션이 빠지므로 전환하지 않습니다.
+
+use crate::connection::{ConnectionConfig, MySqlConnection};
+use crate::error::{CdcError, Result};
+use crate::gtid::GtidSet;
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// 장애 조치 재시도 정책
+///
+/// 연결이 실패할 때마다 `initial_backoff` 부터 두 배씩 (`max_backoff` 까지) 기다린 뒤 다음 후보를
+/// 시도하고, 진행 없이 후보 목록을 `max_rounds` 번 돌면 포기합니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FailoverPolicy {
+    pub max_rounds: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for FailoverPolicy {
+    fn default() -> Self {
+        FailoverPolicy {
+            max_rounds: 10,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(30),
+        }
+    }
+}
+
+impl FailoverPolicy {
+    /// `failures` 번째 연속 실패 후 기다릴 시간
+    pub fn backoff(&self, failures: u32) -> Duration {
+        let factor = 2u32.saturating_pow(failures.saturating_sub(1));
+        self.initial_backoff.saturating_mul(factor).min(self.max_backoff)
+    }
+}
+
+/// 전환할 수 있는 후보 서버
+#[derive(Debug, Clone)]
+pub struct SourceHost {
+    /// 후보 목록에서의 위치
+    pub index: usize,
+    pub config: ConnectionConfig,
+    pub server_id: u32,
+}
+
+/// 후보 서버의 GTID 상태로 `processed` 부터 빠짐없이 재개할 수 있는지 확인
+pub fn check_candidate(executed: &GtidSet, purged: &GtidSet, processed: &GtidSet) -> Result<()> {
+    // 처리한 트랜잭션을 모두 가지고 있지 않으면 이전 서버보다 뒤처진 replica
+    let missing = processed.subtract(executed);
+    if !missing.is_empty() {
+        return Err(CdcError::GtidError(format!(
+            "Candidate is missing processed transactions: {}",
+            missing
+        )));
+    }
+
+    // 아직 받지 않은 트랜잭션이 purge 되었으면 건너뛰게 됨
+    let skipped = purged.subtract(processed);
+    if !skipped.is_empty() {
+        return Err(CdcError::GtidError(format!(
+            "Candidate has purged unprocessed transactions: {}",
+            skipped
+        )));
+    }
+
+    Ok(())
+}
+
+/// `start` 위치부터 후보를 차례로 시도하여 재개할 수 있는 첫 번째 서버 선택
+///
+/// 목록 끝에 도달하면 처음부터 다시 시도하며, 모든 후보가 실패하면 마지막 에러를 반환합니다.
+/// 설정 에러 (`CdcError::ConfigError`) 는 모든 후보에 똑같이 적용되므로 바로 반환합니다.
+pub async fn select_source(
+    candidates: &[ConnectionConfig],
+    start: usize,
+    processed: &GtidSet,
+) -> Result<SourceHost> {
+    let mut last_error = None;
+
+    for offset in 0..candidates.len() {
+        let index = (start + offset) % candidates.len();
+        let config = &candidates[index];
+
+        match probe(config, processed).await {
+            Ok(server_id) => {
+                info!("Selected binlog source {} (server_id={})", config.endpoint(), server_id);
+                return Ok(SourceHost {
+                    index,
+                    config: config.clone(),
+                    server_id,
+                });
+            }
+            Err(e @ CdcError::ConfigError(_)) => return Err(e),
+            Err(e) => {
+                warn!("Skipping binlog source {}: {}", config.endpoint(), e);
+                last_error = Some(e);
+            }
+        }
+    }
+
+    Err(CdcError::ConnectionError(format!(
+        "No binlog source can resume from {}: {}",
+        processed,
+        last_error.map_or_else(|| "no candidates".to_string(), |e| e.to_string())
+    )))
+}
+
+/// 후보 서버에 연결하여 GTID 상태 확인 후 server_id 반환
+async fn probe(config: &ConnectionConfig, processed: &GtidSet) -> Result<u32> {
+    let mut conn = MySqlConnection::connect(config.clone()).await?;
+
+    if !conn.is_gtid_mode_enabled().await? {
+        return Err(CdcError::GtidError("GTID mode is not enabled".to_string()));
+    }
+
+    let executed = conn.get_executed_gtid_set().await?;
+    let purged = conn.get_purged_gtid_set().await?;
+    check_candidate(&executed, &purged, processed)?;
+
+    let server_id = conn.get_server_id().await?;
+    conn.close().await?;
+
+    Ok(server_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UUID: &str = "3e11fa47-71ca-11e1-9e33-c80aa9429562";
+
+    fn gtid_set(ranges: &str) -> GtidSet {
+        if ranges.is_empty() {
+            return GtidSet::new();
+        }
+        GtidSet::parse(&format!("{}:{}", UUID, ranges)).unwrap()
+    }
+
+    #[test]
+    fn test_check_candidate() {
+        let processed = gtid_set("1-100");
+
+        // 앞서 있거나 같은 replica
+        assert!(check_candidate(&gtid_set("1-120"), &gtid_set("1-50"), &processed).is_ok());
+        assert!(check_candidate(&gtid_set("1-100"), &gtid_set(""), &processed).is_ok());
+
+        // 뒤처진 replica
+        let behind = check_candidate(&gtid_set("1-90"), &gtid_set(""), &processed);
+        assert!(matches!(behind, Err(CdcError::GtidError(message)) if message.contains("91-100")));
+
+        // 받지 않은 트랜잭션 101-110 이 purge 됨
+        let purged = check_candidate(&gtid_set("1-120"), &gtid_set("1-110"), &processed);
+        assert!(matches!(purged, Err(CdcError::GtidError(message)) if message.contains("101-110")));
+    }
+
+    #[test]
+    fn test_failover_backoff() {
+        let policy = FailoverPolicy::default();
+        assert_eq!(policy.backoff(1), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(800));
+        assert_eq!(policy.backoff(10), Duration::from_secs(30));
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_secs(30));
+    }
+}
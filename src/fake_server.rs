@@ -0,0 +1,2107 @@
+//! 통합 테스트용 가짜 MySQL 소스 서버
+//!
+//! docker MySQL 없이 `BinlogClient` 와 `PacketChannel` 을 시험할 수 있도록 로컬 TCP 포트에서
+//! 복제에 필요한 만큼의 프로토콜을 구현합니다.
+//!
+//! - greeting, mysql_native_password / caching_sha2_password (fast / full auth) 인증
+//! - COM_QUERY: `SET` 세션 변수 (체크섬, heartbeat 등), `SELECT @변수`, `SHOW VARIABLES`,
+//!   `SHOW MASTER STATUS`, `SHOW BINARY LOGS`, 미리 지정한 결과셋,
+//!   잠금 / 트랜잭션 문장 (`FLUSH TABLES WITH READ LOCK`, `START TRANSACTION` 등, 항상 OK)
+//! - COM_STMT_PREPARE / COM_STMT_EXECUTE / COM_STMT_CLOSE: `?` 자리에 바인딩한 값 (정수는 그대로,
+//!   문자열과 시각은 작은따옴표로 감싼 텍스트) 을 넣은 문장을 COM_QUERY 처럼 처리 (결과는 바이너리 행)
+//! - COM_REGISTER_SLAVE, COM_PING, COM_QUIT
+//! - COM_BINLOG_DUMP / COM_BINLOG_DUMP_GTID: binlog 파일 (fixture 파일 또는
+//!   `BinlogFileBuilder` 로 만든 바이트) 의 이벤트 전송
+//!
+//! `Fault` 로 연결 끊김, ERR 패킷 (예: 1236), 잘린 패킷, 느린 전송을 주입합니다.
+//! 다른 crate 의 테스트에서는 `test-support` feature 로 사용할 수 있습니다.
+
+use crate::auth::{self, capabilities, AuthPlugin};
+use crate::binlog_client::ends_transaction;
+use crate::binlog_file::{BinlogFileReader, FIRST_EVENT_POSITION};
+use crate::connection::ConnectionConfig;
+use crate::error::{error_codes, CdcError, Result};
+use crate::events::{BinlogEvent, BinlogEventData, ChecksumAlgorithm, EventType};
+use crate::gtid::GtidSet;
+use crate::offset::BinlogPosition;
+use crate::protocol::{self, MAX_PAYLOAD_LENGTH};
+use crate::tls::SslMode;
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use parking_lot::Mutex;
+use rand::Rng;
+use regex::Regex;
+use std::collections::{BTreeMap, HashMap};
+use std::io::{Cursor, Read, Write};
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::{JoinHandle, JoinSet};
+use tracing::debug;
+
+const COM_QUIT: u8 = 0x01;
+const COM_QUERY: u8 = 0x03;
+const COM_PING: u8 = 0x0e;
+const COM_BINLOG_DUMP: u8 = 0x12;
+const COM_REGISTER_SLAVE: u8 = 0x15;
+const COM_STMT_PREPARE: u8 = 0x16;
+const COM_STMT_EXECUTE: u8 = 0x17;
+const COM_STMT_CLOSE: u8 = 0x19;
+const COM_BINLOG_DUMP_GTID: u8 = 0x1e;
+
+/// COM_BINLOG_DUMP flag: binlog 끝에서 EOF 전송
+const BINLOG_DUMP_NON_BLOCK: u16 = 0x01;
+
+/// COM_BINLOG_DUMP_GTID flag: GTID 집합 데이터 포함
+const BINLOG_THROUGH_GTID: u16 = 0x04;
+
+/// 서버가 만든 이벤트 (LOG_EVENT_ARTIFICIAL_F)
+const LOG_EVENT_ARTIFICIAL_F: u16 = 0x20;
+
+/// caching_sha2_password 전체 인증에서 클라이언트의 공개키 요청
+const CACHING_SHA2_REQUEST_PUBLIC_KEY: u8 = 0x02;
+
+/// SERVER_STATUS_AUTOCOMMIT
+const SERVER_STATUS: u16 = 0x0002;
+
+/// utf8mb4_0900_ai_ci
+const SERVER_COLLATION: u8 = 255;
+
+/// ER_UNKNOWN_COM_ERROR
+const UNKNOWN_COMMAND: u16 = 1047;
+
+/// ER_PARSE_ERROR
+const PARSE_ERROR: u16 = 1064;
+
+/// ER_UNKNOWN_STMT_HANDLER
+const UNKNOWN_STATEMENT: u16 = 1243;
+
+/// 아무 일도 하지 않고 OK 를 돌려주는 문장 (대문자 접두사)
+const NO_OP_STATEMENTS: &[&str] = &[
+    "FLUSH ",
+    "LOCK ",
+    "UNLOCK ",
+    "START TRANSACTION",
+    "BEGIN",
+    "COMMIT",
+    "ROLLBACK",
+];
+
+/// 이벤트 헤더의 event_length / next_pos 위치
+const EVENT_LENGTH_OFFSET: usize = 9;
+const NEXT_POSITION_OFFSET: usize = 13;
+
+const SERVER_CAPABILITIES: u32 = capabilities::LONG_PASSWORD
+    | capabilities::FOUND_ROWS
+    | capabilities::LONG_FLAG
+    | capabilities::CONNECT_WITH_DB
+    | capabilities::PROTOCOL_41
+    | capabilities::TRANSACTIONS
+    | capabilities::SECURE_CONNECTION
+    | capabilities::MULTI_STATEMENTS
+    | capabilities::MULTI_RESULTS
+    | capabilities::PLUGIN_AUTH
+    | capabilities::CONNECT_ATTRS
+    | capabilities::PLUGIN_AUTH_LENENC_CLIENT_DATA;
+
+/// 연결에 주입할 장애
+///
+/// 이벤트 수는 덤프 응답으로 보낸 이벤트 패킷 수 (가상 ROTATE / FORMAT_DESCRIPTION 포함) 입니다.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fault {
+    /// 이벤트 `after_events` 개를 보낸 뒤 연결 끊기
+    Disconnect { after_events: usize },
+    /// 이벤트 `after_events` 개를 보낸 뒤 ERR 패킷을 보내고 연결 끊기
+    Error {
+        after_events: usize,
+        code: u16,
+        message: String,
+    },
+    /// `event` 번째 (0 부터) 이벤트 패킷을 절반만 보내고 연결 끊기
+    Truncate { event: usize },
+    /// 모든 패킷을 `chunk_size` 바이트씩 `delay` 간격으로 나누어 전송
+    Slow { chunk_size: usize, delay: Duration },
+}
+
+impl Fault {
+    /// 덤프 요청에 바로 ERR 1236 응답 (purge 된 binlog)
+    pub fn binlog_purged() -> Self {
+        Fault::Error {
+            after_events: 0,
+            code: error_codes::FATAL_ERROR_READING_BINLOG,
+            message: "Could not find first log file name in binary log index file".to_string(),
+        }
+    }
+}
+
+/// 클라이언트가 보낸 덤프 요청
+#[derive(Debug, Clone)]
+pub struct DumpRequest {
+    pub server_id: u32,
+    /// GTID 덤프는 빈 문자열
+    pub filename: String,
+    pub position: u64,
+    /// COM_BINLOG_DUMP_GTID 로 보낸 실행 완료 GTID 집합
+    pub gtid_set: Option<GtidSet>,
+    pub non_blocking: bool,
+}
+
+/// 컬럼 이름과 텍스트 행
+type ResultSet = (Vec<String>, Vec<Vec<Option<String>>>);
+
+/// 미리 지정한 쿼리 응답
+#[derive(Debug, Clone)]
+enum ScriptedQuery {
+    Rows {
+        columns: Vec<String>,
+        rows: Vec<Vec<Option<String>>>,
+    },
+    Error {
+        code: u16,
+        message: String,
+    },
+}
+
+#[derive(Debug, Clone)]
+enum BinlogSource {
+    Bytes(String, Vec<u8>),
+    Fixture(PathBuf),
+}
+
+/// 가짜 서버 설정
+#[derive(Debug, Clone)]
+pub struct FakeServerBuilder {
+    server_version: String,
+    server_id: u32,
+    server_uuid: String,
+    username: String,
+    password: String,
+    default_auth_plugin: AuthPlugin,
+    auth_plugin: AuthPlugin,
+    full_auth: bool,
+    variables: BTreeMap<String, String>,
+    queries: Vec<(String, ScriptedQuery)>,
+    binlogs: Vec<BinlogSource>,
+    faults: Vec<Fault>,
+    once_faults: Vec<Fault>,
+}
+
+impl Default for FakeServerBuilder {
+    fn default() -> Self {
+        FakeServerBuilder {
+            server_version: "8.0.35".to_string(),
+            server_id: 1,
+            server_uuid: "3e11fa47-71ca-11e1-9e33-c80aa9429562".to_string(),
+            username: "repl".to_string(),
+            password: "repl_password".to_string(),
+            default_auth_plugin: AuthPlugin::CachingSha2Password,
+            auth_plugin: AuthPlugin::CachingSha2Password,
+            full_auth: false,
+            variables: BTreeMap::new(),
+            queries: Vec::new(),
+            binlogs: Vec::new(),
+            faults: Vec::new(),
+            once_faults: Vec::new(),
+        }
+    }
+}
+
+impl FakeServerBuilder {
+    /// 8.0.35, server_id 1, 계정 repl / repl_password (caching_sha2_password)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn server_version(mut self, server_version: &str) -> Self {
+        self.server_version = server_version.to_string();
+        self
+    }
+
+    pub fn server_id(mut self, server_id: u32) -> Self {
+        self.server_id = server_id;
+        self
+    }
+
+    pub fn server_uuid(mut self, server_uuid: &str) -> Self {
+        self.server_uuid = server_uuid.to_string();
+        self
+    }
+
+    /// 접속을 허용할 계정
+    pub fn user(mut self, username: &str, password: &str) -> Self {
+        self.username = username.to_string();
+        self.password = password.to_string();
+        self
+    }
+
+    /// greeting 에 알리는 기본 인증 플러그인 (계정 플러그인과 다르면 AuthSwitchRequest 전송)
+    pub fn default_auth_plugin(mut self, plugin: AuthPlugin) -> Self {
+        self.default_auth_plugin = plugin;
+        self
+    }
+
+    /// 계정의 인증 플러그인 (mysql_native_password, caching_sha2_password, mysql_clear_password)
+    pub fn auth_plugin(mut self, plugin: AuthPlugin) -> Self {
+        self.auth_plugin = plugin;
+        self
+    }
+
+    /// caching_sha2_password 캐시 미스 (평문 또는 RSA 로 비밀번호를 받는 전체 인증)
+    pub fn full_auth(mut self, full_auth: bool) -> Self {
+        self.full_auth = full_auth;
+        self
+    }
+
+    /// 전역 변수 (`SELECT @@name`, `SHOW VARIABLES`, `SET @x = @@global.name`)
+    pub fn variable(mut self, name: &str, value: &str) -> Self {
+        self.variables
+            .insert(name.to_ascii_lowercase(), value.to_string());
+        self
+    }
+
+    /// `sql` 과 같은 쿼리 (대소문자 무시) 에 돌려줄 결과셋
+    pub fn query_result(
+        mut self,
+        sql: &str,
+        columns: &[&str],
+        rows: Vec<Vec<Option<String>>>,
+    ) -> Self {
+        let columns = columns.iter().map(|c| c.to_string()).collect();
+        self.queries
+            .push((sql.to_string(), ScriptedQuery::Rows { columns, rows }));
+        self
+    }
+
+    /// `sql` 과 같은 쿼리에 돌려줄 ERR 패킷
+    pub fn query_error(mut self, sql: &str, code: u16, message: &str) -> Self {
+        let message = message.to_string();
+        self.queries
+            .push((sql.to_string(), ScriptedQuery::Error { code, message }));
+        self
+    }
+
+    /// 메모리의 binlog 파일 추가 (추가한 순서가 인덱스 순서)
+    pub fn binlog(mut self, name: &str, data: Vec<u8>) -> Self {
+        self.binlogs
+            .push(BinlogSource::Bytes(name.to_string(), data));
+        self
+    }
+
+    /// fixture binlog 파일 추가 (파일명이 binlog 이름)
+    pub fn binlog_fixture(mut self, path: impl AsRef<Path>) -> Self {
+        self.binlogs
+            .push(BinlogSource::Fixture(path.as_ref().to_path_buf()));
+        self
+    }
+
+    /// 모든 연결에 적용할 장애
+    pub fn fault(mut self, fault: Fault) -> Self {
+        self.faults.push(fault);
+        self
+    }
+
+    /// 다음 연결 하나에만 적용할 장애 (재연결 시험용)
+    pub fn fault_once(mut self, fault: Fault) -> Self {
+        self.once_faults.push(fault);
+        self
+    }
+
+    /// binlog 파일을 읽고 127.0.0.1 의 빈 포트에서 서버 시작
+    pub async fn start(mut self) -> Result<FakeServer> {
+        let mut files = Vec::with_capacity(self.binlogs.len());
+        for source in &self.binlogs {
+            let (name, data) = match source {
+                BinlogSource::Bytes(name, data) => (name.clone(), data.clone()),
+                BinlogSource::Fixture(path) => {
+                    let name = path.file_name().map(|n| n.to_string_lossy().to_string());
+                    let data = std::fs::read(path).map_err(|e| {
+                        CdcError::IoError(format!("Failed to read {}: {}", path.display(), e))
+                    })?;
+                    (name.unwrap_or_default(), data)
+                }
+            };
+            files.push(BinlogData::parse(name, data)?);
+        }
+
+        let executed = files.iter().flat_map(|file| &file.events).try_fold(
+            GtidSet::new(),
+            |mut executed, stored| {
+                if let BinlogEventData::Gtid(gtid) = &stored.event.data {
+                    executed.add_gtid(&gtid.gtid)?;
+                }
+                Ok::<_, CdcError>(executed)
+            },
+        )?;
+        let checksum = match files.first().map(|file| file.checksum) {
+            Some(ChecksumAlgorithm::None) => "NONE",
+            _ => "CRC32",
+        };
+        let defaults = [
+            ("server_id", self.server_id.to_string()),
+            ("server_uuid", self.server_uuid.clone()),
+            ("version", self.server_version.clone()),
+            ("log_bin", "ON".to_string()),
+            ("binlog_format", "ROW".to_string()),
+            ("binlog_row_image", "FULL".to_string()),
+            ("binlog_checksum", checksum.to_string()),
+            ("gtid_mode", "ON".to_string()),
+            ("enforce_gtid_consistency", "ON".to_string()),
+            ("gtid_executed", executed.to_string()),
+            ("gtid_purged", String::new()),
+        ];
+        for (name, value) in defaults {
+            self.variables.entry(name.to_string()).or_insert(value);
+        }
+
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .map_err(|e| CdcError::IoError(format!("Failed to bind fake server: {}", e)))?;
+        let addr = listener.local_addr()?;
+
+        let shared = Arc::new(Shared {
+            once_faults: Mutex::new(std::mem::take(&mut self.once_faults)),
+            settings: self,
+            files,
+            log: Mutex::new(ServerLog::default()),
+            rsa_key: OnceLock::new(),
+        });
+        let task = tokio::spawn(accept(listener, shared.clone()));
+
+        Ok(FakeServer { addr, shared, task })
+    }
+}
+
+/// 실행 중인 가짜 서버 (drop 하면 모든 연결을 닫음)
+pub struct FakeServer {
+    addr: SocketAddr,
+    shared: Arc<Shared>,
+    task: JoinHandle<()>,
+}
+
+impl FakeServer {
+    pub fn builder() -> FakeServerBuilder {
+        FakeServerBuilder::new()
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// 이 서버에 설정된 계정으로 접속하는 설정 (TLS 없음, RSA 공개키 요청 허용)
+    pub fn connection_config(&self) -> ConnectionConfig {
+        let settings = &self.shared.settings;
+        ConnectionConfig {
+            port: self.addr.port(),
+            password: settings.password.clone(),
+            server_id: settings.server_id + 100,
+            ssl_mode: SslMode::Disabled,
+            allow_public_key_retrieval: true,
+            ..ConnectionConfig::new(self.addr.ip().to_string(), &settings.username)
+        }
+    }
+
+    /// 받은 연결 수
+    pub fn connections(&self) -> usize {
+        self.shared.log.lock().connections
+    }
+
+    /// 인증한 연결마다 받은 연결 속성 (CLIENT_CONNECT_ATTRS)
+    pub fn connect_attributes(&self) -> Vec<BTreeMap<String, String>> {
+        self.shared.log.lock().connect_attrs.clone()
+    }
+
+    /// 실행된 COM_QUERY 문장
+    pub fn queries(&self) -> Vec<String> {
+        self.shared.log.lock().queries.clone()
+    }
+
+    /// COM_REGISTER_SLAVE 로 등록한 replica 의 server_id
+    pub fn registered_replicas(&self) -> Vec<u32> {
+        self.shared.log.lock().replicas.clone()
+    }
+
+    /// 받은 덤프 요청
+    pub fn dump_requests(&self) -> Vec<DumpRequest> {
+        self.shared.log.lock().dumps.clone()
+    }
+}
+
+impl Drop for FakeServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// 연결들이 공유하는 서버 상태
+struct Shared {
+    settings: FakeServerBuilder,
+    files: Vec<BinlogData>,
+    log: Mutex<ServerLog>,
+    once_faults: Mutex<Vec<Fault>>,
+    /// caching_sha2_password 전체 인증용 RSA 키 (처음 요청될 때 생성)
+    rsa_key: OnceLock<rsa::RsaPrivateKey>,
+}
+
+#[derive(Default)]
+struct ServerLog {
+    connections: usize,
+    connect_attrs: Vec<BTreeMap<String, String>>,
+    queries: Vec<String>,
+    replicas: Vec<u32>,
+    dumps: Vec<DumpRequest>,
+}
+
+/// 서버가 보낼 binlog 파일
+struct BinlogData {
+    name: String,
+    /// 마지막 완전한 이벤트의 끝
+    size: u64,
+    checksum: ChecksumAlgorithm,
+    events: Vec<StoredEvent>,
+}
+
+struct StoredEvent {
+    offset: u64,
+    raw: Vec<u8>,
+    event: BinlogEvent,
+}
+
+impl BinlogData {
+    fn parse(name: String, data: Vec<u8>) -> Result<Self> {
+        let mut reader = BinlogFileReader::new(Cursor::new(&data[..]))?;
+        let mut events = Vec::new();
+        while let Some(file_event) = reader.next_event()? {
+            let raw = data[file_event.offset as usize..file_event.next_offset() as usize].to_vec();
+            events.push(StoredEvent {
+                offset: file_event.offset,
+                raw,
+                event: file_event.event,
+            });
+        }
+
+        Ok(BinlogData {
+            name,
+            size: reader.position(),
+            checksum: reader.checksum(),
+            events,
+        })
+    }
+}
+
+/// 덤프 응답으로 보낼 이벤트와 그 이후의 binlog 위치
+struct OutgoingEvent {
+    data: Vec<u8>,
+    position: Option<BinlogPosition>,
+}
+
+async fn accept(listener: TcpListener, shared: Arc<Shared>) {
+    // 서버 task 가 abort 되면 JoinSet 과 함께 모든 연결 task 도 취소됨
+    let mut connections = JoinSet::new();
+    let mut connection_id = 0u32;
+
+    while let Ok((stream, _)) = listener.accept().await {
+        connection_id += 1;
+        shared.log.lock().connections += 1;
+
+        let mut faults = shared.settings.faults.clone();
+        faults.append(&mut shared.once_faults.lock());
+
+        let connection = Connection {
+            stream,
+            shared: shared.clone(),
+            id: connection_id,
+            faults,
+            session: HashMap::new(),
+            statements: HashMap::new(),
+        };
+        connections.spawn(connection.serve());
+        while connections.try_join_next().is_some() {}
+    }
+}
+
+/// 클라이언트 연결 하나
+struct Connection {
+    stream: TcpStream,
+    shared: Arc<Shared>,
+    id: u32,
+    faults: Vec<Fault>,
+    /// 세션 사용자 변수 (이름은 소문자, '@' 제외)
+    session: HashMap<String, String>,
+    /// prepared statement id -> 문장
+    statements: HashMap<u32, String>,
+}
+
+impl Connection {
+    async fn serve(mut self) {
+        if let Err(e) = self.run().await {
+            debug!("Fake server connection {} closed: {}", self.id, e);
+        }
+    }
+
+    async fn run(&mut self) -> Result<()> {
+        if !self.handshake().await? {
+            return Ok(());
+        }
+
+        while let Some((_, packet)) = self.read_packet().await? {
+            let Some((&command, body)) = packet.split_first() else {
+                return Err(CdcError::ProtocolError("Empty command packet".to_string()));
+            };
+
+            match command {
+                COM_QUIT => return Ok(()),
+                COM_PING => self.write_ok(1).await?,
+                COM_QUERY => self.query(body, false).await?,
+                COM_STMT_PREPARE => self.prepare(body).await?,
+                COM_STMT_EXECUTE => self.execute(body).await?,
+                // 응답 없음
+                COM_STMT_CLOSE => {
+                    let id = Cursor::new(body).read_u32::<LittleEndian>()?;
+                    self.statements.remove(&id);
+                }
+                COM_REGISTER_SLAVE => {
+                    let server_id = Cursor::new(body).read_u32::<LittleEndian>()?;
+                    self.shared.log.lock().replicas.push(server_id);
+                    self.write_ok(1).await?;
+                }
+                COM_BINLOG_DUMP | COM_BINLOG_DUMP_GTID => {
+                    let request = parse_dump_request(command, body)?;
+                    if !self.dump(request).await? {
+                        return Ok(());
+                    }
+                }
+                _ => {
+                    let message = format!("Unknown command 0x{:02x}", command);
+                    self.write_error(1, UNKNOWN_COMMAND, "08S01", &message)
+                        .await?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// greeting 과 인증 (인증에 실패하면 ERR 를 보내고 false)
+    async fn handshake(&mut self) -> Result<bool> {
+        let scramble: Vec<u8> = {
+            let mut rng = rand::thread_rng();
+            (0..20).map(|_| rng.gen_range(1..=127)).collect()
+        };
+        let settings = &self.shared.settings;
+        let greeting = encode_greeting(
+            &settings.server_version,
+            self.id,
+            &scramble,
+            settings.default_auth_plugin,
+        )?;
+        self.write_packet(&greeting, 0).await?;
+
+        let Some((sequence, packet)) = self.read_packet().await? else {
+            return Ok(false);
+        };
+        let response = HandshakeInfo::parse(&packet)?;
+        let mut sequence = sequence.wrapping_add(1);
+
+        // 클라이언트가 고른 플러그인이 계정 플러그인과 다르면 전환 요청
+        let plugin = self.shared.settings.auth_plugin;
+        let mut auth_data = response.auth_response;
+        if response.plugin.as_deref() != Some(plugin.name()) {
+            let mut switch = vec![auth::AUTH_SWITCH_REQUEST];
+            switch.extend_from_slice(plugin.name().as_bytes());
+            switch.push(0);
+            switch.extend_from_slice(&scramble);
+            switch.push(0);
+            self.write_packet(&switch, sequence).await?;
+
+            let Some((next, data)) = self.read_packet().await? else {
+                return Ok(false);
+            };
+            auth_data = data;
+            sequence = next.wrapping_add(1);
+        }
+
+        let password = self.shared.settings.password.clone();
+        let accepted = match plugin {
+            AuthPlugin::MysqlNativePassword => {
+                verify_native_password(&password, &scramble, &auth_data)
+            }
+            AuthPlugin::CachingSha2Password => {
+                match self
+                    .caching_sha2(&password, &scramble, &auth_data, &mut sequence)
+                    .await?
+                {
+                    Some(accepted) => accepted,
+                    None => return Ok(false),
+                }
+            }
+            AuthPlugin::MysqlClearPassword => {
+                auth_data.strip_suffix(&[0]).unwrap_or(&auth_data) == password.as_bytes()
+            }
+            AuthPlugin::Sha256Password => {
+                return Err(CdcError::ProtocolError(
+                    "sha256_password is not supported by the fake server".to_string(),
+                ))
+            }
+        };
+
+        if !accepted || response.username != self.shared.settings.username {
+            let message = format!(
+                "Access denied for user '{}'@'localhost' (using password: {})",
+                response.username,
+                if auth_data.is_empty() { "NO" } else { "YES" }
+            );
+            self.write_error(sequence, error_codes::ACCESS_DENIED, "28000", &message)
+                .await?;
+            return Ok(false);
+        }
+
+        self.shared.log.lock().connect_attrs.push(response.connect_attrs);
+        self.write_ok(sequence).await?;
+        Ok(true)
+    }
+
+    /// caching_sha2_password 인증 (클라이언트가 연결을 끊으면 None)
+    ///
+    /// 캐시 히트면 scramble 응답을 검증하고, `full_auth` 면 평문 또는 RSA 로 받은 비밀번호를 비교합니다.
+    async fn caching_sha2(
+        &mut self,
+        password: &str,
+        scramble: &[u8],
+        auth_data: &[u8],
+        sequence: &mut u8,
+    ) -> Result<Option<bool>> {
+        if password.is_empty() || auth_data.is_empty() {
+            return Ok(Some(password.is_empty() && auth_data.is_empty()));
+        }
+
+        if !self.shared.settings.full_auth {
+            if !verify_caching_sha2(password, scramble, auth_data) {
+                return Ok(Some(false));
+            }
+            let fast_auth = [auth::AUTH_MORE_DATA, auth::CACHING_SHA2_FAST_AUTH_SUCCESS];
+            self.write_packet(&fast_auth, *sequence).await?;
+            *sequence = sequence.wrapping_add(1);
+            return Ok(Some(true));
+        }
+
+        let full_auth = [auth::AUTH_MORE_DATA, auth::CACHING_SHA2_PERFORM_FULL_AUTH];
+        self.write_packet(&full_auth, *sequence).await?;
+        let Some((next, mut response)) = self.read_packet().await? else {
+            return Ok(None);
+        };
+        *sequence = next.wrapping_add(1);
+
+        if response == [CACHING_SHA2_REQUEST_PUBLIC_KEY] {
+            let shared = self.shared.clone();
+            let key = shared.rsa_key();
+            let pem = public_key_pem(key)?;
+            let mut packet = vec![auth::AUTH_MORE_DATA];
+            packet.extend_from_slice(pem.as_bytes());
+            self.write_packet(&packet, *sequence).await?;
+
+            let Some((next, encrypted)) = self.read_packet().await? else {
+                return Ok(None);
+            };
+            *sequence = next.wrapping_add(1);
+
+            // (password + '\0') XOR scramble
+            let oaep = rsa::Oaep::new::<sha1::Sha1>();
+            response = match key.decrypt(oaep, &encrypted) {
+                Ok(plain) => plain
+                    .iter()
+                    .enumerate()
+                    .map(|(i, byte)| byte ^ scramble[i % scramble.len()])
+                    .collect(),
+                Err(_) => return Ok(Some(false)),
+            };
+        }
+
+        Ok(Some(
+            response.strip_suffix(&[0]).unwrap_or(&response) == password.as_bytes(),
+        ))
+    }
+
+    /// `binary` 면 결과셋을 바이너리 행으로 전송 (COM_STMT_EXECUTE)
+    async fn query(&mut self, sql: &[u8], binary: bool) -> Result<()> {
+        let sql = String::from_utf8_lossy(sql);
+        let sql = sql.trim().trim_end_matches(';').trim();
+        self.shared.log.lock().queries.push(sql.to_string());
+
+        let queries = &self.shared.settings.queries;
+        let scripted = queries.iter().find(|(q, _)| q.eq_ignore_ascii_case(sql));
+        match scripted.map(|(_, response)| response.clone()) {
+            Some(ScriptedQuery::Rows { columns, rows }) => {
+                return self.write_result_set(&columns, &rows, binary).await;
+            }
+            Some(ScriptedQuery::Error { code, message }) => {
+                return self.write_error(1, code, "HY000", &message).await;
+            }
+            None => {}
+        }
+
+        let upper = sql.to_ascii_uppercase();
+        let result = if upper.starts_with("SET ") {
+            self.set_variables(&sql[4..]);
+            return self.write_ok(1).await;
+        } else if NO_OP_STATEMENTS.iter().any(|s| upper.starts_with(s)) {
+            return self.write_ok(1).await;
+        } else if upper.starts_with("SELECT ") {
+            self.select_variables(&sql[7..])
+        } else if upper.starts_with("SHOW ") {
+            self.show(&upper, sql)
+        } else {
+            None
+        };
+
+        match result {
+            Some((columns, rows)) => self.write_result_set(&columns, &rows, binary).await,
+            None => {
+                let message = format!("Statement not supported by the fake server: {}", sql);
+                self.write_error(1, PARSE_ERROR, "42000", &message).await
+            }
+        }
+    }
+
+    /// 파라미터 수만 세고 결과 컬럼은 실행할 때 알림
+    async fn prepare(&mut self, sql: &[u8]) -> Result<()> {
+        let sql = String::from_utf8_lossy(sql).to_string();
+        let params = sql.matches('?').count();
+        let id = self.statements.keys().max().map_or(1, |id| id + 1);
+        self.statements.insert(id, sql);
+
+        // OK + statement id + 컬럼 수 + 파라미터 수 + reserved + warning 수
+        let mut ok = vec![0x00];
+        ok.extend_from_slice(&id.to_le_bytes());
+        ok.extend_from_slice(&0u16.to_le_bytes());
+        ok.extend_from_slice(&(params as u16).to_le_bytes());
+        ok.push(0x00);
+        ok.extend_from_slice(&0u16.to_le_bytes());
+
+        let mut packets = vec![ok];
+        if params > 0 {
+            for _ in 0..params {
+                packets.push(encode_column_definition("?")?);
+            }
+            packets.push(eof_packet());
+        }
+        for (i, packet) in packets.iter().enumerate() {
+            self.write_packet(packet, (i + 1) as u8).await?;
+        }
+        Ok(())
+    }
+
+    /// 바인딩한 값을 `?` 자리에 넣은 문장 실행
+    async fn execute(&mut self, body: &[u8]) -> Result<()> {
+        let id = Cursor::new(body).read_u32::<LittleEndian>()?;
+        let Some(sql) = self.statements.get(&id).cloned() else {
+            let message = format!("Unknown prepared statement handler ({})", id);
+            return self
+                .write_error(1, UNKNOWN_STATEMENT, "HY000", &message)
+                .await;
+        };
+
+        // statement id (4) + flags (1) + iteration count (4)
+        let params = body.get(9..).unwrap_or_default();
+        let params = parse_execute_params(params, sql.matches('?').count())?;
+        let mut parts = sql.split('?');
+        let mut bound = parts.next().unwrap_or_default().to_string();
+        for (param, part) in params.iter().zip(parts) {
+            bound.push_str(param);
+            bound.push_str(part);
+        }
+        self.query(bound.as_bytes(), true).await
+    }
+
+    /// `@name = value, ...` (전역 변수 설정과 SET NAMES 등은 무시)
+    fn set_variables(&mut self, assignments: &str) {
+        for assignment in assignments.split(',') {
+            let Some((name, value)) = assignment.split_once('=') else {
+                continue;
+            };
+            let name = name.trim();
+            let Some(name) = name.strip_prefix('@').filter(|n| !n.starts_with('@')) else {
+                continue;
+            };
+            let value = self.evaluate(value.trim()).unwrap_or_default();
+            self.session.insert(name.to_ascii_lowercase(), value);
+        }
+    }
+
+    /// 리터럴, `@변수`, `@@변수` 값
+    fn evaluate(&self, term: &str) -> Option<String> {
+        if let Some(name) = term.strip_prefix("@@") {
+            let name = name.to_ascii_lowercase();
+            let name = ["global.", "session."]
+                .iter()
+                .find_map(|scope| name.strip_prefix(scope))
+                .unwrap_or(&name);
+            return self.shared.settings.variables.get(name).cloned();
+        }
+        if let Some(name) = term.strip_prefix('@') {
+            return self.session.get(&name.to_ascii_lowercase()).cloned();
+        }
+        let quoted = ['\'', '"']
+            .iter()
+            .find_map(|&q| term.strip_prefix(q).and_then(|t| t.strip_suffix(q)));
+        Some(quoted.unwrap_or(term).to_string())
+    }
+
+    /// `SELECT @a, @@b` 형식만 지원
+    fn select_variables(&self, terms: &str) -> Option<ResultSet> {
+        let mut columns = Vec::new();
+        let mut row = Vec::new();
+        for term in terms.split(',').map(str::trim) {
+            if !term.starts_with('@') {
+                return None;
+            }
+            columns.push(term.to_string());
+            row.push(self.evaluate(term));
+        }
+        Some((columns, vec![row]))
+    }
+
+    fn show(&self, upper: &str, sql: &str) -> Option<ResultSet> {
+        let files = &self.shared.files;
+        let columns = |names: &[&str]| names.iter().map(|n| n.to_string()).collect();
+
+        if upper.starts_with("SHOW MASTER STATUS") || upper.starts_with("SHOW BINARY LOG STATUS") {
+            let executed = self.shared.settings.variables.get("gtid_executed").cloned();
+            let rows = files
+                .last()
+                .map(|file| {
+                    let size = file.size.to_string();
+                    vec![
+                        Some(file.name.clone()),
+                        Some(size),
+                        Some(String::new()),
+                        Some(String::new()),
+                        executed,
+                    ]
+                })
+                .into_iter()
+                .collect();
+            let names = columns(&[
+                "File",
+                "Position",
+                "Binlog_Do_DB",
+                "Binlog_Ignore_DB",
+                "Executed_Gtid_Set",
+            ]);
+            return Some((names, rows));
+        }
+
+        if upper.starts_with("SHOW BINARY LOGS") || upper.starts_with("SHOW MASTER LOGS") {
+            let rows = files
+                .iter()
+                .map(|file| {
+                    vec![
+                        Some(file.name.clone()),
+                        Some(file.size.to_string()),
+                        Some("No".into()),
+                    ]
+                })
+                .collect();
+            return Some((columns(&["Log_name", "File_size", "Encrypted"]), rows));
+        }
+
+        if upper.contains(" VARIABLES") {
+            // LIKE 'pattern' 또는 WHERE Variable_name IN ('a', 'b') / = 'a'
+            let quoted = Regex::new(r"'([^']*)'").ok()?;
+            let literals: Vec<String> = quoted
+                .captures_iter(sql)
+                .map(|c| c[1].to_ascii_lowercase())
+                .collect();
+            let like = match upper.contains(" LIKE ") {
+                true => Some(like_pattern(literals.first()?)?),
+                false => None,
+            };
+            let filtered = upper.contains(" WHERE ");
+
+            let rows = self
+                .shared
+                .settings
+                .variables
+                .iter()
+                .filter(|(name, _)| match &like {
+                    Some(pattern) => pattern.is_match(name),
+                    None => !filtered || literals.contains(name),
+                })
+                .map(|(name, value)| vec![Some(name.clone()), Some(value.clone())])
+                .collect();
+            return Some((columns(&["Variable_name", "Value"]), rows));
+        }
+
+        None
+    }
+
+    /// 덤프 응답 (연결을 계속 쓸 수 있으면 true)
+    async fn dump(&mut self, request: DumpRequest) -> Result<bool> {
+        self.shared.log.lock().dumps.push(request.clone());
+
+        let checksum = match self.session.get("master_binlog_checksum") {
+            Some(value) if value.eq_ignore_ascii_case("CRC32") => Some(ChecksumAlgorithm::Crc32),
+            Some(_) => Some(ChecksumAlgorithm::None),
+            None => None,
+        };
+
+        let events = match self.dump_events(&request, checksum) {
+            Ok(events) => events,
+            Err(message) => {
+                let code = error_codes::FATAL_ERROR_READING_BINLOG;
+                self.write_error(1, code, "HY000", &message).await?;
+                return Ok(false);
+            }
+        };
+
+        let mut sequence = 1u8;
+        let mut position = None;
+        for count in 0..=events.len() {
+            if self.inject_error(count, sequence).await? {
+                return Ok(false);
+            }
+            let Some(event) = events.get(count) else {
+                break;
+            };
+
+            let mut packet = Vec::with_capacity(event.data.len() + 1);
+            packet.push(0x00);
+            packet.extend_from_slice(&event.data);
+
+            if self.faults.contains(&Fault::Truncate { event: count }) {
+                let frames = encode_frames(&packet, sequence);
+                self.write_raw(&frames[..frames.len() / 2]).await?;
+                return Ok(false);
+            }
+
+            self.write_packet(&packet, sequence).await?;
+            sequence = sequence.wrapping_add(1);
+            position = event.position.clone().or(position);
+        }
+
+        if request.non_blocking {
+            self.write_packet(&eof_packet(), sequence).await?;
+            return Ok(true);
+        }
+
+        // 새 이벤트가 없으므로 클라이언트가 끊을 때까지 heartbeat 만 전송
+        let period = self
+            .session
+            .get("master_heartbeat_period")
+            .and_then(|nanos| nanos.parse::<u64>().ok())
+            .filter(|&nanos| nanos > 0)
+            .map(Duration::from_nanos);
+        let mut buffer = [0u8; 1024];
+        loop {
+            tokio::select! {
+                read = tokio::io::AsyncReadExt::read(&mut self.stream, &mut buffer) => {
+                    // semi-sync ACK 등은 무시
+                    if matches!(read, Ok(0) | Err(_)) {
+                        return Ok(false);
+                    }
+                }
+                _ = tokio::time::sleep(period.unwrap_or_default()), if period.is_some() => {
+                    let (name, next_pos) = position
+                        .as_ref()
+                        .map_or((String::new(), 0), |p| (p.filename.clone(), p.position));
+                    let heartbeat = artificial_event(
+                        EventType::HeartbeatLogEvent,
+                        self.shared.settings.server_id,
+                        next_pos as u32,
+                        name.as_bytes(),
+                        checksum.unwrap_or(ChecksumAlgorithm::None),
+                    )?;
+                    let mut packet = vec![0x00];
+                    packet.extend_from_slice(&heartbeat);
+                    self.write_packet(&packet, sequence).await?;
+                    sequence = sequence.wrapping_add(1);
+                }
+            }
+        }
+    }
+
+    /// 보낼 이벤트 목록 (요청을 처리할 수 없으면 ERR 1236 메시지)
+    ///
+    /// 가상 ROTATE, 중간 위치에서 시작하면 next_pos 0 의 FORMAT_DESCRIPTION, 이후 파일 이벤트 순서이며,
+    /// GTID 덤프는 첫 파일부터 요청한 집합에 포함된 트랜잭션을 건너뜁니다.
+    /// 클라이언트가 체크섬 'NONE' 을 요청하면 CRC32 를 떼고 보냅니다.
+    fn dump_events(
+        &self,
+        request: &DumpRequest,
+        checksum: Option<ChecksumAlgorithm>,
+    ) -> std::result::Result<Vec<OutgoingEvent>, String> {
+        let files = &self.shared.files;
+        let server_id = self.shared.settings.server_id;
+
+        let checksum = match checksum {
+            Some(checksum) => checksum,
+            None if files.iter().all(|f| f.checksum == ChecksumAlgorithm::None) => {
+                ChecksumAlgorithm::None
+            }
+            None => {
+                return Err(
+                    "Replica can not handle replication events with the checksum \
+                            that source is configured to log"
+                        .to_string(),
+                )
+            }
+        };
+
+        let (index, position) = match request.gtid_set {
+            Some(_) => (0, FIRST_EVENT_POSITION),
+            None if request.filename.is_empty() => (0, request.position),
+            None => match files.iter().position(|f| f.name == request.filename) {
+                Some(index) => (index, request.position),
+                None => {
+                    return Err(
+                        "Could not find first log file name in binary log index file".to_string(),
+                    )
+                }
+            },
+        };
+        let file = files
+            .get(index)
+            .ok_or_else(|| "Binary log is not open".to_string())?;
+        if position > file.size {
+            return Err(
+                "Client requested source to start replication from position > file size"
+                    .to_string(),
+            );
+        }
+        if position < FIRST_EVENT_POSITION
+            || (position < file.size && !file.events.iter().any(|e| e.offset == position))
+        {
+            return Err(format!(
+                "Client requested source to start replication from impossible position {}",
+                position
+            ));
+        }
+
+        let mut body = position.to_le_bytes().to_vec();
+        body.extend_from_slice(file.name.as_bytes());
+        let rotate = artificial_event(EventType::RotateEvent, server_id, 0, &body, checksum)
+            .map_err(|e| e.to_string())?;
+        let mut events = vec![OutgoingEvent {
+            data: rotate,
+            position: Some(BinlogPosition::new(file.name.clone(), position)),
+        }];
+
+        // 중간 위치에서 시작해도 클라이언트는 FORMAT_DESCRIPTION 을 먼저 받음
+        if let Some(format) = file
+            .events
+            .first()
+            .filter(|_| position > FIRST_EVENT_POSITION)
+        {
+            let mut data = format.raw.clone();
+            data[NEXT_POSITION_OFFSET..NEXT_POSITION_OFFSET + 4].fill(0);
+            if file.checksum == ChecksumAlgorithm::Crc32 {
+                let end = data.len() - 4;
+                let crc = crc32fast::hash(&data[..end]);
+                data[end..].copy_from_slice(&crc.to_le_bytes());
+            }
+            events.push(OutgoingEvent {
+                data,
+                position: None,
+            });
+        }
+
+        let mut skipping = false;
+        for (i, file) in files[index..].iter().enumerate() {
+            for stored in file.events.iter().filter(|e| i > 0 || e.offset >= position) {
+                if let Some(executed) = &request.gtid_set {
+                    if let BinlogEventData::Gtid(gtid) = &stored.event.data {
+                        skipping = executed.contains(&gtid.gtid);
+                    }
+                    if skipping {
+                        skipping = !ends_transaction(&stored.event.data);
+                        continue;
+                    }
+                }
+
+                let position = match &stored.event.data {
+                    BinlogEventData::Rotate(rotate) => Some(BinlogPosition::new(
+                        rotate.next_binlog_name.clone(),
+                        rotate.position,
+                    )),
+                    _ => Some(BinlogPosition::new(
+                        file.name.clone(),
+                        stored.offset + stored.raw.len() as u64,
+                    )),
+                };
+                let data = convert_checksum(stored, file.checksum, checksum);
+                events.push(OutgoingEvent { data, position });
+            }
+        }
+
+        Ok(events)
+    }
+
+    /// `count` 개의 이벤트를 보낸 시점의 Disconnect / Error 장애 (연결을 끊어야 하면 true)
+    async fn inject_error(&mut self, count: usize, sequence: u8) -> Result<bool> {
+        for fault in self.faults.clone() {
+            match fault {
+                Fault::Disconnect { after_events } if after_events == count => return Ok(true),
+                Fault::Error {
+                    after_events,
+                    code,
+                    message,
+                } if after_events == count => {
+                    self.write_error(sequence, code, "HY000", &message).await?;
+                    return Ok(true);
+                }
+                _ => {}
+            }
+        }
+        Ok(false)
+    }
+
+    /// 패킷 읽기 (클라이언트가 연결을 닫았으면 None)
+    async fn read_packet(&mut self) -> Result<Option<(u8, Vec<u8>)>> {
+        let mut payload = Vec::new();
+        loop {
+            let mut header = [0u8; 4];
+            match tokio::io::AsyncReadExt::read_exact(&mut self.stream, &mut header).await {
+                Ok(_) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+                Err(e) => return Err(e.into()),
+            }
+
+            let length = u32::from_le_bytes([header[0], header[1], header[2], 0]) as usize;
+            let start = payload.len();
+            payload.resize(start + length, 0);
+            tokio::io::AsyncReadExt::read_exact(&mut self.stream, &mut payload[start..]).await?;
+
+            if length < MAX_PAYLOAD_LENGTH {
+                return Ok(Some((header[3], payload)));
+            }
+        }
+    }
+
+    async fn write_packet(&mut self, payload: &[u8], sequence: u8) -> Result<()> {
+        let frames = encode_frames(payload, sequence);
+        self.write_raw(&frames).await
+    }
+
+    /// Slow 장애가 있으면 나누어 전송
+    async fn write_raw(&mut self, data: &[u8]) -> Result<()> {
+        let slow = self.faults.iter().find_map(|fault| match fault {
+            Fault::Slow { chunk_size, delay } => Some((*chunk_size, *delay)),
+            _ => None,
+        });
+
+        match slow {
+            Some((chunk_size, delay)) => {
+                for chunk in data.chunks(chunk_size.max(1)) {
+                    tokio::io::AsyncWriteExt::write_all(&mut self.stream, chunk).await?;
+                    tokio::io::AsyncWriteExt::flush(&mut self.stream).await?;
+                    tokio::time::sleep(delay).await;
+                }
+            }
+            None => tokio::io::AsyncWriteExt::write_all(&mut self.stream, data).await?,
+        }
+        Ok(())
+    }
+
+    async fn write_ok(&mut self, sequence: u8) -> Result<()> {
+        let mut packet = vec![0x00, 0x00, 0x00];
+        packet.extend_from_slice(&SERVER_STATUS.to_le_bytes());
+        packet.extend_from_slice(&0u16.to_le_bytes());
+        self.write_packet(&packet, sequence).await
+    }
+
+    async fn write_error(
+        &mut self,
+        sequence: u8,
+        code: u16,
+        sql_state: &str,
+        message: &str,
+    ) -> Result<()> {
+        let mut packet = vec![0xFF];
+        packet.extend_from_slice(&code.to_le_bytes());
+        packet.push(b'#');
+        packet.extend_from_slice(sql_state.as_bytes());
+        packet.extend_from_slice(message.as_bytes());
+        self.write_packet(&packet, sequence).await
+    }
+
+    /// 결과셋 (컬럼 정의와 행 뒤에 EOF, 모든 값은 VAR_STRING)
+    ///
+    /// 바이너리 행은 헤더 0x00 과 NULL bitmap (2 bit offset) 뒤에 NULL 이 아닌 값만 담습니다.
+    async fn write_result_set(
+        &mut self,
+        columns: &[String],
+        rows: &[Vec<Option<String>>],
+        binary: bool,
+    ) -> Result<()> {
+        let mut packets = Vec::with_capacity(columns.len() + rows.len() + 3);
+
+        let mut count = Vec::new();
+        auth::write_lenenc_int(&mut count, columns.len() as u64)?;
+        packets.push(count);
+        for column in columns {
+            packets.push(encode_column_definition(column)?);
+        }
+        packets.push(eof_packet());
+
+        for row in rows {
+            let mut packet = Vec::new();
+            if binary {
+                let mut bitmap = vec![0u8; (row.len() + 9) / 8];
+                for (i, _) in row.iter().enumerate().filter(|(_, v)| v.is_none()) {
+                    bitmap[(i + 2) / 8] |= 1 << ((i + 2) % 8);
+                }
+                packet.push(0x00);
+                packet.extend_from_slice(&bitmap);
+            }
+            for value in row {
+                match value {
+                    Some(value) => auth::write_lenenc_str(&mut packet, value)?,
+                    None if binary => {}
+                    None => packet.push(0xFB),
+                }
+            }
+            packets.push(packet);
+        }
+        packets.push(eof_packet());
+
+        for (i, packet) in packets.iter().enumerate() {
+            self.write_packet(packet, (i + 1) as u8).await?;
+        }
+        Ok(())
+    }
+}
+
+impl Shared {
+    fn rsa_key(&self) -> &rsa::RsaPrivateKey {
+        // 테스트용이므로 생성이 빠른 1024 bit 사용
+        self.rsa_key.get_or_init(|| {
+            rsa::RsaPrivateKey::new(&mut rand::thread_rng(), 1024)
+                .expect("failed to generate RSA key")
+        })
+    }
+}
+
+/// 클라이언트 핸드셰이크 응답 중 인증에 필요한 부분
+struct HandshakeInfo {
+    username: String,
+    auth_response: Vec<u8>,
+    plugin: Option<String>,
+    connect_attrs: BTreeMap<String, String>,
+}
+
+impl HandshakeInfo {
+    fn parse(data: &[u8]) -> Result<Self> {
+        let truncated = || CdcError::ProtocolError("Truncated handshake response".to_string());
+
+        let capabilities = Cursor::new(data).read_u32::<LittleEndian>()?;
+        // capabilities (4) + max packet (4) + collation (1) + reserved (23)
+        let mut rest = data.get(32..).ok_or_else(truncated)?;
+
+        let username = read_null_terminated(&mut rest).ok_or_else(truncated)?;
+        let auth_length = if capabilities & capabilities::PLUGIN_AUTH_LENENC_CLIENT_DATA != 0 {
+            protocol::read_lenenc_int(&mut rest)? as usize
+        } else {
+            let (&length, tail) = rest.split_first().ok_or_else(truncated)?;
+            rest = tail;
+            length as usize
+        };
+        let auth_response = rest.get(..auth_length).ok_or_else(truncated)?.to_vec();
+        rest = &rest[auth_length..];
+
+        if capabilities & capabilities::CONNECT_WITH_DB != 0 {
+            read_null_terminated(&mut rest).ok_or_else(truncated)?;
+        }
+        let plugin = if capabilities & capabilities::PLUGIN_AUTH != 0 {
+            read_null_terminated(&mut rest)
+        } else {
+            None
+        };
+
+        // 전체 길이 + (key, value) lenenc 문자열 쌍
+        let mut connect_attrs = BTreeMap::new();
+        if capabilities & capabilities::CONNECT_ATTRS != 0 && !rest.is_empty() {
+            let length = protocol::read_lenenc_int(&mut rest)? as usize;
+            let mut attrs = rest.get(..length).ok_or_else(truncated)?;
+            let mut strings = Vec::new();
+            while !attrs.is_empty() {
+                let length = protocol::read_lenenc_int(&mut attrs)? as usize;
+                let value = attrs.get(..length).ok_or_else(truncated)?;
+                strings.push(String::from_utf8_lossy(value).to_string());
+                attrs = &attrs[length..];
+            }
+            let mut strings = strings.into_iter();
+            while let (Some(key), Some(value)) = (strings.next(), strings.next()) {
+                connect_attrs.insert(key, value);
+            }
+        }
+
+        Ok(HandshakeInfo {
+            username: String::from_utf8_lossy(&username).to_string(),
+            auth_response,
+            plugin: plugin.map(|p| String::from_utf8_lossy(&p).to_string()),
+            connect_attrs,
+        })
+    }
+}
+
+fn read_null_terminated(data: &mut &[u8]) -> Option<Vec<u8>> {
+    let end = data.iter().position(|&b| b == 0)?;
+    let value = data[..end].to_vec();
+    *data = &data[end + 1..];
+    Some(value)
+}
+
+/// COM_STMT_EXECUTE 의 파라미터: NULL bitmap + new_params_bound_flag + 타입 (2 x n) + 값
+///
+/// 정수와 실수는 그대로, 문자열과 시각은 작은따옴표로 감싼 텍스트, NULL 은 `NULL` 로 읽습니다.
+fn parse_execute_params(mut data: &[u8], count: usize) -> Result<Vec<String>> {
+    if count == 0 {
+        return Ok(Vec::new());
+    }
+    let truncated = || CdcError::ProtocolError("Truncated COM_STMT_EXECUTE".to_string());
+
+    let bitmap = data
+        .get(..count.div_ceil(8))
+        .ok_or_else(truncated)?
+        .to_vec();
+    // mysql_async 는 매번 타입을 보냄 (new_params_bound_flag = 1)
+    let types = data
+        .get(bitmap.len() + 1..bitmap.len() + 1 + count * 2)
+        .ok_or_else(truncated)?
+        .to_vec();
+    data = &data[bitmap.len() + 1 + count * 2..];
+
+    let quote = |text: &str| format!("'{}'", text.replace('\'', "''"));
+    let mut params = Vec::with_capacity(count);
+    for i in 0..count {
+        if bitmap[i / 8] & (1 << (i % 8)) != 0 {
+            params.push("NULL".to_string());
+            continue;
+        }
+        let unsigned = types[i * 2 + 1] & 0x80 != 0;
+        let param = match types[i * 2] {
+            0x01 if unsigned => data.read_u8()?.to_string(),
+            0x01 => data.read_i8()?.to_string(),
+            0x02 if unsigned => data.read_u16::<LittleEndian>()?.to_string(),
+            0x02 => data.read_i16::<LittleEndian>()?.to_string(),
+            0x03 if unsigned => data.read_u32::<LittleEndian>()?.to_string(),
+            0x03 => data.read_i32::<LittleEndian>()?.to_string(),
+            0x08 if unsigned => data.read_u64::<LittleEndian>()?.to_string(),
+            0x08 => data.read_i64::<LittleEndian>()?.to_string(),
+            0x04 => data.read_f32::<LittleEndian>()?.to_string(),
+            0x05 => data.read_f64::<LittleEndian>()?.to_string(),
+            // DATE, DATETIME, TIMESTAMP: 길이 + 연 (2) 월 일 [시 분 초 [마이크로초 (4)]]
+            0x07 | 0x0a | 0x0c => {
+                let length = data.read_u8()?;
+                let mut text = String::from("0000-00-00");
+                if length >= 4 {
+                    let year = data.read_u16::<LittleEndian>()?;
+                    text = format!("{:04}-{:02}-{:02}", year, data.read_u8()?, data.read_u8()?);
+                }
+                if length >= 7 {
+                    let (hour, minute, second) =
+                        (data.read_u8()?, data.read_u8()?, data.read_u8()?);
+                    text.push_str(&format!(" {:02}:{:02}:{:02}", hour, minute, second));
+                }
+                if length >= 11 {
+                    text.push_str(&format!(".{:06}", data.read_u32::<LittleEndian>()?));
+                }
+                quote(&text)
+            }
+            // TIME: 길이 + 부호 + 일 (4) + 시 분 초 [마이크로초 (4)]
+            0x0b => {
+                let length = data.read_u8()?;
+                let mut text = String::from("00:00:00");
+                if length >= 8 {
+                    let sign = if data.read_u8()? == 1 { "-" } else { "" };
+                    let days = data.read_u32::<LittleEndian>()?;
+                    let (hour, minute, second) =
+                        (data.read_u8()?, data.read_u8()?, data.read_u8()?);
+                    let hours = days * 24 + hour as u32;
+                    text = format!("{}{:02}:{:02}:{:02}", sign, hours, minute, second);
+                }
+                if length >= 12 {
+                    text.push_str(&format!(".{:06}", data.read_u32::<LittleEndian>()?));
+                }
+                quote(&text)
+            }
+            _ => {
+                let length = protocol::read_lenenc_int(&mut data)? as usize;
+                let value = data.get(..length).ok_or_else(truncated)?;
+                data = &data[length..];
+                quote(&String::from_utf8_lossy(value))
+            }
+        };
+        params.push(param);
+    }
+    Ok(params)
+}
+
+/// COM_BINLOG_DUMP: position (4) + flags (2) + server_id (4) + 파일명
+/// COM_BINLOG_DUMP_GTID: flags (2) + server_id (4) + 파일명 길이 (4) + 파일명 + position (8)
+/// [+ GTID 데이터 길이 (4) + GTID 데이터]
+fn parse_dump_request(command: u8, body: &[u8]) -> Result<DumpRequest> {
+    let mut cursor = Cursor::new(body);
+
+    if command == COM_BINLOG_DUMP {
+        let position = cursor.read_u32::<LittleEndian>()? as u64;
+        let flags = cursor.read_u16::<LittleEndian>()?;
+        let server_id = cursor.read_u32::<LittleEndian>()?;
+        let name = &body[cursor.position() as usize..];
+        let name = name.strip_suffix(&[0]).unwrap_or(name);
+        return Ok(DumpRequest {
+            server_id,
+            filename: String::from_utf8_lossy(name).to_string(),
+            position,
+            gtid_set: None,
+            non_blocking: flags & BINLOG_DUMP_NON_BLOCK != 0,
+        });
+    }
+
+    let flags = cursor.read_u16::<LittleEndian>()?;
+    let server_id = cursor.read_u32::<LittleEndian>()?;
+    let mut name = vec![0u8; cursor.read_u32::<LittleEndian>()? as usize];
+    cursor.read_exact(&mut name)?;
+    let position = cursor.read_u64::<LittleEndian>()?;
+
+    let gtid_set = if flags & BINLOG_THROUGH_GTID != 0 {
+        let mut data = vec![0u8; cursor.read_u32::<LittleEndian>()? as usize];
+        cursor.read_exact(&mut data)?;
+        GtidSet::decode(&data)?
+    } else {
+        GtidSet::new()
+    };
+
+    Ok(DumpRequest {
+        server_id,
+        filename: String::from_utf8_lossy(&name).to_string(),
+        position,
+        gtid_set: Some(gtid_set),
+        non_blocking: flags & BINLOG_DUMP_NON_BLOCK != 0,
+    })
+}
+
+/// HandshakeV10 greeting
+fn encode_greeting(
+    server_version: &str,
+    connection_id: u32,
+    scramble: &[u8],
+    plugin: AuthPlugin,
+) -> Result<Vec<u8>> {
+    let mut buffer = Vec::new();
+    buffer.write_u8(10)?;
+    buffer.write_all(server_version.as_bytes())?;
+    buffer.write_u8(0)?;
+    buffer.write_u32::<LittleEndian>(connection_id)?;
+    buffer.write_all(&scramble[..8])?;
+    buffer.write_u8(0)?;
+    buffer.write_u16::<LittleEndian>(SERVER_CAPABILITIES as u16)?;
+    buffer.write_u8(SERVER_COLLATION)?;
+    buffer.write_u16::<LittleEndian>(SERVER_STATUS)?;
+    buffer.write_u16::<LittleEndian>((SERVER_CAPABILITIES >> 16) as u16)?;
+    buffer.write_u8(scramble.len() as u8 + 1)?;
+    buffer.write_all(&[0u8; 10])?;
+    buffer.write_all(&scramble[8..])?;
+    buffer.write_u8(0)?;
+    buffer.write_all(plugin.name().as_bytes())?;
+    buffer.write_u8(0)?;
+    Ok(buffer)
+}
+
+/// 컬럼 정의 (Protocol::ColumnDefinition41, VAR_STRING)
+fn encode_column_definition(name: &str) -> Result<Vec<u8>> {
+    let mut buffer = Vec::new();
+    for field in ["def", "", "", "", name, name] {
+        auth::write_lenenc_str(&mut buffer, field)?;
+    }
+    buffer.write_u8(0x0c)?;
+    buffer.write_u16::<LittleEndian>(SERVER_COLLATION as u16)?;
+    buffer.write_u32::<LittleEndian>(1024)?;
+    buffer.write_u8(0xfd)?;
+    buffer.write_u16::<LittleEndian>(0)?;
+    buffer.write_u8(0)?;
+    buffer.write_u16::<LittleEndian>(0)?;
+    Ok(buffer)
+}
+
+fn eof_packet() -> Vec<u8> {
+    let mut packet = vec![0xFE, 0x00, 0x00];
+    packet.extend_from_slice(&SERVER_STATUS.to_le_bytes());
+    packet
+}
+
+/// 패킷 프레임 (0xFFFFFF 이상은 연속 프레임)
+fn encode_frames(payload: &[u8], sequence: u8) -> Vec<u8> {
+    let mut frames = Vec::with_capacity(payload.len() + 4);
+    let mut sequence = sequence;
+    let mut chunks = payload.chunks(MAX_PAYLOAD_LENGTH).peekable();
+    loop {
+        let chunk = chunks.next().unwrap_or_default();
+        frames.extend_from_slice(&(chunk.len() as u32).to_le_bytes()[..3]);
+        frames.push(sequence);
+        frames.extend_from_slice(chunk);
+        sequence = sequence.wrapping_add(1);
+        if chunks.peek().is_none() && chunk.len() < MAX_PAYLOAD_LENGTH {
+            return frames;
+        }
+    }
+}
+
+/// 서버가 만든 이벤트 (가상 ROTATE, HEARTBEAT)
+fn artificial_event(
+    event_type: EventType,
+    server_id: u32,
+    next_pos: u32,
+    body: &[u8],
+    checksum: ChecksumAlgorithm,
+) -> Result<Vec<u8>> {
+    let length = 19 + body.len() + checksum.checksum_len();
+    let mut data = Vec::with_capacity(length);
+    data.write_u32::<LittleEndian>(0)?;
+    data.write_u8(event_type as u8)?;
+    data.write_u32::<LittleEndian>(server_id)?;
+    data.write_u32::<LittleEndian>(length as u32)?;
+    data.write_u32::<LittleEndian>(next_pos)?;
+    data.write_u16::<LittleEndian>(LOG_EVENT_ARTIFICIAL_F)?;
+    data.write_all(body)?;
+    if checksum == ChecksumAlgorithm::Crc32 {
+        let crc = crc32fast::hash(&data);
+        data.write_u32::<LittleEndian>(crc)?;
+    }
+    Ok(data)
+}
+
+/// 클라이언트가 체크섬 없이 받겠다고 하면 CRC32 제거 (FORMAT_DESCRIPTION 은 그대로)
+fn convert_checksum(
+    stored: &StoredEvent,
+    file_checksum: ChecksumAlgorithm,
+    checksum: ChecksumAlgorithm,
+) -> Vec<u8> {
+    let is_format = matches!(stored.event.data, BinlogEventData::FormatDescription(_));
+    if is_format || file_checksum == checksum || file_checksum == ChecksumAlgorithm::None {
+        return stored.raw.clone();
+    }
+
+    let mut data = stored.raw[..stored.raw.len() - file_checksum.checksum_len()].to_vec();
+    let length = (data.len() as u32).to_le_bytes();
+    data[EVENT_LENGTH_OFFSET..EVENT_LENGTH_OFFSET + 4].copy_from_slice(&length);
+    data
+}
+
+/// SHA1(password) XOR SHA1(scramble + SHA1(SHA1(password))) 검증
+fn verify_native_password(password: &str, scramble: &[u8], response: &[u8]) -> bool {
+    use sha1::{Digest, Sha1};
+
+    if password.is_empty() || response.is_empty() {
+        return password.is_empty() && response.is_empty();
+    }
+
+    // 서버는 SHA1(SHA1(password)) 만 저장하므로 응답에서 SHA1(password) 를 복원해 비교
+    let stored = Sha1::digest(Sha1::digest(password.as_bytes()));
+    let mask = Sha1::new()
+        .chain_update(scramble)
+        .chain_update(stored)
+        .finalize();
+    if response.len() != mask.len() {
+        return false;
+    }
+    let candidate: Vec<u8> = response
+        .iter()
+        .zip(mask.iter())
+        .map(|(a, b)| a ^ b)
+        .collect();
+    Sha1::digest(&candidate) == stored
+}
+
+/// SHA256(password) XOR SHA256(SHA256(SHA256(password)) + scramble) 검증
+fn verify_caching_sha2(password: &str, scramble: &[u8], response: &[u8]) -> bool {
+    use sha2::{Digest, Sha256};
+
+    let stored = Sha256::digest(Sha256::digest(password.as_bytes()));
+    let mask = Sha256::new()
+        .chain_update(stored)
+        .chain_update(scramble)
+        .finalize();
+    if response.len() != mask.len() {
+        return false;
+    }
+    let candidate: Vec<u8> = response
+        .iter()
+        .zip(mask.iter())
+        .map(|(a, b)| a ^ b)
+        .collect();
+    Sha256::digest(&candidate) == stored
+}
+
+fn public_key_pem(key: &rsa::RsaPrivateKey) -> Result<String> {
+    use rsa::pkcs8::{EncodePublicKey, LineEnding};
+
+    key.to_public_key()
+        .to_public_key_pem(LineEnding::LF)
+        .map_err(|e| CdcError::Other(format!("Failed to encode public key: {}", e)))
+}
+
+/// SQL LIKE 패턴 ('%', '_') 을 정규식으로 변환
+fn like_pattern(pattern: &str) -> Option<Regex> {
+    let mut regex = String::from("^");
+    for c in pattern.chars() {
+        match c {
+            '%' => regex.push_str(".*"),
+            '_' => regex.push('.'),
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+    Regex::new(&regex).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::HandshakeResponse;
+    use crate::binlog_client::BinlogClient;
+    use crate::binlog_writer::BinlogFileBuilder;
+    use crate::events::{GtidEventData, QueryEventData};
+    use crate::failover::FailoverPolicy;
+    use crate::protocol::{GreetingPacket, PacketChannel};
+
+    const UUID: &str = "3e11fa47-71ca-11e1-9e33-c80aa9429562";
+
+    fn query(sql: &str) -> BinlogEventData {
+        BinlogEventData::Query(QueryEventData {
+            thread_id: 1,
+            exec_time: 0,
+            database: "shop".to_string(),
+            query: sql.to_string(),
+        })
+    }
+
+    fn gtid(sequence: u64) -> BinlogEventData {
+        BinlogEventData::Gtid(GtidEventData {
+            gtid: format!("{}:{}", UUID, sequence),
+            committed: true,
+        })
+    }
+
+    /// GTID 1, 2 트랜잭션과 ROTATE 가 있는 첫 파일, GTID 3 이 있는 두 번째 파일
+    fn binlog_files() -> (Vec<u8>, Vec<u8>) {
+        let first = BinlogFileBuilder::new()
+            .event(gtid(1))
+            .event(query("BEGIN"))
+            .event(BinlogEventData::Xid(1))
+            .event(gtid(2))
+            .event(query("BEGIN"))
+            .event(BinlogEventData::Xid(2))
+            .rotate("mysql-bin.000002")
+            .build()
+            .unwrap();
+        let second = BinlogFileBuilder::new()
+            .event(gtid(3))
+            .event(query("CREATE TABLE t (id INT)"))
+            .build()
+            .unwrap();
+        (first, second)
+    }
+
+    async fn start_server(builder: FakeServerBuilder) -> FakeServer {
+        let (first, second) = binlog_files();
+        builder
+            .binlog("mysql-bin.000001", first)
+            .binlog("mysql-bin.000002", second)
+            .start()
+            .await
+            .unwrap()
+    }
+
+    /// non-blocking 덤프로 끝까지 받은 이벤트와 마지막 위치
+    async fn stream_all(client: BinlogClient) -> (Result<BinlogPosition>, Vec<BinlogEvent>) {
+        let mut stream = client.non_blocking(true).stream().await.unwrap();
+        let mut events = Vec::new();
+        while let Some(event) = stream.recv().await {
+            events.push(event);
+        }
+        (stream.finish().await, events)
+    }
+
+    fn gtids(events: &[BinlogEvent]) -> Vec<String> {
+        events
+            .iter()
+            .filter_map(|event| match &event.data {
+                BinlogEventData::Gtid(gtid) => Some(gtid.gtid.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// PacketChannel 로 직접 접속 (mysql_native_password)
+    async fn connect(server: &FakeServer) -> PacketChannel {
+        let config = server.connection_config();
+        let mut channel = PacketChannel::connect(&config.hostname, config.port)
+            .await
+            .unwrap();
+        let greeting = GreetingPacket::parse(&channel.read_packet().await.unwrap()).unwrap();
+        let auth_response = auth::create_auth_response(&config.password, &greeting.scramble);
+        let response = HandshakeResponse {
+            username: &config.username,
+            auth_response: &auth_response,
+            database: None,
+            collation: greeting.server_collation,
+            plugin: AuthPlugin::MysqlNativePassword,
+            extra_capabilities: 0,
+            connect_attrs: None,
+            zstd_level: None,
+        }
+        .encode()
+        .unwrap();
+        channel.write_packet(&response, 1).await.unwrap();
+        assert!(protocol::is_ok_packet(
+            &channel.read_packet().await.unwrap()
+        ));
+        channel
+    }
+
+    #[tokio::test]
+    async fn test_binlog_client_streams_fixture_files() {
+        let dir = std::env::temp_dir().join(format!("fake_server_fixture_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let (first, second) = binlog_files();
+        std::fs::write(dir.join("mysql-bin.000001"), first).unwrap();
+
+        let server = FakeServer::builder()
+            .binlog_fixture(dir.join("mysql-bin.000001"))
+            .binlog("mysql-bin.000002", second.clone())
+            .start()
+            .await
+            .unwrap();
+        let client = BinlogClient::new(server.connection_config(), "mysql-bin.000001".into(), 4);
+        let (result, events) = stream_all(client).await;
+
+        // 서버가 CRC32 를 떼고 보내므로 체크섬 없이 파싱됨
+        let end = BinlogPosition::new("mysql-bin.000002".to_string(), second.len() as u64);
+        assert_eq!(result.unwrap(), end);
+        assert_eq!(gtids(&events), [1, 2, 3].map(|n| format!("{}:{}", UUID, n)));
+        assert!(matches!(&events[0].data, BinlogEventData::Rotate(r) if r.position == 4));
+        assert!(events.iter().any(|e| matches!(&e.data,
+            BinlogEventData::Query(q) if q.query == "CREATE TABLE t (id INT)")));
+
+        assert_eq!(server.queries()[0], "SET @master_binlog_checksum='NONE'");
+        let dumps = server.dump_requests();
+        assert_eq!(
+            (dumps[0].filename.as_str(), dumps[0].position),
+            ("mysql-bin.000001", 4)
+        );
+        assert!(dumps[0].non_blocking);
+
+        // 중간 위치에서 재개하면 next_pos 0 의 FORMAT_DESCRIPTION 다음에 이어지는 이벤트
+        let mut reader = BinlogFileReader::new(Cursor::new(&second[..])).unwrap();
+        let resume = reader.next_event().unwrap().unwrap().next_offset();
+        let client = BinlogClient::new(
+            server.connection_config(),
+            "mysql-bin.000002".into(),
+            resume,
+        );
+        let (result, events) = stream_all(client).await;
+        assert_eq!(result.unwrap(), end);
+        assert!(matches!(
+            events[1].data,
+            BinlogEventData::FormatDescription(_)
+        ));
+        assert_eq!(events[1].header.next_pos, 0);
+        assert_eq!(gtids(&events), [format!("{}:3", UUID)]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_gtid_dump_skips_executed_transactions() {
+        let server = start_server(FakeServer::builder()).await;
+        let executed = GtidSet::parse(&format!("{}:1", UUID)).unwrap();
+        let client =
+            BinlogClient::new(server.connection_config(), String::new(), 4).gtid_set(executed);
+        let (result, events) = stream_all(client).await;
+
+        assert!(result.is_ok());
+        assert_eq!(gtids(&events), [2, 3].map(|n| format!("{}:{}", UUID, n)));
+        let request = &server.dump_requests()[0];
+        assert_eq!(
+            request.gtid_set.as_ref().unwrap().to_string(),
+            format!("{}:1", UUID)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_authentication() {
+        // greeting 은 native, 계정은 caching_sha2 (AuthSwitchRequest 후 fast auth)
+        let server = start_server(
+            FakeServer::builder().default_auth_plugin(AuthPlugin::MysqlNativePassword),
+        )
+        .await;
+        let client = BinlogClient::new(server.connection_config(), "mysql-bin.000002".into(), 4);
+        assert!(stream_all(client).await.0.is_ok());
+
+        let mut config = server.connection_config();
+        config.password = "wrong".to_string();
+        let (result, _) = stream_all(BinlogClient::new(config, String::new(), 4)).await;
+        assert!(matches!(result, Err(CdcError::AccessDenied(_))));
+
+        // 캐시 미스면 RSA 공개키를 받아 비밀번호 전송
+        let server = start_server(FakeServer::builder().full_auth(true)).await;
+        let client = BinlogClient::new(server.connection_config(), "mysql-bin.000002".into(), 4);
+        assert!(stream_all(client).await.0.is_ok());
+
+        let server =
+            start_server(FakeServer::builder().auth_plugin(AuthPlugin::MysqlNativePassword)).await;
+        let client = BinlogClient::new(server.connection_config(), "mysql-bin.000002".into(), 4);
+        assert!(stream_all(client).await.0.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_fault_injection() {
+        let start = |server: &FakeServer| {
+            BinlogClient::new(server.connection_config(), "mysql-bin.000001".into(), 4)
+        };
+
+        let server = start_server(FakeServer::builder().fault(Fault::binlog_purged())).await;
+        let (result, events) = stream_all(start(&server)).await;
+        assert!(matches!(result, Err(CdcError::BinlogPurged(_))));
+        assert!(events.is_empty());
+
+        let server =
+            start_server(FakeServer::builder().fault(Fault::Disconnect { after_events: 3 })).await;
+        let (result, events) = stream_all(start(&server)).await;
+        assert!(result.unwrap_err().is_retryable());
+        assert_eq!(events.len(), 3);
+
+        let server = start_server(FakeServer::builder().fault(Fault::Truncate { event: 2 })).await;
+        let (result, events) = stream_all(start(&server)).await;
+        assert!(result.is_err());
+        assert_eq!(events.len(), 2);
+
+        // 한 번만 끊기고 다음 연결은 정상
+        let server = start_server(
+            FakeServer::builder()
+                .fault_once(Fault::Disconnect { after_events: 1 })
+                .fault(Fault::Slow {
+                    chunk_size: 7,
+                    delay: Duration::from_millis(1),
+                }),
+        )
+        .await;
+        assert!(stream_all(start(&server)).await.0.is_err());
+        let (result, events) = stream_all(start(&server)).await;
+        assert!(result.is_ok());
+        assert_eq!(gtids(&events).len(), 3);
+        assert_eq!(server.connections(), 2);
+    }
+
+    /// `primary` 에 먼저 접속하고 `replica` 로 장애 조치하는 설정
+    fn failover_config(primary: &FakeServer, replica: &FakeServer) -> ConnectionConfig {
+        let mut config = primary.connection_config();
+        config.failover_hosts = vec![crate::connection::HostAddress::new(
+            replica.addr().ip().to_string(),
+            replica.addr().port(),
+        )];
+        config
+    }
+
+    #[tokio::test]
+    async fn test_failover_stops_on_config_error() {
+        // 메타데이터 연결 (mysql_async) 은 성공하지만 replication 인증은 항상 실패
+        let primary = start_server(FakeServer::builder()).await;
+        let replica = start_server(FakeServer::builder().server_id(2)).await;
+        let mut config = failover_config(&primary, &replica);
+        config.server_public_key_path = Some("/nonexistent/public_key.pem".into());
+
+        let client = BinlogClient::new(config, String::new(), 4).gtid_set(GtidSet::new());
+        let (result, events) = stream_all(client).await;
+
+        assert!(matches!(result, Err(CdcError::ConfigError(_))));
+        assert!(events.is_empty());
+        assert!(primary.dump_requests().is_empty());
+        assert_eq!(replica.connections(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_failover_gives_up_after_max_rounds() {
+        let disconnect = || FakeServer::builder().fault(Fault::Disconnect { after_events: 0 });
+        let primary = start_server(disconnect()).await;
+        let replica = start_server(disconnect().server_id(2)).await;
+
+        let policy = FailoverPolicy {
+            max_rounds: 2,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(5),
+        };
+        let client = BinlogClient::new(failover_config(&primary, &replica), String::new(), 4)
+            .gtid_set(GtidSet::new())
+            .failover_policy(policy);
+        let (result, _) = stream_all(client).await;
+
+        let error = result.unwrap_err();
+        assert!(error.to_string().contains("Giving up failover after 2 rounds"), "{}", error);
+        assert_eq!(primary.dump_requests().len(), 2);
+        assert_eq!(replica.dump_requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_prepared_statements() {
+        use crate::connection::MySqlConnection;
+        use crate::events::CellValue;
+
+        const BOUND: &str =
+            "SELECT * FROM t WHERE (a, b, c, d) > (-7, 'it''s', NULL, '2024-03-01 12:30:00')";
+        let server = start_server(FakeServer::builder().query_result(
+            BOUND,
+            &["a", "b"],
+            vec![
+                vec![Some("1".to_string()), None],
+                vec![None, Some("x".to_string())],
+            ],
+        ))
+        .await;
+        let mut conn = MySqlConnection::connect(server.connection_config())
+            .await
+            .unwrap();
+
+        let datetime = chrono::NaiveDate::from_ymd_opt(2024, 3, 1)
+            .and_then(|d| d.and_hms_opt(12, 30, 0))
+            .unwrap()
+            .and_utc();
+        let params = [
+            CellValue::Int64(-7),
+            CellValue::String("it's".to_string()),
+            CellValue::Null,
+            CellValue::DateTime(datetime),
+        ];
+        let rows = conn
+            .exec_values("SELECT * FROM t WHERE (a, b, c, d) > (?, ?, ?, ?)", &params)
+            .await
+            .unwrap();
+        assert_eq!(server.queries().last().unwrap(), BOUND);
+        assert!(matches!(&rows[..], [first, second]
+            if matches!(&first[..], [CellValue::String(a), CellValue::Null] if a == "1")
+                && matches!(&second[..], [CellValue::Null, CellValue::String(b)] if b == "x")));
+    }
+
+    #[tokio::test]
+    async fn test_query_stream() {
+        use crate::connection::MySqlConnection;
+        use crate::events::CellValue;
+
+        let server = start_server(FakeServer::builder().query_result(
+            "SELECT * FROM t",
+            &["a"],
+            vec![vec![Some("1".to_string())], vec![None]],
+        ))
+        .await;
+        let mut conn = MySqlConnection::connect(server.connection_config())
+            .await
+            .unwrap();
+
+        let mut rows = conn.query_stream("SELECT * FROM t").await.unwrap();
+        assert!(matches!(rows.next().await.unwrap().as_deref(),
+            Some([CellValue::String(a)]) if a == "1"));
+        assert!(matches!(rows.next().await.unwrap().as_deref(), Some([CellValue::Null])));
+        assert!(rows.next().await.unwrap().is_none());
+        drop(rows);
+
+        // 결과를 끝까지 읽은 뒤에도 연결을 계속 사용
+        conn.execute("COMMIT").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_connect_attributes_on_both_connections() {
+        use crate::connection::MySqlConnection;
+
+        let server = start_server(FakeServer::builder()).await;
+        let mut config = server.connection_config();
+        config.connect_attrs.insert("app".to_string(), "cdc".to_string());
+
+        MySqlConnection::connect(config.clone()).await.unwrap();
+        let (result, _) = stream_all(BinlogClient::new(config, "mysql-bin.000001".into(), 4)).await;
+        result.unwrap();
+
+        // 메타데이터 연결 (mysql_async) + replication 연결
+        let attrs = server.connect_attributes();
+        assert!(attrs.len() >= 2);
+        for attrs in &attrs {
+            assert_eq!(attrs["_client_name"], env!("CARGO_PKG_NAME"));
+            assert_eq!(attrs["_pid"], std::process::id().to_string());
+            assert_eq!(attrs["app"], "cdc");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_queries_and_heartbeat() {
+        let server = start_server(
+            FakeServer::builder()
+                .auth_plugin(AuthPlugin::MysqlNativePassword)
+                .query_result(
+                    "SELECT COUNT(*) FROM shop.t",
+                    &["COUNT(*)"],
+                    vec![vec![Some("42".to_string())]],
+                )
+                .query_error("FLUSH TABLES WITH READ LOCK", 1227, "Access denied"),
+        )
+        .await;
+        let mut channel = connect(&server).await;
+
+        let logs = channel.query("SHOW BINARY LOGS").await.unwrap();
+        assert_eq!(logs.len(), 2);
+        assert_eq!(logs[1][0].as_deref(), Some("mysql-bin.000002"));
+        let status = channel.query("SHOW MASTER STATUS").await.unwrap();
+        assert_eq!(status[0][4], Some(format!("{}:1-3", UUID)));
+        let variables = channel
+            .query("SHOW GLOBAL VARIABLES LIKE 'binlog_%'")
+            .await
+            .unwrap();
+        assert_eq!(variables.len(), 3);
+        let server_id = channel.query("SELECT @@server_id").await.unwrap();
+        assert_eq!(server_id, vec![vec![Some("1".to_string())]]);
+        assert_eq!(
+            channel.query("SELECT COUNT(*) FROM shop.t").await.unwrap()[0][0],
+            Some("42".to_string())
+        );
+        assert!(matches!(
+            channel.query("FLUSH TABLES WITH READ LOCK").await,
+            Err(CdcError::AccessDenied(_))
+        ));
+
+        // blocking 덤프는 마지막 이벤트 뒤에 heartbeat 전송
+        channel
+            .query("SET @master_binlog_checksum = @@global.binlog_checksum")
+            .await
+            .unwrap();
+        channel
+            .query("SET @master_heartbeat_period = 1000000")
+            .await
+            .unwrap();
+        let checksum = channel
+            .query("SELECT @master_binlog_checksum")
+            .await
+            .unwrap();
+        assert_eq!(checksum[0][0].as_deref(), Some("CRC32"));
+
+        let mut register = vec![COM_REGISTER_SLAVE];
+        register.extend_from_slice(&101u32.to_le_bytes());
+        channel.write_packet(&register, 0).await.unwrap();
+        assert!(protocol::is_ok_packet(
+            &channel.read_packet().await.unwrap()
+        ));
+        assert_eq!(server.registered_replicas(), [101]);
+
+        let mut dump = vec![COM_BINLOG_DUMP];
+        dump.extend_from_slice(&4u32.to_le_bytes());
+        dump.extend_from_slice(&0u16.to_le_bytes());
+        dump.extend_from_slice(&101u32.to_le_bytes());
+        dump.extend_from_slice(b"mysql-bin.000002");
+        channel.write_packet(&dump, 0).await.unwrap();
+        let heartbeat = loop {
+            let packet = channel.read_packet().await.unwrap();
+            if packet[5] == EventType::HeartbeatLogEvent as u8 {
+                break packet;
+            }
+        };
+        assert_eq!(&heartbeat[20..heartbeat.len() - 4], b"mysql-bin.000002");
+    }
+}
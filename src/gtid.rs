@@ -2,11 +2,15 @@
 //!
 //! GTID 형식: UUID:sequence-number
 //! 여러 서버의 GTID 집합을 추적: "uuid1:1-100,uuid2:1-50"
+//!
+//! MariaDB GTID 형식: domain-server-sequence (예: "0-1-100")
+//! 복제 위치는 domain 별 마지막 GTID 목록: "0-1-100,1-2-50"
 
+use crate::binlog::format_uuid;
 use crate::error::{CdcError, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
-use regex::Regex;
+use std::fmt;
 
 /// GTID 범위 (sequence 범위)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
@@ -79,11 +83,29 @@ impl UUIDGtidSet {
         Ok(())
     }
 
+    /// 구간 추가 (겹치거나 연접한 구간은 병합)
+    pub fn add_range(&mut self, range: GtidRange) {
+        self.ranges.push(range);
+        self.ranges.sort();
+
+        let mut merged: Vec<GtidRange> = Vec::with_capacity(self.ranges.len());
+        for range in self.ranges.drain(..) {
+            match merged.last_mut().and_then(|last| last.merge(&range).map(|m| (last, m))) {
+                Some((last, m)) => *last = m,
+                None => merged.push(range),
+            }
+        }
+        self.ranges = merged;
+    }
+
     pub fn contains(&self, sequence: u64) -> bool {
         self.ranges.iter().any(|r| r.contains(sequence))
     }
 
-    pub fn to_string(&self) -> String {
+}
+
+impl fmt::Display for UUIDGtidSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let range_strs: Vec<String> = self.ranges.iter()
             .map(|r| {
                 if r.start == r.end {
@@ -93,7 +115,7 @@ impl UUIDGtidSet {
                 }
             })
             .collect();
-        range_strs.join(",")
+        write!(f, "{}", range_strs.join(","))
     }
 }
 
@@ -110,70 +132,38 @@ impl GtidSet {
         }
     }
 
-    /// GTID 문자열 파싱 (format: "uuid1:1-100,200,uuid2:1-50")
+    /// GTID 문자열 파싱
+    ///
+    /// MySQL 형식 ("uuid1:1-100:200,uuid2:1-50") 과 구간을 ',' 로 구분한 형식
+    /// ("uuid1:1-100,200,uuid2:1-50") 을 모두 지원합니다. 줄바꿈과 공백은 무시합니다.
     pub fn parse(gtid_str: &str) -> Result<Self> {
         let mut gtid_set = GtidSet::new();
 
-        if gtid_str.is_empty() || gtid_str == "NULL" {
+        if gtid_str.trim().is_empty() || gtid_str == "NULL" {
             return Ok(gtid_set);
         }
 
-        // 간단한 파싱 방식: split(':')과 ','를 이용
-        let mut i = 0;
-        let chars: Vec<char> = gtid_str.chars().collect();
-
-        while i < chars.len() {
-            // UUID 파싱
-            let uuid_start = i;
-            while i < chars.len() && chars[i] != ':' {
-                i += 1;
-            }
-
-            if i >= chars.len() {
-                break;
-            }
-
-            let uuid = chars[uuid_start..i].iter().collect::<String>();
-            i += 1; // ':'  skip
-
-            // 범위 파싱
-            let ranges_start = i;
-            while i < chars.len() && chars[i] != ',' || (i > 0 && i + 1 < chars.len() && is_uuid_start(&chars, i + 1)) {
-                if chars[i] == ',' && is_uuid_start(&chars, i + 1) {
-                    break;
-                }
-                i += 1;
-            }
-
-            let ranges_str = chars[ranges_start..i].iter().collect::<String>();
-            let mut uuid_gtid_set = UUIDGtidSet::new(uuid);
-
-            // 범위 문자열 파싱 (1-100, 200, 300-400)
-            for range_part in ranges_str.split(',') {
-                let range_part = range_part.trim();
-                if range_part.is_empty() {
-                    continue;
-                }
-                if range_part.contains('-') && !range_part.starts_with('-') {
-                    let parts: Vec<&str> = range_part.split('-').collect();
-                    if parts.len() == 2 {
-                        let start = parts[0].parse::<u64>()
-                            .map_err(|_| CdcError::GtidError(format!("Invalid range: {}", range_part)))?;
-                        let end = parts[1].parse::<u64>()
-                            .map_err(|_| CdcError::GtidError(format!("Invalid range: {}", range_part)))?;
-                        uuid_gtid_set.ranges.push(GtidRange::new(start, end)?);
-                    }
-                } else {
-                    let seq = range_part.parse::<u64>()
-                        .map_err(|_| CdcError::GtidError(format!("Invalid sequence: {}", range_part)))?;
-                    uuid_gtid_set.add_gtid(seq)?;
-                }
-            }
-
-            gtid_set.sets.insert(uuid_gtid_set.uuid.clone(), uuid_gtid_set);
-
-            if i < chars.len() && chars[i] == ',' {
-                i += 1;
+        let mut current: Option<String> = None;
+        for part in gtid_str.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            // "uuid:구간:구간" 이면 새 UUID, 아니면 이전 UUID 의 구간
+            let mut fields = part.split(':');
+            let intervals = if part.contains(':') {
+                let uuid = fields.next().unwrap_or_default().trim().to_ascii_lowercase();
+                current = Some(uuid);
+                fields.collect::<Vec<_>>()
+            } else {
+                vec![part]
+            };
+
+            let uuid = current
+                .clone()
+                .ok_or_else(|| CdcError::GtidError(format!("Missing UUID: {}", part)))?;
+            let uuid_set = gtid_set
+                .sets
+                .entry(uuid.clone())
+                .or_insert_with(|| UUIDGtidSet::new(uuid));
+            for interval in intervals {
+                uuid_set.add_range(parse_interval(interval.trim())?);
             }
         }
 
@@ -241,11 +231,105 @@ impl GtidSet {
         result
     }
 
-    pub fn to_string(&self) -> String {
-        if self.sets.is_empty() {
-            return String::new();
+    pub fn is_empty(&self) -> bool {
+        self.sets.iter().all(|(_, set)| set.ranges.is_empty())
+    }
+
+    /// `other` 의 모든 GTID 를 포함하는지 여부
+    pub fn contains_set(&self, other: &GtidSet) -> bool {
+        other.subtract(self).is_empty()
+    }
+
+    /// COM_BINLOG_DUMP_GTID 용 바이너리 인코딩
+    ///
+    /// n_sids (8) + n_sids * (uuid (16) + n_intervals (8) + n_intervals * (start (8) + end (8))),
+    /// end 는 구간에 포함되지 않는 값 (마지막 sequence + 1)
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        let sets: Vec<&UUIDGtidSet> = self.sets.values().filter(|s| !s.ranges.is_empty()).collect();
+
+        let mut buffer = Vec::new();
+        buffer.extend_from_slice(&(sets.len() as u64).to_le_bytes());
+        for set in sets {
+            buffer.extend_from_slice(&parse_uuid(&set.uuid)?);
+            buffer.extend_from_slice(&(set.ranges.len() as u64).to_le_bytes());
+            for range in &set.ranges {
+                buffer.extend_from_slice(&range.start.to_le_bytes());
+                buffer.extend_from_slice(&(range.end + 1).to_le_bytes());
+            }
         }
 
+        Ok(buffer)
+    }
+
+    /// `encode` 형식의 바이너리를 GTID 집합으로 변환
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        let truncated = || CdcError::GtidError("Truncated GTID set data".to_string());
+        let mut data = data;
+        let read_u64 = |data: &mut &[u8]| -> Result<u64> {
+            let (value, rest) = data.split_first_chunk::<8>().ok_or_else(truncated)?;
+            *data = rest;
+            Ok(u64::from_le_bytes(*value))
+        };
+
+        let mut gtid_set = GtidSet::new();
+        let sid_count = read_u64(&mut data)?;
+        for _ in 0..sid_count {
+            let (uuid, rest) = data.split_first_chunk::<16>().ok_or_else(truncated)?;
+            data = rest;
+            let uuid = format_uuid(uuid);
+            let uuid_set = gtid_set
+                .sets
+                .entry(uuid.clone())
+                .or_insert_with(|| UUIDGtidSet::new(uuid));
+
+            let interval_count = read_u64(&mut data)?;
+            for _ in 0..interval_count {
+                let start = read_u64(&mut data)?;
+                let end = read_u64(&mut data)?;
+                uuid_set.add_range(GtidRange::new(start, end.saturating_sub(1))?);
+            }
+        }
+
+        Ok(gtid_set)
+    }
+}
+
+/// "1-100" 또는 "200" 형식의 구간 파싱
+fn parse_interval(interval: &str) -> Result<GtidRange> {
+    let invalid = || CdcError::GtidError(format!("Invalid range: {}", interval));
+
+    match interval.split_once('-') {
+        Some((start, end)) => GtidRange::new(
+            start.trim().parse().map_err(|_| invalid())?,
+            end.trim().parse().map_err(|_| invalid())?,
+        ),
+        None => {
+            let sequence = interval.parse().map_err(|_| invalid())?;
+            GtidRange::new(sequence, sequence)
+        }
+    }
+}
+
+/// "3e11fa47-71ca-11e1-9e33-c80aa9429562" 형식의 UUID 를 16 바이트로 변환
+pub(crate) fn parse_uuid(uuid: &str) -> Result<[u8; 16]> {
+    let invalid = || CdcError::GtidError(format!("Invalid server UUID: {}", uuid));
+
+    let hex: Vec<u8> = uuid.bytes().filter(|&b| b != b'-').collect();
+    if hex.len() != 32 {
+        return Err(invalid());
+    }
+
+    let mut bytes = [0u8; 16];
+    for (byte, pair) in bytes.iter_mut().zip(hex.chunks(2)) {
+        let pair = std::str::from_utf8(pair).map_err(|_| invalid())?;
+        *byte = u8::from_str_radix(pair, 16).map_err(|_| invalid())?;
+    }
+
+    Ok(bytes)
+}
+
+impl fmt::Display for GtidSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut parts = Vec::new();
         for (uuid, uuid_set) in &self.sets {
             let ranges_str = uuid_set.to_string();
@@ -253,32 +337,102 @@ impl GtidSet {
                 parts.push(format!("{}:{}", uuid, ranges_str));
             }
         }
-        parts.join(",")
+        write!(f, "{}", parts.join(","))
     }
+}
 
-    pub fn is_empty(&self) -> bool {
-        self.sets.iter().all(|(_, set)| set.ranges.is_empty())
+/// MariaDB GTID (domain_id-server_id-sequence)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MariadbGtid {
+    pub domain_id: u32,
+    pub server_id: u32,
+    pub sequence: u64,
+}
+
+impl MariadbGtid {
+    pub fn new(domain_id: u32, server_id: u32, sequence: u64) -> Self {
+        MariadbGtid {
+            domain_id,
+            server_id,
+            sequence,
+        }
+    }
+
+    /// GTID 문자열 파싱 (format: "0-1-100")
+    pub fn parse(gtid: &str) -> Result<Self> {
+        let invalid = || CdcError::GtidError(format!("Invalid MariaDB GTID: {}", gtid));
+
+        let mut parts = gtid.trim().splitn(3, '-');
+        let mut next = || parts.next().ok_or_else(invalid);
+        let domain_id = next()?.parse().map_err(|_| invalid())?;
+        let server_id = next()?.parse().map_err(|_| invalid())?;
+        let sequence = next()?.parse().map_err(|_| invalid())?;
+
+        Ok(MariadbGtid::new(domain_id, server_id, sequence))
     }
 }
 
-/// UUID 시작 여부 확인 (간단한 휴리스틱)
-fn is_uuid_start(chars: &[char], pos: usize) -> bool {
-    if pos + 3 >= chars.len() {
-        return false;
+impl fmt::Display for MariadbGtid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}-{}", self.domain_id, self.server_id, self.sequence)
     }
+}
 
-    // UUID는 보통 16진수와 '-'를 포함하고 ':' 전에 여러 문자를 가짐
-    let mut hex_count = 0;
-    for i in pos..pos.saturating_add(10).min(chars.len()) {
-        if chars[i].is_ascii_hexdigit() || chars[i] == '-' {
-            hex_count += 1;
-        } else if chars[i] == ':' {
-            return hex_count > 8;
-        } else {
-            return false;
+/// MariaDB GTID 위치 (domain 별 마지막 GTID, `@@gtid_current_pos` 형식)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MariadbGtidSet {
+    pub domains: BTreeMap<u32, MariadbGtid>,
+}
+
+impl MariadbGtidSet {
+    pub fn new() -> Self {
+        MariadbGtidSet {
+            domains: BTreeMap::new(),
         }
     }
-    false
+
+    /// GTID 위치 문자열 파싱 (format: "0-1-100,1-2-50")
+    pub fn parse(gtid_str: &str) -> Result<Self> {
+        let mut gtid_set = MariadbGtidSet::new();
+
+        if gtid_str.is_empty() || gtid_str == "NULL" {
+            return Ok(gtid_set);
+        }
+
+        for part in gtid_str.split(',').filter(|part| !part.trim().is_empty()) {
+            gtid_set.add_gtid(MariadbGtid::parse(part)?);
+        }
+
+        Ok(gtid_set)
+    }
+
+    /// 처리한 GTID 반영 (해당 domain 의 위치를 이 GTID 로 교체)
+    pub fn add_gtid(&mut self, gtid: MariadbGtid) {
+        self.domains.insert(gtid.domain_id, gtid);
+    }
+
+    /// domain 의 현재 위치
+    pub fn get(&self, domain_id: u32) -> Option<&MariadbGtid> {
+        self.domains.get(&domain_id)
+    }
+
+    /// GTID 가 이미 처리된 위치 이전인지 여부 (같은 domain 의 sequence 비교)
+    pub fn contains(&self, gtid: &MariadbGtid) -> bool {
+        self.domains
+            .get(&gtid.domain_id)
+            .is_some_and(|current| current.sequence >= gtid.sequence)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.domains.is_empty()
+    }
+}
+
+impl fmt::Display for MariadbGtidSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parts: Vec<String> = self.domains.values().map(MariadbGtid::to_string).collect();
+        write!(f, "{}", parts.join(","))
+    }
 }
 
 #[cfg(test)]
@@ -299,4 +453,52 @@ mod tests {
         assert!(gtid_set.contains("550e8400-e29b-41d4-a716-446655440000:50"));
         assert!(!gtid_set.contains("550e8400-e29b-41d4-a716-446655440000:51"));
     }
+
+    #[test]
+    fn test_gtid_contains_set_and_encode() {
+        let uuid = "3e11fa47-71ca-11e1-9e33-c80aa9429562";
+        let executed = GtidSet::parse(&format!("{}:1-10", uuid)).unwrap();
+        let ours = GtidSet::parse(&format!("{}:1-5,7", uuid)).unwrap();
+        assert!(executed.contains_set(&ours));
+        assert!(!ours.contains_set(&executed));
+        assert!(ours.contains_set(&GtidSet::new()));
+
+        // MySQL 형식 (구간은 ':' 로 구분, UUID 사이 줄바꿈)
+        let mysql = GtidSet::parse(&format!(
+            "{}:1-5:7,\n4E11FA47-71CA-11E1-9E33-C80AA9429562:1-2",
+            uuid
+        ))
+        .unwrap();
+        assert_eq!(mysql.sets[uuid].ranges, ours.sets[uuid].ranges);
+        assert!(mysql.contains("4e11fa47-71ca-11e1-9e33-c80aa9429562:2"));
+        assert!(GtidSet::parse(&format!("{}:1-x", uuid)).is_err());
+
+        let encoded = ours.encode().unwrap();
+        assert_eq!(encoded.len(), 8 + 16 + 8 + 2 * 16);
+        assert_eq!(&encoded[..8], &1u64.to_le_bytes());
+        assert_eq!(&encoded[8..12], &[0x3e, 0x11, 0xfa, 0x47]);
+        assert_eq!(&encoded[32..48], [1u64.to_le_bytes(), 6u64.to_le_bytes()].concat());
+        assert_eq!(&encoded[48..64], [7u64.to_le_bytes(), 8u64.to_le_bytes()].concat());
+
+        assert_eq!(GtidSet::new().encode().unwrap(), 0u64.to_le_bytes());
+
+        let decoded = GtidSet::decode(&encoded).unwrap();
+        assert_eq!(decoded.to_string(), ours.to_string());
+        assert!(GtidSet::decode(&encoded[..40]).is_err());
+    }
+
+    #[test]
+    fn test_mariadb_gtid_set() {
+        let mut gtid_set = MariadbGtidSet::parse("0-1-100, 1-2-50").unwrap();
+        assert_eq!(gtid_set.get(0), Some(&MariadbGtid::new(0, 1, 100)));
+        assert!(gtid_set.contains(&MariadbGtid::new(0, 3, 99)));
+        assert!(!gtid_set.contains(&MariadbGtid::new(2, 1, 1)));
+
+        // 같은 domain 은 마지막 GTID 로 교체
+        gtid_set.add_gtid(MariadbGtid::parse("0-3-101").unwrap());
+        assert_eq!(gtid_set.to_string(), "0-3-101,1-2-50");
+
+        assert!(MariadbGtid::parse("0-1").is_err());
+        assert!(MariadbGtidSet::parse("").unwrap().is_empty());
+    }
 }
@@ -7,16 +7,30 @@
 //! - 변경 이벤트 추적
 //! - 연결 관리 및 재시작
 
+pub mod archive;
 pub mod auth;
 pub mod binlog;
 pub mod binlog_client;
+pub mod binlog_encryption;
+pub mod binlog_file;
+pub mod binlog_index;
+pub mod binlog_writer;
 pub mod cdc_engine;
+pub mod compression;
 pub mod connection;
 pub mod error;
 pub mod events;
+pub mod failover;
+#[cfg(any(test, feature = "test-support"))]
+pub mod fake_server;
 pub mod gtid;
 pub mod offset;
 pub mod protocol;
+pub mod queue;
+pub mod server;
+#[cfg(test)]
+mod test_fixtures;
+pub mod tls;
 
 pub use binlog_client::BinlogClient;
 pub use cdc_engine::CdcEngine;
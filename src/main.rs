@@ -1,7 +1,7 @@
 /// MySQL CDC 엔진 사용 예제
 ///
 /// 이 프로그램은 MySQL 데이터베이스의 변경 데이터를 캡처하고 처리합니다.
-use rust_mysql::cdc_engine::{CdcConfig, CdcEngine, SnapshotMode};
+use rust_mysql::cdc_engine::{CdcConfig, CdcEngine, SnapshotMode, DEFAULT_SNAPSHOT_CHUNK_SIZE};
 use rust_mysql::connection::ConnectionConfig;
 use std::env;
 use tracing::info;
@@ -24,12 +24,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             database: Some(env::var("DB_NAME").unwrap_or_else(|_| "testdb".to_string())),
             server_id: 1,
             timeout: std::time::Duration::from_secs(30),
+            ..Default::default()
         },
         databases: vec!["test".to_string()],
         tables: None,
         snapshot_mode: SnapshotMode::Initial,
         include_ddl: true,
         gtid_filter: None,
+        queue: Default::default(),
+        snapshot_locking: Default::default(),
+        snapshot_chunk_size: DEFAULT_SNAPSHOT_CHUNK_SIZE,
     };
 
     info!("Starting MySQL CDC Engine");
@@ -45,7 +49,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     info!("CDC Engine started successfully");
 
     // 스냅샷 처리
-    let _snapshot_rx = engine.snapshot().await?;
+    let _snapshot = engine.snapshot().await?;
     info!("Snapshot phase started");
 
     // Binlog 스트리밍
@@ -56,7 +60,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     info!("Listening for change events...");
 
     // 실제 구현에서는:
-    // - _snapshot_rx와 _binlog_rx에서 이벤트를 수신
+    // - _snapshot과 _binlog_rx에서 이벤트를 수신
     // - ChangeEvent를 처리 (Kafka, 파일, 데이터베이스 등으로 전송)
     // - 오프셋 관리
     while let Some(event) = binlog_rx.recv().await {
@@ -83,9 +87,12 @@ mod integration_tests {
             snapshot_mode: cdc_engine::SnapshotMode::Never,
             include_ddl: false,
             gtid_filter: None,
+            queue: Default::default(),
+            snapshot_locking: Default::default(),
+            snapshot_chunk_size: cdc_engine::DEFAULT_SNAPSHOT_CHUNK_SIZE,
         };
 
-        let mut engine = CdcEngine::new(config);
+        let _engine = CdcEngine::new(config);
         // engine.start().await?; // 실제 테스트 시 활성화
 
         Ok(())
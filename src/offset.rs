@@ -3,8 +3,10 @@
 //! Binlog 파일명 + 위치로 정확한 재시작 지점을 추적합니다.
 //! 예: "mysql-bin.000003" 파일의 4097 바이트 위치
 
+use crate::events::CellValue;
 use crate::gtid::GtidSet;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt;
 
 /// Binlog 파일 위치 정보
@@ -24,12 +26,25 @@ impl BinlogPosition {
     /// 파일명에서 시퀀스 번호 추출
     pub fn file_sequence(&self) -> Option<u64> {
         self.filename
-            .split('.')
-            .last()
+            .rsplit('.')
+            .next()
             .and_then(|s| s.parse().ok())
     }
 }
 
+/// 같은 파일이면 위치로, 다른 파일이면 파일 시퀀스 번호로 비교
+impl PartialOrd for BinlogPosition {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        if self.filename == other.filename {
+            return Some(self.position.cmp(&other.position));
+        }
+        match (self.file_sequence(), other.file_sequence()) {
+            (Some(a), Some(b)) if a != b => Some(a.cmp(&b)),
+            _ => None,
+        }
+    }
+}
+
 impl fmt::Display for BinlogPosition {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}:{}", self.filename, self.position)
@@ -47,8 +62,18 @@ pub struct BinlogOffset {
     pub snapshot_completed: bool,
     /// 남은 이벤트 수 (스냅샷 재시작 시)
     pub events_to_skip: Option<u64>,
-    /// 남은 행 수 (스냅샷 재시작 시)
-    pub rows_to_skip: Option<u64>,
+    /// 테이블 ("db.table") 별 스냅샷 진행 상황 (스냅샷 재시작 시 이어서 읽기)
+    #[serde(default)]
+    pub snapshot_progress: BTreeMap<String, TableSnapshotProgress>,
+}
+
+/// 테이블 스냅샷 진행 상황 (청크 단위 체크포인트)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TableSnapshotProgress {
+    /// 마지막으로 보낸 청크의 마지막 기본 키 (기본 키 컬럼 순서)
+    pub last_key: Option<Vec<CellValue>>,
+    /// 테이블을 모두 읽었는지 여부
+    pub completed: bool,
 }
 
 impl BinlogOffset {
@@ -58,7 +83,7 @@ impl BinlogOffset {
             gtid_set: GtidSet::new(),
             snapshot_completed: false,
             events_to_skip: None,
-            rows_to_skip: None,
+            snapshot_progress: BTreeMap::new(),
         }
     }
 
@@ -155,6 +180,15 @@ mod tests {
         assert_eq!(pos.file_sequence(), Some(123));
     }
 
+    #[test]
+    fn test_binlog_position_order() {
+        let pos = |file: &str, position| BinlogPosition::new(file.to_string(), position);
+
+        assert!(pos("mysql-bin.000001", 4096) > pos("mysql-bin.000001", 4));
+        assert!(pos("mysql-bin.000002", 4) > pos("mysql-bin.000001", 4096));
+        assert_eq!(pos("mysql-bin.000001", 4).partial_cmp(&pos("other", 4)), None);
+    }
+
     #[test]
     fn test_source_info_json() {
         let mut info = SourceInfo::new(1, "mysql-bin.000001".to_string());
@@ -163,4 +197,29 @@ mod tests {
         let json = info.to_json();
         assert_eq!(json["server_id"], 1);
     }
+
+    #[test]
+    fn test_snapshot_progress_json() {
+        let mut offset = BinlogOffset::new("mysql-bin.000001".to_string());
+        offset.snapshot_progress.insert(
+            "shop.orders".to_string(),
+            TableSnapshotProgress {
+                last_key: Some(vec![CellValue::String("eu".to_string()), CellValue::Int64(7)]),
+                completed: false,
+            },
+        );
+
+        let json = serde_json::to_string(&offset).unwrap();
+        let restored: BinlogOffset = serde_json::from_str(&json).unwrap();
+        let progress = &restored.snapshot_progress["shop.orders"];
+        assert!(!progress.completed);
+        assert!(matches!(progress.last_key.as_deref(),
+            Some([CellValue::String(region), CellValue::Int64(7)]) if region == "eu"));
+
+        // 진행 상황이 없던 이전 형식의 오프셋
+        let mut value = serde_json::to_value(&offset).unwrap();
+        value.as_object_mut().unwrap().remove("snapshot_progress");
+        let restored: BinlogOffset = serde_json::from_value(value).unwrap();
+        assert!(restored.snapshot_progress.is_empty());
+    }
 }
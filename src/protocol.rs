@@ -2,19 +2,84 @@
 //!
 //! mysql-binlog-connector-java의 PacketChannel과 동일한 기능 제공
 
+use crate::auth::{capabilities, AuthPlugin};
+use crate::compression::{Compression, COMPRESSED_HEADER_SIZE, MIN_COMPRESS_LENGTH};
 use crate::error::{CdcError, Result};
-use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use crate::server::ServerInfo;
+use byteorder::{LittleEndian, ReadBytesExt};
+use bytes::{Buf, Bytes, BytesMut};
 use std::io::Read;
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
 use tokio::net::TcpStream;
 use tracing::debug;
 
+/// 패킷 채널의 전송 계층 (TCP, Unix socket, TLS)
+pub trait Transport: AsyncRead + AsyncWrite + Unpin + Send {}
+
+impl<T: AsyncRead + AsyncWrite + Unpin + Send> Transport for T {}
+
+/// 단일 패킷 프레임의 최대 payload 크기 (이 크기의 프레임 뒤에는 연속 프레임이 옴)
+pub const MAX_PAYLOAD_LENGTH: usize = 0xFF_FFFF;
+
+/// 기본 max_allowed_packet (MySQL replica_max_allowed_packet 기본값과 동일한 1GB)
+pub const DEFAULT_MAX_ALLOWED_PACKET: usize = 1024 * 1024 * 1024;
+
 /// MySQL 패킷 채널
 pub struct PacketChannel {
-    stream: TcpStream,
+    stream: Box<dyn Transport>,
+    tls: bool,
+    local: bool,
+    /// 다음에 읽을 프레임의 예상 sequence id
+    sequence: u8,
+    max_allowed_packet: usize,
+    compression: Compression,
+    /// 다음에 읽을 압축 프레임의 예상 sequence id
+    compressed_read_sequence: u8,
+    /// 다음에 쓸 압축 프레임의 sequence id
+    compressed_write_sequence: u8,
+    /// 압축 해제 후 아직 읽지 않은 데이터
+    decompressed: BytesMut,
+    /// 소켓에서 읽었지만 아직 처리하지 않은 데이터
+    read_buffer: BytesMut,
+    /// 다음 패킷을 완성하는 데 필요한 read_buffer 크기
+    read_hint: usize,
+    /// 다음 프레임의 sequence id 를 검증 없이 받아들일지 여부 (out-of-band 패킷 전송 후)
+    resync: bool,
+    /// 다음 압축 프레임의 sequence id 를 검증 없이 받아들일지 여부 (out-of-band 패킷 전송 후)
+    compressed_resync: bool,
 }
 
+/// COM_QUERY 명령어 코드
+const COM_QUERY: u8 = 0x03;
+
+/// 한 번에 소켓에서 읽는 최소 크기
+const READ_CHUNK_SIZE: usize = 8 * 1024;
+
 impl PacketChannel {
+    /// 임의의 스트림으로 패킷 채널 생성
+    pub fn from_stream<T: Transport + 'static>(stream: T) -> Self {
+        Self::new(Box::new(stream), false)
+    }
+
+    fn new(stream: Box<dyn Transport>, local: bool) -> Self {
+        PacketChannel {
+            stream,
+            tls: false,
+            local,
+            sequence: 0,
+            max_allowed_packet: DEFAULT_MAX_ALLOWED_PACKET,
+            compression: Compression::Disabled,
+            compressed_read_sequence: 0,
+            compressed_write_sequence: 0,
+            decompressed: BytesMut::new(),
+            read_buffer: BytesMut::new(),
+            read_hint: 0,
+            resync: false,
+            compressed_resync: false,
+        }
+    }
+
     /// 새 패킷 채널 생성 (TCP 연결)
     pub async fn connect(hostname: &str, port: u16) -> Result<Self> {
         let addr = format!("{}:{}", hostname, port);
@@ -24,58 +89,421 @@ impl PacketChannel {
 
         debug!("Connected to MySQL at {}", addr);
 
-        Ok(PacketChannel { stream })
+        Ok(Self::new(Box::new(stream), false))
+    }
+
+    /// 새 패킷 채널 생성 (Unix domain socket 연결)
+    #[cfg(unix)]
+    pub async fn connect_unix(path: &std::path::Path) -> Result<Self> {
+        let stream = tokio::net::UnixStream::connect(path).await.map_err(|e| {
+            CdcError::ConnectionError(format!("Failed to connect to {}: {}", path.display(), e))
+        })?;
+
+        debug!("Connected to MySQL at {}", path.display());
+
+        Ok(Self::new(Box::new(stream), true))
+    }
+
+    /// 새 패킷 채널 생성 (Unix domain socket 연결)
+    #[cfg(not(unix))]
+    pub async fn connect_unix(path: &std::path::Path) -> Result<Self> {
+        Err(CdcError::ConfigError(format!(
+            "Unix domain sockets are not supported on this platform: {}",
+            path.display()
+        )))
+    }
+
+    /// 현재 연결을 TLS로 업그레이드 (SSL request 패킷 전송 후 호출)
+    pub async fn upgrade_to_tls(
+        self,
+        tls_config: Arc<rustls::ClientConfig>,
+        server_name: rustls::ServerName,
+    ) -> Result<Self> {
+        let connector = tokio_rustls::TlsConnector::from(tls_config);
+        let stream = connector
+            .connect(server_name, self.stream)
+            .await
+            .map_err(|e| CdcError::ConnectionError(format!("TLS handshake failed: {}", e)))?;
+
+        debug!("TLS handshake completed");
+
+        Ok(PacketChannel {
+            stream: Box::new(stream),
+            tls: true,
+            local: self.local,
+            sequence: self.sequence,
+            max_allowed_packet: self.max_allowed_packet,
+            compression: self.compression,
+            compressed_read_sequence: self.compressed_read_sequence,
+            compressed_write_sequence: self.compressed_write_sequence,
+            decompressed: self.decompressed,
+            read_buffer: self.read_buffer,
+            read_hint: self.read_hint,
+            resync: self.resync,
+            compressed_resync: self.compressed_resync,
+        })
+    }
+
+    /// 읽기/쓰기 가능한 최대 논리 패킷 크기 설정
+    pub fn set_max_allowed_packet(&mut self, max_allowed_packet: usize) {
+        self.max_allowed_packet = max_allowed_packet;
+    }
+
+    /// TLS 로 암호화된 채널인지 여부
+    pub fn is_tls(&self) -> bool {
+        self.tls
+    }
+
+    /// Unix socket 연결인지 여부
+    pub fn is_local(&self) -> bool {
+        self.local
+    }
+
+    /// RSA 없이 sha256 계열 비밀번호를 보내도 되는 채널인지 여부 (TLS 또는 Unix socket)
+    pub fn is_secure(&self) -> bool {
+        self.tls || self.local
+    }
+
+    /// 압축 프로토콜 활성화 (인증 OK 패킷 수신 후 호출)
+    pub fn enable_compression(&mut self, compression: Compression) {
+        self.compression = compression;
+        self.compressed_read_sequence = 0;
+        self.compressed_write_sequence = 0;
+        self.decompressed.clear();
+    }
+
+    /// 현재 사용 중인 압축 알고리즘
+    pub fn compression(&self) -> Compression {
+        self.compression
     }
 
     /// 패킷 읽기
-    pub async fn read_packet(&mut self) -> Result<Vec<u8>> {
-        // 패킷 헤더 읽기 (3 bytes length + 1 byte sequence)
-        let mut len_buf = [0u8; 3];
-        self.stream.read_exact(&mut len_buf).await
-            .map_err(|e| CdcError::IoError(format!("Failed to read packet length: {}", e)))?;
-        let length = u32::from_le_bytes([len_buf[0], len_buf[1], len_buf[2], 0]);
+    ///
+    /// 16MB (0xFFFFFF) 이상의 payload 는 여러 프레임으로 나뉘어 오므로
+    /// 길이가 0xFFFFFF 미만인 프레임이 나올 때까지 이어 붙입니다.
+    ///
+    /// 반환되는 `Bytes` 는 내부 읽기 버퍼를 복사 없이 공유합니다. 패킷을 모두 놓으면
+    /// 버퍼 공간은 다음 읽기에 재사용됩니다.
+    pub async fn read_packet(&mut self) -> Result<Bytes> {
+        loop {
+            if let Some(packet) = self.try_read_packet()? {
+                return Ok(packet);
+            }
+            self.fill_read_buffer().await?;
+        }
+    }
 
-        let _sequence = self.stream.read_u8().await
-            .map_err(|e| CdcError::IoError(format!("Failed to read sequence: {}", e)))?;
+    /// 이미 읽어 둔 데이터에 완전한 패킷이 있으면 꺼내기 (I/O 없음)
+    pub fn try_read_packet(&mut self) -> Result<Option<Bytes>> {
+        if self.compression.is_enabled() {
+            while self.decompress_frame()? {}
+        }
 
-        // 패킷 본문 읽기
-        let mut buffer = vec![0u8; length as usize];
-        self.stream.read_exact(&mut buffer).await
-            .map_err(|e| CdcError::IoError(format!("Failed to read packet body: {}", e)))?;
+        let buffer = if self.compression.is_enabled() {
+            &mut self.decompressed
+        } else {
+            &mut self.read_buffer
+        };
+
+        // 1. 논리 패킷을 이루는 프레임이 모두 도착했는지 확인 (버퍼는 그대로 둠)
+        let mut expected = self.sequence;
+        let mut offset = 0;
+        let mut total = 0;
+        loop {
+            // 패킷 헤더 (3 bytes length + 1 byte sequence)
+            let Some(header) = buffer.get(offset..offset + 4) else {
+                if !self.compression.is_enabled() {
+                    self.read_hint = offset + 4;
+                }
+                return Ok(None);
+            };
+            let length = u32::from_le_bytes([header[0], header[1], header[2], 0]) as usize;
+
+            let sequence = header[3];
+            if self.resync && offset == 0 {
+                expected = sequence;
+            }
+            if sequence != expected {
+                return Err(CdcError::ProtocolError(format!(
+                    "Packet out of order: expected sequence {}, got {}",
+                    expected, sequence
+                )));
+            }
+            expected = sequence.wrapping_add(1);
+
+            total += length;
+            if total > self.max_allowed_packet {
+                return Err(CdcError::ProtocolError(format!(
+                    "Packet larger than max_allowed_packet ({} bytes)",
+                    self.max_allowed_packet
+                )));
+            }
+
+            offset += 4 + length;
+            if buffer.len() < offset {
+                if !self.compression.is_enabled() {
+                    self.read_hint = offset;
+                }
+                return Ok(None);
+            }
+
+            if length < MAX_PAYLOAD_LENGTH {
+                break;
+            }
+        }
 
-        Ok(buffer)
+        // 2. 프레임 헤더를 제외한 payload (단일 프레임이면 복사 없이 잘라냄)
+        let mut frames = buffer.split_to(offset);
+        let packet = if offset == total + 4 {
+            frames.advance(4);
+            frames.freeze()
+        } else {
+            let mut packet = BytesMut::with_capacity(total);
+            while !frames.is_empty() {
+                let length = u32::from_le_bytes([frames[0], frames[1], frames[2], 0]) as usize;
+                frames.advance(4);
+                packet.extend_from_slice(&frames.split_to(length));
+            }
+            packet.freeze()
+        };
+
+        self.sequence = expected;
+        self.resync = false;
+        self.read_hint = 0;
+
+        Ok(Some(packet))
+    }
+
+    /// 소켓에서 읽을 수 있는 만큼 읽어 내부 버퍼에 추가
+    ///
+    /// 읽기 도중 취소되어도 데이터를 잃지 않으므로 `tokio::select!` 에서 사용할 수 있습니다.
+    pub async fn fill_read_buffer(&mut self) -> Result<()> {
+        let wanted = self.read_hint.saturating_sub(self.read_buffer.len());
+        self.read_buffer.reserve(wanted.max(READ_CHUNK_SIZE));
+
+        let read = self.stream.read_buf(&mut self.read_buffer).await
+            .map_err(|e| CdcError::IoError(format!("Failed to read packet: {}", e)))?;
+        if read == 0 {
+            return Err(CdcError::IoError("Connection closed by server".to_string()));
+        }
+
+        Ok(())
     }
 
     /// 패킷 쓰기
+    ///
+    /// `sequence` 는 첫 프레임의 sequence id 이며, 0xFFFFFF 이상의 payload 는
+    /// 연속 프레임으로 나누어 전송합니다.
     pub async fn write_packet(&mut self, data: &[u8], sequence: u8) -> Result<()> {
-        let length = data.len() as u32;
-
-        // 패킷 헤더 작성
-        let mut header = Vec::new();
-        WriteBytesExt::write_u24::<LittleEndian>(&mut header, length)
-            .map_err(|e| CdcError::IoError(format!("Failed to write length: {}", e)))?;
-        WriteBytesExt::write_u8(&mut header, sequence)
-            .map_err(|e| CdcError::IoError(format!("Failed to write sequence: {}", e)))?;
-
-        // 전송
-        self.stream.write_all(&header).await
-            .map_err(|e| CdcError::IoError(format!("Failed to write header: {}", e)))?;
-        self.stream.write_all(data).await
-            .map_err(|e| CdcError::IoError(format!("Failed to write data: {}", e)))?;
+        let sequence = self.write_frames(data, sequence).await?;
+
+        // 서버 응답은 이어지는 sequence 로 옴
+        self.sequence = sequence;
+        self.compressed_read_sequence = self.compressed_write_sequence;
+
+        Ok(())
+    }
+
+    /// 패킷 프레임을 쓰고 다음 프레임의 sequence id 반환 (읽기 쪽 상태는 건드리지 않음)
+    async fn write_frames(&mut self, data: &[u8], sequence: u8) -> Result<u8> {
+        if data.len() > self.max_allowed_packet {
+            return Err(CdcError::ProtocolError(format!(
+                "Packet larger than max_allowed_packet ({} bytes)",
+                self.max_allowed_packet
+            )));
+        }
+
+        let frame_count = data.len() / MAX_PAYLOAD_LENGTH + 1;
+        let mut frames = Vec::with_capacity(data.len() + frame_count * 4);
+
+        let first_sequence = sequence;
+        let mut sequence = sequence;
+        let mut chunks = data.chunks(MAX_PAYLOAD_LENGTH).peekable();
+        loop {
+            let chunk = chunks.next().unwrap_or_default();
+            frames.extend_from_slice(&(chunk.len() as u32).to_le_bytes()[..3]);
+            frames.push(sequence);
+            frames.extend_from_slice(chunk);
+            sequence = sequence.wrapping_add(1);
+
+            // 마지막 프레임이 정확히 0xFFFFFF 이면 빈 프레임으로 종료를 알림
+            if chunks.peek().is_none() && chunk.len() < MAX_PAYLOAD_LENGTH {
+                break;
+            }
+        }
+
+        if self.compression.is_enabled() {
+            // 새 명령이 시작되면 압축 sequence 도 0 부터 다시 시작
+            if first_sequence == 0 {
+                self.compressed_write_sequence = 0;
+            }
+            self.write_compressed(&frames).await?;
+        } else {
+            self.stream.write_all(&frames).await
+                .map_err(|e| CdcError::IoError(format!("Failed to write packet: {}", e)))?;
+        }
+
         self.stream.flush().await
             .map_err(|e| CdcError::IoError(format!("Failed to flush: {}", e)))?;
 
+        Ok(sequence)
+    }
+
+    /// 완전히 도착한 압축 프레임 하나를 압축 해제 버퍼로 옮기기
+    fn decompress_frame(&mut self) -> Result<bool> {
+        let Some(header) = self.read_buffer.get(..COMPRESSED_HEADER_SIZE) else {
+            self.read_hint = COMPRESSED_HEADER_SIZE;
+            return Ok(false);
+        };
+
+        let compressed_length = u32::from_le_bytes([header[0], header[1], header[2], 0]) as usize;
+        let sequence = header[3];
+        let uncompressed_length = u32::from_le_bytes([header[4], header[5], header[6], 0]) as usize;
+
+        if self.read_buffer.len() < COMPRESSED_HEADER_SIZE + compressed_length {
+            self.read_hint = COMPRESSED_HEADER_SIZE + compressed_length;
+            return Ok(false);
+        }
+
+        if self.compressed_resync {
+            self.compressed_read_sequence = sequence;
+            self.compressed_resync = false;
+        }
+        if sequence != self.compressed_read_sequence {
+            return Err(CdcError::ProtocolError(format!(
+                "Compressed packet out of order: expected sequence {}, got {}",
+                self.compressed_read_sequence, sequence
+            )));
+        }
+        self.compressed_read_sequence = sequence.wrapping_add(1);
+
+        self.read_buffer.advance(COMPRESSED_HEADER_SIZE);
+        let payload = self.read_buffer.split_to(compressed_length);
+
+        // uncompressed length 가 0 이면 압축되지 않은 payload
+        // (버퍼가 비어 있으면 unsplit 은 복사 없이 그대로 넘겨받음)
+        if uncompressed_length == 0 {
+            self.decompressed.unsplit(payload);
+        } else {
+            let data = self.compression.decompress(&payload, uncompressed_length)?;
+            self.decompressed.extend_from_slice(&data);
+        }
+        self.read_hint = 0;
+
+        Ok(true)
+    }
+
+    /// 패킷 프레임들을 압축 프레임으로 감싸서 쓰기
+    async fn write_compressed(&mut self, frames: &[u8]) -> Result<()> {
+        for chunk in frames.chunks(MAX_PAYLOAD_LENGTH) {
+            let compressed = if chunk.len() < MIN_COMPRESS_LENGTH {
+                None
+            } else {
+                // 압축해도 크기가 줄지 않으면 원본 그대로 전송
+                Some(self.compression.compress(chunk)?).filter(|c| c.len() < chunk.len())
+            };
+
+            let (payload, uncompressed_length) = match compressed {
+                Some(ref compressed) => (compressed.as_slice(), chunk.len()),
+                None => (chunk, 0),
+            };
+
+            let mut header = [0u8; COMPRESSED_HEADER_SIZE];
+            header[..3].copy_from_slice(&(payload.len() as u32).to_le_bytes()[..3]);
+            header[3] = self.compressed_write_sequence;
+            header[4..].copy_from_slice(&(uncompressed_length as u32).to_le_bytes()[..3]);
+            self.compressed_write_sequence = self.compressed_write_sequence.wrapping_add(1);
+
+            self.stream.write_all(&header).await
+                .map_err(|e| CdcError::IoError(format!("Failed to write compressed header: {}", e)))?;
+            self.stream.write_all(payload).await
+                .map_err(|e| CdcError::IoError(format!("Failed to write compressed data: {}", e)))?;
+        }
+
+        Ok(())
+    }
+
+    /// COM_QUERY 실행 후 텍스트 결과셋 읽기 (결과셋이 없으면 빈 Vec)
+    pub async fn query(&mut self, sql: &str) -> Result<Vec<Vec<Option<String>>>> {
+        let mut command = vec![COM_QUERY];
+        command.extend_from_slice(sql.as_bytes());
+        self.write_packet(&command, 0).await?;
+
+        let packet = self.read_packet().await?;
+        if is_error_packet(&packet) {
+            return Err(parse_error_packet(&packet));
+        }
+        if is_ok_packet(&packet) {
+            return Ok(Vec::new());
+        }
+
+        // 컬럼 정의는 사용하지 않으므로 EOF 까지 건너뜀
+        let column_count = read_lenenc_int(&mut &packet[..])? as usize;
+        for _ in 0..column_count {
+            self.read_packet().await?;
+        }
+        let eof = self.read_packet().await?;
+        if !is_eof_packet(&eof) {
+            return Err(CdcError::ProtocolError(
+                "Expected EOF after column definitions".to_string(),
+            ));
+        }
+
+        let mut rows = Vec::new();
+        loop {
+            let packet = self.read_packet().await?;
+            if is_error_packet(&packet) {
+                return Err(parse_error_packet(&packet));
+            }
+            if is_eof_packet(&packet) {
+                return Ok(rows);
+            }
+
+            let mut data = &packet[..];
+            let mut row = Vec::with_capacity(column_count);
+            for _ in 0..column_count {
+                // 0xFB 는 NULL
+                if data.first() == Some(&0xFB) {
+                    data = &data[1..];
+                    row.push(None);
+                    continue;
+                }
+                let length = read_lenenc_int(&mut data)? as usize;
+                let value = data.get(..length).ok_or_else(|| {
+                    CdcError::ProtocolError("Truncated result set row".to_string())
+                })?;
+                row.push(Some(String::from_utf8_lossy(value).to_string()));
+                data = &data[length..];
+            }
+            rows.push(row);
+        }
+    }
+
+    /// 현재 명령과 무관한 패킷 쓰기 (예: semi-sync ACK)
+    ///
+    /// sequence 0 으로 전송하며, 읽기 쪽 sequence 는 그대로 둡니다. 서버가 이어서 보내는
+    /// 패킷과 압축 프레임의 sequence 는 압축 해제 버퍼에 남은 데이터와 관계없이 검증 없이 다시 맞춥니다.
+    pub async fn write_out_of_band_packet(&mut self, data: &[u8]) -> Result<()> {
+        self.write_frames(data, 0).await?;
+        self.resync = true;
+        self.compressed_resync = true;
         Ok(())
     }
 
     /// 스트림에서 직접 읽기 (binlog 이벤트용)
     pub async fn read_raw(&mut self, buffer: &mut [u8]) -> Result<usize> {
+        if !self.read_buffer.is_empty() {
+            let length = buffer.len().min(self.read_buffer.len());
+            buffer[..length].copy_from_slice(&self.read_buffer.split_to(length));
+            return Ok(length);
+        }
+
         self.stream.read(buffer).await
             .map_err(|e| CdcError::IoError(format!("Failed to read raw data: {}", e)))
     }
 }
 
-/// Greeting 패킷 파싱
+/// Greeting 패킷 파싱 (Protocol::HandshakeV10)
 pub struct GreetingPacket {
     pub protocol_version: u8,
     pub server_version: String,
@@ -84,81 +512,183 @@ pub struct GreetingPacket {
     pub server_capabilities: u32,
     pub server_collation: u8,
     pub server_status: u16,
+    /// 서버 기본 인증 플러그인 이름 (예: "mysql_native_password"), CLIENT_PLUGIN_AUTH 일 때만 전송
+    pub auth_plugin_name: Option<String>,
+    /// 서버 버전 문자열로 감지한 서버 종류와 버전
+    pub server_info: ServerInfo,
 }
 
+/// HandshakeV10 프로토콜 버전
+const PROTOCOL_VERSION_10: u8 = 10;
+
 impl GreetingPacket {
     pub fn parse(data: &[u8]) -> Result<Self> {
         let mut cursor = std::io::Cursor::new(data);
 
         // Protocol version (1 byte)
         let protocol_version = ReadBytesExt::read_u8(&mut cursor)
-            .map_err(|e| CdcError::ProtocolError(format!("Failed to read protocol version: {}", e)))?;
+            .map_err(greeting_error("protocol version"))?;
+        if protocol_version != PROTOCOL_VERSION_10 {
+            return Err(CdcError::ProtocolError(format!(
+                "Unsupported protocol version: {}",
+                protocol_version
+            )));
+        }
 
         // Server version (null-terminated string)
         let server_version = read_null_terminated_string(&mut cursor)?;
 
         // Thread ID (4 bytes)
         let thread_id = ReadBytesExt::read_u32::<LittleEndian>(&mut cursor)
-            .map_err(|e| CdcError::ProtocolError(format!("Failed to read thread ID: {}", e)))?;
+            .map_err(greeting_error("thread ID"))?;
 
         // Auth plugin data part 1 (8 bytes)
-        let mut scramble_part1 = vec![0u8; 8];
-        Read::read_exact(&mut cursor, &mut scramble_part1)
-            .map_err(|e| CdcError::ProtocolError(format!("Failed to read scramble part 1: {}", e)))?;
+        let mut scramble = vec![0u8; 8];
+        Read::read_exact(&mut cursor, &mut scramble).map_err(greeting_error("scramble part 1"))?;
 
         // Filler (1 byte, always 0x00)
-        ReadBytesExt::read_u8(&mut cursor)
-            .map_err(|e| CdcError::ProtocolError(format!("Failed to read filler: {}", e)))?;
+        ReadBytesExt::read_u8(&mut cursor).map_err(greeting_error("filler"))?;
 
         // Capability flags (lower 2 bytes)
         let capabilities_lower = ReadBytesExt::read_u16::<LittleEndian>(&mut cursor)
-            .map_err(|e| CdcError::ProtocolError(format!("Failed to read capabilities: {}", e)))?;
+            .map_err(greeting_error("capabilities"))?;
+
+        let mut greeting = GreetingPacket {
+            protocol_version,
+            server_info: ServerInfo::detect(&server_version),
+            server_version,
+            thread_id,
+            scramble,
+            server_capabilities: capabilities_lower as u32,
+            server_collation: 0,
+            server_status: 0,
+            auth_plugin_name: None,
+        };
+
+        // 아주 오래된 서버는 capability 하위 2 바이트에서 끝남
+        if cursor.position() as usize == data.len() {
+            return Ok(greeting);
+        }
 
         // Character set (1 byte)
-        let server_collation = ReadBytesExt::read_u8(&mut cursor)
-            .map_err(|e| CdcError::ProtocolError(format!("Failed to read collation: {}", e)))?;
+        greeting.server_collation =
+            ReadBytesExt::read_u8(&mut cursor).map_err(greeting_error("collation"))?;
 
         // Status flags (2 bytes)
-        let server_status = ReadBytesExt::read_u16::<LittleEndian>(&mut cursor)
-            .map_err(|e| CdcError::ProtocolError(format!("Failed to read status: {}", e)))?;
+        greeting.server_status = ReadBytesExt::read_u16::<LittleEndian>(&mut cursor)
+            .map_err(greeting_error("status"))?;
 
         // Capability flags (upper 2 bytes)
         let capabilities_upper = ReadBytesExt::read_u16::<LittleEndian>(&mut cursor)
-            .map_err(|e| CdcError::ProtocolError(format!("Failed to read capabilities upper: {}", e)))?;
+            .map_err(greeting_error("capabilities upper"))?;
+        greeting.server_capabilities |= (capabilities_upper as u32) << 16;
+        let capabilities = greeting.server_capabilities;
+
+        // Length of auth plugin data (1 byte, CLIENT_PLUGIN_AUTH 가 아니면 0)
+        let auth_data_len =
+            ReadBytesExt::read_u8(&mut cursor).map_err(greeting_error("auth data length"))?;
+
+        // Reserved (10 bytes, MariaDB 는 마지막 4 바이트에 확장 capability 사용)
+        let mut reserved = [0u8; 10];
+        Read::read_exact(&mut cursor, &mut reserved).map_err(greeting_error("reserved"))?;
+
+        // Auth plugin data part 2 (CLIENT_SECURE_CONNECTION, 최소 13 bytes)
+        if capabilities & capabilities::SECURE_CONNECTION != 0 {
+            let scramble_len = std::cmp::max(13, auth_data_len.saturating_sub(8)) as usize;
+            let mut scramble_part2 = vec![0u8; scramble_len];
+            Read::read_exact(&mut cursor, &mut scramble_part2)
+                .map_err(greeting_error("scramble part 2"))?;
+
+            // 마지막 null byte 제외
+            let part2 = scramble_part2.strip_suffix(&[0]).unwrap_or(&scramble_part2);
+            greeting.scramble.extend_from_slice(part2);
+        }
 
-        let server_capabilities = (capabilities_upper as u32) << 16 | capabilities_lower as u32;
+        // Auth plugin name (CLIENT_PLUGIN_AUTH, 일부 구버전 서버는 terminator 없이 끝남)
+        if capabilities & capabilities::PLUGIN_AUTH != 0 {
+            let rest = &data[cursor.position() as usize..];
+            let end = rest.iter().position(|&b| b == 0).unwrap_or(rest.len());
+            if end > 0 {
+                greeting.auth_plugin_name = Some(String::from_utf8_lossy(&rest[..end]).to_string());
+            }
+        }
 
-        // Length of auth plugin data (1 byte)
-        let auth_data_len = ReadBytesExt::read_u8(&mut cursor)
-            .map_err(|e| CdcError::ProtocolError(format!("Failed to read auth data length: {}", e)))?;
+        Ok(greeting)
+    }
 
-        // Reserved (10 bytes)
-        let mut reserved = vec![0u8; 10];
-        Read::read_exact(&mut cursor, &mut reserved)
-            .map_err(|e| CdcError::ProtocolError(format!("Failed to read reserved: {}", e)))?;
+    /// 첫 핸드셰이크 응답에 사용할 인증 플러그인
+    ///
+    /// CLIENT_PLUGIN_AUTH 를 지원하지 않는 서버는 mysql_native_password 만 사용할 수 있고,
+    /// 지원하지 않는 플러그인을 알려 온 경우에는 서버 종류별 기본 플러그인으로 시작합니다.
+    pub fn auth_plugin(&self) -> AuthPlugin {
+        if self.server_capabilities & capabilities::PLUGIN_AUTH == 0 {
+            return AuthPlugin::MysqlNativePassword;
+        }
 
-        // Auth plugin data part 2 (at least 13 bytes)
-        let scramble_len = std::cmp::max(13, auth_data_len.saturating_sub(8)) as usize;
-        let mut scramble_part2 = vec![0u8; scramble_len];
-        Read::read_exact(&mut cursor, &mut scramble_part2)
-            .map_err(|e| CdcError::ProtocolError(format!("Failed to read scramble part 2: {}", e)))?;
+        self.auth_plugin_name
+            .as_deref()
+            .and_then(AuthPlugin::from_name)
+            .unwrap_or_else(|| self.server_info.default_auth_plugin())
+    }
+}
 
-        // Combine scramble parts
-        let mut scramble = scramble_part1;
-        scramble.extend_from_slice(&scramble_part2[..scramble_part2.len()-1]); // 마지막 null byte 제외
+fn greeting_error(field: &'static str) -> impl FnOnce(std::io::Error) -> CdcError {
+    move |e| CdcError::ProtocolError(format!("Failed to read {}: {}", field, e))
+}
 
-        Ok(GreetingPacket {
-            protocol_version,
-            server_version,
-            thread_id,
-            scramble,
-            server_capabilities,
-            server_collation,
-            server_status,
+/// ERR 패킷 파싱
+///
+/// 0xFF + error code (2) + ['#' + SQL state (5)] + message
+/// 핸드셰이크 전 에러 (예: Too many connections) 에는 SQL state 가 없습니다.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrPacket {
+    pub error_code: u16,
+    /// SQL state marker ('#'), CLIENT_PROTOCOL_41 이전 에러에는 없음
+    pub sql_state_marker: Option<u8>,
+    pub sql_state: String,
+    pub error_message: String,
+}
+
+impl ErrPacket {
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        if !is_error_packet(data) || data.len() < 3 {
+            return Err(CdcError::ProtocolError("Invalid ERR packet".to_string()));
+        }
+
+        let error_code = u16::from_le_bytes([data[1], data[2]]);
+
+        let (sql_state_marker, sql_state, message) = match data.get(3) {
+            Some(&b'#') if data.len() >= 9 => (
+                Some(b'#'),
+                String::from_utf8_lossy(&data[4..9]).to_string(),
+                &data[9..],
+            ),
+            _ => (None, "HY000".to_string(), &data[3..]),
+        };
+
+        Ok(ErrPacket {
+            error_code,
+            sql_state_marker,
+            sql_state,
+            error_message: String::from_utf8_lossy(message).to_string(),
         })
     }
 }
 
+impl From<ErrPacket> for CdcError {
+    fn from(err: ErrPacket) -> Self {
+        CdcError::from_server_error(err.error_code, &err.sql_state, &err.error_message)
+    }
+}
+
+/// ERR 패킷을 CdcError 로 변환 (파싱 실패 시 ProtocolError)
+pub fn parse_error_packet(data: &[u8]) -> CdcError {
+    match ErrPacket::parse(data) {
+        Ok(err) => err.into(),
+        Err(e) => e,
+    }
+}
+
 /// null로 끝나는 문자열 읽기
 fn read_null_terminated_string<R: Read>(reader: &mut R) -> Result<String> {
     let mut bytes = Vec::new();
@@ -184,6 +714,40 @@ pub fn is_ok_packet(data: &[u8]) -> bool {
     !data.is_empty() && data[0] == 0x00
 }
 
+/// EOF 패킷 확인 (0xFE, 9 바이트 미만)
+pub fn is_eof_packet(data: &[u8]) -> bool {
+    data.first() == Some(&0xFE) && data.len() < 9
+}
+
+/// Length-encoded integer 읽기
+pub(crate) fn read_lenenc_int(data: &mut &[u8]) -> Result<u64> {
+    let truncated = || CdcError::ProtocolError("Truncated length-encoded integer".to_string());
+
+    let (&first, rest) = data.split_first().ok_or_else(truncated)?;
+    let size = match first {
+        0xFC => 2,
+        0xFD => 3,
+        0xFE => 8,
+        0xFB | 0xFF => {
+            return Err(CdcError::ProtocolError(format!(
+                "Invalid length-encoded integer: 0x{:02x}",
+                first
+            )))
+        }
+        _ => {
+            *data = rest;
+            return Ok(first as u64);
+        }
+    };
+
+    let bytes = rest.get(..size).ok_or_else(truncated)?;
+    let mut value = [0u8; 8];
+    value[..size].copy_from_slice(bytes);
+    *data = &rest[size..];
+
+    Ok(u64::from_le_bytes(value))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -199,4 +763,233 @@ mod tests {
         assert!(is_ok_packet(&[0x00, 0x01, 0x02]));
         assert!(!is_ok_packet(&[0xFF, 0x01, 0x02]));
     }
+
+    #[test]
+    fn test_parse_err_packet() {
+        let mut data = vec![0xFF, 0xD4, 0x04, b'#'];
+        data.extend_from_slice(b"HY000Could not find first log file name in binary log index file");
+        let err = ErrPacket::parse(&data).unwrap();
+        assert_eq!(err.error_code, 1236);
+        assert_eq!(err.sql_state_marker, Some(b'#'));
+        assert_eq!(err.sql_state, "HY000");
+        assert!(matches!(CdcError::from(err), CdcError::BinlogPurged(_)));
+
+        // SQL state 없는 핸드셰이크 전 에러
+        let mut data = vec![0xFF, 0x10, 0x04];
+        data.extend_from_slice(b"Too many connections");
+        let err = ErrPacket::parse(&data).unwrap();
+        assert_eq!(err.sql_state_marker, None);
+        assert_eq!(err.error_message, "Too many connections");
+        assert!(matches!(parse_error_packet(&data), CdcError::TooManyConnections(_)));
+    }
+
+    /// HandshakeV10 greeting (scramble 20 bytes)
+    fn greeting_packet(version: &str, capabilities: u32, plugin: Option<&str>) -> Vec<u8> {
+        let mut data = vec![PROTOCOL_VERSION_10];
+        data.extend_from_slice(version.as_bytes());
+        data.push(0);
+        data.extend_from_slice(&42u32.to_le_bytes());
+        data.extend_from_slice(b"abcdefgh");
+        data.push(0);
+        data.extend_from_slice(&(capabilities as u16).to_le_bytes());
+        data.push(0xFF);
+        data.extend_from_slice(&2u16.to_le_bytes());
+        data.extend_from_slice(&((capabilities >> 16) as u16).to_le_bytes());
+        data.push(if plugin.is_some() { 21 } else { 0 });
+        data.extend_from_slice(&[0; 10]);
+        if capabilities & capabilities::SECURE_CONNECTION != 0 {
+            data.extend_from_slice(b"ijklmnopqrst\0");
+        }
+        if let Some(plugin) = plugin {
+            data.extend_from_slice(plugin.as_bytes());
+            data.push(0);
+        }
+        data
+    }
+
+    #[test]
+    fn test_parse_greeting() {
+        use crate::server::ServerFlavor;
+
+        let caps = capabilities::PROTOCOL_41
+            | capabilities::SECURE_CONNECTION
+            | capabilities::PLUGIN_AUTH;
+
+        let data = greeting_packet("8.0.35", caps, Some("caching_sha2_password"));
+        let greeting = GreetingPacket::parse(&data).unwrap();
+        assert_eq!(greeting.thread_id, 42);
+        assert_eq!(greeting.scramble, b"abcdefghijklmnopqrst");
+        assert_eq!(greeting.server_collation, 0xFF);
+        assert_eq!(greeting.auth_plugin_name.as_deref(), Some("caching_sha2_password"));
+        assert_eq!(greeting.auth_plugin(), AuthPlugin::CachingSha2Password);
+        assert_eq!(greeting.server_info.flavor, ServerFlavor::MySql);
+
+        // MariaDB: 5.5.5- 접두사, 지원하지 않는 플러그인은 기본 플러그인으로 시작
+        let data = greeting_packet("5.5.5-10.6.12-MariaDB-log", caps, Some("client_ed25519"));
+        let greeting = GreetingPacket::parse(&data).unwrap();
+        assert_eq!(greeting.server_info.flavor, ServerFlavor::MariaDb);
+        assert_eq!(greeting.server_info.version.major, 10);
+        assert_eq!(greeting.auth_plugin(), AuthPlugin::MysqlNativePassword);
+    }
+
+    #[test]
+    fn test_parse_greeting_without_optional_fields() {
+        // CLIENT_PLUGIN_AUTH 없음 -> 플러그인 이름 없이 끝남
+        let data = greeting_packet("5.1.73", capabilities::SECURE_CONNECTION, None);
+        let greeting = GreetingPacket::parse(&data).unwrap();
+        assert_eq!(greeting.scramble.len(), 20);
+        assert_eq!(greeting.auth_plugin_name, None);
+        assert_eq!(greeting.auth_plugin(), AuthPlugin::MysqlNativePassword);
+
+        // capability 하위 2 바이트에서 끝나는 greeting
+        let data = greeting_packet("4.0.30", 0, None);
+        let greeting = GreetingPacket::parse(&data[..data.len() - 16]).unwrap();
+        assert_eq!(greeting.scramble, b"abcdefgh");
+        assert_eq!(greeting.server_capabilities, 0);
+
+        let mut data = greeting_packet("8.0.35", 0, None);
+        data[0] = 9;
+        assert!(matches!(GreetingPacket::parse(&data), Err(CdcError::ProtocolError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_multi_packet_roundtrip() {
+        let (client, server) = tokio::io::duplex(64 * 1024);
+        let mut writer = PacketChannel::from_stream(client);
+        let mut reader = PacketChannel::from_stream(server);
+
+        // 정확히 0xFFFFFF 배수인 경우 빈 종료 프레임이 필요
+        for size in [MAX_PAYLOAD_LENGTH + 10, MAX_PAYLOAD_LENGTH * 2] {
+            let data: Vec<u8> = (0..size).map(|i| i as u8).collect();
+            let (written, read) = tokio::join!(writer.write_packet(&data, 0), reader.read_packet());
+            written.unwrap();
+            assert_eq!(read.unwrap(), data);
+
+            // 다음 명령은 다시 sequence 0 부터 시작
+            reader.sequence = 0;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_packet_shares_read_buffer() {
+        let (client, mut server) = tokio::io::duplex(1024);
+        let mut reader = PacketChannel::from_stream(client);
+
+        // 한 번의 읽기로 두 패킷이 버퍼에 들어옴
+        server.write_all(&[3, 0, 0, 0, 1, 2, 3, 2, 0, 0, 1, 4, 5]).await.unwrap();
+        let first = reader.read_packet().await.unwrap();
+        let second = reader.read_packet().await.unwrap();
+
+        assert_eq!(first, vec![1, 2, 3]);
+        assert_eq!(second, vec![4, 5]);
+        // 두 번째 패킷은 첫 패킷 payload + 헤더 4 바이트 뒤에 그대로 위치
+        assert_eq!(second.as_ptr(), first[first.len()..].as_ptr().wrapping_add(4));
+    }
+
+    #[tokio::test]
+    async fn test_read_packet_validates_sequence_and_size() {
+        let (client, server) = tokio::io::duplex(1024);
+        let mut writer = PacketChannel::from_stream(client);
+        let mut reader = PacketChannel::from_stream(server);
+
+        writer.write_packet(&[1, 2, 3], 5).await.unwrap();
+        assert!(matches!(reader.read_packet().await, Err(CdcError::ProtocolError(_))));
+
+        let (client, server) = tokio::io::duplex(1024);
+        let mut writer = PacketChannel::from_stream(client);
+        let mut reader = PacketChannel::from_stream(server);
+        reader.set_max_allowed_packet(2);
+
+        writer.write_packet(&[1, 2, 3], 0).await.unwrap();
+        assert!(matches!(reader.read_packet().await, Err(CdcError::ProtocolError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_compressed_roundtrip() {
+        for compression in [Compression::Zlib, Compression::Zstd { level: 3 }] {
+            let (client, server) = tokio::io::duplex(64 * 1024);
+            let mut writer = PacketChannel::from_stream(client);
+            let mut reader = PacketChannel::from_stream(server);
+            writer.enable_compression(compression);
+            reader.enable_compression(compression);
+
+            // 작은 패킷은 압축 없이, 큰 패킷은 압축 + 연속 프레임으로 전송
+            for size in [10, 4096, MAX_PAYLOAD_LENGTH + 10] {
+                let data: Vec<u8> = (0..size).map(|i| (i % 7) as u8).collect();
+                let (written, read) =
+                    tokio::join!(writer.write_packet(&data, 0), reader.read_packet());
+                written.unwrap();
+                assert_eq!(read.unwrap(), data);
+
+                reader.sequence = 0;
+                reader.compressed_read_sequence = 0;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_query_result_set() {
+        let (client, server) = tokio::io::duplex(1024);
+        let mut channel = PacketChannel::from_stream(client);
+        let mut server = PacketChannel::from_stream(server);
+
+        let respond = async {
+            let command = server.read_packet().await.unwrap();
+            assert_eq!(command, &b"\x03SELECT @@a, @@b"[..]);
+
+            let packets: Vec<&[u8]> = vec![
+                &[0x02],
+                b"column-a",
+                b"column-b",
+                &[0xFE, 0, 0, 2, 0],
+                &[0x02, b'O', b'N', 0xFB],
+                &[0xFE, 0, 0, 2, 0],
+            ];
+            for (i, packet) in packets.into_iter().enumerate() {
+                server.write_packet(packet, i as u8 + 1).await.unwrap();
+            }
+        };
+
+        let (rows, _) = tokio::join!(channel.query("SELECT @@a, @@b"), respond);
+        assert_eq!(rows.unwrap(), vec![vec![Some("ON".to_string()), None]]);
+    }
+
+    #[tokio::test]
+    async fn test_compressed_header() {
+        let (client, mut server) = tokio::io::duplex(1024);
+        let mut writer = PacketChannel::from_stream(client);
+        writer.enable_compression(Compression::Zlib);
+
+        // MIN_COMPRESS_LENGTH 미만은 uncompressed length 0 으로 원본 전송
+        writer.write_packet(&[0x0e], 0).await.unwrap();
+        let mut frame = [0u8; 12];
+        server.read_exact(&mut frame).await.unwrap();
+        assert_eq!(frame, [5, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0x0e]);
+
+        // 같은 명령의 다음 패킷은 압축 sequence 가 이어짐
+        writer.write_packet(&[0x0e], 1).await.unwrap();
+        server.read_exact(&mut frame).await.unwrap();
+        assert_eq!(frame[3], 1);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_connect_unix() {
+        let path = std::env::temp_dir().join(format!("mysql-cdc-{}.sock", uuid::Uuid::new_v4()));
+        let listener = tokio::net::UnixListener::bind(&path).unwrap();
+
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            stream.write_all(&[0x03, 0x00, 0x00, 0x00, 0x0a, 0x0b, 0x0c]).await.unwrap();
+        });
+
+        let mut channel = PacketChannel::connect_unix(&path).await.unwrap();
+        assert!(channel.is_local());
+        assert!(channel.is_secure());
+        assert!(!channel.is_tls());
+        assert_eq!(channel.read_packet().await.unwrap(), vec![0x0a, 0x0b, 0x0c]);
+
+        server.await.unwrap();
+        std::fs::remove_file(&path).ok();
+    }
 }
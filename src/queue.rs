@@ -0,0 +1,325 @@
+//! 이벤트 큐 (이벤트 수 + 바이트 크기 제한이 있는 bounded channel)
+//!
+//! 컨슈머가 느려 큐가 가득 차면 송신 측이 대기합니다. binlog 리더는 이 동안
+//! 소켓을 읽지 않으므로 TCP flow control 로 MySQL 서버에 backpressure 가 전달됩니다.
+
+use crate::error::{CdcError, Result};
+use crate::events::{BinlogEvent, CellValue, ChangeEvent};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::sync::{mpsc, Semaphore};
+
+pub use tokio::sync::mpsc::error::TryRecvError;
+
+/// 기본 큐 용량 (이벤트 수)
+pub const DEFAULT_QUEUE_CAPACITY: usize = 1024;
+
+/// 기본 큐 용량 (바이트)
+pub const DEFAULT_QUEUE_MAX_BYTES: usize = 64 * 1024 * 1024;
+
+/// 큐 설정
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueueConfig {
+    /// 큐에 쌓일 수 있는 최대 이벤트 수
+    pub capacity: usize,
+    /// 큐에 쌓일 수 있는 이벤트의 최대 크기 합 (None 이면 제한 없음)
+    ///
+    /// 이보다 큰 이벤트 하나는 큐가 빌 때까지 기다린 뒤 전달됩니다.
+    pub max_bytes: Option<usize>,
+}
+
+impl Default for QueueConfig {
+    fn default() -> Self {
+        QueueConfig {
+            capacity: DEFAULT_QUEUE_CAPACITY,
+            max_bytes: Some(DEFAULT_QUEUE_MAX_BYTES),
+        }
+    }
+}
+
+/// 큐에 쌓인 이벤트 수와 크기
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QueueDepth {
+    pub events: usize,
+    pub bytes: usize,
+}
+
+/// 큐 크기 계산에 사용할 이벤트 크기 (대략적인 메모리 사용량)
+pub trait EventSize {
+    fn event_size(&self) -> usize;
+}
+
+impl EventSize for BinlogEvent {
+    fn event_size(&self) -> usize {
+        self.header.event_length as usize
+    }
+}
+
+impl EventSize for ChangeEvent {
+    fn event_size(&self) -> usize {
+        let row_size = |row: &Option<HashMap<String, CellValue>>| {
+            row.iter()
+                .flatten()
+                .map(|(column, value)| column.len() + cell_size(value))
+                .sum::<usize>()
+        };
+
+        std::mem::size_of::<ChangeEvent>()
+            + self.gtid.as_ref().map_or(0, String::len)
+            + self.database.len()
+            + self.table.len()
+            + self.query.as_ref().map_or(0, String::len)
+            + row_size(&self.before)
+            + row_size(&self.after)
+    }
+}
+
+fn cell_size(value: &CellValue) -> usize {
+    match value {
+        CellValue::String(s) | CellValue::Date(s) | CellValue::Time(s) | CellValue::Decimal(s) => {
+            s.len()
+        }
+        CellValue::Bytes(bytes) => bytes.len(),
+        CellValue::Json(json) => json.to_string().len(),
+        _ => std::mem::size_of::<CellValue>(),
+    }
+}
+
+/// 송신/수신 측이 공유하는 상태
+#[derive(Debug)]
+struct Shared {
+    events: AtomicUsize,
+    bytes: AtomicUsize,
+    /// 남은 바이트 용량 (permit 1개 = 1 byte)
+    byte_permits: Option<Semaphore>,
+    max_bytes: usize,
+}
+
+impl Shared {
+    fn depth(&self) -> QueueDepth {
+        QueueDepth {
+            events: self.events.load(Ordering::Relaxed),
+            bytes: self.bytes.load(Ordering::Relaxed),
+        }
+    }
+
+    fn release(&self, size: usize, permits: u32) {
+        self.events.fetch_sub(1, Ordering::Relaxed);
+        self.bytes.fetch_sub(size, Ordering::Relaxed);
+        if let Some(semaphore) = &self.byte_permits {
+            semaphore.add_permits(permits as usize);
+        }
+    }
+}
+
+/// 큐에 들어간 이벤트 (크기와 사용한 permit 수)
+struct Entry<T> {
+    event: T,
+    size: usize,
+    permits: u32,
+}
+
+/// bounded 이벤트 큐 생성
+pub fn bounded<T: EventSize>(config: QueueConfig) -> (EventSender<T>, EventReceiver<T>) {
+    let (tx, rx) = mpsc::channel(config.capacity.max(1));
+
+    // Semaphore::acquire_many 는 u32 단위
+    let max_bytes = config.max_bytes.map(|max| max.clamp(1, u32::MAX as usize));
+    let shared = Arc::new(Shared {
+        events: AtomicUsize::new(0),
+        bytes: AtomicUsize::new(0),
+        byte_permits: max_bytes.map(Semaphore::new),
+        max_bytes: max_bytes.unwrap_or(0),
+    });
+
+    (
+        EventSender {
+            tx,
+            shared: shared.clone(),
+        },
+        EventReceiver { rx, shared },
+    )
+}
+
+/// 큐 송신 측
+pub struct EventSender<T> {
+    tx: mpsc::Sender<Entry<T>>,
+    shared: Arc<Shared>,
+}
+
+impl<T> Clone for EventSender<T> {
+    fn clone(&self) -> Self {
+        EventSender {
+            tx: self.tx.clone(),
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<T: EventSize> EventSender<T> {
+    /// 이벤트 전송 (큐가 가득 차면 자리가 날 때까지 대기)
+    ///
+    /// 대기 중에 취소되어도 큐 용량이 새지 않습니다.
+    pub async fn send(&self, event: T) -> Result<()> {
+        let size = event.event_size();
+
+        // 1. 이벤트 수 용량 확보
+        let slot = self.tx.reserve().await.map_err(|_| CdcError::ChannelClosed)?;
+
+        // 2. 바이트 용량 확보
+        let permits = match &self.shared.byte_permits {
+            Some(semaphore) => {
+                let permits = size.clamp(1, self.shared.max_bytes) as u32;
+                semaphore
+                    .acquire_many(permits)
+                    .await
+                    .map_err(|_| CdcError::ChannelClosed)?
+                    .forget();
+                permits
+            }
+            None => 0,
+        };
+
+        // 3. 전송 (이후로는 대기 없음)
+        self.shared.events.fetch_add(1, Ordering::Relaxed);
+        self.shared.bytes.fetch_add(size, Ordering::Relaxed);
+        slot.send(Entry {
+            event,
+            size,
+            permits,
+        });
+
+        Ok(())
+    }
+}
+
+impl<T> EventSender<T> {
+    /// 수신 측이 닫혔는지 여부
+    pub fn is_closed(&self) -> bool {
+        self.tx.is_closed()
+    }
+
+    /// 큐 모니터링 핸들
+    pub fn monitor(&self) -> QueueMonitor {
+        QueueMonitor {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+/// 큐 수신 측
+pub struct EventReceiver<T> {
+    rx: mpsc::Receiver<Entry<T>>,
+    shared: Arc<Shared>,
+}
+
+impl<T> EventReceiver<T> {
+    /// 다음 이벤트 수신 (모든 송신 측이 닫히면 None)
+    pub async fn recv(&mut self) -> Option<T> {
+        let entry = self.rx.recv().await?;
+        Some(self.take(entry))
+    }
+
+    /// 대기 없이 이벤트 수신
+    pub fn try_recv(&mut self) -> std::result::Result<T, TryRecvError> {
+        let entry = self.rx.try_recv()?;
+        Ok(self.take(entry))
+    }
+
+    /// 현재 큐 깊이
+    pub fn depth(&self) -> QueueDepth {
+        self.shared.depth()
+    }
+
+    /// 큐 모니터링 핸들
+    pub fn monitor(&self) -> QueueMonitor {
+        QueueMonitor {
+            shared: self.shared.clone(),
+        }
+    }
+
+    fn take(&self, entry: Entry<T>) -> T {
+        self.shared.release(entry.size, entry.permits);
+        entry.event
+    }
+}
+
+impl<T> Drop for EventReceiver<T> {
+    fn drop(&mut self) {
+        // 바이트 용량을 기다리는 송신 측을 깨움
+        if let Some(semaphore) = &self.shared.byte_permits {
+            semaphore.close();
+        }
+    }
+}
+
+/// 큐 깊이 조회 핸들 (메트릭 수집용)
+#[derive(Debug, Clone)]
+pub struct QueueMonitor {
+    shared: Arc<Shared>,
+}
+
+impl QueueMonitor {
+    pub fn depth(&self) -> QueueDepth {
+        self.shared.depth()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    struct Sized(usize);
+
+    impl EventSize for Sized {
+        fn event_size(&self) -> usize {
+            self.0
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bytes_backpressure() {
+        let config = QueueConfig {
+            capacity: 10,
+            max_bytes: Some(100),
+        };
+        let (tx, mut rx) = bounded(config);
+
+        tx.send(Sized(60)).await.unwrap();
+        assert_eq!(rx.depth(), QueueDepth { events: 1, bytes: 60 });
+
+        // 바이트 용량 초과 -> 대기
+        let blocked = tokio::time::timeout(Duration::from_millis(20), tx.send(Sized(60))).await;
+        assert!(blocked.is_err());
+
+        let (sent, received) = tokio::join!(tx.send(Sized(60)), rx.recv());
+        sent.unwrap();
+        assert_eq!(received.unwrap().0, 60);
+        assert_eq!(rx.depth(), QueueDepth { events: 1, bytes: 60 });
+
+        // max_bytes 보다 큰 이벤트는 큐가 비면 전달됨
+        rx.recv().await.unwrap();
+        tx.send(Sized(500)).await.unwrap();
+        assert_eq!(rx.try_recv().unwrap().0, 500);
+        assert_eq!(tx.monitor().depth(), QueueDepth::default());
+    }
+
+    #[tokio::test]
+    async fn test_events_backpressure_and_close() {
+        let config = QueueConfig {
+            capacity: 1,
+            max_bytes: Some(100),
+        };
+        let (tx, rx) = bounded(config);
+
+        tx.send(Sized(1)).await.unwrap();
+        let blocked = tokio::time::timeout(Duration::from_millis(20), tx.send(Sized(1))).await;
+        assert!(blocked.is_err());
+
+        drop(rx);
+        assert!(tx.is_closed());
+        assert!(matches!(tx.send(Sized(1)).await, Err(CdcError::ChannelClosed)));
+    }
+}
@@ -0,0 +1,332 @@
+//! 서버 종류 (MySQL, Percona, MariaDB, Aurora) 및 버전 감지
+//!
+//! greeting 의 서버 버전 문자열로 감지하며, 버전 문자열만으로 구분할 수 없는 Percona 와
+//! Aurora 는 `version_comment`, `aurora_version` 변수로 보완합니다.
+
+use crate::auth::AuthPlugin;
+use std::fmt;
+
+/// MariaDB 10.x 가 replication 호환성을 위해 버전 앞에 붙이는 접두사
+pub const MARIADB_VERSION_PREFIX: &str = "5.5.5-";
+
+/// Aurora 버전 문자열의 Aurora 버전 앞 부분 (예: "8.0.mysql_aurora.3.04.0")
+const AURORA_VERSION_MARKER: &str = "mysql_aurora.";
+
+/// AWS 가 공개한 Aurora MySQL 마이너 버전별 호환 MySQL 버전 ((major, minor), MySQL 버전)
+const AURORA_MYSQL_VERSIONS: [((u16, u16), ServerVersion); 11] = [
+    ((2, 11), ServerVersion::new(5, 7, 40)),
+    ((2, 12), ServerVersion::new(5, 7, 40)),
+    ((3, 1), ServerVersion::new(8, 0, 23)),
+    ((3, 2), ServerVersion::new(8, 0, 23)),
+    ((3, 3), ServerVersion::new(8, 0, 26)),
+    ((3, 4), ServerVersion::new(8, 0, 28)),
+    ((3, 5), ServerVersion::new(8, 0, 32)),
+    ((3, 6), ServerVersion::new(8, 0, 34)),
+    ((3, 7), ServerVersion::new(8, 0, 36)),
+    ((3, 8), ServerVersion::new(8, 0, 39)),
+    ((3, 9), ServerVersion::new(8, 0, 40)),
+];
+
+/// 서버 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ServerFlavor {
+    MySql,
+    Percona,
+    MariaDb,
+    Aurora,
+}
+
+impl ServerFlavor {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ServerFlavor::MySql => "MySQL",
+            ServerFlavor::Percona => "Percona Server",
+            ServerFlavor::MariaDb => "MariaDB",
+            ServerFlavor::Aurora => "Aurora MySQL",
+        }
+    }
+}
+
+impl fmt::Display for ServerFlavor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// 서버 버전 (major.minor.patch)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct ServerVersion {
+    pub major: u16,
+    pub minor: u16,
+    pub patch: u16,
+}
+
+impl ServerVersion {
+    pub const fn new(major: u16, minor: u16, patch: u16) -> Self {
+        ServerVersion { major, minor, patch }
+    }
+
+    /// "8.0.35-27-log" 처럼 숫자 버전으로 시작하는 문자열 파싱
+    ///
+    /// 숫자가 아닌 부분이 나오면 나머지 자리는 0 으로 둡니다 (예: "8.0.mysql_aurora.3.04.0").
+    pub fn parse(version: &str) -> Option<Self> {
+        let mut parts = version.split('.').map(|part| {
+            let digits = part.find(|c: char| !c.is_ascii_digit()).unwrap_or(part.len());
+            part[..digits].parse::<u16>().ok()
+        });
+
+        let major = parts.next()??;
+        let minor = parts.next().flatten().unwrap_or(0);
+        let patch = parts.next().flatten().unwrap_or(0);
+
+        Some(ServerVersion::new(major, minor, patch))
+    }
+
+    /// Aurora 버전 (예: "3.04.0") 에 대응하는 MySQL 버전
+    ///
+    /// 공개된 호환 버전 표에 없는 마이너 버전은 그 major 가 호환되는 가장 낮은 MySQL 버전
+    /// (Aurora 2 는 5.7.12, Aurora 3 은 8.0.23) 으로 취급합니다.
+    pub fn from_aurora(aurora_version: &str) -> Option<Self> {
+        let aurora = Self::parse(aurora_version)?;
+        let known = AURORA_MYSQL_VERSIONS
+            .iter()
+            .find(|(version, _)| *version == (aurora.major, aurora.minor))
+            .map(|(_, mysql)| *mysql);
+        match aurora.major {
+            2 => Some(known.unwrap_or(ServerVersion::new(5, 7, 12))),
+            3 => Some(known.unwrap_or(ServerVersion::new(8, 0, 23))),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ServerVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// 감지한 서버 정보
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerInfo {
+    pub flavor: ServerFlavor,
+    pub version: ServerVersion,
+    /// 서버가 보낸 원본 버전 문자열
+    pub version_string: String,
+}
+
+impl ServerInfo {
+    /// 서버 버전 문자열로 감지 (greeting 또는 `SELECT VERSION()`)
+    ///
+    /// Aurora 의 "8.0.mysql_aurora.3.04.0" 은 Aurora 버전으로 호환 MySQL 버전을 정합니다.
+    pub fn detect(server_version: &str) -> Self {
+        let lower = server_version.to_ascii_lowercase();
+
+        let (flavor, version) = match server_version.strip_prefix(MARIADB_VERSION_PREFIX) {
+            Some(rest) => (ServerFlavor::MariaDb, rest),
+            None if lower.contains("mariadb") => (ServerFlavor::MariaDb, server_version),
+            None if lower.contains("aurora") => (ServerFlavor::Aurora, server_version),
+            None if lower.contains("percona") => (ServerFlavor::Percona, server_version),
+            None => (ServerFlavor::MySql, server_version),
+        };
+
+        let aurora_version = version
+            .find(AURORA_VERSION_MARKER)
+            .map(|start| &version[start + AURORA_VERSION_MARKER.len()..])
+            .and_then(ServerVersion::from_aurora);
+
+        ServerInfo {
+            flavor,
+            version: aurora_version.or_else(|| ServerVersion::parse(version)).unwrap_or_default(),
+            version_string: server_version.to_string(),
+        }
+    }
+
+    /// `SHOW GLOBAL VARIABLES` 의 version, version_comment, aurora_version 으로 감지
+    pub fn from_variables<'a>(variables: impl IntoIterator<Item = (&'a str, &'a str)>) -> Self {
+        let mut version = "";
+        let mut version_comment = "";
+        let mut aurora = false;
+        for (name, value) in variables {
+            match name.to_ascii_lowercase().as_str() {
+                "version" => version = value,
+                "version_comment" => version_comment = value,
+                "aurora_version" => aurora = true,
+                _ => {}
+            }
+        }
+
+        let mut info = Self::detect(version);
+        if info.flavor == ServerFlavor::MySql {
+            if aurora {
+                info.flavor = ServerFlavor::Aurora;
+            } else if version_comment.to_ascii_lowercase().contains("percona") {
+                info.flavor = ServerFlavor::Percona;
+            }
+        }
+
+        info
+    }
+
+    pub fn is_mariadb(&self) -> bool {
+        self.flavor == ServerFlavor::MariaDb
+    }
+
+    /// 서버 버전이 `major.minor.patch` 이상인지 여부
+    pub fn at_least(&self, major: u16, minor: u16, patch: u16) -> bool {
+        self.version >= ServerVersion::new(major, minor, patch)
+    }
+
+    /// greeting 에 인증 플러그인이 없거나 지원하지 않는 플러그인일 때 사용할 플러그인
+    ///
+    /// 서버가 다른 플러그인을 원하면 AuthSwitchRequest 로 다시 협상합니다.
+    pub fn default_auth_plugin(&self) -> AuthPlugin {
+        if !self.is_mariadb() && self.at_least(8, 0, 4) {
+            AuthPlugin::CachingSha2Password
+        } else {
+            AuthPlugin::MysqlNativePassword
+        }
+    }
+
+    /// 현재 binlog 파일/위치 조회 명령
+    ///
+    /// MySQL 8.2 부터 SHOW MASTER STATUS 는 deprecated 되었고 8.4 에서 제거되었습니다.
+    /// MariaDB 는 10.5.2 부터 SHOW BINLOG STATUS 를 지원하므로 그 이전은 SHOW MASTER STATUS 를 사용합니다.
+    pub fn binlog_status_query(&self) -> &'static str {
+        match self.flavor {
+            ServerFlavor::MariaDb if self.at_least(10, 5, 2) => "SHOW BINLOG STATUS",
+            ServerFlavor::MariaDb => "SHOW MASTER STATUS",
+            _ if self.at_least(8, 2, 0) => "SHOW BINARY LOG STATUS",
+            _ => "SHOW MASTER STATUS",
+        }
+    }
+
+    /// binlog 파일 목록 조회 명령 (모든 지원 버전에서 동일, 컬럼 구성만 다름)
+    pub fn binary_logs_query(&self) -> &'static str {
+        "SHOW BINARY LOGS"
+    }
+
+    /// 연결된 replica 목록 조회 명령
+    ///
+    /// MySQL 8.0.22, MariaDB 10.5.1 부터 SLAVE 대신 REPLICA 용어를 사용합니다.
+    pub fn replicas_query(&self) -> &'static str {
+        match self.flavor {
+            ServerFlavor::MariaDb if self.at_least(10, 5, 1) => "SHOW REPLICA HOSTS",
+            ServerFlavor::MariaDb => "SHOW SLAVE HOSTS",
+            _ if self.at_least(8, 0, 22) => "SHOW REPLICAS",
+            _ => "SHOW SLAVE HOSTS",
+        }
+    }
+
+    /// replica 상태 조회 명령 (SHOW SLAVE STATUS 는 MySQL 8.4 에서 제거됨)
+    pub fn replica_status_query(&self) -> &'static str {
+        match self.flavor {
+            ServerFlavor::MariaDb if self.at_least(10, 5, 1) => "SHOW REPLICA STATUS",
+            ServerFlavor::MariaDb => "SHOW SLAVE STATUS",
+            _ if self.at_least(8, 0, 22) => "SHOW REPLICA STATUS",
+            _ => "SHOW SLAVE STATUS",
+        }
+    }
+}
+
+impl fmt::Display for ServerInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.flavor, self.version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_flavor() {
+        let cases = [
+            ("8.0.35", ServerFlavor::MySql, ServerVersion::new(8, 0, 35)),
+            ("8.4.0-log", ServerFlavor::MySql, ServerVersion::new(8, 4, 0)),
+            ("5.5.5-10.6.12-MariaDB-log", ServerFlavor::MariaDb, ServerVersion::new(10, 6, 12)),
+            ("11.4.2-MariaDB", ServerFlavor::MariaDb, ServerVersion::new(11, 4, 2)),
+            ("8.0.mysql_aurora.3.04.0", ServerFlavor::Aurora, ServerVersion::new(8, 0, 28)),
+            ("8.0.mysql_aurora.3.02.2", ServerFlavor::Aurora, ServerVersion::new(8, 0, 23)),
+            ("8.0.mysql_aurora.3.08.1", ServerFlavor::Aurora, ServerVersion::new(8, 0, 39)),
+            ("8.0.mysql_aurora.3.99.0", ServerFlavor::Aurora, ServerVersion::new(8, 0, 23)),
+            ("5.7.mysql_aurora.2.07.9", ServerFlavor::Aurora, ServerVersion::new(5, 7, 12)),
+            ("5.7.mysql_aurora.2.11.2", ServerFlavor::Aurora, ServerVersion::new(5, 7, 40)),
+            ("5.7.44-48-Percona", ServerFlavor::Percona, ServerVersion::new(5, 7, 44)),
+        ];
+
+        for (version, flavor, expected) in cases {
+            let info = ServerInfo::detect(version);
+            assert_eq!(info.flavor, flavor, "{}", version);
+            assert_eq!(info.version, expected, "{}", version);
+        }
+    }
+
+    #[test]
+    fn test_from_variables() {
+        let percona = ServerInfo::from_variables([
+            ("version", "8.0.35-27"),
+            ("version_comment", "Percona Server (GPL), Release 27, Revision 2f8eeab2"),
+        ]);
+        assert_eq!(percona.flavor, ServerFlavor::Percona);
+        assert_eq!(percona.version, ServerVersion::new(8, 0, 35));
+
+        let aurora = ServerInfo::from_variables([
+            ("version", "8.0.28"),
+            ("version_comment", "Source distribution"),
+            ("aurora_version", "3.04.0"),
+        ]);
+        assert_eq!(aurora.flavor, ServerFlavor::Aurora);
+
+        let aurora = ServerInfo::from_variables([
+            ("version", "8.0.mysql_aurora.3.04.0"),
+            ("aurora_version", "3.04.0"),
+        ]);
+        assert_eq!(aurora.flavor, ServerFlavor::Aurora);
+        assert_eq!(aurora.version, ServerVersion::new(8, 0, 28));
+    }
+
+    #[test]
+    fn test_version_dependent_choices() {
+        let mysql57 = ServerInfo::detect("5.7.44-log");
+        let mysql80 = ServerInfo::detect("8.0.35");
+        let mysql84 = ServerInfo::detect("8.4.0");
+        let mariadb = ServerInfo::detect("5.5.5-10.11.6-MariaDB");
+        let mariadb104 = ServerInfo::detect("5.5.5-10.4.32-MariaDB");
+        let mariadb1051 = ServerInfo::detect("5.5.5-10.5.1-MariaDB");
+        let mariadb1052 = ServerInfo::detect("5.5.5-10.5.2-MariaDB");
+
+        assert_eq!(mysql57.default_auth_plugin(), AuthPlugin::MysqlNativePassword);
+        assert_eq!(mysql80.default_auth_plugin(), AuthPlugin::CachingSha2Password);
+        assert_eq!(mariadb.default_auth_plugin(), AuthPlugin::MysqlNativePassword);
+
+        assert_eq!(mysql57.binlog_status_query(), "SHOW MASTER STATUS");
+        assert_eq!(mysql80.binlog_status_query(), "SHOW MASTER STATUS");
+        assert_eq!(mysql84.binlog_status_query(), "SHOW BINARY LOG STATUS");
+        assert_eq!(mariadb.binlog_status_query(), "SHOW BINLOG STATUS");
+        assert_eq!(mariadb104.binlog_status_query(), "SHOW MASTER STATUS");
+        assert_eq!(mariadb1051.binlog_status_query(), "SHOW MASTER STATUS");
+        assert_eq!(mariadb1052.binlog_status_query(), "SHOW BINLOG STATUS");
+    }
+
+    #[test]
+    fn test_replication_queries() {
+        let cases = [
+            ("5.7.44-log", "SHOW SLAVE HOSTS", "SHOW SLAVE STATUS"),
+            ("8.0.21", "SHOW SLAVE HOSTS", "SHOW SLAVE STATUS"),
+            ("8.0.22", "SHOW REPLICAS", "SHOW REPLICA STATUS"),
+            ("8.4.0", "SHOW REPLICAS", "SHOW REPLICA STATUS"),
+            ("8.0.mysql_aurora.3.04.0", "SHOW REPLICAS", "SHOW REPLICA STATUS"),
+            ("5.7.mysql_aurora.2.11.2", "SHOW SLAVE HOSTS", "SHOW SLAVE STATUS"),
+            ("5.5.5-10.4.32-MariaDB", "SHOW SLAVE HOSTS", "SHOW SLAVE STATUS"),
+            ("5.5.5-10.11.6-MariaDB", "SHOW REPLICA HOSTS", "SHOW REPLICA STATUS"),
+        ];
+
+        for (version, replicas, replica_status) in cases {
+            let info = ServerInfo::detect(version);
+            assert_eq!(info.replicas_query(), replicas, "{}", version);
+            assert_eq!(info.replica_status_query(), replica_status, "{}", version);
+            assert_eq!(info.binary_logs_query(), "SHOW BINARY LOGS");
+        }
+    }
+}
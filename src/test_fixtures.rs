@@ -0,0 +1,108 @@
+//! 테스트용 binlog 이벤트 바이트
+//!
+//! 파서, 파일 리더, 아카이브, 인덱스, 클라이언트 테스트가 같은 형식의 이벤트를 쓰도록 모아 둔
+//! fixture 입니다. 손상된 이벤트나 임의의 next_pos 처럼 `BinlogEncoder` 로 만들 수 없는 바이트도
+//! 만들 수 있도록 body 는 그대로 기록합니다.
+
+use crate::binlog::{CHECKSUM_VERSION, EVENT_HEADER_SIZE};
+use crate::server::ServerVersion;
+
+/// 이벤트 헤더 + body (+ CRC32)
+///
+/// 기본값은 timestamp 1_700_000_000, server_id 1, next_pos 0, 체크섬 없음이며
+/// event_length 는 body 와 체크섬 길이로 채웁니다.
+#[derive(Debug, Clone)]
+pub(crate) struct RawEvent<'a> {
+    event_type: u8,
+    body: &'a [u8],
+    timestamp: u32,
+    server_id: u32,
+    next_pos: u32,
+    crc: bool,
+}
+
+impl<'a> RawEvent<'a> {
+    pub(crate) fn new(event_type: u8, body: &'a [u8]) -> Self {
+        RawEvent {
+            event_type,
+            body,
+            timestamp: 1_700_000_000,
+            server_id: 1,
+            next_pos: 0,
+            crc: false,
+        }
+    }
+
+    pub(crate) fn timestamp(mut self, timestamp: u32) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
+    pub(crate) fn server_id(mut self, server_id: u32) -> Self {
+        self.server_id = server_id;
+        self
+    }
+
+    pub(crate) fn next_pos(mut self, next_pos: u32) -> Self {
+        self.next_pos = next_pos;
+        self
+    }
+
+    /// CRC32 체크섬을 붙일지 여부
+    pub(crate) fn crc(mut self, crc: bool) -> Self {
+        self.crc = crc;
+        self
+    }
+
+    pub(crate) fn build(&self) -> Vec<u8> {
+        let length = EVENT_HEADER_SIZE + self.body.len() + if self.crc { 4 } else { 0 };
+        let mut data = self.timestamp.to_le_bytes().to_vec();
+        data.push(self.event_type);
+        data.extend_from_slice(&self.server_id.to_le_bytes());
+        data.extend_from_slice(&(length as u32).to_le_bytes());
+        data.extend_from_slice(&self.next_pos.to_le_bytes());
+        data.extend_from_slice(&0u16.to_le_bytes());
+        data.extend_from_slice(self.body);
+        if self.crc {
+            let checksum = crc32fast::hash(&data);
+            data.extend_from_slice(&checksum.to_le_bytes());
+        }
+        data
+    }
+
+    /// 복제 스트림 패킷 payload (OK 바이트 + 이벤트)
+    pub(crate) fn packet(&self) -> Vec<u8> {
+        let mut packet = vec![0x00];
+        packet.extend(self.build());
+        packet
+    }
+}
+
+/// 서버 버전과 체크섬 알고리즘 바이트를 담은 FORMAT_DESCRIPTION body (체크섬 제외)
+pub(crate) fn format_description_body(server_version: &str, algorithm: Option<u8>) -> Vec<u8> {
+    let mut body = 4u16.to_le_bytes().to_vec();
+    let mut version = [0u8; 50];
+    version[..server_version.len()].copy_from_slice(server_version.as_bytes());
+    body.extend_from_slice(&version);
+    body.extend_from_slice(&0u32.to_le_bytes());
+    body.push(EVENT_HEADER_SIZE as u8);
+    body.extend_from_slice(&[0u8; 40]); // post-header 길이
+    if let Some(algorithm) = algorithm {
+        body.push(algorithm);
+    }
+    body
+}
+
+/// 서버 버전에 맞는 FORMAT_DESCRIPTION 이벤트 (5.6.1 이후는 CRC32)
+pub(crate) fn format_description(server_version: &str, next_pos: u32) -> Vec<u8> {
+    let crc = ServerVersion::parse(server_version).is_some_and(|v| v >= CHECKSUM_VERSION);
+    let body = format_description_body(server_version, crc.then_some(1));
+    RawEvent::new(15, &body).next_pos(next_pos).crc(crc).build()
+}
+
+/// ROTATE body (다음 파일의 위치 + 파일명)
+pub(crate) fn rotate_body(name: &str, position: u64) -> Vec<u8> {
+    let mut body = position.to_le_bytes().to_vec();
+    body.extend_from_slice(name.as_bytes());
+    body
+}
@@ -0,0 +1,328 @@
+//! TLS (SSL) 연결 설정
+//!
+//! MySQL 클라이언트의 `--ssl-mode` 와 동일한 모드를 지원합니다.
+//! - DISABLED: 암호화하지 않음
+//! - PREFERRED: 서버가 지원하면 암호화 (인증서 검증 없음)
+//!   서버가 TLS를 지원하지 않거나 Unix socket 이면 평문으로 연결하고,
+//!   TLS 협상 자체가 실패하면 평문으로 재시도하지 않고 에러를 반환합니다 (MySQL 클라이언트와 동일).
+//!   replication 채널과 메타데이터 연결 모두 같은 규칙을 따릅니다.
+//! - REQUIRED: 암호화 필수 (인증서 검증 없음)
+//! - VERIFY_CA: 암호화 + CA 검증
+//! - VERIFY_IDENTITY: 암호화 + CA 검증 + 호스트명 검증
+
+use crate::connection::ConnectionConfig;
+use crate::error::{CdcError, Result};
+use rustls::client::{ServerCertVerified, ServerCertVerifier, WebPkiVerifier};
+use rustls::{Certificate, CertificateError, ClientConfig, PrivateKey, RootCertStore, ServerName};
+use std::fmt;
+use std::io::BufReader;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+/// SSL 모드 (MySQL `--ssl-mode`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SslMode {
+    Disabled,
+    #[default]
+    Preferred,
+    Required,
+    VerifyCa,
+    VerifyIdentity,
+}
+
+impl SslMode {
+    /// TLS 협상을 시도하는지 여부
+    pub fn is_enabled(&self) -> bool {
+        *self != SslMode::Disabled
+    }
+
+    /// 서버가 TLS를 지원하지 않을 때 연결을 거부해야 하는지 여부
+    pub fn is_required(&self) -> bool {
+        !matches!(self, SslMode::Disabled | SslMode::Preferred)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SslMode::Disabled => "DISABLED",
+            SslMode::Preferred => "PREFERRED",
+            SslMode::Required => "REQUIRED",
+            SslMode::VerifyCa => "VERIFY_CA",
+            SslMode::VerifyIdentity => "VERIFY_IDENTITY",
+        }
+    }
+}
+
+impl FromStr for SslMode {
+    type Err = CdcError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_uppercase().as_str() {
+            "DISABLED" => Ok(SslMode::Disabled),
+            "PREFERRED" => Ok(SslMode::Preferred),
+            "REQUIRED" => Ok(SslMode::Required),
+            "VERIFY_CA" => Ok(SslMode::VerifyCa),
+            "VERIFY_IDENTITY" => Ok(SslMode::VerifyIdentity),
+            _ => Err(CdcError::Other(format!("Invalid ssl mode: {}", s))),
+        }
+    }
+}
+
+impl fmt::Display for SslMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// replication 채널용 rustls 클라이언트 설정 생성
+pub fn build_client_config(config: &ConnectionConfig) -> Result<Arc<ClientConfig>> {
+    let verifier: Arc<dyn ServerCertVerifier> = match config.ssl_mode {
+        SslMode::Disabled | SslMode::Preferred | SslMode::Required => Arc::new(NoVerification),
+        SslMode::VerifyCa => Arc::new(CaOnlyVerification(WebPkiVerifier::new(
+            load_root_store(config.ssl_ca.as_deref())?,
+            None,
+        ))),
+        SslMode::VerifyIdentity => Arc::new(WebPkiVerifier::new(
+            load_root_store(config.ssl_ca.as_deref())?,
+            None,
+        )),
+    };
+
+    let builder = ClientConfig::builder()
+        .with_safe_defaults()
+        .with_custom_certificate_verifier(verifier);
+
+    let client_config = match (&config.ssl_cert, &config.ssl_key) {
+        (Some(cert), Some(key)) => builder
+            .with_client_auth_cert(load_certs(cert)?, load_private_key(key)?)
+            .map_err(|e| CdcError::ConfigError(format!("Invalid client certificate: {}", e)))?,
+        (None, None) => builder.with_no_client_auth(),
+        _ => {
+            return Err(CdcError::ConfigError(
+                "ssl_cert and ssl_key must be configured together".to_string(),
+            ))
+        }
+    };
+
+    Ok(Arc::new(client_config))
+}
+
+/// 서버 인증서 검증에 사용할 호스트명
+pub fn server_name(hostname: &str) -> Result<ServerName> {
+    ServerName::try_from(hostname).map_err(|e| {
+        CdcError::ConfigError(format!("Invalid TLS server name {}: {}", hostname, e))
+    })
+}
+
+/// mysql_async 메타데이터 연결용 SSL 옵션 생성
+///
+/// replication 채널과 같이 PREFERRED 의 Unix socket 연결은 TLS를 생략합니다.
+pub fn mysql_async_ssl_opts(config: &ConnectionConfig) -> Option<mysql_async::SslOpts> {
+    if config.socket.is_some() && !config.ssl_mode.is_required() {
+        return None;
+    }
+
+    let ssl_opts = match config.ssl_mode {
+        SslMode::Disabled => return None,
+        SslMode::Preferred | SslMode::Required => mysql_async::SslOpts::default()
+            .with_danger_accept_invalid_certs(true)
+            .with_danger_skip_domain_validation(true),
+        SslMode::VerifyCa => mysql_async::SslOpts::default()
+            .with_root_certs(config.ssl_ca.iter().cloned().map(Into::into).collect())
+            .with_danger_skip_domain_validation(true),
+        SslMode::VerifyIdentity => mysql_async::SslOpts::default()
+            .with_root_certs(config.ssl_ca.iter().cloned().map(Into::into).collect()),
+    };
+
+    let identity = match (&config.ssl_cert, &config.ssl_key) {
+        (Some(cert), Some(key)) => {
+            Some(mysql_async::ClientIdentity::new(cert.clone().into(), key.clone().into()))
+        }
+        _ => None,
+    };
+
+    Some(ssl_opts.with_client_identity(identity))
+}
+
+/// CA 인증서 로드 (설정되지 않으면 공개 루트 인증서 사용)
+fn load_root_store(ca_path: Option<&Path>) -> Result<RootCertStore> {
+    let mut roots = RootCertStore::empty();
+
+    match ca_path {
+        Some(path) => {
+            let certs = load_certs(path)?;
+            for cert in &certs {
+                roots.add(cert).map_err(|e| {
+                    CdcError::ConfigError(format!(
+                        "Invalid CA certificate {}: {}",
+                        path.display(),
+                        e
+                    ))
+                })?;
+            }
+        }
+        None => {
+            roots.add_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.iter().map(|ta| {
+                rustls::OwnedTrustAnchor::from_subject_spki_name_constraints(
+                    ta.subject,
+                    ta.spki,
+                    ta.name_constraints,
+                )
+            }));
+        }
+    }
+
+    Ok(roots)
+}
+
+/// PEM 인증서 체인 로드
+fn load_certs(path: &Path) -> Result<Vec<Certificate>> {
+    let file = std::fs::File::open(path).map_err(|e| {
+        CdcError::ConfigError(format!(
+            "Failed to open certificate {}: {}",
+            path.display(),
+            e
+        ))
+    })?;
+
+    let certs = rustls_pemfile::certs(&mut BufReader::new(file)).map_err(|e| {
+        CdcError::ConfigError(format!(
+            "Failed to read certificate {}: {}",
+            path.display(),
+            e
+        ))
+    })?;
+
+    if certs.is_empty() {
+        return Err(CdcError::ConfigError(format!(
+            "No certificate found in {}",
+            path.display()
+        )));
+    }
+
+    Ok(certs.into_iter().map(Certificate).collect())
+}
+
+/// PEM 개인키 로드 (PKCS#1, PKCS#8, SEC1)
+fn load_private_key(path: &Path) -> Result<PrivateKey> {
+    let file = std::fs::File::open(path).map_err(|e| {
+        CdcError::ConfigError(format!(
+            "Failed to open private key {}: {}",
+            path.display(),
+            e
+        ))
+    })?;
+
+    let items = rustls_pemfile::read_all(&mut BufReader::new(file)).map_err(|e| {
+        CdcError::ConfigError(format!(
+            "Failed to read private key {}: {}",
+            path.display(),
+            e
+        ))
+    })?;
+
+    items
+        .into_iter()
+        .find_map(|item| match item {
+            rustls_pemfile::Item::RSAKey(key)
+            | rustls_pemfile::Item::PKCS8Key(key)
+            | rustls_pemfile::Item::ECKey(key) => Some(PrivateKey(key)),
+            _ => None,
+        })
+        .ok_or_else(|| {
+            CdcError::ConfigError(format!("No private key found in {}", path.display()))
+        })
+}
+
+/// 인증서를 검증하지 않는 verifier (PREFERRED, REQUIRED)
+struct NoVerification;
+
+impl ServerCertVerifier for NoVerification {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &Certificate,
+        _intermediates: &[Certificate],
+        _server_name: &ServerName,
+        _scts: &mut dyn Iterator<Item = &[u8]>,
+        _ocsp_response: &[u8],
+        _now: SystemTime,
+    ) -> std::result::Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+}
+
+/// CA 체인만 검증하고 호스트명은 무시하는 verifier (VERIFY_CA)
+struct CaOnlyVerification(WebPkiVerifier);
+
+impl ServerCertVerifier for CaOnlyVerification {
+    fn verify_server_cert(
+        &self,
+        end_entity: &Certificate,
+        intermediates: &[Certificate],
+        server_name: &ServerName,
+        scts: &mut dyn Iterator<Item = &[u8]>,
+        ocsp_response: &[u8],
+        now: SystemTime,
+    ) -> std::result::Result<ServerCertVerified, rustls::Error> {
+        match self.0.verify_server_cert(
+            end_entity,
+            intermediates,
+            server_name,
+            scts,
+            ocsp_response,
+            now,
+        ) {
+            Err(rustls::Error::InvalidCertificate(CertificateError::NotValidForName)) => {
+                Ok(ServerCertVerified::assertion())
+            }
+            other => other,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ssl_mode_from_str() {
+        assert_eq!("verify_ca".parse::<SslMode>().unwrap(), SslMode::VerifyCa);
+        assert_eq!(
+            "VERIFY_IDENTITY".parse::<SslMode>().unwrap(),
+            SslMode::VerifyIdentity
+        );
+        assert!("ON".parse::<SslMode>().is_err());
+        assert!(SslMode::Required.is_required());
+        assert!(!SslMode::Preferred.is_required());
+    }
+
+    #[test]
+    fn test_build_client_config_requires_cert_and_key() {
+        let mut config = ConnectionConfig {
+            ssl_mode: SslMode::Required,
+            ..Default::default()
+        };
+        assert!(build_client_config(&config).is_ok());
+
+        config.ssl_cert = Some("/nonexistent/client-cert.pem".into());
+        assert!(build_client_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_mysql_async_ssl_opts() {
+        let mut config = ConnectionConfig::default();
+        assert!(mysql_async_ssl_opts(&config).is_some());
+
+        // PREFERRED 의 Unix socket 은 replication 채널과 같이 TLS 생략
+        config.socket = Some("/var/run/mysqld/mysqld.sock".into());
+        assert!(mysql_async_ssl_opts(&config).is_none());
+
+        config.ssl_mode = SslMode::Required;
+        assert!(mysql_async_ssl_opts(&config).is_some());
+
+        config.ssl_mode = SslMode::Disabled;
+        config.socket = None;
+        assert!(mysql_async_ssl_opts(&config).is_none());
+    }
+}